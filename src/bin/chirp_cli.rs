@@ -0,0 +1,686 @@
+//! Command-line front end for CHIRP-RS
+//! Backs up, programs and converts radio images without the Qt GUI
+//!
+//! Subcommands:
+//! - download: read a radio into a .img (or .csv for command-mode radios)
+//! - upload: write a .img (or .csv) to a radio
+//! - export-csv / import-csv: convert between .img memories and CHIRP CSV
+//! - copy: map one image's memories into another radio's image
+//! - list-drivers / list-ports: show supported radios and serial ports
+
+use anyhow::{anyhow, bail, Context};
+use chirp_rs::channel_copy::{CopyFilter, CopyPlan, Placement};
+use chirp_rs::core::{Memory, RadioFeatures};
+use chirp_rs::drivers::{
+    detect_image, find_driver, get_driver, init_drivers, list_drivers, DetectError, DriverInfo,
+    DynCloneModeRadio,
+};
+use chirp_rs::formats::{
+    d75, export_csv, import_csv, import_repeaters, load_d75, load_img, save_d75, save_img, Metadata,
+};
+use chirp_rs::memmap::MemoryMap;
+use chirp_rs::radio_ops::{download_from_radio, upload_to_radio, ProgressFn};
+use chirp_rs::serial::comm::list_ports;
+use std::env;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+use tracing_subscriber::EnvFilter;
+
+/// Command line arguments
+#[derive(Default)]
+struct Args {
+    command: String,
+    port: Option<String>,
+    radio: Option<String>,
+    output: Option<String>,
+    image: Option<String>,
+    range: Option<String>,
+    band: Option<String>,
+    mode: Option<String>,
+    bank: Option<String>,
+    name: Option<String>,
+    keep_numbers: bool,
+    files: Vec<String>,
+}
+
+impl Args {
+    fn port(&self) -> anyhow::Result<&str> {
+        self.port
+            .as_deref()
+            .ok_or_else(|| anyhow!("--port is required"))
+    }
+
+    fn output(&self) -> anyhow::Result<&str> {
+        self.output
+            .as_deref()
+            .ok_or_else(|| anyhow!("-o <file> is required"))
+    }
+
+    fn file(&self, what: &str) -> anyhow::Result<&str> {
+        match self.files.as_slice() {
+            [file] => Ok(file),
+            [] => bail!("missing {} argument", what),
+            _ => bail!("expected a single {} argument", what),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| "warn".into()))
+        .with_writer(std::io::stderr)
+        .init();
+
+    let args = parse_args()?;
+    init_drivers();
+
+    match args.command.as_str() {
+        "download" => download(&args).await,
+        "upload" => upload(&args).await,
+        "export-csv" => export(&args),
+        "import-csv" => import(&args),
+        "copy" => copy(&args),
+        "convert-repeaters" => convert_repeaters(&args),
+        "list-drivers" => {
+            let mut drivers = list_drivers();
+            drivers.sort_by_key(|d| d.full_name());
+            for driver in drivers {
+                let mode = if driver.is_clone_mode {
+                    "clone"
+                } else {
+                    "command"
+                };
+                println!("{:<24} {} mode", driver.full_name(), mode);
+            }
+            Ok(())
+        }
+        "list-ports" => {
+            let ports = list_ports()?;
+            if ports.is_empty() {
+                eprintln!("No serial ports found");
+            }
+            for port in ports {
+                println!("{}", port);
+            }
+            Ok(())
+        }
+        command => {
+            eprintln!("Unknown command: {}", command);
+            print_usage(&program());
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Read memories from a radio and save them as an image (or CSV)
+async fn download(args: &Args) -> anyhow::Result<()> {
+    let port = args.port()?;
+    let info = radio_driver(args.radio.as_deref())?;
+    let output = args.output()?;
+
+    let (memories, mmap) = download_from_radio(
+        port.to_string(),
+        info.vendor.clone(),
+        info.model.clone(),
+        progress(),
+    )
+    .await?;
+    eprintln!();
+
+    if is_csv(output) {
+        export_csv(output, &memories)?;
+    } else if mmap.is_empty() {
+        bail!(
+            "{} has no memory image; download to a .csv file instead",
+            info.full_name()
+        );
+    } else {
+        save_image(output, &mmap, &info)?;
+    }
+
+    let used = memories.iter().filter(|m| !m.empty).count();
+    println!(
+        "Saved {} memories from {} to {}",
+        used,
+        info.full_name(),
+        output
+    );
+    Ok(())
+}
+
+/// Write an image (or CSV) to a radio
+async fn upload(args: &Args) -> anyhow::Result<()> {
+    let port = args.port()?;
+    let file = args.file("<file>")?;
+
+    let (info, mmap, mut memories) = if is_csv(file) {
+        let info = radio_driver(args.radio.as_deref())?;
+        let features = info
+            .create()
+            .map(|driver| driver.radio().get_features())
+            .unwrap_or_default();
+        let memories = read_csv(file)?
+            .iter()
+            .map(|mem| coerce(&features, mem))
+            .collect();
+        // Clone-mode radios write a whole image, so the CSV is merged into a base image
+        let mmap = match (&args.image, info.is_clone_mode) {
+            (Some(image), _) => read_image(image)?.0,
+            (None, false) => MemoryMap::new(vec![]),
+            (None, true) => bail!(
+                "{} is programmed from a full image; pass --image <base.img>",
+                info.full_name()
+            ),
+        };
+        (info, mmap, memories)
+    } else {
+        let (info, mut radio, mmap) = load_image(file, args.radio.as_deref())?;
+        (info, mmap, radio.get_memories()?)
+    };
+
+    // Command-mode radios only receive memories flagged as modified
+    for mem in &mut memories {
+        mem.modified = true;
+    }
+
+    upload_to_radio(
+        port.to_string(),
+        mmap,
+        memories,
+        info.vendor.clone(),
+        info.model.clone(),
+        progress(),
+    )
+    .await?;
+    eprintln!();
+
+    println!("Uploaded {} to {}", file, info.full_name());
+    Ok(())
+}
+
+/// Export the memories in an image to CSV
+fn export(args: &Args) -> anyhow::Result<()> {
+    let file = args.file("<image>")?;
+    let output = args.output()?;
+
+    let (_, mut radio, _) = load_image(file, args.radio.as_deref())?;
+    let memories = radio.get_memories()?;
+    export_csv(output, &memories)?;
+
+    let used = memories.iter().filter(|m| !m.empty).count();
+    println!("Exported {} memories to {}", used, output);
+    Ok(())
+}
+
+/// Merge CSV memories into a base image and save the result
+fn import(args: &Args) -> anyhow::Result<()> {
+    let file = args.file("<csv>")?;
+    let output = args.output()?;
+    let image = args
+        .image
+        .as_deref()
+        .ok_or_else(|| anyhow!("--image <base.img> is required"))?;
+
+    let (info, mut radio, _) = load_image(image, args.radio.as_deref())?;
+    let features = radio.get_features();
+    let (first, last) = features.memory_bounds;
+
+    let mut imported = 0;
+    for mem in read_csv(file)? {
+        if mem.empty {
+            continue;
+        }
+        if mem.number < first || mem.number > last {
+            eprintln!(
+                "Skipping memory #{}: {} only has memories {}-{}",
+                mem.number,
+                info.full_name(),
+                first,
+                last
+            );
+            continue;
+        }
+        let mem = coerce(&features, &mem);
+        radio
+            .set_memory(&mem)
+            .with_context(|| format!("Failed to update memory #{}", mem.number))?;
+        imported += 1;
+    }
+
+    let mmap = radio
+        .mmap()
+        .ok_or_else(|| anyhow!("Memory map not available after update"))?;
+    save_image(output, mmap, &info)?;
+
+    println!("Imported {} memories into {}", imported, output);
+    Ok(())
+}
+
+/// Convert a RepeaterBook or RadioReference export to CHIRP CSV
+fn convert_repeaters(args: &Args) -> anyhow::Result<()> {
+    let file = args.file("<export.json|export.csv>")?;
+    let output = args.output()?;
+
+    let report = import_repeaters(file)?;
+    if !report.is_clean() {
+        eprintln!("{}: {}", file, report);
+    }
+    export_csv(output, &report.memories)?;
+
+    println!(
+        "Converted {} repeaters to {}",
+        report.memories.len(),
+        output
+    );
+    Ok(())
+}
+
+/// Copy the memories of one image into another radio's image
+fn copy(args: &Args) -> anyhow::Result<()> {
+    let file = args.file("<source.img>")?;
+    let image = args
+        .image
+        .as_deref()
+        .ok_or_else(|| anyhow!("--image <target.img> is required"))?;
+
+    let filter = copy_filter(args)?;
+
+    let (source_info, mut source, _) = load_image(file, None)?;
+    let (info, mut target, _) = load_image(image, args.radio.as_deref())?;
+    let features = target.get_features();
+    let placement = if args.keep_numbers {
+        Placement::KeepNumbers
+    } else {
+        Placement::Sequential(features.memory_bounds.0)
+    };
+    let plan = CopyPlan::new(&source.get_memories()?, &features, &filter, placement);
+
+    println!(
+        "{} -> {}: {} memories",
+        source_info.full_name(),
+        info.full_name(),
+        plan.mappings.len()
+    );
+    for mapping in &plan.mappings {
+        let mem = &mapping.memory;
+        println!(
+            "  #{:<5} -> #{:<4} {:<16} {} {}",
+            mapping.source,
+            mapping.target,
+            mem.name,
+            Memory::format_freq(mem.freq),
+            mem.mode
+        );
+        for msg in &mapping.messages {
+            println!("      {}", msg.message());
+        }
+    }
+    for skipped in &plan.unplaced {
+        println!(
+            "  #{:<5} skipped  {:<16} {}",
+            skipped.source, skipped.name, skipped.reason
+        );
+    }
+
+    let Some(output) = &args.output else {
+        println!("Preview only; pass -o <file.img> to write the result");
+        return Ok(());
+    };
+    let mmap = plan.apply(target.as_mut())?;
+    save_image(output, &mmap, &info)?;
+    println!("Saved {} memories to {}", plan.mappings.len(), output);
+    Ok(())
+}
+
+/// Build the copy filter from --range, --band, --mode, --bank and --name
+fn copy_filter(args: &Args) -> anyhow::Result<CopyFilter> {
+    let mut filter = CopyFilter::new();
+    if let Some(range) = &args.range {
+        let (first, last) = range
+            .split_once('-')
+            .and_then(|(a, b)| Some((a.trim().parse().ok()?, b.trim().parse().ok()?)))
+            .ok_or_else(|| anyhow!("--range must look like 1-128, got {}", range))?;
+        filter = filter.with_range(first, last);
+    }
+    for band in args.band.iter().flat_map(|bands| bands.split(',')) {
+        let (low, high) = band
+            .split_once('-')
+            .and_then(|(a, b)| Some((Memory::parse_freq(a).ok()?, Memory::parse_freq(b).ok()?)))
+            .filter(|(low, high)| low < high)
+            .ok_or_else(|| anyhow!("--band must look like 144-148 (MHz), got {}", band))?;
+        filter = filter.with_band(low, high);
+    }
+    for mode in args.mode.iter().flat_map(|modes| modes.split(',')) {
+        let mode = mode
+            .trim()
+            .parse()
+            .map_err(|_| anyhow!("Unknown mode for --mode: {}", mode))?;
+        filter = filter.with_mode(mode);
+    }
+    if let Some(bank) = &args.bank {
+        let bank = bank
+            .trim()
+            .parse()
+            .map_err(|_| anyhow!("--bank must be a bank number, got {}", bank))?;
+        filter = filter.with_bank(bank);
+    }
+    if let Some(name) = &args.name {
+        filter = filter.with_name(name.as_str());
+    }
+    Ok(filter)
+}
+
+/// Adapt a CSV memory to the target radio, reporting what changed
+fn coerce(features: &RadioFeatures, mem: &Memory) -> Memory {
+    let (mem, msgs) = features.coerce_memory(mem);
+    for msg in msgs {
+        eprintln!("Memory #{}: {}", mem.number, msg.message());
+    }
+    mem
+}
+
+/// Import a CSV file, reporting skipped rows and defaulted fields
+fn read_csv(file: &str) -> anyhow::Result<Vec<Memory>> {
+    let report = import_csv(file)?;
+    if !report.is_clean() {
+        eprintln!("{}: {}", file, report);
+    }
+    Ok(report.memories)
+}
+
+/// Look up the driver named by --radio
+fn radio_driver(name: Option<&str>) -> anyhow::Result<DriverInfo> {
+    let name = name.ok_or_else(|| anyhow!("--radio is required"))?;
+    find_driver(name).ok_or_else(|| {
+        let mut models: Vec<String> = list_drivers().into_iter().map(|d| d.model).collect();
+        models.sort();
+        anyhow!(
+            "Unknown radio type: {} (supported: {})",
+            name,
+            models.join(", ")
+        )
+    })
+}
+
+/// Load an image and its clone-mode driver (from --radio, metadata or detection)
+fn load_image(
+    path: &str,
+    radio: Option<&str>,
+) -> anyhow::Result<(DriverInfo, Box<dyn DynCloneModeRadio>, MemoryMap)> {
+    let (mmap, metadata) = read_image(path)?;
+
+    let info = if radio.is_some() {
+        radio_driver(radio)?
+    } else if !metadata.vendor.is_empty() {
+        get_driver(&metadata.vendor, &metadata.model)
+            .ok_or_else(|| anyhow!("Unknown radio: {} {}", metadata.vendor, metadata.model))?
+    } else {
+        match detect_image(mmap.get_packed(), path) {
+            Ok(info) => info,
+            Err(DetectError::Ambiguous(candidates)) => {
+                eprintln!(
+                    "Note: Image matches several radios, using {}. Use --radio to override.",
+                    candidates[0].full_name()
+                );
+                candidates[0].clone()
+            }
+            Err(e) => bail!("{}. Use --radio to choose a driver.", e),
+        }
+    };
+
+    let mut driver = info
+        .create()
+        .and_then(|driver| driver.into_clone_mode())
+        .ok_or_else(|| anyhow!("{} can't be loaded from a memory image", info.full_name()))?;
+    driver.load_mmap(&mmap)?;
+    Ok((info, driver, mmap))
+}
+
+/// Read a CHIRP .img or Kenwood MCP (.d74/.d75) file
+fn read_image(path: &str) -> anyhow::Result<(MemoryMap, Metadata)> {
+    let result = if d75::is_mcp_file(path) {
+        load_d75(path).map_err(anyhow::Error::from)
+    } else {
+        load_img(path).map_err(anyhow::Error::from)
+    };
+    result.with_context(|| format!("Failed to load {}", path))
+}
+
+/// Save an image in the format given by the file extension
+fn save_image(path: &str, mmap: &MemoryMap, info: &DriverInfo) -> anyhow::Result<()> {
+    let mut metadata = Metadata::new(&info.vendor, &info.model);
+    metadata.variant = image_variant(mmap, info).unwrap_or_default();
+    if d75::is_mcp_file(path) {
+        save_d75(path, mmap, &metadata)?;
+    } else {
+        save_img(path, mmap, &metadata)?;
+    }
+    Ok(())
+}
+
+/// Sub-model the driver detects in an image, if any
+fn image_variant(mmap: &MemoryMap, info: &DriverInfo) -> Option<String> {
+    let mut driver = info.create()?.into_clone_mode()?;
+    driver.load_mmap(mmap).ok()?;
+    driver.variant().map(str::to_string)
+}
+
+/// Progress line on stderr
+fn progress() -> ProgressFn {
+    Arc::new(|current, total, message| {
+        eprint!("\r[{}/{}] {:<40}", current, total, message);
+        std::io::stderr().flush().ok();
+    })
+}
+
+fn is_csv(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
+}
+
+fn program() -> String {
+    env::args()
+        .next()
+        .unwrap_or_else(|| "chirp-cli".to_string())
+}
+
+/// Parse command line arguments
+fn parse_args() -> anyhow::Result<Args> {
+    let program = program();
+    let args = parse_arg_list(&program, env::args().skip(1))?;
+
+    if args.command.is_empty() {
+        print_usage(&program);
+        std::process::exit(1);
+    }
+
+    Ok(args)
+}
+
+/// Parse the arguments after the program name
+fn parse_arg_list(program: &str, mut iter: impl Iterator<Item = String>) -> anyhow::Result<Args> {
+    let mut args = Args::default();
+
+    while let Some(arg) = iter.next() {
+        let slot = match arg.as_str() {
+            "--port" | "-p" => &mut args.port,
+            "--radio" | "-r" => &mut args.radio,
+            "--output" | "-o" => &mut args.output,
+            "--image" | "-i" => &mut args.image,
+            "--range" => &mut args.range,
+            "--band" => &mut args.band,
+            "--mode" => &mut args.mode,
+            "--bank" => &mut args.bank,
+            "--name" => &mut args.name,
+            "--keep-numbers" => {
+                args.keep_numbers = true;
+                continue;
+            }
+            "--help" | "-h" => {
+                print_usage(program);
+                std::process::exit(0);
+            }
+            flag if flag.starts_with('-') => {
+                eprintln!("Unknown flag: {}", flag);
+                print_usage(program);
+                std::process::exit(1);
+            }
+            _ if args.command.is_empty() => {
+                args.command = arg;
+                continue;
+            }
+            _ => {
+                args.files.push(arg);
+                continue;
+            }
+        };
+        *slot = Some(
+            iter.next()
+                .ok_or_else(|| anyhow!("{} requires a value", arg))?,
+        );
+    }
+
+    Ok(args)
+}
+
+/// Print usage information
+fn print_usage(program: &str) {
+    eprintln!("Usage: {} <command> [OPTIONS]", program);
+    eprintln!();
+    eprintln!("Commands:");
+    eprintln!("  download --port <port> --radio <type> -o <file.img|file.csv>");
+    eprintln!("  upload --port <port> <file.img>");
+    eprintln!("  upload --port <port> --radio <type> [--image <base.img>] <file.csv>");
+    eprintln!("  export-csv <file.img> -o <file.csv>");
+    eprintln!("  import-csv <file.csv> --image <base.img> -o <file.img>");
+    eprintln!(
+        "  copy <source.img> --image <target.img> [FILTERS] [--keep-numbers] [-o <file.img>]"
+    );
+    eprintln!("  convert-repeaters <export.json|export.csv> -o <file.csv>");
+    eprintln!("  list-drivers");
+    eprintln!("  list-ports");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  -p, --port <port>      Serial port, tcp://host:port or rfc2217://host:port");
+    eprintln!("  -r, --radio <type>     Radio model (e.g. uv5r, thd75, \"Icom IC-9700\")");
+    eprintln!("  -o, --output <file>    Output file (.img, .csv, or .d74/.d75 for Kenwood MCP)");
+    eprintln!("  -i, --image <file>     Base image for CSV import/upload, or copy target");
+    eprintln!("      --range <a-b>      Source memories to copy (default: all)");
+    eprintln!("      --band <lo-hi>     Copy only these frequencies in MHz (comma-separated)");
+    eprintln!("      --mode <mode>      Copy only these modes, e.g. FM,NFM");
+    eprintln!("      --bank <n>         Copy only memories in this bank or group");
+    eprintln!("      --name <text>      Copy only names containing this text");
+    eprintln!(
+        "      --keep-numbers     Keep source memory numbers instead of packing from the first"
+    );
+    eprintln!("  -h, --help             Show this help message");
+    eprintln!();
+    eprintln!("Examples:");
+    eprintln!(
+        "  {} download --port /dev/ttyUSB0 --radio uv5r -o backup.img",
+        program
+    );
+    eprintln!("  {} export-csv backup.img -o channels.csv", program);
+    eprintln!(
+        "  {} import-csv channels.csv --image backup.img -o new.img",
+        program
+    );
+    eprintln!("  {} upload --port /dev/ttyUSB0 new.img", program);
+    eprintln!(
+        "  {} copy d75.img --image uv5r.img --range 0-199 -o uv5r-new.img",
+        program
+    );
+    eprintln!(
+        "  {} copy d75.img --image ft60.img --band 144-148 --mode FM --keep-numbers",
+        program
+    );
+    eprintln!(
+        "  {} convert-repeaters repeaterbook.json -o repeaters.csv",
+        program
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chirp_rs::core::Mode;
+
+    fn parse(args: &[&str]) -> Args {
+        parse_arg_list("chirp-cli", args.iter().map(|arg| arg.to_string())).unwrap()
+    }
+
+    #[test]
+    fn test_copy_flags() {
+        let args = parse(&[
+            "copy",
+            "d75.img",
+            "--image",
+            "uv5r.img",
+            "--range",
+            "0-199",
+            "--band",
+            "144-148,420-450",
+            "--mode",
+            "FM,NFM",
+            "--bank",
+            "3",
+            "--name",
+            "rpt",
+            "--keep-numbers",
+        ]);
+        assert_eq!(args.command, "copy");
+        assert_eq!(args.files, vec!["d75.img"]);
+        assert!(args.keep_numbers);
+
+        let filter = copy_filter(&args).unwrap();
+        assert_eq!(filter.range, Some((0, 199)));
+        assert_eq!(
+            filter.bands,
+            vec![(144_000_000, 148_000_000), (420_000_000, 450_000_000)]
+        );
+        assert_eq!(filter.modes, vec![Mode::Fm, Mode::Nfm]);
+        assert_eq!(filter.bank, Some(3));
+        assert_eq!(filter.name.as_deref(), Some("rpt"));
+
+        let mut mem = Memory::new(12);
+        mem.freq = 146_520_000;
+        mem.mode = Mode::Fm;
+        mem.bank = 3;
+        mem.name = "RPT1".to_string();
+        assert!(filter.matches(&mem));
+        mem.freq = 222_000_000;
+        assert!(!filter.matches(&mem));
+    }
+
+    #[test]
+    fn test_copy_flags_defaults() {
+        let args = parse(&["copy", "d75.img", "--image", "uv5r.img"]);
+        assert!(!args.keep_numbers);
+        let filter = copy_filter(&args).unwrap();
+        assert_eq!(filter.range, None);
+        assert!(filter.bands.is_empty());
+        assert!(filter.modes.is_empty());
+    }
+
+    #[test]
+    fn test_copy_flags_invalid() {
+        for (flag, value) in [
+            ("--band", "148-144"),
+            ("--band", "2m"),
+            ("--mode", "XYZ"),
+            ("--bank", "A"),
+            ("--range", "1"),
+        ] {
+            let args = parse(&["copy", "d75.img", flag, value]);
+            assert!(copy_filter(&args).is_err(), "{} {}", flag, value);
+        }
+        assert!(parse_arg_list(
+            "chirp-cli",
+            ["copy", "--band"].map(String::from).into_iter()
+        )
+        .is_err());
+    }
+}
@@ -8,10 +8,11 @@
 //! - Comprehensive tone information (CTCSS, DTCS)
 //! - Raw memory/bank data (with --raw flag)
 
-use chirp_rs::drivers::thd75::THD75Radio;
-use chirp_rs::drivers::uv5r::UV5RRadio;
-use chirp_rs::drivers::{CloneModeRadio, Radio};
-use chirp_rs::formats::img::load_img;
+use chirp_rs::core::{Duplex, Mode, Skip, ToneMode};
+use chirp_rs::drivers::{
+    detect_image, find_driver, get_driver, init_drivers, list_drivers, DetectError, Radio,
+};
+use chirp_rs::formats::{d75, load_d75, load_img};
 use chirp_rs::memmap::MemoryMap;
 use std::env;
 
@@ -25,10 +26,15 @@ struct Args {
 
 fn main() -> anyhow::Result<()> {
     let args = parse_args()?;
+    init_drivers();
 
-    // Load file (handles both .img and raw dumps)
+    // Load file (handles .img, Kenwood MCP .d74/.d75 and raw dumps)
     println!("Loading file: {}", args.file);
-    let (mmap, metadata) = load_img(&args.file)?;
+    let (mmap, metadata) = if d75::is_mcp_file(&args.file) {
+        load_d75(&args.file)?
+    } else {
+        load_img(&args.file)?
+    };
 
     // Display metadata if available (indicates .img format)
     if !metadata.vendor.is_empty() {
@@ -40,48 +46,55 @@ fn main() -> anyhow::Result<()> {
     println!("Memory map size: {} bytes\n", mmap.len());
 
     // Determine which driver to use based on --radio arg, metadata, or default
-    let (vendor, model) = if let Some(ref radio) = args.radio_type {
+    let driver_info = if let Some(ref radio) = args.radio_type {
         // Use explicit radio type from command line
-        match radio.to_lowercase().as_str() {
-            "uv5r" | "uv-5r" => ("Baofeng".to_string(), "UV-5R".to_string()),
-            "thd75" | "th-d75" => ("Kenwood".to_string(), "TH-D75".to_string()),
-            "thd74" | "th-d74" => ("Kenwood".to_string(), "TH-D74".to_string()),
-            _ => {
+        match find_driver(radio) {
+            Some(info) => info,
+            None => {
+                let mut models: Vec<String> = list_drivers().into_iter().map(|d| d.model).collect();
+                models.sort();
                 eprintln!("Unknown radio type: {}", radio);
-                eprintln!("Supported types: uv5r, thd75, thd74");
+                eprintln!("Supported types: {}", models.join(", "));
                 std::process::exit(1);
             }
         }
     } else if !metadata.vendor.is_empty() {
         // Use metadata from .img file
-        (metadata.vendor.clone(), metadata.model.clone())
+        get_driver(&metadata.vendor, &metadata.model).ok_or_else(|| {
+            anyhow::anyhow!("Unknown radio: {} {}", metadata.vendor, metadata.model)
+        })?
     } else {
-        // Auto-detect based on file size or default to TH-D75
-        if mmap.len() <= 0x2000 {
-            // Small file, likely UV-5R (6152 bytes = 0x1808)
-            println!("Note: Auto-detected UV-5R based on file size. Use --radio to override.");
-            ("Baofeng".to_string(), "UV-5R".to_string())
-        } else {
-            // Large file, likely TH-D75
-            ("Kenwood".to_string(), "TH-D75".to_string())
+        // Auto-detect by asking every driver whether it recognizes the image
+        match detect_image(mmap.get_packed(), &args.file) {
+            Ok(info) => {
+                println!(
+                    "Note: Auto-detected {}. Use --radio to override.",
+                    info.full_name()
+                );
+                info
+            }
+            Err(DetectError::Ambiguous(candidates)) => {
+                let names: Vec<String> = candidates.iter().map(|d| d.full_name()).collect();
+                println!(
+                    "Note: Image matches several radios ({}), using {}. Use --radio to override.",
+                    names.join(", "),
+                    names[0]
+                );
+                candidates[0].clone()
+            }
+            Err(e) => anyhow::bail!("{}. Use --radio to choose a driver.", e),
         }
     };
+    let (vendor, model) = (driver_info.vendor.clone(), driver_info.model.clone());
 
-    // Create appropriate radio driver and get memories
-    let (memories, has_banks) = match (vendor.to_lowercase().as_str(), model.as_str()) {
-        ("baofeng", "UV-5R") => {
-            let mut radio = UV5RRadio::new();
-            radio.process_mmap(&mmap)?;
-            let mems = get_memories_filtered(&mut radio, &args)?;
-            (mems, false) // UV-5R has no banks
-        }
-        ("kenwood", "TH-D75") | ("kenwood", "TH-D74") | _ => {
-            let mut radio = THD75Radio::new();
-            radio.process_mmap(&mmap)?;
-            let mems = get_memories_filtered(&mut radio, &args)?;
-            (mems, true) // TH-D75 has banks
-        }
-    };
+    // Create the radio driver through the registry and get memories
+    let mut radio = driver_info
+        .create()
+        .and_then(|driver| driver.into_clone_mode())
+        .ok_or_else(|| anyhow::anyhow!("{} can't be loaded from a memory image", model))?;
+    radio.load_mmap(&mmap)?;
+    let memories = get_memories_filtered(radio.as_mut(), &args)?;
+    let has_banks = radio.get_features().has_bank;
 
     // Display bank names for radios that have them
     if !metadata.vendor.is_empty() && has_banks {
@@ -219,7 +232,7 @@ fn print_usage(program: &str) {
     eprintln!();
     eprintln!("Options:");
     eprintln!("  --raw               Show raw memory/bank data (debug mode)");
-    eprintln!("  --radio <type>      Force radio type (uv5r, thd75) for raw files");
+    eprintln!("  --radio <type>      Force radio type (uv5r, thd75) instead of auto-detecting");
     eprintln!("  -h, --help          Show this help message");
     eprintln!();
     eprintln!("Examples:");
@@ -308,15 +321,15 @@ fn print_memory(
     println!("  Mode:         {}", mem.mode);
     println!(
         "  Duplex:       {}",
-        if mem.duplex.is_empty() {
+        if mem.duplex == Duplex::Simplex {
             "none"
         } else {
-            &mem.duplex
+            mem.duplex.as_str()
         }
     );
 
     // Show D-STAR fields for DV mode
-    if mem.mode == "DV" {
+    if mem.mode == Mode::Dv {
         if !mem.dv_urcall.is_empty() {
             println!("  URCALL:       {}", mem.dv_urcall);
         }
@@ -333,24 +346,24 @@ fn print_memory(
         // Show tone information for non-DV modes
         println!(
             "  Tone Mode:    {}",
-            if mem.tmode.is_empty() {
+            if mem.tmode == ToneMode::None {
                 "none"
             } else {
-                &mem.tmode
+                mem.tmode.as_str()
             }
         );
 
-        match mem.tmode.as_str() {
-            "Tone" => {
+        match mem.tmode {
+            ToneMode::Tone => {
                 // Encode only - just show TX tone
                 println!("  CTCSS TX:     {} Hz", mem.rtone);
             }
-            "TSQL" => {
+            ToneMode::Tsql => {
                 // Tone squelch - show both TX and RX
                 println!("  CTCSS TX:     {} Hz", mem.rtone);
                 println!("  CTCSS RX:     {} Hz", mem.ctone);
             }
-            "DTCS" => {
+            ToneMode::Dtcs => {
                 // Digital codes
                 println!("  DTCS:         {}", mem.dtcs);
             }
@@ -362,10 +375,10 @@ fn print_memory(
 
     println!(
         "  Skip:         {}",
-        if mem.skip.is_empty() {
+        if mem.skip == Skip::None {
             "none"
         } else {
-            &mem.skip
+            mem.skip.as_str()
         }
     );
     println!("  Tuning Step:  {} kHz", mem.tuning_step);
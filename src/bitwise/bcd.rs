@@ -101,10 +101,10 @@ pub fn int_to_bcd_le(value: u64, num_bytes: usize) -> Result<Vec<u8>> {
     let mut result = vec![0u8; num_bytes];
     let mut remaining = value;
 
-    for i in 0..num_bytes {
+    for byte in result.iter_mut() {
         let two_digits = (remaining % 100) as u8;
         remaining /= 100;
-        result[i] = digits_to_bcd_byte(two_digits / 10, two_digits % 10)?;
+        *byte = digits_to_bcd_byte(two_digits / 10, two_digits % 10)?;
     }
 
     if remaining > 0 {
@@ -3,8 +3,9 @@
 use serde::{Deserialize, Serialize};
 
 /// Endianness for multi-byte values
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum Endianness {
+    #[default]
     Big,
     Little,
 }
@@ -19,12 +20,6 @@ impl Endianness {
     }
 }
 
-impl Default for Endianness {
-    fn default() -> Self {
-        Endianness::Big
-    }
-}
-
 /// A trait for types that can be read from binary data
 pub trait FromBytes: Sized {
     fn from_bytes_be(data: &[u8]) -> Result<Self, String>;
@@ -55,7 +50,7 @@ pub trait ToBytes {
 
 impl FromBytes for u8 {
     fn from_bytes_be(data: &[u8]) -> Result<Self, String> {
-        data.get(0)
+        data.first()
             .copied()
             .ok_or_else(|| "Insufficient data".to_string())
     }
@@ -0,0 +1,378 @@
+// Channel copy - map one radio's memories onto another radio
+// UI-independent: builds a preview of where each channel lands and what
+// had to change, then writes the plan through the target driver.
+//
+// Typical use is programming a mixed fleet from one master list, e.g. the
+// TH-D75's 1000 channels filtered down to the UV-5R's 128 slots.
+
+use crate::core::{Memory, Mode, RadioFeatures, ValidationMessage};
+use crate::drivers::{DynCloneModeRadio, RadioError};
+use crate::memmap::MemoryMap;
+use crate::radio_ops::{RadioOpError, RadioOpResult};
+use std::collections::BTreeSet;
+
+/// Which source memories to copy (an empty filter copies every used memory)
+#[derive(Debug, Clone, Default)]
+pub struct CopyFilter {
+    /// Source locations, inclusive
+    pub range: Option<(u32, u32)>,
+    /// Frequency ranges (low_hz, high_hz); a memory must fall in one of them
+    pub bands: Vec<(u64, u64)>,
+    /// Modes to copy
+    pub modes: Vec<Mode>,
+    /// Bank or group number
+    pub bank: Option<u8>,
+    /// Case-insensitive name substring
+    pub name: Option<String>,
+}
+
+impl CopyFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_range(mut self, first: u32, last: u32) -> Self {
+        self.range = Some((first, last));
+        self
+    }
+
+    pub fn with_band(mut self, low_hz: u64, high_hz: u64) -> Self {
+        self.bands.push((low_hz, high_hz));
+        self
+    }
+
+    pub fn with_mode(mut self, mode: Mode) -> Self {
+        self.modes.push(mode);
+        self
+    }
+
+    pub fn with_bank(mut self, bank: u8) -> Self {
+        self.bank = Some(bank);
+        self
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Check whether a source memory passes the filter
+    pub fn matches(&self, mem: &Memory) -> bool {
+        if mem.empty {
+            return false;
+        }
+        if let Some((first, last)) = self.range {
+            if mem.number < first || mem.number > last {
+                return false;
+            }
+        }
+        if !self.bands.is_empty()
+            && !self
+                .bands
+                .iter()
+                .any(|(lo, hi)| mem.freq >= *lo && mem.freq < *hi)
+        {
+            return false;
+        }
+        if !self.modes.is_empty() && !self.modes.contains(&mem.mode) {
+            return false;
+        }
+        if self.bank.is_some_and(|bank| bank != mem.bank) {
+            return false;
+        }
+        if let Some(name) = &self.name {
+            if !mem.name.to_lowercase().contains(&name.to_lowercase()) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Where copied memories go in the target radio
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placement {
+    /// Fill consecutive locations starting at the given one
+    Sequential(u32),
+    /// Keep each memory's source location
+    KeepNumbers,
+}
+
+/// One source memory and where it lands
+#[derive(Debug, Clone)]
+pub struct ChannelMapping {
+    /// Source location
+    pub source: u32,
+    /// Target location
+    pub target: u32,
+    /// The memory as it will be written to the target
+    pub memory: Memory,
+    /// Changes made to fit the target, and remaining warnings
+    pub messages: Vec<ValidationMessage>,
+}
+
+/// A source memory that won't be copied
+#[derive(Debug, Clone)]
+pub struct Unplaced {
+    pub source: u32,
+    pub name: String,
+    pub reason: String,
+}
+
+/// Proposed copy from one radio to another
+#[derive(Debug, Clone)]
+pub struct CopyPlan {
+    /// Memories to write, in target order
+    pub mappings: Vec<ChannelMapping>,
+    /// Memories that passed the filter but can't be copied
+    pub unplaced: Vec<Unplaced>,
+    /// Erase target memories that aren't in the plan
+    pub clear_unused: bool,
+}
+
+impl CopyPlan {
+    /// Map filtered source memories onto the target radio
+    pub fn new(
+        source: &[Memory],
+        target: &RadioFeatures,
+        filter: &CopyFilter,
+        placement: Placement,
+    ) -> Self {
+        let (first, last) = target.memory_bounds;
+        let mut next = match placement {
+            Placement::Sequential(start) => start.max(first),
+            Placement::KeepNumbers => first,
+        };
+        let mut plan = Self {
+            mappings: Vec::new(),
+            unplaced: Vec::new(),
+            clear_unused: false,
+        };
+        // Source lists from multi-band radios can repeat a location number
+        let mut used = BTreeSet::new();
+
+        for src in source.iter().filter(|mem| filter.matches(mem)) {
+            let unplaced = |reason: String| Unplaced {
+                source: src.number,
+                name: src.name.clone(),
+                reason,
+            };
+
+            let (mut mem, mut messages) = target.coerce_memory(src);
+            if !target.has_bank {
+                mem.bank = 0;
+            }
+
+            // Validate before placing so rejected memories don't use up a location
+            let mut checked = mem.clone();
+            checked.number = first;
+            let validation = target.validate_memory(&checked);
+            let error = validation
+                .iter()
+                .chain(&messages)
+                .find(|msg| msg.is_error())
+                .map(|msg| msg.message().to_string());
+            if let Some(error) = error {
+                plan.unplaced.push(unplaced(error));
+                continue;
+            }
+
+            let number = match placement {
+                Placement::KeepNumbers if src.number < first || src.number > last => {
+                    plan.unplaced.push(unplaced(format!(
+                        "Location {} is outside the target's range {}-{}",
+                        src.number, first, last
+                    )));
+                    continue;
+                }
+                Placement::KeepNumbers if used.contains(&src.number) => {
+                    plan.unplaced.push(unplaced(format!(
+                        "Location {} is already taken by another source memory",
+                        src.number
+                    )));
+                    continue;
+                }
+                Placement::KeepNumbers => src.number,
+                Placement::Sequential(_) if next > last => {
+                    plan.unplaced.push(unplaced("No free location".to_string()));
+                    continue;
+                }
+                Placement::Sequential(_) => {
+                    next += 1;
+                    next - 1
+                }
+            };
+
+            used.insert(number);
+            mem.number = number;
+            mem.modified = true;
+            messages.extend(validation.into_iter().filter(|msg| msg.is_warning()));
+            plan.mappings.push(ChannelMapping {
+                source: src.number,
+                target: number,
+                memory: mem,
+                messages,
+            });
+        }
+
+        plan
+    }
+
+    /// Erase every other target memory when the plan is applied
+    pub fn with_clear_unused(mut self) -> Self {
+        self.clear_unused = true;
+        self
+    }
+
+    /// Write the plan through the target driver and return the new image
+    pub fn apply(&self, target: &mut dyn DynCloneModeRadio) -> RadioOpResult<MemoryMap> {
+        let update_error = |number| move |source| RadioOpError::UpdateMemory { number, source };
+
+        if self.clear_unused {
+            let mapped: BTreeSet<u32> = self.mappings.iter().map(|m| m.target).collect();
+            let (first, last) = target.get_features().memory_bounds;
+            for number in (first..=last).filter(|n| !mapped.contains(n)) {
+                let mem = target.get_memory(number).map_err(update_error(number))?;
+                if mem.is_some_and(|mem| !mem.empty) {
+                    target.delete_memory(number).map_err(update_error(number))?;
+                }
+            }
+        }
+
+        for mapping in &self.mappings {
+            target
+                .set_memory(&mapping.memory)
+                .map_err(update_error(mapping.target))?;
+        }
+
+        target.mmap().cloned().ok_or_else(|| {
+            RadioOpError::MemoryMap(RadioError::Radio(
+                "Memory map not available after update".to_string(),
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Duplex, ToneMode};
+    use crate::drivers::{uv5r::UV5RRadio, Radio};
+    use crate::formats::load_img;
+
+    /// TH-D75 style master list: 2m and 70cm FM, D-STAR and an airband channel
+    fn master_list() -> Vec<Memory> {
+        let channel = |number: u32, freq: u64, name: &str, mode: Mode| {
+            let mut mem = Memory::new(number);
+            mem.freq = freq;
+            mem.name = name.to_string();
+            mem.mode = mode;
+            mem
+        };
+        let mut repeater = channel(10, 146_940_000, "W6CX Repeater", Mode::Fm);
+        repeater.duplex = Duplex::Minus;
+        repeater.offset = 600_000;
+        repeater.tmode = ToneMode::Tone;
+        repeater.rtone = 100.0;
+
+        vec![
+            channel(0, 146_520_000, "Calling", Mode::Fm),
+            repeater,
+            Memory::new_empty(11),
+            channel(500, 145_230_000, "DV Node", Mode::Dv),
+            channel(900, 446_000_000, "UHF Calling", Mode::Nfm),
+            channel(999, 118_100_000, "Tower", Mode::Am),
+        ]
+    }
+
+    fn uv5r() -> UV5RRadio {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("test_data/Baofeng_UV-5R_20260211.img");
+        let (mmap, _) = load_img(path).unwrap();
+        let mut radio = UV5RRadio::new();
+        radio.load_mmap(&mmap).unwrap();
+        radio
+    }
+
+    #[test]
+    fn test_plan_sequential() {
+        let features = UV5RRadio::new().get_features();
+        let plan = CopyPlan::new(
+            &master_list(),
+            &features,
+            &CopyFilter::new(),
+            Placement::Sequential(20),
+        );
+
+        let placed: Vec<(u32, u32)> = plan.mappings.iter().map(|m| (m.source, m.target)).collect();
+        assert_eq!(placed, vec![(0, 20), (10, 21), (500, 22), (900, 23)]);
+
+        // Name truncated and DV converted to FM, both reported
+        assert_eq!(plan.mappings[1].memory.name, "W6CX RE");
+        assert_eq!(plan.mappings[2].memory.mode, Mode::Fm);
+        assert!(!plan.mappings[2].messages.is_empty());
+
+        // Airband AM can't be fixed up for a UV-5R
+        assert_eq!(plan.unplaced.len(), 1);
+        assert_eq!(plan.unplaced[0].source, 999);
+
+        // Keeping numbers only works for locations the target has
+        let plan = CopyPlan::new(
+            &master_list(),
+            &features,
+            &CopyFilter::new().with_band(144_000_000, 148_000_000),
+            Placement::KeepNumbers,
+        );
+        assert_eq!(plan.mappings.len(), 2);
+        assert_eq!(plan.unplaced[0].source, 500);
+
+        // The same location from two bands only lands once
+        let mut list = master_list();
+        let mut other_band = list[0].clone();
+        other_band.band = Some(2);
+        other_band.freq = 446_500_000;
+        list.push(other_band);
+        let plan = CopyPlan::new(&list, &features, &CopyFilter::new(), Placement::KeepNumbers);
+        let targets: Vec<u32> = plan.mappings.iter().map(|m| m.target).collect();
+        assert_eq!(targets.iter().filter(|&&n| n == 0).count(), 1);
+        assert!(plan
+            .unplaced
+            .iter()
+            .any(|u| u.source == 0 && u.reason.contains("already taken")));
+    }
+
+    #[test]
+    fn test_filter() {
+        let filter = CopyFilter::new().with_range(0, 100).with_name("call");
+        let list = master_list();
+        let matched: Vec<u32> = list
+            .iter()
+            .filter(|m| filter.matches(m))
+            .map(|m| m.number)
+            .collect();
+        assert_eq!(matched, vec![0]);
+
+        let filter = CopyFilter::new().with_mode(Mode::Nfm).with_mode(Mode::Am);
+        assert_eq!(list.iter().filter(|m| filter.matches(m)).count(), 2);
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut target = uv5r();
+        let features = target.get_features();
+        let filter = CopyFilter::new().with_mode(Mode::Fm);
+        let plan = CopyPlan::new(&master_list(), &features, &filter, Placement::Sequential(0))
+            .with_clear_unused();
+        let mmap = plan.apply(&mut target).unwrap();
+
+        let mut radio = UV5RRadio::new();
+        radio.load_mmap(&mmap).unwrap();
+        let mem = radio.get_memory(1).unwrap().unwrap();
+        assert_eq!(mem.freq, 146_940_000);
+        assert_eq!(mem.name, "W6CX RE");
+        assert_eq!(mem.duplex, Duplex::Minus);
+        assert_eq!(mem.tmode, ToneMode::Tone);
+        assert_eq!(radio.get_memory(0).unwrap().unwrap().name, "CALLING");
+        assert!(radio.get_memory(2).unwrap().is_none_or(|mem| mem.empty));
+    }
+}
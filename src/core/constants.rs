@@ -40,27 +40,6 @@ pub const ALL_DTCS_CODES: [u16; 512] = {
     codes
 };
 
-/// Radio modes - master list that should remain stable
-pub const MODES: &[&str] = &[
-    "WFM", "FM", "NFM", "AM", "NAM", "DV", "USB", "LSB", "CW", "RTTY", "DIG", "PKT", "NCW", "NCWR",
-    "CWR", "P25", "Auto", "RTTYR", "FSK", "FSKR", "DMR", "DN",
-];
-
-/// Tone modes
-pub const TONE_MODES: &[&str] = &["", "Tone", "TSQL", "DTCS", "DTCS-R", "TSQL-R", "Cross"];
-
-/// Cross-mode combinations
-pub const CROSS_MODES: &[&str] = &[
-    "Tone->Tone",
-    "DTCS->",
-    "->DTCS",
-    "Tone->DTCS",
-    "DTCS->Tone",
-    "->Tone",
-    "DTCS->DTCS",
-    "Tone->",
-];
-
 /// Tuning steps (in kHz)
 pub const TUNING_STEPS: &[f32] = &[
     5.0, 6.25, 10.0, 12.5, 15.0, 20.0, 25.0, 30.0, 50.0, 100.0, 125.0, 200.0, 9.0, 1.0, 2.5,
@@ -69,15 +48,6 @@ pub const TUNING_STEPS: &[f32] = &[
 /// Common tuning steps (default for RadioFeatures)
 pub const COMMON_TUNING_STEPS: &[f32] = &[5.0, 10.0, 15.0, 20.0, 25.0, 30.0, 50.0, 100.0];
 
-/// Skip values
-pub const SKIP_VALUES: &[&str] = &["", "S", "P"];
-
-/// Duplex modes
-pub const DUPLEX_MODES: &[&str] = &["", "+", "-", "split", "off"];
-
-/// DTCS polarity combinations
-pub const DTCS_POLARITIES: &[&str] = &["NN", "NR", "RN", "RR"];
-
 /// Validate a tone value
 pub fn is_valid_tone(tone: f32) -> bool {
     tone > 50.0 && tone < 300.0
@@ -88,26 +58,6 @@ pub fn is_valid_dtcs(code: u16) -> bool {
     ALL_DTCS_CODES.contains(&code)
 }
 
-/// Validate a mode
-pub fn is_valid_mode(mode: &str) -> bool {
-    MODES.contains(&mode)
-}
-
-/// Validate a tone mode
-pub fn is_valid_tone_mode(tmode: &str) -> bool {
-    TONE_MODES.contains(&tmode)
-}
-
-/// Validate duplex
-pub fn is_valid_duplex(duplex: &str) -> bool {
-    DUPLEX_MODES.contains(&duplex)
-}
-
-/// Validate skip value
-pub fn is_valid_skip(skip: &str) -> bool {
-    SKIP_VALUES.contains(&skip)
-}
-
 /// Character sets for name validation
 pub const CHARSET_UPPER_NUMERIC: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ 1234567890";
 pub const CHARSET_ALPHANUMERIC: &str =
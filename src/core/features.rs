@@ -2,6 +2,7 @@
 // Reference: chirp/chirp_common.py lines 891-1211
 
 use super::constants::*;
+use super::fields::{CrossMode, DtcsPolarity, Duplex, Mode, Skip, ToneMode};
 use super::memory::Memory;
 use super::power::PowerLevel;
 use serde::{Deserialize, Serialize};
@@ -37,18 +38,18 @@ pub struct RadioFeatures {
     pub has_implicit_calls: bool,
 
     // Valid values/lists
-    pub valid_modes: Vec<String>,
-    pub valid_tmodes: Vec<String>,
-    pub valid_duplexes: Vec<String>,
+    pub valid_modes: Vec<Mode>,
+    pub valid_tmodes: Vec<ToneMode>,
+    pub valid_duplexes: Vec<Duplex>,
     pub valid_tuning_steps: Vec<f32>,
     pub valid_bands: Vec<(u64, u64)>, // (low_hz, high_hz) pairs
-    pub valid_skips: Vec<String>,
+    pub valid_skips: Vec<Skip>,
     pub valid_power_levels: Vec<PowerLevel>,
     pub valid_characters: String,
     pub valid_name_length: usize,
-    pub valid_cross_modes: Vec<String>,
+    pub valid_cross_modes: Vec<CrossMode>,
     pub valid_tones: Vec<f32>,
-    pub valid_dtcs_pols: Vec<String>,
+    pub valid_dtcs_pols: Vec<DtcsPolarity>,
     pub valid_dtcs_codes: Vec<u16>,
     pub valid_special_chans: Vec<String>,
 
@@ -85,18 +86,18 @@ impl Default for RadioFeatures {
             has_implicit_calls: false,
 
             // Valid values - sensible defaults
-            valid_modes: MODES.iter().map(|s| s.to_string()).collect(),
+            valid_modes: Mode::ALL.to_vec(),
             valid_tmodes: Vec::new(),
-            valid_duplexes: vec!["".to_string(), "+".to_string(), "-".to_string()],
+            valid_duplexes: vec![Duplex::Simplex, Duplex::Plus, Duplex::Minus],
             valid_tuning_steps: COMMON_TUNING_STEPS.to_vec(),
             valid_bands: Vec::new(),
-            valid_skips: vec!["".to_string(), "S".to_string()],
+            valid_skips: vec![Skip::None, Skip::Skip],
             valid_power_levels: Vec::new(),
             valid_characters: CHARSET_UPPER_NUMERIC.to_string(),
             valid_name_length: 6,
-            valid_cross_modes: CROSS_MODES.iter().map(|s| s.to_string()).collect(),
+            valid_cross_modes: CrossMode::ALL.to_vec(),
             valid_tones: TONES.to_vec(),
-            valid_dtcs_pols: DTCS_POLARITIES.iter().map(|s| s.to_string()).collect(),
+            valid_dtcs_pols: DtcsPolarity::ALL.to_vec(),
             valid_dtcs_codes: DTCS_CODES.to_vec(),
             valid_special_chans: Vec::new(),
             memory_bounds: (0, 1),
@@ -172,7 +173,7 @@ impl RadioFeatures {
         if !self.valid_modes.is_empty()
             && !self.valid_modes.contains(&mem.mode)
             && !mem.immutable.contains(&"mode".to_string())
-            && mem.mode != "Auto"
+            && mem.mode != Mode::Auto
         {
             msgs.push(ValidationMessage::Error(format!(
                 "Mode {} not supported",
@@ -186,7 +187,7 @@ impl RadioFeatures {
                 "Tone mode {} not supported",
                 mem.tmode
             )));
-        } else if mem.tmode == "Cross" {
+        } else if mem.tmode == ToneMode::Cross {
             // Check cross mode
             if !self.valid_cross_modes.is_empty()
                 && !self.valid_cross_modes.contains(&mem.cross_mode)
@@ -278,14 +279,9 @@ impl RadioFeatures {
         // Check TX frequency (for split/offset)
         if !self.valid_bands.is_empty()
             && !self.valid_duplexes.is_empty()
-            && (mem.duplex == "split" || mem.duplex == "-" || mem.duplex == "+")
+            && matches!(mem.duplex, Duplex::Split | Duplex::Minus | Duplex::Plus)
         {
-            let tx_freq = match mem.duplex.as_str() {
-                "split" => mem.offset,
-                "-" => mem.freq.saturating_sub(mem.offset),
-                "+" => mem.freq + mem.offset,
-                _ => mem.freq,
-            };
+            let tx_freq = mem.duplex.tx_freq(mem.freq, mem.offset).unwrap_or(mem.freq);
 
             let mut valid = false;
             for (lo, hi) in &self.valid_bands {
@@ -348,6 +344,223 @@ impl RadioFeatures {
         msgs
     }
 
+    /// Adapt a memory from another radio to this one
+    ///
+    /// Fixes what can be fixed (name, mode, tones, step, power, ...) and
+    /// returns a warning for every change. Problems that can't be fixed
+    /// without guessing, like an out-of-band frequency, are left to
+    /// `validate_memory`.
+    pub fn coerce_memory(&self, mem: &Memory) -> (Memory, Vec<ValidationMessage>) {
+        let mut mem = mem.clone();
+        let mut msgs = Vec::new();
+        if mem.empty {
+            return (mem, msgs);
+        }
+
+        // Name: fold case, replace unsupported characters, truncate
+        let name = if self.has_name {
+            self.coerce_name(&mem.name)
+        } else {
+            String::new()
+        };
+        if name != mem.name {
+            msgs.push(ValidationMessage::Warning(format!(
+                "Name \"{}\" changed to \"{}\"",
+                mem.name, name
+            )));
+            mem.name = name;
+        }
+
+        // Mode: fall back to the closest mode the radio has
+        if !self.valid_modes.is_empty()
+            && !self.valid_modes.contains(&mem.mode)
+            && mem.mode != Mode::Auto
+        {
+            if let Some(mode) = fallback_modes(mem.mode)
+                .iter()
+                .find(|m| self.valid_modes.contains(m))
+            {
+                msgs.push(ValidationMessage::Warning(format!(
+                    "Mode {} changed to {}",
+                    mem.mode, mode
+                )));
+                if mem.mode == Mode::Dv {
+                    mem.dv_urcall.clear();
+                    mem.dv_rpt1call.clear();
+                    mem.dv_rpt2call.clear();
+                    mem.dv_code = 0;
+                }
+                mem.mode = *mode;
+            }
+        }
+
+        // Tone mode: drop what the radio can't do
+        let tmode_ok = self.valid_tmodes.is_empty() || self.valid_tmodes.contains(&mem.tmode);
+        let cross_ok = mem.tmode != ToneMode::Cross
+            || self.valid_cross_modes.is_empty()
+            || self.valid_cross_modes.contains(&mem.cross_mode);
+        if !tmode_ok || !cross_ok {
+            let tmode = if mem.tmode == ToneMode::Cross {
+                format!("Cross ({})", mem.cross_mode)
+            } else {
+                mem.tmode.to_string()
+            };
+            msgs.push(ValidationMessage::Warning(format!(
+                "Tone mode {} not supported, removed",
+                tmode
+            )));
+            mem.tmode = ToneMode::None;
+        }
+
+        // Tones and DTCS codes: snap to the nearest supported value. Drivers
+        // store the unused ones too, so all of them are snapped, but only the
+        // ones the tone mode uses are reported.
+        let used = ToneUse::of(&mem, self.has_ctone);
+        if !self.valid_tones.is_empty() {
+            for (tone, in_use) in [(&mut mem.rtone, used.rtone), (&mut mem.ctone, used.ctone)] {
+                let nearest = nearest(&self.valid_tones, *tone);
+                if nearest != *tone {
+                    if in_use {
+                        msgs.push(ValidationMessage::Warning(format!(
+                            "Tone {:.1} changed to {:.1}",
+                            tone, nearest
+                        )));
+                    }
+                    *tone = nearest;
+                }
+            }
+        }
+        if !self.valid_dtcs_codes.is_empty() {
+            for (code, in_use) in [(&mut mem.dtcs, used.dtcs), (&mut mem.rx_dtcs, used.rx_dtcs)] {
+                let nearest = nearest_code(&self.valid_dtcs_codes, *code);
+                if nearest != *code {
+                    if in_use {
+                        msgs.push(ValidationMessage::Warning(format!(
+                            "DTCS code {:03} changed to {:03}",
+                            code, nearest
+                        )));
+                    }
+                    *code = nearest;
+                }
+            }
+        }
+
+        // DTCS polarity
+        if self.has_dtcs_polarity
+            && !self.valid_dtcs_pols.is_empty()
+            && !self.valid_dtcs_pols.contains(&mem.dtcs_polarity)
+        {
+            let pol = self.valid_dtcs_pols[0];
+            msgs.push(ValidationMessage::Warning(format!(
+                "DTCS polarity {} changed to {}",
+                mem.dtcs_polarity, pol
+            )));
+            mem.dtcs_polarity = pol;
+        }
+
+        // Duplex: turn split into an offset when the radio can't do split
+        if !self.valid_duplexes.is_empty() && !self.valid_duplexes.contains(&mem.duplex) {
+            let duplex = match mem.duplex {
+                Duplex::Split if mem.offset >= mem.freq => Some(Duplex::Plus),
+                Duplex::Split => Some(Duplex::Minus),
+                _ => None,
+            };
+            match duplex.filter(|d| self.valid_duplexes.contains(d)) {
+                Some(duplex) => {
+                    let offset = mem.offset.abs_diff(mem.freq);
+                    msgs.push(ValidationMessage::Warning(format!(
+                        "Split TX {} changed to offset {}{}",
+                        Memory::format_freq(mem.offset),
+                        duplex,
+                        Memory::format_freq(offset)
+                    )));
+                    mem.duplex = duplex;
+                    mem.offset = offset;
+                }
+                None => msgs.push(ValidationMessage::Error(format!(
+                    "Duplex {} not supported",
+                    mem.duplex
+                ))),
+            }
+        }
+
+        // Tuning step
+        if !self.valid_tuning_steps.is_empty()
+            && !self.has_nostep_tuning
+            && !self.valid_tuning_steps.contains(&mem.tuning_step)
+        {
+            let step = nearest(&self.valid_tuning_steps, mem.tuning_step);
+            msgs.push(ValidationMessage::Warning(format!(
+                "Tuning step {:.2} changed to {:.2}",
+                mem.tuning_step, step
+            )));
+            mem.tuning_step = step;
+        }
+
+        // Power: pick the closest level (in dBm)
+        if let Some(power) = mem.power.take() {
+            mem.power = if self.valid_power_levels.is_empty()
+                || self.has_variable_power
+                || self.valid_power_levels.contains(&power)
+            {
+                Some(power)
+            } else {
+                let level = self
+                    .valid_power_levels
+                    .iter()
+                    .min_by(|a, b| {
+                        (a.dbm() - power.dbm())
+                            .abs()
+                            .total_cmp(&(b.dbm() - power.dbm()).abs())
+                    })
+                    .cloned();
+                if let Some(level) = &level {
+                    msgs.push(ValidationMessage::Warning(format!(
+                        "Power level {} changed to {}",
+                        power, level
+                    )));
+                }
+                level
+            };
+        }
+
+        // Skip
+        if !self.valid_skips.is_empty() && !self.valid_skips.contains(&mem.skip) {
+            msgs.push(ValidationMessage::Warning(format!(
+                "Skip {} not supported, removed",
+                mem.skip
+            )));
+            mem.skip = Skip::None;
+        }
+
+        (mem, msgs)
+    }
+
+    /// Make a name fit `valid_characters` and `valid_name_length`
+    fn coerce_name(&self, name: &str) -> String {
+        let fits =
+            |ch: char| self.valid_characters.is_empty() || self.valid_characters.contains(ch);
+        let name: String = name
+            .chars()
+            .filter_map(|ch| {
+                if fits(ch) {
+                    Some(ch)
+                } else if fits(ch.to_ascii_uppercase()) {
+                    Some(ch.to_ascii_uppercase())
+                } else if fits(ch.to_ascii_lowercase()) {
+                    Some(ch.to_ascii_lowercase())
+                } else if fits(' ') {
+                    Some(' ')
+                } else {
+                    // No space either, so drop the character
+                    None
+                }
+            })
+            .take(self.valid_name_length)
+            .collect();
+        name.trim_end().to_string()
+    }
+
     /// Split validation messages into warnings and errors
     pub fn split_messages(msgs: &[ValidationMessage]) -> (Vec<String>, Vec<String>) {
         let warnings = msgs
@@ -370,6 +583,78 @@ impl RadioFeatures {
     }
 }
 
+/// Closest value in a list (the list must not be empty)
+fn nearest(values: &[f32], value: f32) -> f32 {
+    values
+        .iter()
+        .copied()
+        .min_by(|a, b| (a - value).abs().total_cmp(&(b - value).abs()))
+        .unwrap_or(value)
+}
+
+/// Closest DTCS code in a list (the list must not be empty)
+fn nearest_code(codes: &[u16], code: u16) -> u16 {
+    codes
+        .iter()
+        .copied()
+        .min_by_key(|c| c.abs_diff(code))
+        .unwrap_or(code)
+}
+
+/// Which tone and DTCS fields a memory's tone mode uses
+struct ToneUse {
+    rtone: bool,
+    ctone: bool,
+    dtcs: bool,
+    rx_dtcs: bool,
+}
+
+impl ToneUse {
+    /// Radios without `has_ctone` squelch on `rtone` as well
+    fn of(mem: &Memory, has_ctone: bool) -> Self {
+        // (rtone, ctone, dtcs, rx_dtcs)
+        let used = match mem.tmode {
+            ToneMode::Tone => (true, false, false, false),
+            ToneMode::Tsql | ToneMode::TsqlR => (!has_ctone, true, false, false),
+            ToneMode::Dtcs => (false, false, true, false),
+            ToneMode::DtcsR => (false, false, false, true),
+            ToneMode::Cross => match mem.cross_mode {
+                CrossMode::ToneToTone => (true, true, false, false),
+                CrossMode::ToneToDtcs => (true, false, false, true),
+                CrossMode::ToneToNone => (true, false, false, false),
+                CrossMode::DtcsToTone => (false, true, true, false),
+                CrossMode::DtcsToDtcs => (false, false, true, true),
+                CrossMode::DtcsToNone => (false, false, true, false),
+                CrossMode::NoneToTone => (false, true, false, false),
+                CrossMode::NoneToDtcs => (false, false, false, true),
+            },
+            _ => (false, false, false, false),
+        };
+        ToneUse {
+            rtone: used.0,
+            ctone: used.1,
+            dtcs: used.2,
+            rx_dtcs: used.3,
+        }
+    }
+}
+
+/// Modes to try, in order, when a radio lacks `mode`
+fn fallback_modes(mode: Mode) -> &'static [Mode] {
+    match mode {
+        Mode::Nfm => &[Mode::Fm],
+        Mode::Fm => &[Mode::Nfm],
+        Mode::Wfm => &[Mode::Fm, Mode::Nfm],
+        Mode::Dv | Mode::Dn | Mode::Dmr | Mode::P25 => &[Mode::Fm, Mode::Nfm],
+        Mode::Am => &[Mode::Nam],
+        Mode::Nam => &[Mode::Am],
+        Mode::Cwr | Mode::Ncw | Mode::Ncwr => &[Mode::Cw],
+        Mode::Rttyr => &[Mode::Rtty],
+        Mode::Fskr => &[Mode::Fsk],
+        _ => &[],
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -386,24 +671,116 @@ mod tests {
     #[test]
     fn test_validation() {
         let rf = RadioFeatures {
-            valid_modes: vec!["FM".to_string(), "AM".to_string()],
-            valid_tmodes: vec!["".to_string(), "Tone".to_string()],
+            valid_modes: vec![Mode::Fm, Mode::Am],
+            valid_tmodes: vec![ToneMode::None, ToneMode::Tone],
             memory_bounds: (1, 200),
             ..Default::default()
         };
 
         let mut mem = Memory::new(1);
         mem.freq = 146_520_000;
-        mem.mode = "FM".to_string();
-        mem.tmode = "Tone".to_string();
+        mem.mode = Mode::Fm;
+        mem.tmode = ToneMode::Tone;
 
         let msgs = rf.validate_memory(&mem);
         assert!(msgs.is_empty());
 
-        // Invalid mode
-        mem.mode = "INVALID".to_string();
+        // Unsupported mode
+        mem.mode = Mode::Usb;
         let msgs = rf.validate_memory(&mem);
         assert!(!msgs.is_empty());
         assert!(msgs[0].is_error());
     }
+
+    #[test]
+    fn test_coerce_memory() {
+        let rf = RadioFeatures {
+            valid_modes: vec![Mode::Fm, Mode::Nfm],
+            valid_tmodes: vec![ToneMode::None, ToneMode::Tone, ToneMode::Tsql],
+            valid_tuning_steps: vec![5.0, 12.5, 25.0],
+            valid_power_levels: vec![
+                PowerLevel::from_watts("High", 4.0),
+                PowerLevel::from_watts("Low", 1.0),
+            ],
+            valid_name_length: 7,
+            memory_bounds: (0, 127),
+            ..Default::default()
+        };
+
+        let mut mem = Memory::new(3);
+        mem.freq = 145_230_000;
+        mem.name = "Club-Rpt DV".to_string();
+        mem.mode = Mode::Dv;
+        mem.dv_urcall = "CQCQCQ".to_string();
+        mem.tmode = ToneMode::Dtcs;
+        mem.rtone = 100.2;
+        mem.tuning_step = 6.25;
+        mem.power = Some(PowerLevel::from_watts("Mid", 2.5));
+        mem.duplex = Duplex::Split;
+        mem.offset = 144_630_000;
+
+        let (coerced, msgs) = rf.coerce_memory(&mem);
+        assert_eq!(coerced.name, "CLUB RP");
+        assert_eq!(coerced.mode, Mode::Fm);
+        assert!(coerced.dv_urcall.is_empty());
+        assert_eq!(coerced.tmode, ToneMode::None);
+        assert_eq!(coerced.rtone, 100.0);
+        assert_eq!(coerced.tuning_step, 5.0);
+        assert_eq!(coerced.power.as_ref().unwrap().label(), "High");
+        assert_eq!(coerced.duplex, Duplex::Minus);
+        assert_eq!(coerced.offset, 600_000);
+        // The 100.2 Hz tone is snapped, but DTCS doesn't use it, so no warning
+        assert_eq!(msgs.len(), 6);
+        assert!(msgs.iter().all(|m| m.is_warning()));
+        assert!(rf.validate_memory(&coerced).is_empty());
+
+        // Already valid memories come back unchanged
+        let (again, msgs) = rf.coerce_memory(&coerced);
+        assert!(msgs.is_empty());
+        assert_eq!(again.name, coerced.name);
+
+        // Without a space in the charset, unknown characters are dropped
+        let rf = RadioFeatures {
+            valid_characters: "ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789".to_string(),
+            valid_name_length: 8,
+            ..rf
+        };
+        let (coerced, _) = rf.coerce_memory(&mem);
+        assert_eq!(coerced.name, "CLUBRPTD");
+        assert!(rf.validate_memory(&coerced).is_empty());
+    }
+
+    #[test]
+    fn test_coerce_tones_and_codes() {
+        let rf = RadioFeatures {
+            valid_modes: vec![Mode::Fm, Mode::Nfm],
+            valid_dtcs_codes: vec![23, 25, 754],
+            ..Default::default()
+        };
+
+        let mut mem = Memory::new(1);
+        mem.freq = 146_520_000;
+        mem.tmode = ToneMode::Cross;
+        mem.cross_mode = CrossMode::ToneToDtcs;
+        mem.rtone = 100.2;
+        mem.ctone = 71.1;
+        mem.dtcs = 26;
+        mem.rx_dtcs = 750;
+        let (coerced, msgs) = rf.coerce_memory(&mem);
+        assert_eq!((coerced.rtone, coerced.ctone), (100.0, 71.9));
+        assert_eq!((coerced.dtcs, coerced.rx_dtcs), (25, 754));
+        // Tone->DTCS uses rtone and rx_dtcs, so only those are reported
+        let (warnings, _) = RadioFeatures::split_messages(&msgs);
+        assert_eq!(
+            warnings,
+            vec![
+                "Tone 100.2 changed to 100.0",
+                "DTCS code 750 changed to 754"
+            ]
+        );
+
+        // Broadcast FM stays wide on a radio without WFM
+        mem.mode = Mode::Wfm;
+        assert_eq!(rf.coerce_memory(&mem).0.mode, Mode::Fm);
+    }
 }
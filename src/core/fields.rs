@@ -0,0 +1,275 @@
+// Typed memory fields: tone mode, duplex, mode, skip, cross mode, DTCS polarity
+// Reference: chirp/chirp_common.py lines 30-110 (TONE_MODES, MODES, ...)
+//
+// Each enum displays as CHIRP's CSV spelling and parses case-insensitively,
+// so "tsql" from a hand-edited CSV reads as TSQL. Serde uses the same text.
+
+use super::memory::MemoryError;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Define a field enum with its CHIRP spellings
+macro_rules! chirp_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident ($error:ident) {
+            $( $(#[$vmeta:meta])* $variant:ident => $text:literal, )+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        pub enum $name {
+            $( $(#[$vmeta])* $variant, )+
+        }
+
+        impl $name {
+            /// Every value, in CHIRP's order
+            pub const ALL: &'static [$name] = &[$($name::$variant),+];
+
+            /// CHIRP's spelling of this value
+            pub const fn as_str(self) -> &'static str {
+                match self {
+                    $($name::$variant => $text,)+
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = MemoryError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let s = s.trim();
+                $name::ALL
+                    .iter()
+                    .copied()
+                    .find(|value| value.as_str().eq_ignore_ascii_case(s))
+                    .ok_or_else(|| MemoryError::$error(s.to_string()))
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let text = String::deserialize(deserializer)?;
+                text.parse().map_err(de::Error::custom)
+            }
+        }
+    };
+}
+
+chirp_enum! {
+    /// Tone squelch mode
+    pub enum ToneMode (InvalidToneMode) {
+        /// No tone
+        #[default]
+        None => "",
+        /// CTCSS encode only
+        Tone => "Tone",
+        /// CTCSS encode and decode
+        Tsql => "TSQL",
+        /// DCS encode and decode
+        Dtcs => "DTCS",
+        /// DCS decode only (reverse)
+        DtcsR => "DTCS-R",
+        /// CTCSS decode only (reverse)
+        TsqlR => "TSQL-R",
+        /// Different TX and RX squelch, see `CrossMode`
+        Cross => "Cross",
+    }
+}
+
+chirp_enum! {
+    /// Transmit offset direction
+    pub enum Duplex (InvalidDuplex) {
+        /// Transmit on the receive frequency
+        #[default]
+        Simplex => "",
+        Plus => "+",
+        Minus => "-",
+        /// Transmit on the frequency stored in `offset`
+        Split => "split",
+        /// Transmit inhibited
+        Off => "off",
+    }
+}
+
+impl Duplex {
+    /// Transmit frequency for a receive frequency and offset (None if TX is off)
+    pub fn tx_freq(self, freq: u64, offset: u64) -> Option<u64> {
+        match self {
+            Duplex::Simplex => Some(freq),
+            Duplex::Plus => Some(freq + offset),
+            Duplex::Minus => Some(freq.saturating_sub(offset)),
+            Duplex::Split => Some(offset),
+            Duplex::Off => None,
+        }
+    }
+}
+
+chirp_enum! {
+    /// Operating mode
+    pub enum Mode (InvalidMode) {
+        Wfm => "WFM",
+        #[default]
+        Fm => "FM",
+        Nfm => "NFM",
+        Am => "AM",
+        Nam => "NAM",
+        Dv => "DV",
+        Usb => "USB",
+        Lsb => "LSB",
+        Cw => "CW",
+        Rtty => "RTTY",
+        Dig => "DIG",
+        Pkt => "PKT",
+        Ncw => "NCW",
+        Ncwr => "NCWR",
+        Cwr => "CWR",
+        P25 => "P25",
+        Auto => "Auto",
+        Rttyr => "RTTYR",
+        Fsk => "FSK",
+        Fskr => "FSKR",
+        Dmr => "DMR",
+        Dn => "DN",
+        /// D-STAR high-speed data (Icom)
+        Dd => "DD",
+    }
+}
+
+chirp_enum! {
+    /// Scan behaviour
+    pub enum Skip (InvalidSkip) {
+        /// Scanned normally
+        #[default]
+        None => "",
+        /// Skipped during scan
+        Skip => "S",
+        /// Priority channel
+        Priority => "P",
+    }
+}
+
+chirp_enum! {
+    /// TX and RX squelch for `ToneMode::Cross`, written "TX->RX"
+    pub enum CrossMode (InvalidCrossMode) {
+        #[default]
+        ToneToTone => "Tone->Tone",
+        DtcsToNone => "DTCS->",
+        NoneToDtcs => "->DTCS",
+        ToneToDtcs => "Tone->DTCS",
+        DtcsToTone => "DTCS->Tone",
+        NoneToTone => "->Tone",
+        DtcsToDtcs => "DTCS->DTCS",
+        ToneToNone => "Tone->",
+    }
+}
+
+chirp_enum! {
+    /// DCS polarity, TX then RX: N(ormal) or R(everse)
+    pub enum DtcsPolarity (InvalidDtcsPolarity) {
+        #[default]
+        NN => "NN",
+        NR => "NR",
+        RN => "RN",
+        RR => "RR",
+    }
+}
+
+impl DtcsPolarity {
+    /// Build from the TX and RX polarity ('N' or 'R')
+    pub fn from_chars(tx: char, rx: char) -> Self {
+        match (tx == 'R', rx == 'R') {
+            (false, false) => DtcsPolarity::NN,
+            (false, true) => DtcsPolarity::NR,
+            (true, false) => DtcsPolarity::RN,
+            (true, true) => DtcsPolarity::RR,
+        }
+    }
+
+    /// TX polarity ('N' or 'R')
+    pub fn tx(self) -> char {
+        self.as_str().as_bytes()[0] as char
+    }
+
+    /// RX polarity ('N' or 'R')
+    pub fn rx(self) -> char {
+        self.as_str().as_bytes()[1] as char
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chirp_spellings() {
+        for tmode in ToneMode::ALL {
+            assert_eq!(tmode.to_string().parse::<ToneMode>().unwrap(), *tmode);
+        }
+        assert_eq!("tsql".parse::<ToneMode>().unwrap(), ToneMode::Tsql);
+        assert_eq!(" dtcs-r ".parse::<ToneMode>().unwrap(), ToneMode::DtcsR);
+        assert_eq!("".parse::<ToneMode>().unwrap(), ToneMode::None);
+        assert_eq!("SPLIT".parse::<Duplex>().unwrap(), Duplex::Split);
+        assert_eq!("nfm".parse::<Mode>().unwrap(), Mode::Nfm);
+        assert_eq!("AUTO".parse::<Mode>().unwrap().to_string(), "Auto");
+        assert_eq!("s".parse::<Skip>().unwrap(), Skip::Skip);
+        assert_eq!(
+            "tone->dtcs".parse::<CrossMode>().unwrap(),
+            CrossMode::ToneToDtcs
+        );
+        assert_eq!(CrossMode::DtcsToNone.to_string(), "DTCS->");
+
+        assert!(matches!(
+            "TSQ".parse::<ToneMode>(),
+            Err(MemoryError::InvalidToneMode(_))
+        ));
+        assert!(matches!(
+            "FMN".parse::<Mode>(),
+            Err(MemoryError::InvalidMode(_))
+        ));
+        assert!("NX".parse::<DtcsPolarity>().is_err());
+    }
+
+    #[test]
+    fn test_helpers() {
+        assert_eq!(
+            Duplex::Minus.tx_freq(147_000_000, 600_000),
+            Some(146_400_000)
+        );
+        assert_eq!(
+            Duplex::Split.tx_freq(146_520_000, 446_000_000),
+            Some(446_000_000)
+        );
+        assert_eq!(Duplex::Off.tx_freq(146_520_000, 0), None);
+
+        let pol = DtcsPolarity::from_chars('R', 'N');
+        assert_eq!(pol, DtcsPolarity::RN);
+        assert_eq!((pol.tx(), pol.rx()), ('R', 'N'));
+    }
+
+    #[test]
+    fn test_serde_uses_chirp_text() {
+        let mut serializer = serde_json::Serializer::new(Vec::new());
+        ToneMode::TsqlR.serialize(&mut serializer).unwrap();
+        assert_eq!(serializer.into_inner(), b"\"TSQL-R\"");
+
+        let mut deserializer = serde_json::Deserializer::from_str("\"split\"");
+        assert_eq!(
+            Duplex::deserialize(&mut deserializer).unwrap(),
+            Duplex::Split
+        );
+    }
+}
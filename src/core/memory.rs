@@ -1,6 +1,7 @@
 // Memory structure representing a single radio memory channel
 // Reference: chirp/chirp_common.py lines 280-645
 
+use super::fields::{CrossMode, DtcsPolarity, Duplex, Mode, Skip, ToneMode};
 use super::power::PowerLevel;
 use crate::core::constants::*;
 use serde::{Deserialize, Serialize};
@@ -30,6 +31,12 @@ pub enum MemoryError {
     #[error("Invalid skip value: {0}")]
     InvalidSkip(String),
 
+    #[error("Invalid cross mode: {0}")]
+    InvalidCrossMode(String),
+
+    #[error("Invalid DTCS polarity: {0}")]
+    InvalidDtcsPolarity(String),
+
     #[error("Field {0} is immutable")]
     ImmutableField(String),
 
@@ -70,28 +77,28 @@ pub struct Memory {
     pub rx_dtcs: u16,
 
     /// Tone mode ("", "Tone", "TSQL", "DTCS", "DTCS-R", "TSQL-R", "Cross")
-    pub tmode: String,
+    pub tmode: ToneMode,
 
     /// Cross mode for complex tone setups
-    pub cross_mode: String,
+    pub cross_mode: CrossMode,
 
     /// DTCS polarity ("NN", "NR", "RN", "RR")
-    pub dtcs_polarity: String,
+    pub dtcs_polarity: DtcsPolarity,
 
     /// Skip flag ("", "S" for skip, "P" for priority)
-    pub skip: String,
+    pub skip: Skip,
 
     /// Power level
     pub power: Option<PowerLevel>,
 
     /// Duplex ("", "+", "-", "split", "off")
-    pub duplex: String,
+    pub duplex: Duplex,
 
     /// Offset frequency in Hz
     pub offset: u64,
 
     /// Mode (e.g., "FM", "NFM", "AM", "USB", "LSB", "DV")
-    pub mode: String,
+    pub mode: Mode,
 
     /// Tuning step in kHz
     pub tuning_step: f32,
@@ -151,14 +158,14 @@ impl Memory {
             ctone: 88.5,
             dtcs: 23,
             rx_dtcs: 23,
-            tmode: String::new(),
-            cross_mode: "Tone->Tone".to_string(),
-            dtcs_polarity: "NN".to_string(),
-            skip: String::new(),
+            tmode: ToneMode::None,
+            cross_mode: CrossMode::ToneToTone,
+            dtcs_polarity: DtcsPolarity::NN,
+            skip: Skip::None,
             power: None,
-            duplex: String::new(),
+            duplex: Duplex::Simplex,
             offset: 600000, // 600 kHz default
-            mode: "FM".to_string(),
+            mode: Mode::Fm,
             tuning_step: 5.0,
             comment: String::new(),
             empty: false,
@@ -253,12 +260,9 @@ impl Memory {
     }
 
     /// Validate all fields
+    /// Mode, tone mode, duplex and skip are checked when they are parsed
     pub fn validate(&self) -> Result<()> {
         // Validate tones
-        if !self.tmode.is_empty() && !is_valid_tone_mode(&self.tmode) {
-            return Err(MemoryError::InvalidToneMode(self.tmode.clone()));
-        }
-
         if !is_valid_tone(self.rtone) {
             return Err(MemoryError::InvalidTone(self.rtone));
         }
@@ -276,21 +280,6 @@ impl Memory {
             return Err(MemoryError::InvalidDtcs(self.rx_dtcs));
         }
 
-        // Validate mode
-        if !is_valid_mode(&self.mode) {
-            return Err(MemoryError::InvalidMode(self.mode.clone()));
-        }
-
-        // Validate duplex
-        if !is_valid_duplex(&self.duplex) {
-            return Err(MemoryError::InvalidDuplex(self.duplex.clone()));
-        }
-
-        // Validate skip
-        if !is_valid_skip(&self.skip) {
-            return Err(MemoryError::InvalidSkip(self.skip.clone()));
-        }
-
         Ok(())
     }
 
@@ -299,8 +288,7 @@ impl Memory {
         self.clone()
     }
 
-    /// CSV header format
-    /// CSV header matching official CHIRP format (22 columns including Band)
+    /// CSV header: the columns Python CHIRP writes, plus Bank and Band
     pub const CSV_HEADER: &'static [&'static str] = &[
         "Location",
         "Name",
@@ -323,29 +311,37 @@ impl Memory {
         "RPT1CALL",
         "RPT2CALL",
         "DVCODE",
+        "Bank",
         "Band", // Added for multi-band radios (e.g., IC-9700)
-                // Note: "Bank" is NOT part of official CHIRP CSV format
-                // We can still import it if present, but don't export it by default
     ];
 
-    /// Export to CSV row (matches official CHIRP format - 22 columns including Band)
+    /// Export to CSV row (CHIRP's columns plus Bank and Band)
     pub fn to_csv(&self) -> Vec<String> {
+        // D-STAR fields are written whatever the mode, so nothing stored is
+        // lost. A zero DV code outside DV mode is the default and stays empty,
+        // as CHIRP leaves it.
+        let dv_code = if self.mode == Mode::Dv || self.dv_code != 0 {
+            self.dv_code.to_string()
+        } else {
+            String::new()
+        };
+
         vec![
             format!("{}", self.number),
             self.name.clone(),
             Self::format_freq(self.freq),
-            self.duplex.clone(),
+            self.duplex.to_string(),
             Self::format_freq(self.offset),
-            self.tmode.clone(),
+            self.tmode.to_string(),
             format!("{:.1}", self.rtone),
             format!("{:.1}", self.ctone),
             format!("{:03}", self.dtcs),
-            self.dtcs_polarity.clone(),
+            self.dtcs_polarity.to_string(),
             format!("{:03}", self.rx_dtcs),
-            self.cross_mode.clone(),
-            self.mode.clone(),
+            self.cross_mode.to_string(),
+            self.mode.to_string(),
             format!("{:.2}", self.tuning_step),
-            self.skip.clone(),
+            self.skip.to_string(),
             self.power
                 .as_ref()
                 .map(|p| p.to_string())
@@ -354,22 +350,34 @@ impl Memory {
             self.dv_urcall.clone(),
             self.dv_rpt1call.clone(),
             self.dv_rpt2call.clone(),
-            format!("{}", self.dv_code),
+            dv_code,
+            format!("{}", self.bank),
             self.band.map(|b| b.to_string()).unwrap_or_default(),
-            // Note: Bank is NOT exported (not part of official CHIRP CSV format)
         ]
     }
 }
 
 impl fmt::Display for Memory {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let tenc = if self.tmode == "Tone" { "*" } else { " " };
-        let tsql = if self.tmode == "TSQL" { "*" } else { " " };
-        let dtcs = if self.tmode == "DTCS" { "*" } else { " " };
-        let dup = if self.duplex.is_empty() {
+        let tenc = if self.tmode == ToneMode::Tone {
+            "*"
+        } else {
+            " "
+        };
+        let tsql = if self.tmode == ToneMode::Tsql {
+            "*"
+        } else {
+            " "
+        };
+        let dtcs = if self.tmode == ToneMode::Dtcs {
+            "*"
+        } else {
+            " "
+        };
+        let dup = if self.duplex == Duplex::Simplex {
             "/"
         } else {
-            &self.duplex
+            self.duplex.as_str()
         };
 
         write!(
@@ -482,7 +490,7 @@ mod tests {
     fn test_memory_creation() {
         let mem = Memory::new(1);
         assert_eq!(mem.number, 1);
-        assert_eq!(mem.mode, "FM");
+        assert_eq!(mem.mode, Mode::Fm);
         assert_eq!(mem.rtone, 88.5);
         assert!(!mem.empty);
 
@@ -503,7 +511,13 @@ mod tests {
         mem.freq = 146_520_000;
         assert!(mem.validate().is_ok());
 
-        mem.mode = "INVALID".to_string();
+        mem.rtone = 0.0;
         assert!(mem.validate().is_err());
+
+        // Unknown modes can no longer be stored at all
+        assert!(matches!(
+            "INVALID".parse::<Mode>(),
+            Err(MemoryError::InvalidMode(_))
+        ));
     }
 }
@@ -1,12 +1,16 @@
 // Core module containing fundamental CHIRP data structures
 pub mod constants;
 pub mod features;
+pub mod fields;
 pub mod memory;
 pub mod power;
+pub mod settings;
 pub mod validation;
 
 // Re-export commonly used types
 pub use constants::*;
-pub use features::RadioFeatures;
+pub use features::{RadioFeatures, ValidationMessage};
+pub use fields::{CrossMode, DtcsPolarity, Duplex, Mode, Skip, ToneMode};
 pub use memory::{DVMemory, Memory};
 pub use power::PowerLevel;
+pub use settings::{RadioSetting, RadioSettingGroup, SettingError, SettingValue};
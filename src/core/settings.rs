@@ -0,0 +1,443 @@
+// Radio settings tree (squelch, VOX, backlight, callsigns, ...)
+// Reference: chirp/settings.py
+//
+// Drivers expose their non-memory settings as a tree of named groups. Each
+// setting carries a typed value with its own bounds, so the UI can build an
+// editor without knowing anything about the radio.
+
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum SettingError {
+    #[error("Value {value} is out of range ({min}-{max})")]
+    OutOfRange { value: i64, min: i64, max: i64 },
+
+    #[error("Invalid option: {0}")]
+    InvalidOption(String),
+
+    #[error("Value is longer than {0} characters")]
+    TooLong(usize),
+
+    #[error("Invalid character: {0:?}")]
+    InvalidCharacter(char),
+
+    #[error("Invalid value {value:?}, expected {expected}")]
+    WrongType {
+        value: String,
+        expected: &'static str,
+    },
+
+    #[error("Unknown setting: {0}")]
+    UnknownSetting(String),
+}
+
+pub type SettingResult<T> = std::result::Result<T, SettingError>;
+
+/// Typed value of a single setting
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettingValue {
+    /// Integer in `min..=max`, adjusted in multiples of `step`
+    Integer {
+        value: i64,
+        min: i64,
+        max: i64,
+        step: i64,
+    },
+    /// One of a fixed list of options
+    List { options: Vec<String>, index: usize },
+    /// On/off switch
+    Boolean(bool),
+    /// Text limited to `max_length` characters (and `charset`, if given)
+    String {
+        value: String,
+        max_length: usize,
+        charset: Option<String>,
+    },
+}
+
+impl SettingValue {
+    /// Integer value with a step of 1
+    pub fn integer(value: i64, min: i64, max: i64) -> Self {
+        SettingValue::Integer {
+            value,
+            min,
+            max,
+            step: 1,
+        }
+    }
+
+    /// List value; `index` selects the current option
+    pub fn list<S: AsRef<str>>(options: &[S], index: usize) -> Self {
+        SettingValue::List {
+            options: options.iter().map(|o| o.as_ref().to_string()).collect(),
+            index,
+        }
+    }
+
+    /// Boolean value
+    pub fn boolean(value: bool) -> Self {
+        SettingValue::Boolean(value)
+    }
+
+    /// String value with any characters allowed
+    pub fn string(value: impl Into<String>, max_length: usize) -> Self {
+        SettingValue::String {
+            value: value.into(),
+            max_length,
+            charset: None,
+        }
+    }
+
+    /// Restrict a string value to the given characters
+    pub fn with_charset(mut self, allowed: &str) -> Self {
+        if let SettingValue::String { charset, .. } = &mut self {
+            *charset = Some(allowed.to_string());
+        }
+        self
+    }
+
+    /// Set the step of an integer value (a step below 1 allows any value in range)
+    pub fn with_step(mut self, new_step: i64) -> Self {
+        if let SettingValue::Integer { step, .. } = &mut self {
+            *step = new_step.max(1);
+        }
+        self
+    }
+
+    /// Get an integer value
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            SettingValue::Integer { value, .. } => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Get the selected index of a list value
+    pub fn as_index(&self) -> Option<usize> {
+        match self {
+            SettingValue::List { index, .. } => Some(*index),
+            _ => None,
+        }
+    }
+
+    /// Get a boolean value
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            SettingValue::Boolean(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Get a string value, or the selected option of a list
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            SettingValue::String { value, .. } => Some(value),
+            SettingValue::List { options, index } => options.get(*index).map(|s| s.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Set an integer value
+    pub fn set_integer(&mut self, new_value: i64) -> SettingResult<()> {
+        match self {
+            SettingValue::Integer {
+                value,
+                min,
+                max,
+                step,
+            } => {
+                if new_value < *min
+                    || new_value > *max
+                    || (*step > 0 && (new_value - *min) % *step != 0)
+                {
+                    return Err(SettingError::OutOfRange {
+                        value: new_value,
+                        min: *min,
+                        max: *max,
+                    });
+                }
+                *value = new_value;
+                Ok(())
+            }
+            _ => Err(self.wrong_type(new_value.to_string())),
+        }
+    }
+
+    /// Select a list option by index
+    pub fn set_index(&mut self, new_index: usize) -> SettingResult<()> {
+        match self {
+            SettingValue::List { options, index } => {
+                if new_index >= options.len() {
+                    return Err(SettingError::InvalidOption(new_index.to_string()));
+                }
+                *index = new_index;
+                Ok(())
+            }
+            _ => Err(self.wrong_type(new_index.to_string())),
+        }
+    }
+
+    /// Set a boolean value
+    pub fn set_bool(&mut self, new_value: bool) -> SettingResult<()> {
+        match self {
+            SettingValue::Boolean(value) => {
+                *value = new_value;
+                Ok(())
+            }
+            _ => Err(self.wrong_type(new_value.to_string())),
+        }
+    }
+
+    /// Set a string value, or select a list option by name
+    pub fn set_string(&mut self, new_value: &str) -> SettingResult<()> {
+        match self {
+            SettingValue::String {
+                value,
+                max_length,
+                charset,
+            } => {
+                if new_value.chars().count() > *max_length {
+                    return Err(SettingError::TooLong(*max_length));
+                }
+                if let Some(charset) = charset {
+                    if let Some(c) = new_value.chars().find(|&c| !charset.contains(c)) {
+                        return Err(SettingError::InvalidCharacter(c));
+                    }
+                }
+                *value = new_value.to_string();
+                Ok(())
+            }
+            SettingValue::List { options, index } => {
+                let found = options
+                    .iter()
+                    .position(|o| o.eq_ignore_ascii_case(new_value))
+                    .ok_or_else(|| SettingError::InvalidOption(new_value.to_string()))?;
+                *index = found;
+                Ok(())
+            }
+            _ => Err(self.wrong_type(new_value.to_string())),
+        }
+    }
+
+    /// Parse and set a value from text (as typed by a user or read from a file)
+    pub fn set_from_str(&mut self, text: &str) -> SettingResult<()> {
+        let text = text.trim();
+        match self {
+            SettingValue::Integer { .. } => {
+                let value = text.parse().map_err(|_| self.wrong_type(text))?;
+                self.set_integer(value)
+            }
+            SettingValue::Boolean(_) => match text.to_ascii_lowercase().as_str() {
+                "true" | "on" | "yes" | "1" => self.set_bool(true),
+                "false" | "off" | "no" | "0" => self.set_bool(false),
+                _ => Err(self.wrong_type(text)),
+            },
+            SettingValue::List { .. } | SettingValue::String { .. } => self.set_string(text),
+        }
+    }
+
+    fn wrong_type(&self, value: impl Into<String>) -> SettingError {
+        let expected = match self {
+            SettingValue::Integer { .. } => "an integer",
+            SettingValue::List { .. } => "a list option",
+            SettingValue::Boolean(_) => "a boolean",
+            SettingValue::String { .. } => "a string",
+        };
+        SettingError::WrongType {
+            value: value.into(),
+            expected,
+        }
+    }
+}
+
+impl fmt::Display for SettingValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingValue::Integer { value, .. } => write!(f, "{}", value),
+            SettingValue::List { options, index } => {
+                write!(f, "{}", options.get(*index).map_or("", |s| s.as_str()))
+            }
+            SettingValue::Boolean(value) => write!(f, "{}", if *value { "On" } else { "Off" }),
+            SettingValue::String { value, .. } => write!(f, "{}", value),
+        }
+    }
+}
+
+/// A single named setting
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadioSetting {
+    /// Driver key, unique within the radio
+    pub name: String,
+    /// Display label
+    pub label: String,
+    /// Help text shown next to the editor
+    pub help: String,
+    pub value: SettingValue,
+}
+
+impl RadioSetting {
+    pub fn new(name: impl Into<String>, label: impl Into<String>, value: SettingValue) -> Self {
+        Self {
+            name: name.into(),
+            label: label.into(),
+            help: String::new(),
+            value,
+        }
+    }
+
+    /// Attach help text
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = help.into();
+        self
+    }
+}
+
+/// A named group of settings and subgroups
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RadioSettingGroup {
+    pub name: String,
+    pub label: String,
+    pub settings: Vec<RadioSetting>,
+    pub groups: Vec<RadioSettingGroup>,
+}
+
+impl RadioSettingGroup {
+    pub fn new(name: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            label: label.into(),
+            settings: Vec::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    /// Add a setting to this group
+    pub fn add(&mut self, setting: RadioSetting) {
+        self.settings.push(setting);
+    }
+
+    /// Add a subgroup
+    pub fn add_group(&mut self, group: RadioSettingGroup) {
+        self.groups.push(group);
+    }
+
+    /// Iterate over every setting in this group and its subgroups
+    pub fn iter(&self) -> Box<dyn Iterator<Item = &RadioSetting> + '_> {
+        Box::new(
+            self.settings
+                .iter()
+                .chain(self.groups.iter().flat_map(|g| g.iter())),
+        )
+    }
+
+    /// Find a setting by name anywhere in the tree
+    pub fn get(&self, name: &str) -> Option<&RadioSetting> {
+        self.iter().find(|s| s.name == name)
+    }
+
+    /// Find a setting by name anywhere in the tree, mutably
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut RadioSetting> {
+        if let Some(setting) = self.settings.iter_mut().find(|s| s.name == name) {
+            return Some(setting);
+        }
+        self.groups.iter_mut().find_map(|g| g.get_mut(name))
+    }
+
+    /// Parse and set a setting's value by name
+    pub fn set(&mut self, name: &str, text: &str) -> SettingResult<()> {
+        self.get_mut(name)
+            .ok_or_else(|| SettingError::UnknownSetting(name.to_string()))?
+            .value
+            .set_from_str(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> RadioSettingGroup {
+        let mut basic = RadioSettingGroup::new("basic", "Basic Settings");
+        basic.add(RadioSetting::new(
+            "squelch",
+            "Squelch Level",
+            SettingValue::integer(3, 0, 9),
+        ));
+        basic.add(RadioSetting::new(
+            "save",
+            "Battery Saver",
+            SettingValue::list(&["OFF", "1:1", "1:2"], 1),
+        ));
+
+        let mut root = RadioSettingGroup::new("root", "Settings");
+        root.add(RadioSetting::new(
+            "beep",
+            "Beep",
+            SettingValue::boolean(true),
+        ));
+        root.add_group(basic);
+        root
+    }
+
+    #[test]
+    fn test_tree_lookup() {
+        let mut root = tree();
+        let names: Vec<_> = root.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["beep", "squelch", "save"]);
+
+        root.set("squelch", "7").unwrap();
+        root.set("save", "1:2").unwrap();
+        root.set("beep", "off").unwrap();
+        assert_eq!(root.get("squelch").unwrap().value.as_integer(), Some(7));
+        assert_eq!(root.get("save").unwrap().value.as_index(), Some(2));
+        assert_eq!(root.get("beep").unwrap().value.to_string(), "Off");
+
+        assert_eq!(
+            root.set("vox", "1"),
+            Err(SettingError::UnknownSetting("vox".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_value_validation() {
+        let mut value = SettingValue::integer(15, 15, 600).with_step(15);
+        assert!(value.set_integer(60).is_ok());
+        assert!(matches!(
+            value.set_integer(610),
+            Err(SettingError::OutOfRange { .. })
+        ));
+        assert!(value.set_integer(61).is_err());
+
+        let mut unstepped = SettingValue::integer(0, 0, 10).with_step(0);
+        assert!(unstepped.set_integer(7).is_ok());
+        let mut raw = SettingValue::Integer {
+            value: 0,
+            min: 0,
+            max: 10,
+            step: 0,
+        };
+        assert!(raw.set_integer(3).is_ok());
+        assert!(matches!(
+            value.set_from_str("abc"),
+            Err(SettingError::WrongType { .. })
+        ));
+
+        let mut list = SettingValue::list(&["A", "B"], 0);
+        assert!(matches!(
+            list.set_index(2),
+            Err(SettingError::InvalidOption(_))
+        ));
+        assert!(list.set_string("C").is_err());
+
+        let mut text =
+            SettingValue::string("", 6).with_charset("ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789");
+        assert!(text.set_string("W3POG").is_ok());
+        assert_eq!(text.set_string("W3POG12"), Err(SettingError::TooLong(6)));
+        assert_eq!(
+            text.set_string("w3pog"),
+            Err(SettingError::InvalidCharacter('w'))
+        );
+        assert_eq!(text.as_str(), Some("W3POG"));
+    }
+}
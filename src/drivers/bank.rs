@@ -0,0 +1,261 @@
+// Bank model: named banks and memory membership
+// Reference: chirp/chirp_common.py (Bank, BankModel, MTOBankModel)
+//
+// Radios organize memories into banks in one of two ways. Icom radios let a
+// memory sit in any number of banks, while the TH-D75 puts every memory in
+// exactly one group. Both are exposed through the same trait; `BankStyle`
+// tells callers which rules apply. `group_by_bank` lists a bank view's
+// memories under either style.
+
+use super::traits::RadioResult;
+use crate::core::Memory;
+use std::collections::HashMap;
+
+/// A bank (or group) of memories
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bank {
+    /// Bank number as stored by the radio
+    pub index: usize,
+    pub name: String,
+}
+
+impl Bank {
+    pub fn new(index: usize, name: impl Into<String>) -> Self {
+        Self {
+            index,
+            name: name.into(),
+        }
+    }
+}
+
+/// How memories map onto banks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BankStyle {
+    /// Each memory is in exactly one bank; adding it to a bank moves it (TH-D75 groups)
+    Single,
+    /// A memory can be in any number of banks, including none (Icom style)
+    Multiple,
+}
+
+/// Access to a radio's banks
+pub trait BankModel {
+    /// How memories map onto banks
+    fn bank_style(&self) -> BankStyle;
+
+    /// Longest bank name the radio stores
+    fn bank_name_length(&self) -> usize;
+
+    /// List all banks
+    fn get_banks(&self) -> RadioResult<Vec<Bank>>;
+
+    /// Rename a bank
+    fn rename_bank(&mut self, bank: usize, name: &str) -> RadioResult<()>;
+
+    /// Banks a memory belongs to (empty if the memory is unused)
+    fn get_memory_banks(&self, number: u32) -> RadioResult<Vec<usize>>;
+
+    /// Put a memory in a bank
+    fn add_memory_to_bank(&mut self, number: u32, bank: usize) -> RadioResult<()>;
+
+    /// Take a memory out of a bank
+    fn remove_memory_from_bank(&mut self, number: u32, bank: usize) -> RadioResult<()>;
+
+    /// Memories in a bank, in channel order
+    fn get_bank_memories(&self, bank: usize) -> RadioResult<Vec<u32>>;
+}
+
+/// Positions in `memories` of the used memories in each bank, for bank views
+///
+/// A memory in several banks is listed under each of them. Banks without
+/// used memories are left out.
+pub fn group_by_bank(
+    model: &dyn BankModel,
+    memories: &[Memory],
+) -> RadioResult<HashMap<usize, Vec<usize>>> {
+    let mut groups = HashMap::new();
+    for bank in model.get_banks()? {
+        let numbers = model.get_bank_memories(bank.index)?;
+        let indices: Vec<usize> = memories
+            .iter()
+            .enumerate()
+            .filter(|(_, mem)| !mem.empty && numbers.contains(&mem.number))
+            .map(|(idx, _)| idx)
+            .collect();
+        if !indices.is_empty() {
+            groups.insert(bank.index, indices);
+        }
+    }
+    Ok(groups)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drivers::ft60::{FT60Radio, MEMSIZE as FT60_MEMSIZE};
+    use crate::drivers::test_image::TestImage;
+    use crate::drivers::thd75::THD75Radio;
+    use crate::drivers::{CloneModeRadio, Radio, RadioError};
+    use crate::memmap::MemoryMap;
+    use std::collections::BTreeSet;
+
+    /// Icom-style radio with memories 0-9 and three banks
+    struct MultiBankRadio {
+        names: Vec<String>,
+        members: Vec<BTreeSet<u32>>,
+    }
+
+    impl MultiBankRadio {
+        fn new() -> Self {
+            Self {
+                names: vec!["A".into(), "B".into(), "C".into()],
+                members: vec![BTreeSet::new(); 3],
+            }
+        }
+
+        fn bank(&mut self, bank: usize) -> RadioResult<&mut BTreeSet<u32>> {
+            self.members
+                .get_mut(bank)
+                .ok_or_else(|| RadioError::Radio(format!("No bank {}", bank)))
+        }
+    }
+
+    impl BankModel for MultiBankRadio {
+        fn bank_style(&self) -> BankStyle {
+            BankStyle::Multiple
+        }
+
+        fn bank_name_length(&self) -> usize {
+            8
+        }
+
+        fn get_banks(&self) -> RadioResult<Vec<Bank>> {
+            Ok(self
+                .names
+                .iter()
+                .enumerate()
+                .map(|(i, name)| Bank::new(i, name.clone()))
+                .collect())
+        }
+
+        fn rename_bank(&mut self, bank: usize, name: &str) -> RadioResult<()> {
+            self.bank(bank)?;
+            self.names[bank] = name.to_string();
+            Ok(())
+        }
+
+        fn get_memory_banks(&self, number: u32) -> RadioResult<Vec<usize>> {
+            Ok((0..self.members.len())
+                .filter(|&i| self.members[i].contains(&number))
+                .collect())
+        }
+
+        fn add_memory_to_bank(&mut self, number: u32, bank: usize) -> RadioResult<()> {
+            self.bank(bank)?.insert(number);
+            Ok(())
+        }
+
+        fn remove_memory_from_bank(&mut self, number: u32, bank: usize) -> RadioResult<()> {
+            if !self.bank(bank)?.remove(&number) {
+                return Err(RadioError::Radio(format!(
+                    "Memory #{} is not in bank {}",
+                    number, bank
+                )));
+            }
+            Ok(())
+        }
+
+        fn get_bank_memories(&self, bank: usize) -> RadioResult<Vec<u32>> {
+            Ok(self
+                .members
+                .get(bank)
+                .map(|m| m.iter().copied().collect())
+                .unwrap_or_default())
+        }
+    }
+
+    #[test]
+    fn test_multi_bank_membership() {
+        let mut radio = MultiBankRadio::new();
+        let model: &mut dyn BankModel = &mut radio;
+        assert_eq!(model.bank_style(), BankStyle::Multiple);
+
+        model.add_memory_to_bank(5, 0).unwrap();
+        model.add_memory_to_bank(5, 2).unwrap();
+        model.add_memory_to_bank(1, 2).unwrap();
+        assert_eq!(model.get_memory_banks(5).unwrap(), vec![0, 2]);
+        assert_eq!(model.get_bank_memories(2).unwrap(), vec![1, 5]);
+
+        model.remove_memory_from_bank(5, 0).unwrap();
+        assert_eq!(model.get_memory_banks(5).unwrap(), vec![2]);
+        assert!(model.remove_memory_from_bank(5, 0).is_err());
+
+        model.rename_bank(1, "SIMPLEX").unwrap();
+        assert_eq!(model.get_banks().unwrap()[1], Bank::new(1, "SIMPLEX"));
+    }
+
+    #[test]
+    fn test_group_by_bank_multiple() {
+        // FT-60R: ten banks, a memory can be in any of them
+        let mut radio = TestImage::new(FT60_MEMSIZE, 0x00)
+            .set(0, b"AH017")
+            .load(FT60Radio::new());
+        for number in [1, 2, 3] {
+            let mut mem = Memory::new(number);
+            mem.freq = 146_520_000;
+            radio.set_memory(&mem).unwrap();
+        }
+        let memories = radio.get_memories().unwrap();
+        let model = radio.bank_model().unwrap();
+        assert_eq!(model.bank_style(), BankStyle::Multiple);
+        model.add_memory_to_bank(1, 0).unwrap();
+        model.add_memory_to_bank(1, 9).unwrap();
+        model.add_memory_to_bank(3, 9).unwrap();
+
+        let groups = group_by_bank(model, &memories).unwrap();
+        let numbers = |bank: usize| -> Vec<u32> {
+            groups[&bank].iter().map(|&i| memories[i].number).collect()
+        };
+        assert_eq!(groups.len(), 2);
+        assert_eq!(numbers(0), vec![1]);
+        assert_eq!(numbers(9), vec![1, 3]);
+
+        // Taking memory 1 out of one bank leaves it in the other
+        model.remove_memory_from_bank(1, 9).unwrap();
+        let groups = group_by_bank(model, &memories).unwrap();
+        assert_eq!(groups[&0].len() + groups[&9].len(), 2);
+        assert_eq!(memories[groups[&9][0]].number, 3);
+    }
+
+    #[test]
+    fn test_group_by_bank_single() {
+        // TH-D75: every used memory is in exactly one group
+        let path =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("test_data/radio_dump.bin");
+        let mut radio = THD75Radio::new();
+        radio
+            .process_mmap(&MemoryMap::new(std::fs::read(path).unwrap()))
+            .unwrap();
+        let memories = radio.get_memories().unwrap();
+        let model = radio.bank_model().unwrap();
+        assert_eq!(model.bank_style(), BankStyle::Single);
+        let count = model.get_banks().unwrap().len();
+
+        let groups = group_by_bank(model, &memories).unwrap();
+        for (i, mem) in memories.iter().enumerate().filter(|(_, mem)| !mem.empty) {
+            let listed: Vec<usize> = groups
+                .iter()
+                .filter(|(_, indices)| indices.contains(&i))
+                .map(|(&bank, _)| bank)
+                .collect();
+            // A group number past the last group (the weather channels in
+            // this dump) has no bank to be listed under
+            let expected: Vec<usize> = model
+                .get_memory_banks(mem.number)
+                .unwrap()
+                .into_iter()
+                .filter(|&bank| bank < count)
+                .collect();
+            assert_eq!(listed, expected, "memory #{}", mem.number);
+        }
+    }
+}
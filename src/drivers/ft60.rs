@@ -0,0 +1,915 @@
+// Yaesu FT-60R radio driver
+// Reference: chirp/drivers/ft60.py, chirp/drivers/yaesu_clone.py
+//
+// Yaesu clone mode is driven by the radio. For a download the user starts
+// the transfer from the radio, which sends an 8-byte header and then the rest
+// of the image in 64-byte chunks (the last one is a single byte). The host
+// ACKs (0x06) each one, and the radio waits for that ACK before sending the
+// next. An upload is the same in reverse: the radio waits in receive mode and
+// ACKs the header and every chunk, and the host must read that ACK before
+// sending more. The clone cable is a single wire, so everything the host sends
+// is echoed back first. The last byte of the image is a checksum: the sum of
+// all bytes before it, modulo 256.
+//
+// Image layout:
+//
+//   0x0248  16 bytes per channel (1000 channels)
+//   0x4708  8 bytes per channel: name (6 characters) and display flag
+//   0x69C8  10 banks, 128-byte membership bitmaps (bit per channel)
+//   0x6EC8  2 bits per channel: scan skip / priority
+//   0x6FC8  checksum
+
+use super::bank::{Bank, BankModel, BankStyle};
+use super::image::{channel_index, index_of, ImageAccess};
+use super::traits::{CloneModeRadio, Radio, RadioError, RadioResult, Status, StatusCallback};
+use crate::bitwise::bcd;
+use crate::core::{
+    Duplex, Memory, Mode, PowerLevel, RadioFeatures, Skip, ToneMode, DTCS_CODES, TONES,
+};
+use crate::memmap::MemoryMap;
+use crate::serial::{ConnectionProfile, Transport};
+use std::time::Duration;
+
+/// Image size, checksum included
+pub const MEMSIZE: usize = 0x6FC9;
+
+/// Radio ID at the start of the clone header (and so of every image)
+const MODEL_ID: &[u8] = b"AH017";
+
+/// Clone header sent before the first chunk
+pub const HEADER_SIZE: usize = 8;
+
+/// Clone chunk size, ACKed one at a time in both directions
+pub const CHUNK_SIZE: usize = 64;
+
+const ACK: u8 = 0x06;
+
+/// Memory offsets
+const MEMORY_OFFSET: usize = 0x0248;
+const NAMES_OFFSET: usize = 0x4708;
+const BANKS_OFFSET: usize = 0x69C8;
+const FLAGS_OFFSET: usize = 0x6EC8;
+const CHECKSUM_OFFSET: usize = 0x6FC8;
+
+const MEMORY_SIZE: usize = 16;
+const NAME_SLOT_SIZE: usize = 8;
+const BANK_SIZE: usize = 128;
+
+const NUM_MEMORIES: u32 = 1000;
+const NUM_BANKS: usize = 10;
+const NAME_LENGTH: usize = 6;
+
+/// Name characters, indexed by their code
+const CHARSET: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ ()*+-/=?";
+
+/// Name padding (space)
+const NAME_PAD: u8 = 0x24;
+
+/// The user starts a download on the radio, so allow time for that
+const CONNECTION: ConnectionProfile =
+    ConnectionProfile::new(9600).with_timeout(Duration::from_secs(30));
+
+/// Byte 0 flags
+const USED: u8 = 0x80;
+const NARROW: u8 = 0x20;
+const AM: u8 = 0x10;
+
+/// Display-name flag (name byte 6)
+const USE_NAME: u8 = 0x80;
+
+/// Duplex values by code (codes 0 and 1 are both simplex)
+const DUPLEXES: &[(u8, Duplex)] = &[
+    (0, Duplex::Simplex),
+    (2, Duplex::Minus),
+    (3, Duplex::Plus),
+    (4, Duplex::Split),
+    (5, Duplex::Off),
+];
+
+const TONE_MODES: &[ToneMode] = &[
+    ToneMode::None,
+    ToneMode::Tone,
+    ToneMode::Tsql,
+    ToneMode::TsqlR,
+    ToneMode::Dtcs,
+];
+
+const STEPS: &[f32] = &[5.0, 10.0, 12.5, 15.0, 20.0, 25.0, 50.0, 100.0];
+
+const POWER_LEVELS: &[(&str, f32)] = &[("High", 5.0), ("Mid", 2.0), ("Low", 0.5)];
+
+/// Repeater offset unit
+const OFFSET_STEP: u64 = 50_000;
+
+/// Frequency flags in the top bits of the first BCD byte
+const FREQ_5K: u8 = 0x80;
+const FREQ_FRACTIONAL: u8 = 0x40;
+
+/// Decode a 3-byte BCD frequency
+///
+/// The digits count 10 kHz. 0x80 in the first byte adds 5 kHz, and 0x40
+/// marks a channel off the 5 kHz raster. The radio doesn't store that
+/// fraction, so it is the 12.5 kHz, then 6.25 kHz, then 2.5 kHz step
+/// channel in the next 5 kHz, as CHIRP picks it.
+pub fn decode_freq(data: &[u8]) -> RadioResult<u64> {
+    let digits = [data[0] & 0x3F, data[1], data[2]];
+    let mut freq = bcd::bcd_to_int_be(&digits)? * 10_000;
+    if data[0] & FREQ_5K != 0 {
+        freq += 5000;
+    }
+    if data[0] & FREQ_FRACTIONAL != 0 {
+        let candidates = [1250, 2500, 3750].map(|fraction| freq + fraction);
+        freq = candidates
+            .iter()
+            .find(|f| f.is_multiple_of(12_500))
+            .or_else(|| candidates.iter().find(|f| f.is_multiple_of(6250)))
+            .copied()
+            .unwrap_or(freq + 2500);
+    }
+    Ok(freq)
+}
+
+/// Encode a frequency for `decode_freq`
+pub fn encode_freq(freq: u64) -> RadioResult<[u8; 3]> {
+    let mut flags = 0;
+    if (freq / 1000) % 10 >= 5 {
+        flags |= FREQ_5K;
+    }
+    if !freq.is_multiple_of(5000) {
+        flags |= FREQ_FRACTIONAL;
+    }
+    let digits = bcd::int_to_bcd_be(freq / 10_000, 3)?;
+    let data = [digits[0] | flags, digits[1], digits[2]];
+    if decode_freq(&data)? != freq {
+        return Err(RadioError::Radio(format!(
+            "Frequency {} is not on a 2.5, 6.25 or 12.5 kHz step",
+            freq
+        )));
+    }
+    Ok(data)
+}
+
+/// Checksum over everything before the checksum byte
+fn checksum(data: &[u8]) -> u8 {
+    data[..CHECKSUM_OFFSET]
+        .iter()
+        .fold(0u8, |sum, &b| sum.wrapping_add(b))
+}
+
+/// Send bytes and read back the cable's echo of them
+async fn send<T: Transport + ?Sized>(port: &mut T, data: &[u8]) -> RadioResult<()> {
+    port.write_all(data)
+        .await
+        .map_err(|e| RadioError::Serial(e.to_string()))?;
+    port.flush().await.ok();
+
+    let mut echo = vec![0u8; data.len()];
+    port.read_exact(&mut echo)
+        .await
+        .map_err(|e| RadioError::Serial(e.to_string()))?;
+    if echo != data {
+        return Err(RadioError::InvalidResponse(
+            "Bad echo from the clone cable".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Yaesu FT-60R radio driver
+pub struct FT60Radio {
+    pub mmap: Option<MemoryMap>,
+}
+
+impl FT60Radio {
+    pub fn new() -> Self {
+        Self { mmap: None }
+    }
+
+    /// Offset and shift of a channel's 2-bit scan flag
+    fn skip_bits(index: usize) -> (usize, u8) {
+        (FLAGS_OFFSET + index / 4, ((index % 4) * 2) as u8)
+    }
+
+    /// Offset and mask of a channel's bit in a bank bitmap
+    fn bank_bit(bank: usize, index: usize) -> (usize, u8) {
+        (
+            BANKS_OFFSET + bank * BANK_SIZE + index / 8,
+            1 << (index % 8),
+        )
+    }
+
+    fn is_used(&self, index: usize) -> RadioResult<bool> {
+        Ok(self.read(MEMORY_OFFSET + index * MEMORY_SIZE, 1)?[0] & USED != 0)
+    }
+
+    fn set_bank_bit(&mut self, bank: usize, index: usize, member: bool) -> RadioResult<()> {
+        if bank >= NUM_BANKS {
+            return Err(RadioError::Radio(format!("Invalid bank: {}", bank)));
+        }
+        let (offset, mask) = Self::bank_bit(bank, index);
+        let byte = self.read(offset, 1)?[0];
+        let byte = if member { byte | mask } else { byte & !mask };
+        self.write(offset, &[byte])
+    }
+
+    fn decode_memory(&self, number: u32, index: usize) -> RadioResult<Memory> {
+        let data = self.read(MEMORY_OFFSET + index * MEMORY_SIZE, MEMORY_SIZE)?;
+        let mut mem = Memory::new(number);
+
+        mem.freq = decode_freq(&data[1..4])?;
+        let duplex = data[0] & 0x0F;
+        mem.duplex = DUPLEXES
+            .iter()
+            .find(|(code, _)| *code == duplex)
+            .map_or(Duplex::Simplex, |(_, d)| *d);
+        if mem.duplex == Duplex::Split {
+            mem.offset = decode_freq(&data[5..8])?;
+        } else {
+            mem.offset = data[12] as u64 * OFFSET_STEP;
+        }
+
+        mem.mode = if data[0] & AM != 0 {
+            Mode::Am
+        } else if data[0] & NARROW != 0 {
+            Mode::Nfm
+        } else {
+            Mode::Fm
+        };
+
+        mem.tuning_step = STEPS[((data[4] >> 4) & 0x07) as usize];
+        mem.tmode = TONE_MODES
+            .get((data[4] & 0x07) as usize)
+            .copied()
+            .unwrap_or(ToneMode::None);
+
+        let (label, watts) = POWER_LEVELS[((data[8] >> 6) as usize).min(2)];
+        mem.power = Some(PowerLevel::from_watts(label, watts));
+        if let Some(&tone) = TONES.get((data[8] & 0x3F) as usize) {
+            mem.rtone = tone;
+            mem.ctone = tone;
+        }
+        if let Some(&code) = DTCS_CODES.get((data[9] & 0x7F) as usize) {
+            mem.dtcs = code;
+            mem.rx_dtcs = code;
+        }
+
+        let (offset, shift) = Self::skip_bits(index);
+        mem.skip = match (self.read(offset, 1)?[0] >> shift) & 0x03 {
+            1 => Skip::Skip,
+            2 => Skip::Priority,
+            _ => Skip::None,
+        };
+
+        let name = self.read(NAMES_OFFSET + index * NAME_SLOT_SIZE, NAME_SLOT_SIZE)?;
+        if name[6] & USE_NAME != 0 {
+            mem.name = name[..NAME_LENGTH]
+                .iter()
+                .map_while(|&b| CHARSET.chars().nth(b as usize))
+                .collect::<String>()
+                .trim_end()
+                .to_string();
+        }
+
+        Ok(mem)
+    }
+
+    /// Encode a channel's 16-byte record on top of `current`
+    ///
+    /// Bit 6 of the flags byte, the top bits of the step and DTCS bytes and the
+    /// bytes CHIRP marks unknown are copied through unchanged.
+    fn encode_memory(mem: &Memory, current: &[u8]) -> RadioResult<[u8; MEMORY_SIZE]> {
+        let mut data = [0u8; MEMORY_SIZE];
+        data.copy_from_slice(current);
+
+        let duplex = DUPLEXES
+            .iter()
+            .find(|(_, d)| *d == mem.duplex)
+            .map(|(code, _)| *code)
+            .ok_or_else(|| RadioError::Radio(format!("Unsupported duplex: {}", mem.duplex)))?;
+        let mode = match mem.mode {
+            Mode::Fm => 0,
+            Mode::Nfm => NARROW,
+            Mode::Am => AM,
+            mode => return Err(RadioError::Radio(format!("Unsupported mode: {}", mode))),
+        };
+        data[0] = (data[0] & 0x40) | USED | mode | duplex;
+        data[1..4].copy_from_slice(&encode_freq(mem.freq)?);
+
+        let step = index_of(STEPS, mem.tuning_step, "tuning step")?;
+        let tmode = index_of(TONE_MODES, mem.tmode, "tone mode")?;
+        data[4] = (data[4] & 0x88) | (step << 4) | tmode;
+
+        if mem.duplex == Duplex::Split {
+            data[5..8].copy_from_slice(&encode_freq(mem.offset)?);
+        } else {
+            if !mem.offset.is_multiple_of(OFFSET_STEP) || mem.offset / OFFSET_STEP > u8::MAX as u64
+            {
+                return Err(RadioError::Radio(format!(
+                    "Unsupported offset: {} (50 kHz steps up to 12.75 MHz)",
+                    mem.offset
+                )));
+            }
+            data[12] = (mem.offset / OFFSET_STEP) as u8;
+        }
+
+        let power = match &mem.power {
+            Some(power) => POWER_LEVELS
+                .iter()
+                .position(|(_, watts)| power.watts() >= *watts)
+                .unwrap_or(POWER_LEVELS.len() - 1) as u8,
+            None => 0,
+        };
+        // One tone serves both Tone and TSQL
+        data[8] = (power << 6) | index_of(&TONES, mem.rtone, "tone")?;
+        data[9] = (data[9] & 0x80) | index_of(&DTCS_CODES, mem.dtcs, "DTCS code")?;
+
+        Ok(data)
+    }
+
+    fn encode_name(name: &str) -> RadioResult<[u8; NAME_SLOT_SIZE]> {
+        let mut slot = [NAME_PAD; NAME_SLOT_SIZE];
+        slot[7] = 0xFF;
+        let name = name.trim_end().to_ascii_uppercase();
+        if name.is_empty() {
+            slot[6] = 0;
+            return Ok(slot);
+        }
+        for (byte, c) in slot.iter_mut().zip(name.chars().take(NAME_LENGTH)) {
+            *byte = CHARSET
+                .find(c)
+                .ok_or_else(|| RadioError::Radio(format!("Unsupported character in name: {}", c)))?
+                as u8;
+        }
+        slot[6] = USE_NAME;
+        Ok(slot)
+    }
+}
+
+impl ImageAccess for FT60Radio {
+    fn image(&self) -> Option<&MemoryMap> {
+        self.mmap.as_ref()
+    }
+
+    fn image_mut(&mut self) -> Option<&mut MemoryMap> {
+        self.mmap.as_mut()
+    }
+}
+
+impl Default for FT60Radio {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Radio for FT60Radio {
+    fn vendor(&self) -> &str {
+        "Yaesu"
+    }
+
+    fn model(&self) -> &str {
+        "FT-60R"
+    }
+
+    fn connection_profile(&self) -> ConnectionProfile {
+        CONNECTION
+    }
+
+    fn get_features(&self) -> RadioFeatures {
+        RadioFeatures {
+            memory_bounds: (1, NUM_MEMORIES),
+            valid_modes: vec![Mode::Fm, Mode::Nfm, Mode::Am],
+            valid_tmodes: TONE_MODES.to_vec(),
+            valid_duplexes: DUPLEXES.iter().map(|(_, d)| *d).collect(),
+            valid_tuning_steps: STEPS.to_vec(),
+            valid_tones: TONES.to_vec(),
+            valid_dtcs_codes: DTCS_CODES.to_vec(),
+            valid_power_levels: POWER_LEVELS
+                .iter()
+                .map(|(label, watts)| PowerLevel::from_watts(*label, *watts))
+                .collect(),
+            valid_name_length: NAME_LENGTH,
+            valid_characters: CHARSET.to_string(),
+            valid_bands: vec![(108_000_000, 520_000_000), (700_000_000, 999_990_000)],
+            valid_skips: vec![Skip::None, Skip::Skip, Skip::Priority],
+            has_bank: true,
+            has_dtcs: true,
+            has_dtcs_polarity: false,
+            has_ctone: false,
+            has_cross: false,
+            has_tuning_step: true,
+            has_mode: true,
+            has_offset: true,
+            has_name: true,
+            can_odd_split: true,
+            can_delete: true,
+            ..Default::default()
+        }
+    }
+
+    fn get_memory(&mut self, number: u32) -> RadioResult<Option<Memory>> {
+        let index = channel_index(number, NUM_MEMORIES)?;
+        if !self.is_used(index)? {
+            return Ok(None);
+        }
+        self.decode_memory(number, index).map(Some)
+    }
+
+    fn set_memory(&mut self, memory: &Memory) -> RadioResult<()> {
+        let index = channel_index(memory.number, NUM_MEMORIES)?;
+        let mem_offset = MEMORY_OFFSET + index * MEMORY_SIZE;
+
+        if memory.empty {
+            let byte = self.read(mem_offset, 1)?[0];
+            self.write(mem_offset, &[byte & !USED])?;
+            for bank in 0..NUM_BANKS {
+                self.set_bank_bit(bank, index, false)?;
+            }
+            return self.write(
+                NAMES_OFFSET + index * NAME_SLOT_SIZE,
+                &[0xFF; NAME_SLOT_SIZE],
+            );
+        }
+
+        let current = self.record_base(mem_offset, MEMORY_SIZE, self.is_used(index)?)?;
+        let data = Self::encode_memory(memory, &current)?;
+        let name = Self::encode_name(&memory.name)?;
+
+        let (offset, shift) = Self::skip_bits(index);
+        let skip = match memory.skip {
+            Skip::None => 0,
+            Skip::Skip => 1,
+            Skip::Priority => 2,
+        };
+        let flags = self.read(offset, 1)?[0] & !(0x03 << shift);
+
+        self.write(mem_offset, &data)?;
+        self.write(NAMES_OFFSET + index * NAME_SLOT_SIZE, &name)?;
+        self.write(offset, &[flags | (skip << shift)])
+    }
+
+    fn bank_model(&mut self) -> Option<&mut dyn BankModel> {
+        Some(self)
+    }
+}
+
+/// FT-60R banks: ten unnamed banks, a memory can be in any of them
+impl BankModel for FT60Radio {
+    fn bank_style(&self) -> BankStyle {
+        BankStyle::Multiple
+    }
+
+    fn bank_name_length(&self) -> usize {
+        0
+    }
+
+    fn get_banks(&self) -> RadioResult<Vec<Bank>> {
+        Ok((0..NUM_BANKS)
+            .map(|index| Bank::new(index, format!("Bank {}", index + 1)))
+            .collect())
+    }
+
+    fn rename_bank(&mut self, _bank: usize, _name: &str) -> RadioResult<()> {
+        Err(RadioError::Unsupported(
+            "FT-60R banks can't be renamed".to_string(),
+        ))
+    }
+
+    fn get_memory_banks(&self, number: u32) -> RadioResult<Vec<usize>> {
+        let index = channel_index(number, NUM_MEMORIES)?;
+        if !self.is_used(index)? {
+            return Ok(Vec::new());
+        }
+        let mut banks = Vec::new();
+        for bank in 0..NUM_BANKS {
+            let (offset, mask) = Self::bank_bit(bank, index);
+            if self.read(offset, 1)?[0] & mask != 0 {
+                banks.push(bank);
+            }
+        }
+        Ok(banks)
+    }
+
+    fn add_memory_to_bank(&mut self, number: u32, bank: usize) -> RadioResult<()> {
+        let index = channel_index(number, NUM_MEMORIES)?;
+        if !self.is_used(index)? {
+            return Err(RadioError::Radio(format!("Memory #{} is empty", number)));
+        }
+        self.set_bank_bit(bank, index, true)
+    }
+
+    fn remove_memory_from_bank(&mut self, number: u32, bank: usize) -> RadioResult<()> {
+        if !self.get_memory_banks(number)?.contains(&bank) {
+            return Err(RadioError::Radio(format!(
+                "Memory #{} is not in bank {}",
+                number, bank
+            )));
+        }
+        self.set_bank_bit(bank, number as usize - 1, false)
+    }
+
+    fn get_bank_memories(&self, bank: usize) -> RadioResult<Vec<u32>> {
+        if bank >= NUM_BANKS {
+            return Err(RadioError::Radio(format!("Invalid bank: {}", bank)));
+        }
+        let bitmap = self.read(BANKS_OFFSET + bank * BANK_SIZE, BANK_SIZE)?;
+        let mut memories = Vec::new();
+        for index in 0..NUM_MEMORIES as usize {
+            if bitmap[index / 8] & (1 << (index % 8)) != 0 && self.is_used(index)? {
+                memories.push(index as u32 + 1);
+            }
+        }
+        Ok(memories)
+    }
+}
+
+impl CloneModeRadio for FT60Radio {
+    fn get_memsize(&self) -> usize {
+        MEMSIZE
+    }
+
+    /// Receive the image the radio sends once the user starts the clone
+    async fn sync_in<T: Transport + ?Sized>(
+        &mut self,
+        port: &mut T,
+        status_fn: Option<StatusCallback>,
+    ) -> RadioResult<MemoryMap> {
+        let mut data = vec![0u8; HEADER_SIZE];
+        port.read_exact(&mut data)
+            .await
+            .map_err(|e| RadioError::Serial(e.to_string()))?;
+        send(port, &[ACK]).await?;
+
+        while data.len() < MEMSIZE {
+            let mut chunk = vec![0u8; CHUNK_SIZE.min(MEMSIZE - data.len())];
+            port.read_exact(&mut chunk)
+                .await
+                .map_err(|e| RadioError::Serial(e.to_string()))?;
+            data.extend_from_slice(&chunk);
+            send(port, &[ACK]).await?;
+
+            if let Some(ref callback) = status_fn {
+                let status = Status::new(data.len(), MEMSIZE, "Cloning from radio".to_string());
+                callback(status.current, status.max, &status.message);
+            }
+        }
+
+        let expected = checksum(&data);
+        if data[CHECKSUM_OFFSET] != expected {
+            return Err(RadioError::InvalidResponse(format!(
+                "Checksum mismatch: expected 0x{:02X}, got 0x{:02X}",
+                expected, data[CHECKSUM_OFFSET]
+            )));
+        }
+
+        let mmap = MemoryMap::new(data);
+        self.mmap = Some(mmap.clone());
+        Ok(mmap)
+    }
+
+    /// Send the image to a radio waiting in clone receive mode
+    async fn sync_out<T: Transport + ?Sized>(
+        &mut self,
+        port: &mut T,
+        mmap: &MemoryMap,
+        status_fn: Option<StatusCallback>,
+    ) -> RadioResult<()> {
+        let mut data = mmap.get_packed().to_vec();
+        if data.len() < MEMSIZE {
+            return Err(RadioError::Radio(format!(
+                "Memory map too small: expected {} bytes, got {}",
+                MEMSIZE,
+                data.len()
+            )));
+        }
+        data.truncate(MEMSIZE);
+        data[CHECKSUM_OFFSET] = checksum(&data);
+
+        let (header, body) = data.split_at(HEADER_SIZE);
+        let mut pos = 0;
+        for chunk in std::iter::once(header).chain(body.chunks(CHUNK_SIZE)) {
+            send(port, chunk).await?;
+            let mut reply = [0u8; 1];
+            port.read_exact(&mut reply)
+                .await
+                .map_err(|e| RadioError::Serial(e.to_string()))?;
+            if reply[0] != ACK {
+                tracing::error!("Radio did not ACK the chunk at 0x{:04X}", pos);
+                return Err(RadioError::Nak);
+            }
+            pos += chunk.len();
+
+            if let Some(ref callback) = status_fn {
+                let status = Status::new(pos, MEMSIZE, "Cloning to radio".to_string());
+                callback(status.current, status.max, &status.message);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn process_mmap(&mut self, mmap: &MemoryMap) -> RadioResult<()> {
+        if mmap.len() < MEMSIZE {
+            return Err(RadioError::Radio(format!(
+                "Memory map too small: expected at least {} bytes, got {}",
+                MEMSIZE,
+                mmap.len()
+            )));
+        }
+        self.mmap = Some(mmap.clone());
+        Ok(())
+    }
+
+    fn get_mmap(&self) -> Option<&MemoryMap> {
+        self.mmap.as_ref()
+    }
+
+    fn match_model(data: &[u8], _filename: &str) -> bool {
+        data.len() == MEMSIZE && data.starts_with(MODEL_ID)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drivers::test_image::{check_feature_roundtrip, TestImage};
+    use crate::emulator::{EmulatedPort, FT60Emulator};
+
+    /// Image with every channel unused and the name area erased
+    fn erased_image() -> TestImage {
+        let mut image = TestImage::new(MEMSIZE, 0x00);
+        image
+            .set(0, MODEL_ID)
+            .fill(NAMES_OFFSET..BANKS_OFFSET, 0xFF);
+        image
+    }
+
+    fn byte_at(radio: &FT60Radio, offset: usize) -> u8 {
+        radio.read(offset, 1).unwrap()[0]
+    }
+
+    #[test]
+    fn test_packed_scan_flags_and_bank_bits() {
+        let mut image = erased_image();
+        for index in [0, 1, 2, 3, 998, 999] {
+            // 146.520 FM, 5 kHz step
+            image.set_entry(MEMORY_OFFSET, MEMORY_SIZE, index, &[USED, 0x01, 0x46, 0x52]);
+        }
+        image
+            // Channels 1-4 share one byte, two bits each from the bottom
+            .set(FLAGS_OFFSET, &[0x01 | (2 << 4)])
+            // Channel 1000 is the top bit of the last flags byte
+            .set(FLAGS_OFFSET + 249, &[2 << 6])
+            // Channels 999 and 1000 sit in byte 124 of each bank bitmap
+            .set(BANKS_OFFSET + 124, &[0x40])
+            .set(BANKS_OFFSET + 3 * BANK_SIZE + 124, &[0x80]);
+        let mut radio = image.load(FT60Radio::new());
+
+        let skips: Vec<Skip> = (1..=4)
+            .map(|n| radio.get_memory(n).unwrap().unwrap().skip)
+            .collect();
+        assert_eq!(
+            skips,
+            vec![Skip::Skip, Skip::None, Skip::Priority, Skip::None]
+        );
+        assert_eq!(
+            radio.get_memory(1000).unwrap().unwrap().skip,
+            Skip::Priority
+        );
+
+        // Only channel 2's pair changes
+        let mut mem = radio.get_memory(2).unwrap().unwrap();
+        mem.skip = Skip::Skip;
+        radio.set_memory(&mem).unwrap();
+        assert_eq!(byte_at(&radio, FLAGS_OFFSET), 0x25);
+
+        let model = radio.bank_model().unwrap();
+        assert_eq!(model.get_memory_banks(999).unwrap(), vec![0]);
+        assert_eq!(model.get_memory_banks(1000).unwrap(), vec![3]);
+        model.add_memory_to_bank(1000, 0).unwrap();
+        model.remove_memory_from_bank(999, 0).unwrap();
+        assert_eq!(byte_at(&radio, BANKS_OFFSET + 124), 0x80);
+        assert_eq!(byte_at(&radio, BANKS_OFFSET + 3 * BANK_SIZE + 124), 0x80);
+    }
+
+    /// A split DTCS channel with a name, as the radio stores it
+    ///
+    /// 446.00625 is stored as CHIRP writes it (5 kHz and fractional flags),
+    /// and the flag bits CHIRP
+    /// leaves undocumented (0x40 in byte 0, 0x88 in byte 4, 0x80 in byte 9)
+    /// are set so an edit has something to keep.
+    const SPLIT_RECORD: [u8; MEMORY_SIZE] = [
+        0xC4, 0xC4, 0x46, 0x00, 0xAC, 0x01, 0x46, 0x52, 0x80, 0xE7, 0x00, 0x00, 0x00, 0x5A, 0x5A,
+        0x5A,
+    ];
+
+    /// "(A+B)" in the radio's character set
+    const SPLIT_NAME: [u8; NAME_SLOT_SIZE] = [0x25, 0x0A, 0x28, 0x0B, 0x26, 0x24, 0x80, 0xFF];
+
+    #[tokio::test]
+    async fn test_record_quirks() {
+        let mut image = erased_image();
+        for (index, name_flag) in [(4, USE_NAME), (5, 0)] {
+            image
+                .set_entry(MEMORY_OFFSET, MEMORY_SIZE, index, &SPLIT_RECORD)
+                .set_entry(NAMES_OFFSET, NAME_SLOT_SIZE, index, &SPLIT_NAME)
+                .set(NAMES_OFFSET + index * NAME_SLOT_SIZE + 6, &[name_flag]);
+        }
+        // Unused slot holding leftovers from an earlier channel
+        image.set_entry(MEMORY_OFFSET, MEMORY_SIZE, 6, &[0x7F; MEMORY_SIZE]);
+        let mut image = image.into_bytes();
+        image[CHECKSUM_OFFSET] = checksum(&image);
+
+        let mut emulator = FT60Emulator::new(MemoryMap::new(image));
+        emulator.start_send();
+        let mut radio = FT60Radio::new();
+        radio
+            .sync_in(&mut EmulatedPort::new(emulator), None)
+            .await
+            .unwrap();
+
+        let mem = radio.get_memory(5).unwrap().unwrap();
+        assert_eq!(mem.freq, 446_006_250);
+        assert_eq!((mem.duplex, mem.offset), (Duplex::Split, 146_520_000));
+        assert_eq!((mem.tmode, mem.dtcs), (ToneMode::Dtcs, 754));
+        assert_eq!((mem.tuning_step, mem.mode), (12.5, Mode::Fm));
+        assert_eq!(mem.power.as_ref().unwrap().label(), "Low");
+        assert_eq!(mem.name, "(A+B)");
+        // Same name bytes, but the radio shows the frequency instead
+        assert_eq!(radio.get_memory(6).unwrap().unwrap().name, "");
+        assert!(radio.get_memory(7).unwrap().is_none());
+
+        let mut edited = mem.clone();
+        edited.freq = 445_950_000;
+        edited.name = "fm?".to_string();
+        radio.set_memory(&edited).unwrap();
+        let record = radio
+            .read(MEMORY_OFFSET + 4 * MEMORY_SIZE, MEMORY_SIZE)
+            .unwrap();
+        assert_eq!(&record[..4], &[0xC4, 0x04, 0x45, 0x95]);
+        assert_eq!((record[4], record[9]), (0xAC, 0xE7));
+        assert_eq!(&record[13..], &[0x5A, 0x5A, 0x5A]);
+        let name = radio
+            .read(NAMES_OFFSET + 4 * NAME_SLOT_SIZE, NAME_SLOT_SIZE)
+            .unwrap();
+        assert_eq!(name, &[0x0F, 0x16, 0x2C, 0x24, 0x24, 0x24, 0x80, 0xFF]);
+
+        // The leftovers in an unused slot don't leak into a new channel
+        edited.number = 7;
+        radio.set_memory(&edited).unwrap();
+        let record = radio
+            .read(MEMORY_OFFSET + 6 * MEMORY_SIZE, MEMORY_SIZE)
+            .unwrap();
+        assert_eq!((record[0], record[4], record[9]), (0x84, 0x24, 0x67));
+        assert_eq!(&record[13..], &[0, 0, 0]);
+    }
+
+    #[test]
+    fn test_match_model() {
+        let image = erased_image();
+        assert!(FT60Radio::match_model(image.bytes(), "ft60.img"));
+        // Same size, but not an FT-60R clone header
+        let mut other = erased_image();
+        other.set(0, b"AH003");
+        assert!(!FT60Radio::match_model(other.bytes(), "ft60.img"));
+        assert!(!FT60Radio::match_model(
+            &image.bytes()[..0x2000],
+            "ft60.img"
+        ));
+    }
+
+    #[test]
+    fn test_freq_encoding() {
+        assert_eq!(encode_freq(146_520_000).unwrap(), [0x01, 0x46, 0x52]);
+        assert_eq!(encode_freq(146_515_000).unwrap(), [0x81, 0x46, 0x51]);
+        assert_eq!(encode_freq(146_512_500).unwrap(), [0x41, 0x46, 0x51]);
+        assert_eq!(encode_freq(146_537_500).unwrap(), [0xC1, 0x46, 0x53]);
+        // PMR446 channels 1 and 2 sit on 6.25 kHz offsets
+        assert_eq!(encode_freq(446_006_250).unwrap(), [0xC4, 0x46, 0x00]);
+        assert_eq!(decode_freq(&[0xC4, 0x46, 0x00]).unwrap(), 446_006_250);
+        assert_eq!(decode_freq(&[0xC4, 0x46, 0x01]).unwrap(), 446_018_750);
+        for freq in [446_006_250, 446_018_750, 162_550_000, 999_987_500] {
+            assert_eq!(decode_freq(&encode_freq(freq).unwrap()).unwrap(), freq);
+        }
+        assert!(encode_freq(146_520_100).is_err());
+        // Off the 5 kHz raster but on none of the steps the flag stands for
+        assert!(encode_freq(146_511_250).is_err());
+    }
+
+    #[test]
+    fn test_feature_roundtrip() {
+        let mut radio = erased_image().load(FT60Radio::new());
+        let mut base = Memory::new(10);
+        base.freq = 146_520_000;
+        check_feature_roundtrip(&mut radio, &base);
+        base.freq = 446_000_000;
+        check_feature_roundtrip(&mut radio, &base);
+    }
+
+    #[test]
+    fn test_memory_roundtrip_and_banks() {
+        let mut radio = erased_image().load(FT60Radio::new());
+        assert!(radio.get_memory(1).unwrap().is_none());
+
+        let mut mem = Memory::new(1000);
+        mem.freq = 146_940_000;
+        mem.name = "w6cx".to_string();
+        mem.duplex = Duplex::Minus;
+        mem.offset = 600_000;
+        mem.tmode = ToneMode::Tone;
+        mem.rtone = 100.0;
+        mem.tuning_step = 12.5;
+        mem.mode = Mode::Nfm;
+        mem.skip = Skip::Priority;
+        mem.power = Some(PowerLevel::from_watts("Low", 0.5));
+        radio.set_memory(&mem).unwrap();
+
+        let read = radio.get_memory(1000).unwrap().unwrap();
+        assert_eq!(read.freq, 146_940_000);
+        assert_eq!(read.name, "W6CX");
+        assert_eq!((read.duplex, read.offset), (Duplex::Minus, 600_000));
+        assert_eq!((read.tmode, read.rtone), (ToneMode::Tone, 100.0));
+        assert_eq!(read.tuning_step, 12.5);
+        assert_eq!(read.mode, Mode::Nfm);
+        assert_eq!(read.skip, Skip::Priority);
+        assert_eq!(read.power.unwrap().label(), "Low");
+
+        let mut split = Memory::new(2);
+        split.freq = 446_006_250;
+        split.duplex = Duplex::Split;
+        split.offset = 146_520_000;
+        radio.set_memory(&split).unwrap();
+        let read = radio.get_memory(2).unwrap().unwrap();
+        assert_eq!((read.duplex, read.offset), (Duplex::Split, 146_520_000));
+        assert_eq!(read.name, "");
+
+        let mut wide = mem.clone();
+        wide.offset = 12_800_000;
+        assert!(radio.set_memory(&wide).is_err());
+        wide.offset = 625_000;
+        assert!(radio.set_memory(&wide).is_err());
+        wide.offset = 12_750_000;
+        radio.set_memory(&wide).unwrap();
+        assert_eq!(radio.get_memory(1000).unwrap().unwrap().offset, 12_750_000);
+        radio.set_memory(&mem).unwrap();
+
+        let model = radio.bank_model().unwrap();
+        assert_eq!(model.get_banks().unwrap().len(), 10);
+        model.add_memory_to_bank(1000, 0).unwrap();
+        model.add_memory_to_bank(1000, 9).unwrap();
+        model.add_memory_to_bank(2, 9).unwrap();
+        assert!(model.add_memory_to_bank(3, 0).is_err());
+        assert_eq!(model.get_memory_banks(1000).unwrap(), vec![0, 9]);
+        assert_eq!(model.get_bank_memories(9).unwrap(), vec![2, 1000]);
+        model.remove_memory_from_bank(1000, 0).unwrap();
+        assert!(model.remove_memory_from_bank(1000, 0).is_err());
+
+        let data = radio.mmap.as_ref().unwrap().get_packed();
+        assert_eq!(data[BANKS_OFFSET + 9 * BANK_SIZE + 999 / 8], 1 << (999 % 8));
+
+        radio.delete_memory(1000).unwrap();
+        assert!(radio.get_memory(1000).unwrap().is_none());
+        assert_eq!(
+            radio.bank_model().unwrap().get_bank_memories(9).unwrap(),
+            vec![2]
+        );
+        assert!(matches!(
+            radio.get_memory(0),
+            Err(RadioError::InvalidMemory(0))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_clone_over_emulator() {
+        let mut image: Vec<u8> = (0..MEMSIZE).map(|i| (i * 31) as u8).collect();
+        image[CHECKSUM_OFFSET] = checksum(&image);
+
+        // Corrupt checksum
+        let mut bad = image.clone();
+        bad[CHECKSUM_OFFSET] ^= 0xFF;
+        let mut emulator = FT60Emulator::new(MemoryMap::new(bad.clone()));
+        emulator.start_send();
+        assert!(matches!(
+            FT60Radio::new()
+                .sync_in(&mut EmulatedPort::new(emulator), None)
+                .await,
+            Err(RadioError::InvalidResponse(_))
+        ));
+
+        // Upload recomputes the checksum and waits for the ACK after every chunk
+        let mut port = EmulatedPort::new(FT60Emulator::new(MemoryMap::new_with_size(0)));
+        FT60Radio::new()
+            .sync_out(&mut port, &MemoryMap::new(bad), None)
+            .await
+            .unwrap();
+        assert!(!port.radio().overrun());
+        assert_eq!(port.radio().mmap().get_packed(), &image[..]);
+
+        let mut emulator = FT60Emulator::new(MemoryMap::new(image.clone()));
+        emulator.start_send();
+        let mmap = FT60Radio::new()
+            .sync_in(&mut EmulatedPort::new(emulator), None)
+            .await
+            .unwrap();
+        assert_eq!(mmap.get_packed(), &image[..]);
+    }
+}
@@ -0,0 +1,1272 @@
+// Icom CI-V memory driver (IC-9700, IC-705, ID-52)
+// Reference: chirp/drivers/icomciv.py lines 145-169 (memory formats)
+//            chirp/drivers/icomciv.py lines 1337-1720 (IC-9700 implementation)
+//
+// These radios are programmed channel by channel with the CI-V 0x1A 0x00
+// memory command. What differs between models is data, kept in a
+// `CivProfile`: the CI-V address, the memory record layout, the mode table,
+// the memory banks (the IC-9700 keeps one per band) and the channel range.
+
+use crate::bitwise::bcd;
+use crate::core::{
+    CrossMode, DVMemory, DtcsPolarity, Duplex, Memory, Mode, RadioFeatures, ToneMode,
+};
+use crate::drivers::traits::{BoxFuture, StatusCallback};
+use crate::drivers::{CommandModeRadio, Radio, RadioError, RadioResult};
+use crate::formats::IcfFile;
+use crate::memmap::MemoryMap;
+use crate::serial::{CivProtocol, ConnectionProfile, Transport};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+const CONTROLLER_ADDR: u8 = 0xE0;
+
+/// CI-V at 19200 baud, no flow control. DTR powers the interface; RTS high
+/// would key the transmitter. CI-V replies are quick, so a short timeout.
+const CONNECTION: ConnectionProfile = ConnectionProfile::new(19200)
+    .with_lines(true, false)
+    .with_timeout(Duration::from_secs(2));
+
+/// Layout of a CI-V memory record
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryFormat {
+    /// Record has filter and data-mode bytes after the mode (all-mode rigs)
+    pub has_filter: bool,
+    /// Bytes in the name field
+    pub name_length: usize,
+}
+
+impl MemoryFormat {
+    /// Offset of the duplex/tone mode byte
+    const fn tone_offset(&self) -> usize {
+        if self.has_filter {
+            12
+        } else {
+            10
+        }
+    }
+
+    /// Record size, including bank and channel number
+    pub const fn size(&self) -> usize {
+        self.tone_offset() + 39 + self.name_length
+    }
+}
+
+/// A memory bank, selected by the bank byte of the memory command
+#[derive(Debug)]
+pub struct CivBank {
+    /// Bank byte
+    pub number: u8,
+    /// Display name
+    pub name: &'static str,
+    /// Model name of a driver limited to this bank
+    pub model: &'static str,
+    /// Frequency ranges (Hz)
+    pub bands: &'static [(u64, u64)],
+    /// Modes of the profile's table this bank can't use
+    pub excluded_modes: &'static [Mode],
+}
+
+/// Everything that differs between CI-V memory radios
+#[derive(Debug)]
+pub struct CivProfile {
+    pub model: &'static str,
+    /// CI-V address (also the transceiver ID)
+    pub model_code: u8,
+    pub format: MemoryFormat,
+    /// Mode for each BCD mode code (None = unused code)
+    pub modes: &'static [Option<Mode>],
+    pub banks: &'static [CivBank],
+    /// Channel numbers within each bank
+    pub memory_bounds: (u32, u32),
+}
+
+impl CivProfile {
+    /// Display name for a bank number
+    pub fn bank_name(&self, number: u8) -> &'static str {
+        self.bank(number).map_or("Unknown", |bank| bank.name)
+    }
+
+    fn bank(&self, number: u8) -> Option<&'static CivBank> {
+        self.banks.iter().find(|bank| bank.number == number)
+    }
+
+    /// Bank used for memories without a band tag
+    fn default_bank(&self) -> u8 {
+        self.banks[0].number
+    }
+
+    /// Memories are tagged with their bank (the GUI groups them by band)
+    fn tags_banks(&self) -> bool {
+        self.banks.len() > 1
+    }
+
+    fn channels(&self) -> usize {
+        (self.memory_bounds.1 - self.memory_bounds.0 + 1) as usize
+    }
+}
+
+/// Record layout of the IC-9700 and IC-705
+/// Reference: MEM_IC9700_FORMAT in icomciv.py lines 145-167
+const ALL_MODE_FORMAT: MemoryFormat = MemoryFormat {
+    has_filter: true,
+    name_length: 16,
+};
+
+/// Record layout of the D-STAR handhelds: no filter or data-mode bytes
+const HANDHELD_FORMAT: MemoryFormat = MemoryFormat {
+    has_filter: false,
+    name_length: 16,
+};
+
+// IC-9700 supports these modes
+// Matches Python CHIRP _MODES array (29 entries, indices 0-28)
+const IC9700_MODES: &[Option<Mode>] = &[
+    Some(Mode::Lsb),   // 0
+    Some(Mode::Usb),   // 1
+    Some(Mode::Am),    // 2
+    Some(Mode::Cw),    // 3
+    Some(Mode::Rtty),  // 4
+    Some(Mode::Fm),    // 5
+    Some(Mode::Cwr),   // 6
+    Some(Mode::Rttyr), // 7
+    None,              // 8
+    None,              // 9
+    None,              // 10
+    None,              // 11
+    None,              // 12
+    None,              // 13
+    None,              // 14
+    None,              // 15
+    None,              // 16
+    Some(Mode::Dv),    // 17
+    None,              // 18
+    None,              // 19
+    None,              // 20
+    None,              // 21
+    Some(Mode::Dd),    // 22
+    None,              // 23
+    None,              // 24
+    None,              // 25
+    None,              // 26
+    None,              // 27
+    None,              // 28
+];
+
+// IC-705 mode codes (CI-V reference, "operating mode")
+const IC705_MODES: &[Option<Mode>] = &[
+    Some(Mode::Lsb),   // 0
+    Some(Mode::Usb),   // 1
+    Some(Mode::Am),    // 2
+    Some(Mode::Cw),    // 3
+    Some(Mode::Rtty),  // 4
+    Some(Mode::Fm),    // 5
+    Some(Mode::Wfm),   // 6
+    Some(Mode::Cwr),   // 7
+    Some(Mode::Rttyr), // 8
+    None,              // 9
+    None,              // 10
+    None,              // 11
+    None,              // 12
+    None,              // 13
+    None,              // 14
+    None,              // 15
+    None,              // 16
+    Some(Mode::Dv),    // 17
+];
+
+// ID-52 mode codes (FM, AM and D-STAR only)
+const ID52_MODES: &[Option<Mode>] = &[
+    None,           // 0
+    None,           // 1
+    Some(Mode::Am), // 2
+    None,           // 3
+    None,           // 4
+    Some(Mode::Fm), // 5
+    None,           // 6
+    None,           // 7
+    None,           // 8
+    None,           // 9
+    None,           // 10
+    None,           // 11
+    None,           // 12
+    None,           // 13
+    None,           // 14
+    None,           // 15
+    None,           // 16
+    Some(Mode::Dv), // 17
+];
+
+/// Icom IC-9700: one memory bank per band, 99 channels each
+pub static IC9700: CivProfile = CivProfile {
+    model: "IC-9700",
+    model_code: 0xA2,
+    format: ALL_MODE_FORMAT,
+    modes: IC9700_MODES,
+    banks: &[
+        CivBank {
+            number: 1,
+            name: "VHF (144 MHz)",
+            model: "IC-9700 (VHF)",
+            bands: &[(144_000_000, 148_000_000)],
+            excluded_modes: &[],
+        },
+        CivBank {
+            number: 2,
+            name: "UHF (430 MHz)",
+            model: "IC-9700 (UHF)",
+            bands: &[(430_000_000, 450_000_000)],
+            excluded_modes: &[],
+        },
+        CivBank {
+            number: 3,
+            name: "1.2 GHz (1240 MHz)",
+            model: "IC-9700 (1.2GHz)",
+            bands: &[(1_240_000_000, 1_300_000_000)],
+            // 1.2GHz band doesn't support DD mode
+            excluded_modes: &[Mode::Dd],
+        },
+    ],
+    memory_bounds: (1, 99),
+};
+
+/// Icom IC-705: HF to 70 cm in a single memory bank
+pub static IC705: CivProfile = CivProfile {
+    model: "IC-705",
+    model_code: 0xA4,
+    format: ALL_MODE_FORMAT,
+    modes: IC705_MODES,
+    banks: &[CivBank {
+        number: 1,
+        name: "Memory",
+        model: "IC-705",
+        bands: &[(30_000, 199_999_999), (400_000_000, 470_000_000)],
+        excluded_modes: &[],
+    }],
+    memory_bounds: (1, 99),
+};
+
+/// Icom ID-52: dual-band D-STAR handheld, channels numbered straight through
+pub static ID52: CivProfile = CivProfile {
+    model: "ID-52",
+    model_code: 0xA6,
+    format: HANDHELD_FORMAT,
+    modes: ID52_MODES,
+    banks: &[CivBank {
+        number: 1,
+        name: "Memory",
+        model: "ID-52",
+        bands: &[(118_000_000, 174_000_000), (375_000_000, 550_000_000)],
+        excluded_modes: &[],
+    }],
+    memory_bounds: (0, 1999),
+};
+
+/// Profiles tried by `IcomCivRadio::probe`
+const PROFILES: &[&CivProfile] = &[&IC9700, &IC705, &ID52];
+
+// Cross-mode tone support
+const CROSS_MODES: &[(u8, CrossMode)] = &[
+    (4, CrossMode::DtcsToNone),
+    (5, CrossMode::ToneToDtcs),
+    (6, CrossMode::DtcsToTone),
+    (7, CrossMode::ToneToTone),
+];
+
+/// Status callback shared across the per-band transfers
+type SharedStatusCallback = Arc<dyn Fn(usize, usize, &str) + Send + Sync>;
+
+/// Wrap a shared status callback so messages are prefixed with the bank name
+fn band_status(
+    status_fn: &Option<SharedStatusCallback>,
+    profile: &'static CivProfile,
+    band: u8,
+) -> Option<StatusCallback> {
+    if !profile.tags_banks() {
+        return status_fn.clone().map(|callback| {
+            Box::new(move |current: usize, total: usize, message: &str| {
+                callback(current, total, message)
+            }) as StatusCallback
+        });
+    }
+    status_fn.clone().map(|callback| {
+        Box::new(move |current: usize, total: usize, message: &str| {
+            callback(
+                current,
+                total,
+                &format!("{} - {}", profile.bank_name(band), message),
+            );
+        }) as StatusCallback
+    })
+}
+
+/// CI-V memory record
+///
+/// Byte layout (all-mode format; the handheld format has no filter or
+/// data_mode bytes, so everything after the mode moves up by two):
+/// 0: bank (1 byte)
+/// 1-2: number BCD (2 bytes)
+/// 3: select_memory (1 byte)
+/// 4-8: freq BCD LE (5 bytes)
+/// 9: mode (1 byte)
+/// 10: filter (1 byte)
+/// 11: data_mode (1 byte)
+/// 12: duplex(low 4) | tmode(high 4) (1 byte)
+/// 13: dig_sql (1 byte)
+/// 14-16: rtone BCD (3 bytes)
+/// 17-19: ctone BCD (3 bytes)
+/// 20: dtcs_polarity (1 byte)
+/// 21-22: dtcs BCD (2 bytes)
+/// 23: dig_code (1 byte)
+/// 24-26: duplex_offset BCD LE (3 bytes)
+/// 27-34: urcall (8 bytes)
+/// 35-42: rpt1call (8 bytes)
+/// 43-50: rpt2call (8 bytes)
+/// 51-66: name (16 bytes)
+#[derive(Debug)]
+#[allow(dead_code)]
+struct RawMemory {
+    bank: u8,
+    number: u16, // BCD
+    select_memory: u8,
+    freq: u64, // BCD, little-endian, 5 bytes (10 digits)
+    mode: u8,  // BCD
+    filter: u8,
+    data_mode: u8, // BCD
+    duplex: u8,    // 4 bits
+    tmode: u8,     // 4 bits
+    dig_sql: u8,   // 4 bits
+    rtone: u16,    // BCD, 3 bytes
+    ctone: u16,    // BCD, 3 bytes
+    dtcs_polarity: u8,
+    dtcs: u16, // BCD, 2 bytes
+    dig_code: u8,
+    duplex_offset: u32, // BCD, little-endian, 3 bytes
+    urcall: [u8; 8],
+    rpt1call: [u8; 8],
+    rpt2call: [u8; 8],
+    name: Vec<u8>,
+}
+
+impl RawMemory {
+    /// Parse a memory record in the given format
+    fn from_bytes(data: &[u8], format: &MemoryFormat) -> RadioResult<Self> {
+        if data.len() < format.size() {
+            return Err(RadioError::InvalidResponse(format!(
+                "Memory data too short: {} bytes (expected {})",
+                data.len(),
+                format.size()
+            )));
+        }
+
+        let bank = data[0];
+        let number = bcd::bcd_to_int_be(&data[1..3])? as u16;
+        let select_memory = data[3];
+        let freq = bcd::bcd_to_int_le(&data[4..9])?;
+        // Mode is BCD encoded (bbcd in Python format)
+        let mode = bcd::bcd_to_int_be(&data[9..10])? as u8;
+        let (filter, data_mode) = if format.has_filter {
+            (data[10], data[11])
+        } else {
+            (0, 0)
+        };
+
+        // Everything after the mode is relative to the duplex/tmode byte
+        let t = format.tone_offset();
+
+        // Bitfields: duplex (low 4 bits), tmode (high 4 bits)
+        let duplex = data[t] & 0x0F;
+        let tmode = (data[t] >> 4) & 0x0F;
+
+        // Bitfields: dig_sql (low 4 bits)
+        let dig_sql = data[t + 1] & 0x0F;
+
+        // Tones are BCD, 3 bytes each (6 digits)
+        let rtone = bcd::bcd_to_int_be(&data[t + 2..t + 5])? as u16;
+        let ctone = bcd::bcd_to_int_be(&data[t + 5..t + 8])? as u16;
+
+        let dtcs_polarity = data[t + 8];
+        let dtcs = bcd::bcd_to_int_be(&data[t + 9..t + 11])? as u16;
+        let dig_code = data[t + 11];
+
+        // Duplex offset is little-endian BCD, 3 bytes
+        let duplex_offset = bcd::bcd_to_int_le(&data[t + 12..t + 15])? as u32;
+
+        // D-STAR call signs
+        let mut urcall = [0u8; 8];
+        let mut rpt1call = [0u8; 8];
+        let mut rpt2call = [0u8; 8];
+
+        urcall.copy_from_slice(&data[t + 15..t + 23]);
+        rpt1call.copy_from_slice(&data[t + 23..t + 31]);
+        rpt2call.copy_from_slice(&data[t + 31..t + 39]);
+        let name = data[t + 39..t + 39 + format.name_length].to_vec();
+
+        Ok(Self {
+            bank,
+            number,
+            select_memory,
+            freq,
+            mode,
+            filter,
+            data_mode,
+            duplex,
+            tmode,
+            dig_sql,
+            rtone,
+            ctone,
+            dtcs_polarity,
+            dtcs,
+            dig_code,
+            duplex_offset,
+            urcall,
+            rpt1call,
+            rpt2call,
+            name,
+        })
+    }
+
+    /// Convert to Memory struct
+    fn to_memory(&self, number: u32, modes: &[Option<Mode>]) -> RadioResult<Memory> {
+        let mode_idx = self.mode as usize;
+        tracing::debug!("Parsing memory {}: mode byte = {}", number, self.mode);
+        let mode = modes.get(mode_idx).copied().flatten().ok_or_else(|| {
+            let err = format!("Invalid mode: {} (index out of bounds or None)", self.mode);
+            tracing::error!("{}", err);
+            RadioError::InvalidResponse(err)
+        })?;
+
+        let mut mem = if mode == Mode::Dv {
+            // D-STAR mode - create DVMemory and populate both base and DV fields
+            let mut dv = DVMemory::new(number);
+
+            // Set base Memory DV fields (not DVMemory's duplicate fields)
+            // These will be preserved when we return dv.base below
+            dv.base.dv_urcall = String::from_utf8_lossy(&self.urcall).trim_end().to_string();
+            dv.base.dv_rpt1call = String::from_utf8_lossy(&self.rpt1call)
+                .trim_end()
+                .to_string();
+            dv.base.dv_rpt2call = String::from_utf8_lossy(&self.rpt2call)
+                .trim_end()
+                .to_string();
+            dv.base.dv_code = self.dig_code;
+
+            // Set other base fields
+            dv.base.freq = self.freq;
+            dv.base.name = String::from_utf8_lossy(&self.name).trim_end().to_string();
+            dv.base.mode = mode;
+
+            // Return base memory (Radio trait expects Memory, not DVMemory)
+            // The base.dv_* fields are now populated, so they'll be available
+            dv.base.clone()
+        } else {
+            Memory::new(number)
+        };
+
+        // Set fields directly (for non-DV) or update cloned base (for DV)
+        if mode != Mode::Dv {
+            mem.freq = self.freq;
+            mem.name = String::from_utf8_lossy(&self.name).trim_end().to_string();
+            mem.mode = mode;
+        }
+
+        // Tone mode
+        if let Some((_, cross_mode)) = CROSS_MODES.iter().find(|(code, _)| *code == self.tmode) {
+            mem.tmode = ToneMode::Cross;
+            mem.cross_mode = *cross_mode;
+        } else {
+            mem.tmode = match self.tmode {
+                1 => ToneMode::Tone,
+                2 => ToneMode::Tsql,
+                3 => ToneMode::Dtcs,
+                _ => ToneMode::None,
+            };
+        };
+
+        // Tones
+        mem.rtone = (self.rtone as f32) / 10.0;
+        mem.ctone = (self.ctone as f32) / 10.0;
+        mem.dtcs = self.dtcs;
+
+        // DTCS polarity
+        mem.dtcs_polarity = match self.dtcs_polarity {
+            0x11 => DtcsPolarity::RR,
+            0x10 => DtcsPolarity::RN,
+            0x01 => DtcsPolarity::NR,
+            _ => DtcsPolarity::NN,
+        };
+
+        // Duplex
+        mem.duplex = match self.duplex {
+            1 => Duplex::Plus,
+            2 => Duplex::Minus,
+            _ => Duplex::Simplex,
+        };
+        mem.offset = (self.duplex_offset as u64) * 100;
+
+        Ok(mem)
+    }
+
+    /// Convert from Memory struct to a record body (no bank or channel number)
+    fn from_memory(mem: &Memory, profile: &CivProfile) -> RadioResult<Vec<u8>> {
+        let format = &profile.format;
+        let mut data = Vec::with_capacity(format.size());
+
+        // NOTE: Bank and channel are sent in the CI-V command header,
+        // so we don't include them in the memory data itself
+
+        // Select memory (0 for now)
+        data.push(0);
+
+        // Frequency (BCD, little-endian, 5 bytes)
+        let freq_bcd = bcd::int_to_bcd_le(mem.freq, 5)?;
+        data.extend_from_slice(&freq_bcd);
+
+        // Mode (BCD encoded)
+        let mode_idx = profile
+            .modes
+            .iter()
+            .position(|m| *m == Some(mem.mode))
+            .ok_or_else(|| RadioError::Unsupported(format!("Mode not supported: {}", mem.mode)))?;
+        let mode_bcd = bcd::int_to_bcd_be(mode_idx as u64, 1)?;
+        data.push(mode_bcd[0]);
+
+        if format.has_filter {
+            // Filter (1 = FIL1, which is default for most modes)
+            data.push(1);
+
+            // Data mode (0 for now)
+            data.push(0);
+        }
+
+        // Duplex and tmode (bitfield byte)
+        let duplex = match mem.duplex {
+            Duplex::Plus => 1,
+            Duplex::Minus => 2,
+            _ => 0,
+        };
+
+        let tmode = if mem.tmode == ToneMode::Cross {
+            // Find cross mode code
+            CROSS_MODES
+                .iter()
+                .find(|(_, mode)| *mode == mem.cross_mode)
+                .map(|(code, _)| *code)
+                .unwrap_or(0)
+        } else {
+            match mem.tmode {
+                ToneMode::Tone => 1,
+                ToneMode::Tsql => 2,
+                ToneMode::Dtcs => 3,
+                _ => 0,
+            }
+        };
+
+        data.push(duplex | (tmode << 4));
+
+        // Dig_sql (0 for now) + unused
+        data.push(0);
+
+        // Tones (BCD, 3 bytes each)
+        let rtone = (mem.rtone * 10.0) as u16;
+        let ctone = (mem.ctone * 10.0) as u16;
+        let rtone_bcd = bcd::int_to_bcd_be(rtone as u64, 3)?;
+        let ctone_bcd = bcd::int_to_bcd_be(ctone as u64, 3)?;
+        data.extend_from_slice(&rtone_bcd);
+        data.extend_from_slice(&ctone_bcd);
+
+        // DTCS polarity
+        let dtcs_pol = match mem.dtcs_polarity {
+            DtcsPolarity::RR => 0x11,
+            DtcsPolarity::RN => 0x10,
+            DtcsPolarity::NR => 0x01,
+            DtcsPolarity::NN => 0x00,
+        };
+        data.push(dtcs_pol);
+
+        // DTCS code (BCD, 2 bytes)
+        let dtcs_bcd = bcd::int_to_bcd_be(mem.dtcs as u64, 2)?;
+        data.extend_from_slice(&dtcs_bcd);
+
+        // Digital code (dv_code field from Memory struct)
+        data.push(mem.dv_code);
+
+        // Duplex offset (BCD, little-endian, 3 bytes)
+        let offset = (mem.offset / 100) as u32;
+        let offset_bcd = bcd::int_to_bcd_le(offset as u64, 3)?;
+        data.extend_from_slice(&offset_bcd);
+
+        // D-STAR call signs
+        // For non-DV memories, the radio expects "CQCQCQ" as default
+        // For DV memories, these would come from DVMemory
+        let urcall_padded = if mem.dv_urcall.is_empty() {
+            *b"CQCQCQ  " // Default for non-DV
+        } else {
+            let mut buf = [b' '; 8];
+            buf[..mem.dv_urcall.len().min(8)]
+                .copy_from_slice(&mem.dv_urcall.as_bytes()[..mem.dv_urcall.len().min(8)]);
+            buf
+        };
+
+        let rpt1call_padded = if mem.dv_rpt1call.is_empty() {
+            [b' '; 8]
+        } else {
+            let mut buf = [b' '; 8];
+            buf[..mem.dv_rpt1call.len().min(8)]
+                .copy_from_slice(&mem.dv_rpt1call.as_bytes()[..mem.dv_rpt1call.len().min(8)]);
+            buf
+        };
+
+        let rpt2call_padded = if mem.dv_rpt2call.is_empty() {
+            [b' '; 8]
+        } else {
+            let mut buf = [b' '; 8];
+            buf[..mem.dv_rpt2call.len().min(8)]
+                .copy_from_slice(&mem.dv_rpt2call.as_bytes()[..mem.dv_rpt2call.len().min(8)]);
+            buf
+        };
+
+        data.extend_from_slice(&urcall_padded);
+        data.extend_from_slice(&rpt1call_padded);
+        data.extend_from_slice(&rpt2call_padded);
+
+        // Name (space padded)
+        let len = mem.name.len().min(format.name_length);
+        let mut name_padded = vec![b' '; format.name_length];
+        name_padded[..len].copy_from_slice(&mem.name.as_bytes()[..len]);
+        data.extend_from_slice(&name_padded);
+
+        Ok(data)
+    }
+}
+
+/// Icom CI-V memory radio driver
+pub struct IcomCivRadio {
+    profile: &'static CivProfile,
+    protocol: CivProtocol,
+    band: Option<u8>,
+}
+
+impl IcomCivRadio {
+    pub fn new(profile: &'static CivProfile) -> Self {
+        Self {
+            profile,
+            protocol: CivProtocol::new(profile.model_code, CONTROLLER_ADDR),
+            band: None,
+        }
+    }
+
+    /// Driver limited to one memory bank
+    pub fn new_band(profile: &'static CivProfile, band: u8) -> Self {
+        Self {
+            band: Some(band),
+            ..Self::new(profile)
+        }
+    }
+
+    pub fn profile(&self) -> &'static CivProfile {
+        self.profile
+    }
+}
+
+impl Default for IcomCivRadio {
+    fn default() -> Self {
+        Self::new(&IC9700)
+    }
+}
+
+impl Radio for IcomCivRadio {
+    fn vendor(&self) -> &str {
+        "Icom"
+    }
+
+    fn model(&self) -> &str {
+        self.band
+            .and_then(|band| self.profile.bank(band))
+            .map_or(self.profile.model, |bank| bank.model)
+    }
+
+    fn connection_profile(&self) -> ConnectionProfile {
+        CONNECTION
+    }
+
+    fn get_features(&self) -> RadioFeatures {
+        let profile = self.profile;
+        let mut features = RadioFeatures::new();
+        features.memory_bounds = profile.memory_bounds;
+        features.has_name = true;
+        features.valid_name_length = profile.format.name_length;
+        features.has_dtcs = true;
+        features.has_dtcs_polarity = true;
+        features.has_bank = profile.tags_banks();
+
+        // Valid modes and bands depend on the bank
+        let banks: Vec<&CivBank> = match self.band.and_then(|band| profile.bank(band)) {
+            Some(bank) => vec![bank],
+            None => profile.banks.iter().collect(),
+        };
+        features.valid_modes = profile
+            .modes
+            .iter()
+            .filter_map(|m| *m)
+            .filter(|m| !banks.iter().all(|bank| bank.excluded_modes.contains(m)))
+            .collect();
+        features.valid_bands = banks
+            .iter()
+            .flat_map(|bank| bank.bands.iter().copied())
+            .collect();
+
+        features.valid_tmodes = vec![
+            ToneMode::None,
+            ToneMode::Tone,
+            ToneMode::Tsql,
+            ToneMode::Dtcs,
+            ToneMode::Cross,
+        ];
+        features.valid_cross_modes = CROSS_MODES.iter().map(|(_, mode)| *mode).collect();
+
+        features.valid_duplexes = vec![Duplex::Simplex, Duplex::Plus, Duplex::Minus];
+
+        features
+    }
+
+    fn get_memory(&mut self, _number: u32) -> RadioResult<Option<Memory>> {
+        // This requires a serial port connection
+        Err(RadioError::Unsupported(
+            "get_memory requires serial port (use get_memory_from_port)".to_string(),
+        ))
+    }
+
+    fn set_memory(&mut self, _memory: &Memory) -> RadioResult<()> {
+        // This requires a serial port connection
+        Err(RadioError::Unsupported(
+            "set_memory requires serial port (use set_memory_to_port)".to_string(),
+        ))
+    }
+}
+
+impl IcomCivRadio {
+    /// Detect if the CI-V interface echoes commands
+    /// This MUST be called before any other radio operations
+    pub async fn detect_echo<T: Transport + ?Sized>(&mut self, port: &mut T) -> RadioResult<bool> {
+        tracing::debug!("{}: Detecting CI-V echo...", self.profile.model);
+        let has_echo = self.protocol.detect_echo(port).await?;
+        tracing::debug!(
+            "{}: Interface echo detected: {}",
+            self.profile.model,
+            has_echo
+        );
+        Ok(has_echo)
+    }
+
+    /// Identify a CI-V radio on a serial port by asking each known model's
+    /// address for its transceiver ID. Returns the model, or None if nothing
+    /// answered.
+    pub fn probe(port_name: String) -> BoxFuture<'static, RadioResult<Option<String>>> {
+        Box::pin(async move {
+            let mut port = CONNECTION
+                .with_timeout(Duration::from_millis(500))
+                .open(&port_name)?;
+
+            for profile in PROFILES {
+                let mut protocol = CivProtocol::new(profile.model_code, CONTROLLER_ADDR);
+                protocol.detect_echo(&mut port).await?;
+                match protocol.read_transceiver_id(&mut port).await {
+                    Ok(id) if id == profile.model_code => {
+                        return Ok(Some(profile.model.to_string()))
+                    }
+                    Ok(id) => {
+                        tracing::debug!("probe: unexpected CI-V transceiver ID 0x{:02X}", id);
+                    }
+                    Err(_) => {}
+                }
+            }
+            Ok(None)
+        })
+    }
+
+    /// Get a memory from the radio via serial port
+    pub async fn get_memory_from_port<T: Transport + ?Sized>(
+        &mut self,
+        port: &mut T,
+        number: u32,
+    ) -> RadioResult<Option<Memory>> {
+        let bank = self.band.unwrap_or(self.profile.default_bank());
+
+        // Read memory via CI-V protocol
+        let data = self.protocol.read_memory(port, bank, number as u16).await?;
+
+        // Check if empty
+        if data.is_empty() {
+            return Ok(None);
+        }
+
+        // Parse memory
+        let raw = RawMemory::from_bytes(&data, &self.profile.format)?;
+        let mem = raw.to_memory(number, self.profile.modes)?;
+        Ok(Some(mem))
+    }
+
+    /// Set a memory in the radio via serial port
+    pub async fn set_memory_to_port<T: Transport + ?Sized>(
+        &mut self,
+        port: &mut T,
+        memory: &Memory,
+    ) -> RadioResult<()> {
+        let bank = self.band.unwrap_or(self.profile.default_bank());
+
+        tracing::debug!(
+            "set_memory_to_port: bank={}, ch={}, empty={}, freq={}, name='{}', mode='{}'",
+            bank,
+            memory.number,
+            memory.empty,
+            memory.freq,
+            memory.name,
+            memory.mode
+        );
+
+        if memory.empty {
+            // Erase memory
+            tracing::debug!("Erasing memory: bank={}, ch={}", bank, memory.number);
+            self.protocol
+                .erase_memory(port, bank, memory.number as u16)
+                .await?;
+        } else {
+            // Write memory
+            let data = RawMemory::from_memory(memory, self.profile)?;
+            tracing::debug!(
+                "Writing memory: bank={}, ch={}, data_len={}",
+                bank,
+                memory.number,
+                data.len()
+            );
+            self.protocol
+                .write_memory(port, bank, memory.number as u16, &data)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Download all memories from the radio
+    pub async fn download_memories<T: Transport + ?Sized>(
+        &mut self,
+        port: &mut T,
+        status_fn: Option<StatusCallback>,
+    ) -> RadioResult<Vec<Memory>> {
+        let features = self.get_features();
+        let (start, end) = features.memory_bounds;
+        let mut memories = Vec::new();
+
+        for i in start..=end {
+            if let Some(callback) = &status_fn {
+                callback(
+                    (i - start) as usize,
+                    (end - start + 1) as usize,
+                    &format!("Reading memory {}", i),
+                );
+            }
+
+            // Get memory from port, or create empty placeholder if slot is empty
+            let mem = if let Some(mem) = self.get_memory_from_port(port, i).await? {
+                mem
+            } else {
+                // Create empty memory for this slot so users can paste into it
+                Memory::new_empty(i)
+            };
+
+            memories.push(mem);
+        }
+
+        if let Some(callback) = &status_fn {
+            callback(
+                (end - start + 1) as usize,
+                (end - start + 1) as usize,
+                "Download complete",
+            );
+        }
+
+        Ok(memories)
+    }
+
+    /// Upload memories to the radio
+    /// Note: Empty memories will be erased on the radio via erase_memory command
+    pub async fn upload_memories<T: Transport + ?Sized>(
+        &mut self,
+        port: &mut T,
+        memories: &[Memory],
+        status_fn: Option<StatusCallback>,
+    ) -> RadioResult<()> {
+        tracing::debug!(
+            "Uploading {} memories to {}",
+            memories.len(),
+            self.profile.model
+        );
+
+        for (i, mem) in memories.iter().enumerate() {
+            if let Some(callback) = &status_fn {
+                let action = if mem.empty { "Erasing" } else { "Writing" };
+                callback(
+                    i,
+                    memories.len(),
+                    &format!("{} memory {}", action, mem.number),
+                );
+            }
+
+            self.set_memory_to_port(port, mem).await?;
+        }
+
+        if let Some(callback) = &status_fn {
+            callback(memories.len(), memories.len(), "Upload complete");
+        }
+
+        Ok(())
+    }
+
+    /// Pack memories from `download` into an .icf snapshot for offline editing
+    ///
+    /// Each memory is stored as its CI-V record (bank, BCD number, memory
+    /// data) in a 0x50-byte slot per bank and channel; empty slots are 0xFF.
+    /// The layout is CHIRP-RS's own, Icom's CS-xxxx software can't read it.
+    pub fn memories_to_icf(&self, memories: &[Memory]) -> RadioResult<IcfFile> {
+        let profile = self.profile;
+        let mut data = vec![0xFF; profile.banks.len() * profile.channels() * SNAPSHOT_RECORD_SIZE];
+
+        for mem in memories.iter().filter(|m| !m.empty) {
+            let band = mem.band.unwrap_or(profile.default_bank());
+            let offset = snapshot_offset(profile, band, mem.number)
+                .ok_or(RadioError::InvalidMemory(mem.number))?;
+
+            let mut record = vec![band];
+            record.extend(bcd::int_to_bcd_be(mem.number as u64, 2)?);
+            record.extend(RawMemory::from_memory(mem, profile)?);
+            data[offset..offset + record.len()].copy_from_slice(&record);
+        }
+
+        Ok(IcfFile::new(snapshot_model(profile), MemoryMap::new(data))
+            .with_property("Comment", format!("{} memory snapshot", profile.model)))
+    }
+
+    /// Read the memories back out of an `memories_to_icf` snapshot
+    pub fn memories_from_icf(&self, icf: &IcfFile) -> RadioResult<Vec<Memory>> {
+        let profile = self.profile;
+        if icf.model != snapshot_model(profile) {
+            return Err(RadioError::Radio(format!(
+                "Not an {} memory snapshot",
+                profile.model
+            )));
+        }
+
+        let expected = profile.banks.len() * profile.channels() * SNAPSHOT_RECORD_SIZE;
+        if icf.mmap.len() < expected {
+            return Err(RadioError::InvalidResponse(format!(
+                "{} memory snapshot is truncated: {} bytes, expected {}",
+                profile.model,
+                icf.mmap.len(),
+                expected
+            )));
+        }
+
+        let (first, last) = profile.memory_bounds;
+        let mut memories = Vec::new();
+        for bank in profile.banks {
+            for number in first..=last {
+                let offset = snapshot_offset(profile, bank.number, number)
+                    .ok_or(RadioError::InvalidMemory(number))?;
+                let record = icf
+                    .mmap
+                    .get(offset, Some(profile.format.size()))
+                    .map_err(|e| RadioError::InvalidResponse(e.to_string()))?;
+                let mut mem = if record[0] == 0xFF {
+                    Memory::new_empty(number)
+                } else {
+                    RawMemory::from_bytes(record, &profile.format)?
+                        .to_memory(number, profile.modes)?
+                };
+                if profile.tags_banks() {
+                    mem.band = Some(bank.number);
+                }
+                memories.push(mem);
+            }
+        }
+        Ok(memories)
+    }
+}
+
+/// Slot size for one memory in an .icf snapshot
+const SNAPSHOT_RECORD_SIZE: usize = 0x50;
+
+/// Model bytes marking an .icf snapshot (CI-V address in the third byte)
+fn snapshot_model(profile: &CivProfile) -> [u8; 4] {
+    [0x00, 0x00, profile.model_code, 0x00]
+}
+
+/// Offset of a bank's memory in an .icf snapshot
+fn snapshot_offset(profile: &CivProfile, band: u8, number: u32) -> Option<usize> {
+    let bank = profile.banks.iter().position(|bank| bank.number == band)?;
+    let channel = number
+        .checked_sub(profile.memory_bounds.0)
+        .map(|c| c as usize)
+        .filter(|&c| c < profile.channels())?;
+    Some((bank * profile.channels() + channel) * SNAPSHOT_RECORD_SIZE)
+}
+
+impl CommandModeRadio for IcomCivRadio {
+    /// Download every bank (or just this driver's bank) and tag memories with it
+    fn download<'a>(
+        &'a mut self,
+        port: &'a mut dyn Transport,
+        status_fn: Option<StatusCallback>,
+    ) -> BoxFuture<'a, RadioResult<Vec<Memory>>> {
+        Box::pin(async move {
+            let profile = self.profile;
+            let bands: Vec<u8> = match self.band {
+                Some(band) => vec![band],
+                None => profile.banks.iter().map(|bank| bank.number).collect(),
+            };
+            let status_fn: Option<SharedStatusCallback> = status_fn.map(Arc::from);
+
+            let mut all_memories = Vec::new();
+            for (band_idx, &band) in bands.iter().enumerate() {
+                tracing::info!("Downloading Band {} of {}", band_idx + 1, bands.len());
+
+                // CRITICAL: Detect if interface echoes commands before any operations
+                let mut driver = IcomCivRadio::new_band(profile, band);
+                driver.detect_echo(port).await?;
+
+                let mut band_memories = driver
+                    .download_memories(port, band_status(&status_fn, profile, band))
+                    .await?;
+                if profile.tags_banks() {
+                    for mem in &mut band_memories {
+                        mem.band = Some(band);
+                    }
+                }
+                all_memories.extend(band_memories);
+            }
+
+            tracing::info!(
+                "Downloaded total of {} memories from {} band(s)",
+                all_memories.len(),
+                bands.len()
+            );
+            Ok(all_memories)
+        })
+    }
+
+    /// Upload memories grouped by their bank (untagged memories go to the first bank)
+    fn upload<'a>(
+        &'a mut self,
+        port: &'a mut dyn Transport,
+        memories: &'a [Memory],
+        status_fn: Option<StatusCallback>,
+    ) -> BoxFuture<'a, RadioResult<()>> {
+        Box::pin(async move {
+            let profile = self.profile;
+            let mut bands: BTreeMap<u8, Vec<Memory>> = BTreeMap::new();
+            for mem in memories {
+                let band = mem.band.or(self.band).unwrap_or(profile.default_bank());
+                bands.entry(band).or_default().push(mem.clone());
+            }
+            let status_fn: Option<SharedStatusCallback> = status_fn.map(Arc::from);
+
+            for (band, band_mems) in bands {
+                tracing::info!("Uploading Band {} ({} memories)", band, band_mems.len());
+
+                // CRITICAL: Detect if interface echoes commands before any operations
+                let mut driver = IcomCivRadio::new_band(profile, band);
+                driver.detect_echo(port).await?;
+                driver
+                    .upload_memories(port, &band_mems, band_status(&status_fn, profile, band))
+                    .await?;
+            }
+
+            tracing::info!("Upload complete for all bands");
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ic9700_radio_creation() {
+        let radio = IcomCivRadio::new(&IC9700);
+        assert_eq!(radio.vendor(), "Icom");
+        assert_eq!(radio.model(), "IC-9700");
+    }
+
+    #[test]
+    fn test_ic9700_radio_band() {
+        let radio = IcomCivRadio::new_band(&IC9700, 1);
+        assert_eq!(radio.model(), "IC-9700 (VHF)");
+
+        let radio = IcomCivRadio::new_band(&IC9700, 2);
+        assert_eq!(radio.model(), "IC-9700 (UHF)");
+
+        let radio = IcomCivRadio::new_band(&IC9700, 3);
+        assert_eq!(radio.model(), "IC-9700 (1.2GHz)");
+    }
+
+    #[test]
+    fn test_band_name() {
+        assert_eq!(IC9700.bank_name(1), "VHF (144 MHz)");
+        assert_eq!(IC9700.bank_name(3), "1.2 GHz (1240 MHz)");
+        assert_eq!(IC9700.bank_name(9), "Unknown");
+    }
+
+    #[test]
+    fn test_ic9700_features() {
+        let radio = IcomCivRadio::new_band(&IC9700, 1);
+        let features = radio.get_features();
+
+        assert_eq!(features.memory_bounds, (1, 99));
+        assert!(features.has_name);
+        assert_eq!(features.valid_name_length, 16);
+        assert!(features.has_dtcs);
+        assert!(features.has_dtcs_polarity);
+        assert!(features.has_bank);
+        assert!(features.valid_modes.contains(&Mode::Dv));
+        assert!(features.valid_modes.contains(&Mode::Fm));
+    }
+
+    #[test]
+    fn test_profiles() {
+        assert_eq!(IC9700.format.size(), 67);
+        assert_eq!(ID52.format.size(), 65);
+
+        let ic705 = IcomCivRadio::new(&IC705);
+        assert_eq!(ic705.model(), "IC-705");
+        let features = ic705.get_features();
+        assert_eq!(features.memory_bounds, (1, 99));
+        assert!(!features.has_bank);
+        assert!(features.valid_modes.contains(&Mode::Wfm));
+        assert!(!features.valid_modes.contains(&Mode::Dd));
+        assert_eq!(features.valid_bands.len(), 2);
+
+        let id52 = IcomCivRadio::new(&ID52).get_features();
+        assert_eq!(id52.memory_bounds, (0, 1999));
+        assert_eq!(id52.valid_modes, vec![Mode::Am, Mode::Fm, Mode::Dv]);
+
+        // The handheld record has no filter/data-mode bytes
+        let mut mem = Memory::new(1500);
+        mem.freq = 145_500_000;
+        mem.mode = Mode::Fm;
+        mem.name = "SIMPLEX".to_string();
+        let mut record = vec![1, 0x15, 0x00];
+        record.extend(RawMemory::from_memory(&mem, &ID52).unwrap());
+        assert_eq!(record.len(), 65);
+        assert_eq!(record[9], 0x05);
+        let read = RawMemory::from_bytes(&record, &ID52.format)
+            .unwrap()
+            .to_memory(1500, ID52.modes)
+            .unwrap();
+        assert_eq!((read.freq, read.mode), (145_500_000, Mode::Fm));
+        assert_eq!(read.name, "SIMPLEX");
+
+        // USB isn't in the ID-52 mode table
+        mem.mode = Mode::Usb;
+        assert!(RawMemory::from_memory(&mem, &ID52).is_err());
+    }
+
+    #[test]
+    fn test_icf_snapshot() {
+        let radio = IcomCivRadio::new(&IC9700);
+
+        let mut fm = Memory::new(1);
+        fm.freq = 146_940_000;
+        fm.name = "W6CX".to_string();
+        fm.duplex = Duplex::Minus;
+        fm.offset = 600_000;
+        fm.tmode = ToneMode::Tone;
+        fm.rtone = 100.0;
+        fm.band = Some(1);
+
+        let mut dv = Memory::new(99);
+        dv.freq = 442_250_000;
+        dv.name = "DSTAR GW".to_string();
+        dv.mode = Mode::Dv;
+        dv.dv_urcall = "CQCQCQ".to_string();
+        dv.dv_rpt1call = "W3POG  B".to_string();
+        dv.band = Some(2);
+
+        let icf = radio
+            .memories_to_icf(&[fm, dv, Memory::new_empty(2)])
+            .unwrap();
+        let text = icf.to_string();
+        let memories = radio
+            .memories_from_icf(&IcfFile::parse(&text).unwrap())
+            .unwrap();
+        assert_eq!(memories.len(), 3 * 99);
+
+        let fm = &memories[0];
+        assert_eq!((fm.number, fm.band), (1, Some(1)));
+        assert_eq!(fm.freq, 146_940_000);
+        assert_eq!(fm.name, "W6CX");
+        assert_eq!(fm.duplex, Duplex::Minus);
+        assert_eq!(fm.offset, 600_000);
+        assert_eq!((fm.tmode, fm.rtone), (ToneMode::Tone, 100.0));
+        assert!(memories[1].empty);
+
+        let dv = &memories[99 + 98];
+        assert_eq!((dv.number, dv.band), (99, Some(2)));
+        assert_eq!(dv.mode, Mode::Dv);
+        assert_eq!(dv.dv_rpt1call, "W3POG  B");
+
+        let mut other = icf.clone();
+        other.model = vec![0x33, 0x22, 0x00, 0x01];
+        assert!(radio.memories_from_icf(&other).is_err());
+        assert!(IcomCivRadio::new(&IC705).memories_from_icf(&icf).is_err());
+
+        // A snapshot cut short is an error, not a shorter list
+        let lines: Vec<&str> = text.lines().collect();
+        let truncated = lines[..lines.len() - 1].join("\r\n");
+        assert!(matches!(
+            radio.memories_from_icf(&IcfFile::parse(&truncated).unwrap()),
+            Err(RadioError::InvalidResponse(_))
+        ));
+    }
+
+    #[test]
+    fn test_icf_snapshot_id52() {
+        // 2000 channels make a 0x27100-byte image, written with 8-digit addresses
+        let radio = IcomCivRadio::new(&ID52);
+        let channel = |number: u32, freq: u64, name: &str| {
+            let mut mem = Memory::new(number);
+            mem.freq = freq;
+            mem.name = name.to_string();
+            mem
+        };
+        let stored = [
+            channel(0, 145_500_000, "SIMPLEX"),
+            channel(108, 146_520_000, "CALL"),
+            channel(1999, 446_000_000, "UHF CALL"),
+        ];
+
+        let text = radio.memories_to_icf(&stored).unwrap().to_string();
+        assert!(text.contains("\r\n000270E020"));
+        let memories = radio
+            .memories_from_icf(&IcfFile::parse(&text).unwrap())
+            .unwrap();
+        assert_eq!(memories.len(), 2000);
+        assert_eq!(memories.iter().filter(|m| !m.empty).count(), 3);
+
+        for mem in &stored {
+            let read = &memories[mem.number as usize];
+            assert_eq!(read.number, mem.number);
+            assert_eq!((read.freq, read.band), (mem.freq, None));
+            assert_eq!(read.name, mem.name);
+        }
+    }
+
+    #[test]
+    fn test_ic9700_band3_no_dd() {
+        let radio = IcomCivRadio::new_band(&IC9700, 3);
+        let features = radio.get_features();
+
+        assert!(!features.valid_modes.contains(&Mode::Dd));
+    }
+}
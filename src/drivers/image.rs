@@ -0,0 +1,126 @@
+// Access to a clone-mode driver's loaded image
+//
+// Drivers that decode fixed-size channel records straight out of their memory
+// map (FT-60R, UV-K5, TM-V71) implement `ImageAccess` for reads and writes that
+// fail cleanly when no image is loaded or an offset runs past its end, and
+// share the lookups they encode table-indexed fields with.
+
+use super::traits::{RadioError, RadioResult};
+use crate::memmap::MemoryMap;
+
+/// Driver whose channels live at fixed offsets in its memory map
+pub trait ImageAccess {
+    /// Get the loaded image, if any
+    fn image(&self) -> Option<&MemoryMap>;
+
+    /// Get the loaded image mutably, if any
+    fn image_mut(&mut self) -> Option<&mut MemoryMap>;
+
+    /// Read `len` bytes at `offset`
+    fn read(&self, offset: usize, len: usize) -> RadioResult<&[u8]> {
+        self.image()
+            .ok_or_else(not_loaded)?
+            .get(offset, Some(len))
+            .map_err(|e| RadioError::Radio(e.to_string()))
+    }
+
+    /// Overwrite bytes at `offset`
+    fn write(&mut self, offset: usize, data: &[u8]) -> RadioResult<()> {
+        self.image_mut()
+            .ok_or_else(not_loaded)?
+            .set_bytes(offset, data)
+            .map_err(|e| RadioError::Radio(e.to_string()))
+    }
+
+    /// Bytes to encode a channel record over
+    ///
+    /// A channel in use keeps its current bytes, so fields the driver doesn't
+    /// decode survive an edit. An unused slot starts from zeros instead of
+    /// whatever the radio left there.
+    fn record_base(&self, offset: usize, len: usize, used: bool) -> RadioResult<Vec<u8>> {
+        if used {
+            Ok(self.read(offset, len)?.to_vec())
+        } else {
+            Ok(vec![0u8; len])
+        }
+    }
+}
+
+fn not_loaded() -> RadioError {
+    RadioError::Radio("Memory map not loaded".to_string())
+}
+
+/// Table index of a channel numbered from 1 to `count`
+pub fn channel_index(number: u32, count: u32) -> RadioResult<usize> {
+    if (1..=count).contains(&number) {
+        Ok(number as usize - 1)
+    } else {
+        Err(RadioError::InvalidMemory(number))
+    }
+}
+
+/// Index of a value in one of a radio's lookup tables
+pub fn index_of<T: PartialEq + std::fmt::Display>(
+    table: &[T],
+    value: T,
+    what: &str,
+) -> RadioResult<u8> {
+    table
+        .iter()
+        .position(|v| *v == value)
+        .map(|idx| idx as u8)
+        .ok_or_else(|| RadioError::Radio(format!("Unsupported {}: {}", what, value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Image(Option<MemoryMap>);
+
+    impl ImageAccess for Image {
+        fn image(&self) -> Option<&MemoryMap> {
+            self.0.as_ref()
+        }
+
+        fn image_mut(&mut self) -> Option<&mut MemoryMap> {
+            self.0.as_mut()
+        }
+    }
+
+    #[test]
+    fn test_image_access() {
+        let mut image = Image(None);
+        assert!(matches!(image.read(0, 1), Err(RadioError::Radio(_))));
+        assert!(image.write(0, &[1]).is_err());
+
+        image.0 = Some(MemoryMap::new(vec![0xAA; 16]));
+        image.write(4, &[1, 2]).unwrap();
+        assert_eq!(image.read(3, 4).unwrap(), &[0xAA, 1, 2, 0xAA]);
+        assert!(image.read(12, 8).is_err());
+        assert!(image.write(15, &[1, 2]).is_err());
+
+        assert_eq!(image.record_base(4, 2, true).unwrap(), vec![1, 2]);
+        assert_eq!(image.record_base(4, 2, false).unwrap(), vec![0, 0]);
+    }
+
+    #[test]
+    fn test_channel_index() {
+        assert_eq!(channel_index(1, 200).unwrap(), 0);
+        assert_eq!(channel_index(200, 200).unwrap(), 199);
+        assert!(matches!(
+            channel_index(0, 200),
+            Err(RadioError::InvalidMemory(0))
+        ));
+        assert!(channel_index(201, 200).is_err());
+    }
+
+    #[test]
+    fn test_index_of() {
+        assert_eq!(index_of(&[5.0, 12.5, 25.0], 25.0, "step").unwrap(), 2);
+        match index_of(&[5.0, 12.5], 6.25, "tuning step") {
+            Err(RadioError::Radio(msg)) => assert_eq!(msg, "Unsupported tuning step: 6.25"),
+            other => panic!("expected an error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,358 @@
+// Kenwood clone-mode transfer, shared by the TH-D74/D75 and TM-V71/TM-D710
+// Reference: chirp/drivers/thd74.py, chirp/drivers/tmv71_ll.py
+//
+// The radio answers text commands terminated by CR ("ID", "0M PROGRAM"),
+// then transfers the image in 256-byte blocks:
+//
+//   R <block:u16 BE> <size>          read request
+//   W <block:u16 BE> <size> <data>   block from the radio (after a read) or
+//                                    to the radio (upload), ACKed with 0x06
+//   E                                leave programming mode
+//
+// A size of 0 means a full block. The size field is one byte on the
+// TM-V71/TM-D710 and two bytes on the TH-D74/D75.
+
+use super::traits::{BoxFuture, RadioError, RadioResult};
+use crate::serial::{ConnectionProfile, Transport};
+use std::time::{Duration, Instant};
+
+/// Bytes per clone block
+pub const BLOCK_SIZE: usize = 256;
+
+/// Width of the size field in block headers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeField {
+    /// One byte (TM-V71/TM-D710)
+    Byte,
+    /// Two bytes, big-endian (TH-D74/D75)
+    Word,
+}
+
+impl SizeField {
+    /// Header for a block: command letter, block number, size (0 = full block)
+    fn header(self, cmd: u8, block: u16, len: usize) -> Vec<u8> {
+        let size = if len < BLOCK_SIZE { len } else { 0 };
+        let mut header = vec![cmd];
+        header.extend_from_slice(&block.to_be_bytes());
+        match self {
+            SizeField::Byte => header.push(size as u8),
+            SizeField::Word => header.extend_from_slice(&(size as u16).to_be_bytes()),
+        }
+        header
+    }
+}
+
+/// Send a command and get response
+pub async fn command<T: Transport + ?Sized>(port: &mut T, cmd: &str) -> RadioResult<String> {
+    // Clear any stale data before sending command
+    port.clear_input().ok();
+
+    let cmd_bytes = format!("{}\r", cmd);
+    tracing::debug!("command - sending: {:?}", cmd);
+    port.write_all(cmd_bytes.as_bytes())
+        .await
+        .map_err(|e| RadioError::Serial(e.to_string()))?;
+
+    // Flush to ensure command is sent
+    port.flush().await.ok();
+
+    // Read until \r - use a small buffer and read more efficiently
+    let mut response = Vec::new();
+    let mut buffer = [0u8; 64];
+    let start = Instant::now();
+
+    while start.elapsed() < Duration::from_secs(2) {
+        match port.read(&mut buffer).await {
+            Ok(n) => {
+                if n > 0 {
+                    for &byte in &buffer[..n] {
+                        response.push(byte);
+                        if byte == b'\r' {
+                            // Found terminator
+                            let result = String::from_utf8(response)
+                                .map(|s| s.trim().to_string())
+                                .map_err(|_| {
+                                    RadioError::InvalidResponse("Invalid UTF-8".to_string())
+                                })?;
+                            tracing::debug!("command - received: {:?}", result);
+                            return Ok(result);
+                        }
+                    }
+                } else {
+                    // No data yet, small delay before retry
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+            }
+            Err(_) => {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }
+    }
+
+    // Timeout - return what we got
+    let result = String::from_utf8(response)
+        .map(|s| s.trim().to_string())
+        .map_err(|_| RadioError::InvalidResponse("Invalid UTF-8".to_string()))?;
+
+    if result.is_empty() {
+        Err(RadioError::NoResponse)
+    } else {
+        tracing::debug!("command - received (incomplete): {:?}", result);
+        Ok(result)
+    }
+}
+
+/// Get radio ID
+pub async fn get_id<T: Transport + ?Sized>(port: &mut T) -> RadioResult<String> {
+    tracing::debug!("get_id - sending ID command");
+
+    // Try up to 3 times if we get garbage
+    for attempt in 1..=3 {
+        let response = command(port, "ID").await.map_err(|e| {
+            tracing::debug!("get_id - command failed on attempt {}: {}", attempt, e);
+            e
+        })?;
+        tracing::debug!(
+            "get_id - got response on attempt {}: {:?}",
+            attempt,
+            response
+        );
+
+        if response.starts_with("ID ") {
+            return Ok(response.split_whitespace().nth(1).unwrap_or("").to_string());
+        } else if response == "?" && attempt < 3 {
+            // Radio confused, clear and retry
+            tracing::debug!("get_id - got '?', clearing and retrying");
+            port.clear_all().ok();
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            continue;
+        }
+    }
+
+    tracing::debug!("get_id - all attempts failed");
+    Err(RadioError::NoResponse)
+}
+
+/// Wake the radio and read its ID
+pub async fn wake<T: Transport + ?Sized>(port: &mut T) -> RadioResult<String> {
+    tracing::debug!("wake - clearing input buffer");
+    port.clear_input()
+        .map_err(|e| RadioError::Serial(format!("Failed to clear buffer: {}", e)))?;
+
+    tracing::debug!("wake - sending wake-up CRs");
+    port.write_all(b"\r\r")
+        .await
+        .map_err(|e| RadioError::Serial(format!("Failed to send wake-up: {}", e)))?;
+
+    // Wait a bit for radio to wake up
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // Clear any pending data
+    let mut buf = [0u8; 32];
+    let _ = port.read(&mut buf).await;
+
+    tracing::debug!("wake - getting ID");
+    get_id(port).await
+}
+
+/// Put the radio into programming mode
+pub async fn enter_programming<T: Transport + ?Sized>(port: &mut T) -> RadioResult<()> {
+    tracing::debug!("Entering programming mode");
+    let response = command(port, "0M PROGRAM").await?;
+    tracing::debug!("Got response: {:?}", response);
+    if response != "0M" {
+        return Err(RadioError::NoResponse);
+    }
+    Ok(())
+}
+
+/// Leave programming mode (the radio restarts)
+pub async fn exit_programming<T: Transport + ?Sized>(port: &mut T) -> RadioResult<()> {
+    tracing::debug!("Sending exit command");
+    port.write_all(b"E")
+        .await
+        .map_err(|e| RadioError::Serial(e.to_string()))?;
+
+    // Ensure command is sent before the caller touches the port again
+    port.flush().await.ok();
+    Ok(())
+}
+
+/// Read a block from the radio
+pub async fn read_block<T: Transport + ?Sized>(
+    port: &mut T,
+    block: u16,
+    size_field: SizeField,
+) -> RadioResult<Vec<u8>> {
+    let cmd = size_field.header(b'R', block, BLOCK_SIZE);
+
+    if block == 0 {
+        tracing::debug!("read_block 0 - sending command: {:02X?}", cmd);
+    }
+
+    port.write_all(&cmd).await.map_err(|e| {
+        tracing::debug!("read_block {} - write failed: {}", block, e);
+        RadioError::Serial(e.to_string())
+    })?;
+
+    port.flush().await.ok();
+
+    // Read response header: "W" + block number + size
+    let mut header = vec![0u8; cmd.len()];
+    port.read_exact(&mut header).await.map_err(|e| {
+        tracing::debug!("read_block {} - read_exact header failed: {}", block, e);
+        RadioError::Serial(e.to_string())
+    })?;
+
+    if block == 0 {
+        tracing::debug!("read_block 0 - got header: {:02X?}", header);
+    }
+
+    if header[0] != b'W' {
+        return Err(RadioError::InvalidResponse(format!(
+            "Expected 'W', got '{}'",
+            header[0] as char
+        )));
+    }
+
+    let response_block = u16::from_be_bytes([header[1], header[2]]);
+    if response_block != block {
+        return Err(RadioError::InvalidResponse(format!(
+            "Block mismatch: expected {}, got {}",
+            block, response_block
+        )));
+    }
+
+    // Read block data
+    let mut data = vec![0u8; BLOCK_SIZE];
+    port.read_exact(&mut data)
+        .await
+        .map_err(|e| RadioError::Serial(e.to_string()))?;
+
+    // Send ACK
+    port.write_all(&[0x06])
+        .await
+        .map_err(|e| RadioError::Serial(e.to_string()))?;
+
+    // Wait for ACK response
+    let mut ack = [0u8; 1];
+    port.read_exact(&mut ack)
+        .await
+        .map_err(|e| RadioError::Serial(e.to_string()))?;
+
+    if ack[0] != 0x06 {
+        return Err(RadioError::Nak);
+    }
+
+    Ok(data)
+}
+
+/// Write a block to the radio
+pub async fn write_block<T: Transport + ?Sized>(
+    port: &mut T,
+    block: u16,
+    data: &[u8],
+    size_field: SizeField,
+) -> RadioResult<()> {
+    let mut cmd = size_field.header(b'W', block, data.len());
+    cmd.extend_from_slice(data);
+
+    port.write_all(&cmd)
+        .await
+        .map_err(|e| RadioError::Serial(e.to_string()))?;
+
+    port.flush()
+        .await
+        .map_err(|e| RadioError::Serial(e.to_string()))?;
+
+    // Wait for ACK
+    let mut ack = [0u8; 1];
+    port.read_exact(&mut ack)
+        .await
+        .map_err(|e| RadioError::Serial(e.to_string()))?;
+
+    if ack[0] != 0x06 {
+        return Err(RadioError::Nak);
+    }
+
+    Ok(())
+}
+
+/// Identify a Kenwood radio on a serial port using the `ID` command
+///
+/// `ID` is read-only and doesn't enter programming mode, so this is safe to
+/// send to any radio. `models` pairs each expected `ID` answer with the
+/// registered model name, which is returned when the radio matches.
+pub fn probe(
+    port_name: String,
+    connection: ConnectionProfile,
+    models: &'static [(&'static str, &'static str)],
+) -> BoxFuture<'static, RadioResult<Option<String>>> {
+    Box::pin(async move {
+        let mut port = connection
+            .with_timeout(Duration::from_millis(500))
+            .open(&port_name)?;
+
+        match get_id(&mut port).await {
+            Ok(id) => match models.iter().find(|(answer, _)| *answer == id) {
+                Some((_, model)) => Ok(Some(model.to_string())),
+                None => {
+                    tracing::debug!("probe: unexpected Kenwood ID {:?}", id);
+                    Ok(None)
+                }
+            },
+            Err(_) => Ok(None),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serial::mock::MockSerialPort;
+
+    #[tokio::test]
+    async fn test_block_transfer_over_mock() {
+        let payload: Vec<u8> = (0..BLOCK_SIZE).map(|i| i as u8).collect();
+
+        // TM-V71 style: one-byte size field
+        let mut port = MockSerialPort::new();
+        port.push_read_data(&[b'W', 0x00, 0x12, 0x00]);
+        port.push_read_data(&payload);
+        port.push_read_data(&[0x06]);
+        let data = read_block(&mut port, 0x12, SizeField::Byte).await.unwrap();
+        assert_eq!(data, payload);
+        assert_eq!(port.get_written_data(), vec![b'R', 0x00, 0x12, 0x00, 0x06]);
+
+        // TH-D75 style: two-byte size field
+        let mut port = MockSerialPort::new();
+        port.push_read_data(&[b'W', 0x01, 0x00, 0x00, 0x00]);
+        port.push_read_data(&payload);
+        port.push_read_data(&[0x06]);
+        read_block(&mut port, 0x100, SizeField::Word).await.unwrap();
+        assert!(port.was_written(&[b'R', 0x01, 0x00, 0x00, 0x00]));
+
+        // Wrong block number in the reply
+        let mut port = MockSerialPort::new();
+        port.push_read_data(&[b'W', 0x00, 0x13, 0x00]);
+        assert!(matches!(
+            read_block(&mut port, 0x12, SizeField::Byte).await,
+            Err(RadioError::InvalidResponse(_))
+        ));
+
+        // Writes: short blocks carry their length, full blocks 0
+        let mut port = MockSerialPort::new();
+        port.push_read_data(&[0x06, 0x15]);
+        write_block(&mut port, 2, &payload, SizeField::Byte)
+            .await
+            .unwrap();
+        assert!(port
+            .get_written_data()
+            .starts_with(&[b'W', 0x00, 0x02, 0x00, 0x00, 0x01]));
+        assert!(matches!(
+            write_block(&mut port, 3, &payload[..16], SizeField::Word).await,
+            Err(RadioError::Nak)
+        ));
+        assert!(port.was_written(&[b'W', 0x00, 0x03, 0x00, 0x10, 0x00]));
+    }
+}
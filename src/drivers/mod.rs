@@ -1,14 +1,29 @@
 // Radio driver framework
+pub mod bank;
+pub mod image;
+pub mod kenwood;
 pub mod registry;
 pub mod traits;
 
+#[cfg(test)]
+pub(crate) mod test_image;
+
 // Drivers
-pub mod ic9700;
+pub mod ft60;
+pub mod icomciv;
 pub mod thd75;
+pub mod tmv71;
 pub mod uv5r;
+pub mod uvk5;
 
-pub use registry::{get_driver, list_drivers, register_driver, DriverInfo};
-pub use traits::{CloneModeRadio, Radio, RadioError, RadioResult};
+pub use bank::{group_by_bank, Bank, BankModel, BankStyle};
+pub use registry::{
+    detect_image, find_driver, get_driver, list_drivers, match_image, probe_port, register_driver,
+    DetectError, DriverFactory, DriverInfo, ImageMatch, PortProbe, RadioDriver,
+};
+pub use traits::{
+    CloneModeRadio, CommandModeRadio, DynCloneModeRadio, Radio, RadioError, RadioResult,
+};
 
 /// Initialize and register all available radio drivers
 ///
@@ -16,36 +31,139 @@ pub use traits::{CloneModeRadio, Radio, RadioError, RadioResult};
 /// the driver registry with all available radio drivers.
 pub fn init_drivers() {
     // Register Kenwood TH-D75 (CloneModeRadio)
-    register_driver(DriverInfo::new(
-        "Kenwood",
-        "TH-D75",
-        "Dual-band HT with D-STAR support (VHF/UHF)",
-        true, // is_clone_mode
-    ));
+    register_driver(
+        DriverInfo::new(
+            "Kenwood",
+            "TH-D75",
+            "Dual-band HT with D-STAR support (VHF/UHF)",
+            true, // is_clone_mode
+        )
+        .with_factory(|| RadioDriver::CloneMode(Box::new(thd75::THD75Radio::new())))
+        .with_matcher(thd75::THD75Radio::match_model)
+        .with_probe(0, thd75::THD75Radio::probe),
+    );
 
     // Register Kenwood TH-D74 (same driver as TH-D75)
-    register_driver(DriverInfo::new(
-        "Kenwood",
-        "TH-D74",
-        "Dual-band HT with D-STAR support (VHF/UHF)",
-        true, // is_clone_mode
-    ));
+    // No matcher: TH-D74 images are indistinguishable from TH-D75 ones, so
+    // detection reports the TH-D75 driver, which handles both
+    register_driver(
+        DriverInfo::new(
+            "Kenwood",
+            "TH-D74",
+            "Dual-band HT with D-STAR support (VHF/UHF)",
+            true, // is_clone_mode
+        )
+        .with_factory(|| RadioDriver::CloneMode(Box::new(thd75::THD75Radio::new_d74())))
+        .with_probe(0, thd75::THD75Radio::probe),
+    );
+
+    // Register Kenwood TM-V71A (CloneModeRadio)
+    register_driver(
+        DriverInfo::new(
+            "Kenwood",
+            "TM-V71A",
+            "Dual-band mobile (VHF/UHF)",
+            true, // is_clone_mode
+        )
+        .with_factory(|| RadioDriver::CloneMode(Box::new(tmv71::TMV71Radio::new())))
+        .with_matcher(tmv71::TMV71Radio::match_model)
+        .with_probe(0, tmv71::TMV71Radio::probe),
+    );
+
+    // Register Kenwood TM-D710 (same memory layout as the TM-V71A)
+    // No matcher: images are indistinguishable from TM-V71A ones
+    register_driver(
+        DriverInfo::new(
+            "Kenwood",
+            "TM-D710",
+            "Dual-band mobile with APRS (VHF/UHF)",
+            true, // is_clone_mode
+        )
+        .with_factory(|| RadioDriver::CloneMode(Box::new(tmv71::TMV71Radio::new_d710())))
+        .with_probe(0, tmv71::TMV71Radio::probe),
+    );
 
     // Register Icom IC-9700 (CI-V command-based)
-    register_driver(DriverInfo::new(
-        "Icom",
-        "IC-9700",
-        "Tri-band transceiver with D-STAR (VHF/UHF/1.2GHz)",
-        false, // not clone mode - uses CI-V protocol
-    ));
+    register_driver(
+        DriverInfo::new(
+            "Icom",
+            "IC-9700",
+            "Tri-band transceiver with D-STAR (VHF/UHF/1.2GHz)",
+            false, // not clone mode - uses CI-V protocol
+        )
+        .with_factory(|| {
+            RadioDriver::CommandMode(Box::new(icomciv::IcomCivRadio::new(&icomciv::IC9700)))
+        })
+        .with_probe(20, icomciv::IcomCivRadio::probe),
+    );
+
+    // Register Icom IC-705 (CI-V command-based, same driver as the IC-9700)
+    register_driver(
+        DriverInfo::new(
+            "Icom",
+            "IC-705",
+            "Portable HF/VHF/UHF all-mode transceiver with D-STAR",
+            false, // not clone mode - uses CI-V protocol
+        )
+        .with_factory(|| {
+            RadioDriver::CommandMode(Box::new(icomciv::IcomCivRadio::new(&icomciv::IC705)))
+        })
+        .with_probe(20, icomciv::IcomCivRadio::probe),
+    );
+
+    // Register Icom ID-52 (CI-V command-based, same driver as the IC-9700)
+    register_driver(
+        DriverInfo::new(
+            "Icom",
+            "ID-52",
+            "Dual-band D-STAR handheld (VHF/UHF)",
+            false, // not clone mode - uses CI-V protocol
+        )
+        .with_factory(|| {
+            RadioDriver::CommandMode(Box::new(icomciv::IcomCivRadio::new(&icomciv::ID52)))
+        })
+        .with_probe(20, icomciv::IcomCivRadio::probe),
+    );
+
+    // Register Yaesu FT-60R (CloneModeRadio)
+    // No probe: the radio starts the clone, it doesn't answer commands
+    register_driver(
+        DriverInfo::new(
+            "Yaesu",
+            "FT-60R",
+            "Dual-band handheld (VHF/UHF)",
+            true, // is_clone_mode
+        )
+        .with_factory(|| RadioDriver::CloneMode(Box::new(ft60::FT60Radio::new())))
+        .with_matcher(ft60::FT60Radio::match_model),
+    );
+
+    // Register Quansheng UV-K5 (CloneModeRadio)
+    // No probe: a wrong-baud hello could leave another radio in a bad state
+    register_driver(
+        DriverInfo::new(
+            "Quansheng",
+            "UV-K5",
+            "Dual-band handheld (VHF/UHF)",
+            true, // is_clone_mode
+        )
+        .with_factory(|| RadioDriver::CloneMode(Box::new(uvk5::UVK5Radio::new())))
+        .with_matcher(uvk5::UVK5Radio::match_model),
+    );
 
     // Register Baofeng UV-5R (CloneModeRadio)
-    register_driver(DriverInfo::new(
-        "Baofeng",
-        "UV-5R",
-        "Dual-band handheld (VHF/UHF, FM only)",
-        true, // is_clone_mode
-    ));
+    // Probed last: the handshake leaves the radio in clone mode
+    register_driver(
+        DriverInfo::new(
+            "Baofeng",
+            "UV-5R",
+            "Dual-band handheld (VHF/UHF, FM only)",
+            true, // is_clone_mode
+        )
+        .with_factory(|| RadioDriver::CloneMode(Box::new(uv5r::UV5RRadio::new())))
+        .with_matcher(uv5r::UV5RRadio::match_model)
+        .with_probe(u8::MAX, uv5r::UV5RRadio::probe),
+    );
 }
 
 #[cfg(test)]
@@ -71,10 +189,37 @@ mod tests {
             get_driver("Kenwood", "TH-D74").is_some(),
             "TH-D74 not found"
         );
+        assert!(
+            get_driver("Kenwood", "TM-V71A").is_some(),
+            "TM-V71A not found"
+        );
         assert!(get_driver("Icom", "IC-9700").is_some(), "IC-9700 not found");
+        assert!(get_driver("Icom", "IC-705").is_some(), "IC-705 not found");
+        assert!(get_driver("Icom", "ID-52").is_some(), "ID-52 not found");
 
         // Verify specific drivers
         assert!(get_driver("Baofeng", "UV-5R").is_some(), "UV-5R not found");
+        assert!(get_driver("Yaesu", "FT-60R").is_some(), "FT-60R not found");
+        assert!(
+            get_driver("Quansheng", "UV-K5").is_some(),
+            "UV-K5 not found"
+        );
+
+        // Every registered driver can be instantiated (registry tests add
+        // factory-less "Test" entries to the same global registry)
+        for info in drivers.iter().filter(|info| info.vendor != "Test") {
+            let driver = info.create();
+            assert!(driver.is_some(), "{} has no factory", info.full_name());
+            assert_eq!(driver.unwrap().radio().model(), info.model);
+        }
+
+        // The UV-5R's clone-mode handshake runs after every read-only probe
+        let order = |info: &DriverInfo| info.probe.map(|p| p.order);
+        let uv5r = order(&get_driver("Baofeng", "UV-5R").unwrap());
+        assert!(drivers
+            .iter()
+            .filter(|info| info.vendor != "Test" && info.model != "UV-5R")
+            .all(|info| order(info) < uv5r));
 
         // Verify vendors
         let vendors: std::collections::HashSet<String> =
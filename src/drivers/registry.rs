@@ -1,7 +1,83 @@
 // Driver registry for managing radio drivers
 
+use super::traits::{
+    BoxFuture, CommandModeRadio, DynCloneModeRadio, Radio, RadioError, RadioResult,
+};
 use std::collections::HashMap;
 use std::sync::Mutex;
+use thiserror::Error;
+
+/// Constructor for a live driver instance
+pub type DriverFactory = fn() -> RadioDriver;
+
+/// Image matcher, normally a driver's `CloneModeRadio::match_model`
+pub type ImageMatcher = fn(&[u8], &str) -> bool;
+
+/// Port prober: opens the named port, runs the driver's identification
+/// handshake and returns the model that answered (None if nothing did)
+pub type PortProber = fn(String) -> BoxFuture<'static, RadioResult<Option<String>>>;
+
+/// How `probe_port` identifies a driver's radio on a serial port
+#[derive(Debug, Clone, Copy)]
+pub struct PortProbe {
+    /// Probes run in ascending order; read-only handshakes should go first
+    pub order: u8,
+    pub prober: PortProber,
+}
+
+/// A live driver instance created from the registry
+pub enum RadioDriver {
+    /// Radio programmed by cloning its whole memory image (e.g. TH-D75, UV-5R)
+    CloneMode(Box<dyn DynCloneModeRadio>),
+    /// Radio programmed channel by channel over a command protocol (e.g. IC-9700)
+    CommandMode(Box<dyn CommandModeRadio>),
+}
+
+impl RadioDriver {
+    /// Borrow the driver as a plain `Radio`
+    pub fn radio(&self) -> &dyn Radio {
+        match self {
+            RadioDriver::CloneMode(radio) => radio.as_ref(),
+            RadioDriver::CommandMode(radio) => radio.as_ref(),
+        }
+    }
+
+    /// Mutably borrow the driver as a plain `Radio`
+    pub fn radio_mut(&mut self) -> &mut dyn Radio {
+        match self {
+            RadioDriver::CloneMode(radio) => radio.as_mut(),
+            RadioDriver::CommandMode(radio) => radio.as_mut(),
+        }
+    }
+
+    /// Convert into a boxed `Radio`, dropping the mode-specific capabilities
+    pub fn into_radio(self) -> Box<dyn Radio> {
+        match self {
+            RadioDriver::CloneMode(radio) => radio,
+            RadioDriver::CommandMode(radio) => radio,
+        }
+    }
+
+    /// Get the clone-mode capabilities, if this is a clone-mode driver
+    pub fn into_clone_mode(self) -> Option<Box<dyn DynCloneModeRadio>> {
+        match self {
+            RadioDriver::CloneMode(radio) => Some(radio),
+            RadioDriver::CommandMode(_) => None,
+        }
+    }
+
+    /// Get the command-mode capabilities, if this is a command-mode driver
+    pub fn into_command_mode(self) -> Option<Box<dyn CommandModeRadio>> {
+        match self {
+            RadioDriver::CloneMode(_) => None,
+            RadioDriver::CommandMode(radio) => Some(radio),
+        }
+    }
+
+    pub fn is_clone_mode(&self) -> bool {
+        matches!(self, RadioDriver::CloneMode(_))
+    }
+}
 
 /// Information about a radio driver
 #[derive(Debug, Clone)]
@@ -10,6 +86,12 @@ pub struct DriverInfo {
     pub model: String,
     pub description: String,
     pub is_clone_mode: bool,
+    /// Constructor for live instances (None for metadata-only entries)
+    pub factory: Option<DriverFactory>,
+    /// Recognizes this radio's image files (None if images can't be told apart)
+    pub matcher: Option<ImageMatcher>,
+    /// Identifies this radio on a serial port (None if not probeable)
+    pub probe: Option<PortProbe>,
 }
 
 impl DriverInfo {
@@ -24,12 +106,38 @@ impl DriverInfo {
             model: model.into(),
             description: description.into(),
             is_clone_mode,
+            factory: None,
+            matcher: None,
+            probe: None,
         }
     }
 
+    /// Attach the constructor used by `create`
+    pub fn with_factory(mut self, factory: DriverFactory) -> Self {
+        self.factory = Some(factory);
+        self
+    }
+
+    /// Attach the image matcher used by `detect_image`
+    pub fn with_matcher(mut self, matcher: ImageMatcher) -> Self {
+        self.matcher = Some(matcher);
+        self
+    }
+
+    /// Attach the serial port probe used by `probe_port`
+    pub fn with_probe(mut self, order: u8, prober: PortProber) -> Self {
+        self.probe = Some(PortProbe { order, prober });
+        self
+    }
+
     pub fn full_name(&self) -> String {
         format!("{} {}", self.vendor, self.model)
     }
+
+    /// Create a new live instance of this driver
+    pub fn create(&self) -> Option<RadioDriver> {
+        self.factory.map(|factory| factory())
+    }
 }
 
 // Global driver registry
@@ -49,6 +157,24 @@ pub fn get_driver(vendor: &str, model: &str) -> Option<DriverInfo> {
     DRIVER_REGISTRY.lock().unwrap().get(&key).cloned()
 }
 
+/// Find a driver by loose name, e.g. "uv5r", "TH-D75" or "Kenwood TH-D74"
+/// Case, spaces, dashes and underscores are ignored.
+pub fn find_driver(name: &str) -> Option<DriverInfo> {
+    fn normalize(s: &str) -> String {
+        s.chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_lowercase())
+            .collect()
+    }
+
+    let wanted = normalize(name);
+    let mut drivers = list_drivers();
+    drivers.sort_by_key(|d| d.full_name());
+    drivers.into_iter().find(|d| {
+        normalize(&d.model) == wanted || normalize(&format!("{}{}", d.vendor, d.model)) == wanted
+    })
+}
+
 /// List all registered drivers
 pub fn list_drivers() -> Vec<DriverInfo> {
     DRIVER_REGISTRY.lock().unwrap().values().cloned().collect()
@@ -59,10 +185,7 @@ pub fn list_drivers_by_vendor() -> HashMap<String, Vec<DriverInfo>> {
     let mut by_vendor: HashMap<String, Vec<DriverInfo>> = HashMap::new();
 
     for info in list_drivers() {
-        by_vendor
-            .entry(info.vendor.clone())
-            .or_insert_with(Vec::new)
-            .push(info);
+        by_vendor.entry(info.vendor.clone()).or_default().push(info);
     }
 
     // Sort within each vendor
@@ -73,6 +196,121 @@ pub fn list_drivers_by_vendor() -> HashMap<String, Vec<DriverInfo>> {
     by_vendor
 }
 
+/// A driver whose matcher accepted an image
+#[derive(Debug, Clone)]
+pub struct ImageMatch {
+    pub driver: DriverInfo,
+    /// Image size equals the driver's memory map size
+    pub exact_size: bool,
+}
+
+#[derive(Error, Debug)]
+pub enum DetectError {
+    #[error("No registered driver recognizes this image")]
+    NoMatch,
+
+    #[error("Image matches several radios: {}", .0.iter().map(|d| d.full_name()).collect::<Vec<_>>().join(", "))]
+    Ambiguous(Vec<DriverInfo>),
+}
+
+/// Run every registered driver's matcher against an image, best match first
+///
+/// Matches whose size equals the driver's memory map size rank ahead of the rest.
+pub fn match_image(data: &[u8], filename: &str) -> Vec<ImageMatch> {
+    let mut matches: Vec<ImageMatch> = list_drivers()
+        .into_iter()
+        .filter(|info| info.matcher.is_some_and(|matcher| matcher(data, filename)))
+        .map(|info| {
+            let memsize = info
+                .create()
+                .and_then(|driver| driver.into_clone_mode())
+                .map(|radio| radio.memsize());
+            ImageMatch {
+                exact_size: memsize == Some(data.len()),
+                driver: info,
+            }
+        })
+        .collect();
+
+    matches.sort_by(|a, b| {
+        b.exact_size
+            .cmp(&a.exact_size)
+            .then_with(|| a.driver.full_name().cmp(&b.driver.full_name()))
+    });
+    matches
+}
+
+/// Detect which driver an image (e.g. a raw dump without metadata) belongs to
+///
+/// Returns `DetectError::Ambiguous` with the candidates in rank order when
+/// more than one driver matches equally well.
+pub fn detect_image(data: &[u8], filename: &str) -> Result<DriverInfo, DetectError> {
+    let matches = match_image(data, filename);
+    let best = matches.first().ok_or(DetectError::NoMatch)?;
+
+    let tied: Vec<DriverInfo> = matches
+        .iter()
+        .filter(|m| m.exact_size == best.exact_size)
+        .map(|m| m.driver.clone())
+        .collect();
+    if tied.len() > 1 {
+        return Err(DetectError::Ambiguous(tied));
+    }
+    Ok(best.driver.clone())
+}
+
+/// Identify the radio attached to a serial port
+///
+/// Runs each registered driver's probe one after another (in probe order),
+/// stopping at the first radio that answers. Drivers that share a probe, such
+/// as the TH-D74/TH-D75, are resolved by the model the radio reports.
+///
+/// Probes that change the radio's state run last. A UV-5R is identified by
+/// its clone handshake, which leaves it in clone mode until it's power-cycled.
+pub async fn probe_port(port_name: &str) -> RadioResult<DriverInfo> {
+    let mut probes: Vec<(PortProbe, DriverInfo)> = list_drivers()
+        .into_iter()
+        .filter_map(|info| info.probe.map(|probe| (probe, info)))
+        .collect();
+    probes.sort_by(|(a, a_info), (b, b_info)| {
+        a.order
+            .cmp(&b.order)
+            .then_with(|| a_info.full_name().cmp(&b_info.full_name()))
+    });
+
+    let mut tried: Vec<PortProber> = Vec::new();
+    for (probe, info) in probes {
+        // Shared probes only need to run once
+        if tried.iter().any(|&p| std::ptr::fn_addr_eq(p, probe.prober)) {
+            continue;
+        }
+        tried.push(probe.prober);
+
+        tracing::debug!("probe_port: trying {} on {}", info.full_name(), port_name);
+        if let Some(model) = (probe.prober)(port_name.to_string()).await? {
+            tracing::info!("probe_port: {} answered on {}", model, port_name);
+            return probed_driver(&info, &model);
+        }
+    }
+
+    Err(RadioError::NoResponse)
+}
+
+/// Driver registered for the model a probe reported
+///
+/// A probe must report a model registered under its driver's vendor; anything
+/// else is a driver bug, and falling back to the probing driver would pick
+/// the wrong radio for drivers that share a probe.
+fn probed_driver(info: &DriverInfo, model: &str) -> RadioResult<DriverInfo> {
+    get_driver(&info.vendor, model).ok_or_else(|| {
+        RadioError::Unsupported(format!(
+            "{} probe reported unregistered model {:?}",
+            info.full_name(),
+            model
+        ))
+    })
+}
+
 /// Helper macro to register a driver
 #[macro_export]
 macro_rules! register_radio_driver {
@@ -116,15 +354,112 @@ mod tests {
 
     #[test]
     fn test_list_by_vendor() {
-        register_driver(DriverInfo::new("Kenwood", "TH-D75", "Test", true));
-        register_driver(DriverInfo::new("Kenwood", "TH-D74", "Test", true));
-        register_driver(DriverInfo::new("Icom", "IC-9700", "Test", false));
+        // Test-only names: the registry is global and shared with other tests
+        register_driver(DriverInfo::new("Test", "Vendor-1", "Test", true));
+        register_driver(DriverInfo::new("Test", "Vendor-2", "Test", false));
 
         let by_vendor = list_drivers_by_vendor();
-        assert!(by_vendor.contains_key("Kenwood"));
-        assert!(by_vendor.contains_key("Icom"));
+        let models: Vec<&str> = by_vendor["Test"].iter().map(|d| d.model.as_str()).collect();
+        assert!(models.contains(&"Vendor-1"));
+        assert!(models.contains(&"Vendor-2"));
+    }
+
+    #[test]
+    fn test_factory() {
+        let info = DriverInfo::new("Test", "Radio-2", "Test radio", false);
+        assert!(info.create().is_none());
+
+        crate::drivers::init_drivers();
+        let driver = get_driver("Baofeng", "UV-5R")
+            .and_then(|info| info.create())
+            .expect("UV-5R factory");
+        assert!(driver.is_clone_mode());
+        assert_eq!(driver.radio().model(), "UV-5R");
+
+        let info = DriverInfo::new("Test", "Radio-3", "Test radio", false).with_factory(|| {
+            RadioDriver::CommandMode(Box::new(crate::drivers::icomciv::IcomCivRadio::new(
+                &crate::drivers::icomciv::IC9700,
+            )))
+        });
+        let driver = info.create().expect("IC-9700 factory");
+        assert!(!driver.is_clone_mode());
+        assert_eq!(driver.radio().vendor(), "Icom");
+        assert!(driver.into_command_mode().is_some());
+    }
+
+    fn match_all(_data: &[u8], filename: &str) -> bool {
+        filename.ends_with(".zz")
+    }
 
-        let kenwood = &by_vendor["Kenwood"];
-        assert!(kenwood.len() >= 2);
+    #[test]
+    fn test_detect_image() {
+        crate::drivers::init_drivers();
+
+        // Raw UV-5R image: ident header, no metadata
+        let mut data = vec![0xFFu8; 0x1948];
+        data[..8].copy_from_slice(&[0xAA, 0x30, 0x76, 0x04, 0x00, 0x05, 0x20, 0xDD]);
+        assert_eq!(detect_image(&data, "dump.img").unwrap().model, "UV-5R");
+
+        assert!(matches!(
+            detect_image(&[0u8; 16], "unknown.img"),
+            Err(DetectError::NoMatch)
+        ));
+    }
+
+    #[test]
+    fn test_detect_image_ambiguous() {
+        register_driver(DriverInfo::new("Test", "Match-A", "Test", true).with_matcher(match_all));
+        register_driver(DriverInfo::new("Test", "Match-B", "Test", true).with_matcher(match_all));
+
+        match detect_image(&[0u8; 16], "image.zz") {
+            Err(DetectError::Ambiguous(candidates)) => {
+                let names: Vec<_> = candidates.iter().map(|d| d.model.as_str()).collect();
+                assert_eq!(names, ["Match-A", "Match-B"]);
+            }
+            other => panic!("expected ambiguous match, got {:?}", other),
+        }
+    }
+
+    fn probe_none(_port_name: String) -> BoxFuture<'static, RadioResult<Option<String>>> {
+        Box::pin(async { Ok(None) })
+    }
+
+    #[test]
+    fn test_with_probe() {
+        let info = DriverInfo::new("Test", "Radio-4", "Test radio", true).with_probe(5, probe_none);
+        assert_eq!(info.probe.map(|p| p.order), Some(5));
+    }
+
+    #[test]
+    fn test_probed_driver() {
+        register_driver(
+            DriverInfo::new("Test", "Probed-1", "Test", true).with_probe(5, probe_none),
+        );
+        register_driver(
+            DriverInfo::new("Test", "Probed-2", "Test", true).with_probe(5, probe_none),
+        );
+        let info = get_driver("Test", "Probed-1").unwrap();
+
+        assert_eq!(probed_driver(&info, "Probed-2").unwrap().model, "Probed-2");
+        assert!(matches!(
+            probed_driver(&info, "Probed-3"),
+            Err(RadioError::Unsupported(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_probe_port_missing_port() {
+        crate::drivers::init_drivers();
+        let result = probe_port("/dev/chirp-rs-no-such-port").await;
+        assert!(matches!(result, Err(RadioError::Serial(_))));
+    }
+
+    #[test]
+    fn test_find_driver() {
+        crate::drivers::init_drivers();
+        assert_eq!(find_driver("uv5r").unwrap().model, "UV-5R");
+        assert_eq!(find_driver("th-d74").unwrap().model, "TH-D74");
+        assert_eq!(find_driver("Kenwood TH-D75").unwrap().model, "TH-D75");
+        assert!(find_driver("nonexistent").is_none());
     }
 }
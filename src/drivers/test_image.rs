@@ -0,0 +1,263 @@
+// Test fixtures for the drivers that decode records straight out of their image
+//
+// Driver tests lay images out by hand from the radio's memory format rather
+// than with the driver's own encoder, so decoding is checked against the
+// format and not just against the encoder. `TestImage` assembles those images;
+// `check_feature_roundtrip` writes a memory for every value a driver advertises
+// in its features and reads it back.
+
+use super::traits::{CloneModeRadio, Radio};
+use crate::core::{CrossMode, Duplex, Memory, ToneMode};
+use crate::memmap::MemoryMap;
+use std::ops::Range;
+
+/// A clone image assembled byte by byte
+pub struct TestImage {
+    data: Vec<u8>,
+}
+
+impl TestImage {
+    /// Image of `size` bytes, all set to `fill` (0xFF for erased EEPROM)
+    pub fn new(size: usize, fill: u8) -> Self {
+        Self {
+            data: vec![fill; size],
+        }
+    }
+
+    /// Write bytes at `offset`
+    pub fn set(&mut self, offset: usize, bytes: &[u8]) -> &mut Self {
+        self.data[offset..offset + bytes.len()].copy_from_slice(bytes);
+        self
+    }
+
+    /// Write the start of entry `index` in a table of `size`-byte entries at `base`
+    pub fn set_entry(&mut self, base: usize, size: usize, index: usize, bytes: &[u8]) -> &mut Self {
+        assert!(
+            bytes.len() <= size,
+            "entry is {} bytes, not {}",
+            size,
+            bytes.len()
+        );
+        self.set(base + index * size, bytes)
+    }
+
+    /// Set every byte in `range`
+    pub fn fill(&mut self, range: Range<usize>, byte: u8) -> &mut Self {
+        self.data[range].fill(byte);
+        self
+    }
+
+    /// Copy entry `from` of a table over entry `to`
+    pub fn copy_entry(&mut self, base: usize, size: usize, from: usize, to: usize) -> &mut Self {
+        let start = base + from * size;
+        self.data.copy_within(start..start + size, base + to * size);
+        self
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Driver with this image loaded
+    pub fn load<R: CloneModeRadio>(&self, mut radio: R) -> R {
+        radio
+            .process_mmap(&MemoryMap::new(self.data.clone()))
+            .expect("test image should load");
+        radio
+    }
+}
+
+/// Write one memory per advertised feature value and check each reads back
+///
+/// `base` must be a memory the radio accepts as is. Every case changes one
+/// field of it (or the tone fields a tone mode uses) and is written to the
+/// same location.
+pub fn check_feature_roundtrip<R: Radio>(radio: &mut R, base: &Memory) {
+    let features = radio.get_features();
+    let roundtrip = |radio: &mut R, what: &str, mem: &Memory| -> Memory {
+        radio
+            .set_memory(mem)
+            .unwrap_or_else(|e| panic!("{}: set_memory failed: {}", what, e));
+        radio
+            .get_memory(mem.number)
+            .unwrap_or_else(|e| panic!("{}: get_memory failed: {}", what, e))
+            .unwrap_or_else(|| panic!("{}: memory reads back empty", what))
+    };
+
+    for &mode in &features.valid_modes {
+        let mem = Memory {
+            mode,
+            ..base.clone()
+        };
+        let read = roundtrip(radio, &format!("mode {}", mode), &mem);
+        assert_eq!(read.mode, mode);
+    }
+
+    for &duplex in &features.valid_duplexes {
+        let offset = match duplex {
+            Duplex::Plus | Duplex::Minus => 600_000,
+            // Transmit on the low edge of another band, or 1 MHz up in a
+            // single-band radio
+            Duplex::Split => features
+                .valid_bands
+                .iter()
+                .find(|(low, high)| !(*low..*high).contains(&base.freq))
+                .map_or(base.freq + 1_000_000, |(low, _)| low + 1_000_000),
+            _ => 0,
+        };
+        let mem = Memory {
+            duplex,
+            offset,
+            ..base.clone()
+        };
+        let read = roundtrip(radio, &format!("duplex {:?}", duplex), &mem);
+        assert_eq!(read.duplex, duplex);
+        if offset != 0 {
+            assert_eq!(read.offset, offset, "duplex {:?}", duplex);
+        }
+    }
+
+    if features.has_tuning_step {
+        for &tuning_step in &features.valid_tuning_steps {
+            let mem = Memory {
+                tuning_step,
+                ..base.clone()
+            };
+            let read = roundtrip(radio, &format!("step {}", tuning_step), &mem);
+            assert_eq!(read.tuning_step, tuning_step);
+        }
+    }
+
+    for &skip in &features.valid_skips {
+        let mem = Memory {
+            skip,
+            ..base.clone()
+        };
+        let read = roundtrip(radio, &format!("skip {:?}", skip), &mem);
+        assert_eq!(read.skip, skip);
+    }
+
+    for power in &features.valid_power_levels {
+        let mem = Memory {
+            power: Some(power.clone()),
+            ..base.clone()
+        };
+        let read = roundtrip(radio, &format!("power {}", power.label()), &mem);
+        assert_eq!(read.power.as_ref().map(|p| p.label()), Some(power.label()));
+    }
+
+    if features.has_name && features.valid_name_length > 0 {
+        // The last characters of the set, which are the least likely to be
+        // plain letters
+        let name: String = features
+            .valid_characters
+            .chars()
+            .filter(|c| *c != ' ')
+            .rev()
+            .take(features.valid_name_length)
+            .collect();
+        let mem = Memory {
+            name: name.clone(),
+            ..base.clone()
+        };
+        let read = roundtrip(radio, &format!("name {:?}", name), &mem);
+        assert_eq!(read.name, name);
+    }
+
+    check_tone_roundtrip(radio, base, roundtrip);
+}
+
+/// Tone modes and cross modes, with distinct TX and RX values so a cross mode
+/// can't read back as TSQL or DTCS
+fn check_tone_roundtrip<R: Radio>(
+    radio: &mut R,
+    base: &Memory,
+    roundtrip: impl Fn(&mut R, &str, &Memory) -> Memory,
+) {
+    let features = radio.get_features();
+    let (rtone, ctone) = (features.valid_tones[0], features.valid_tones[1]);
+    let (dtcs, rx_dtcs) = (
+        features.valid_dtcs_codes[0],
+        features.valid_dtcs_codes[features.valid_dtcs_codes.len() - 1],
+    );
+
+    let mut cases: Vec<(ToneMode, CrossMode)> = features
+        .valid_tmodes
+        .iter()
+        .filter(|&&tmode| tmode != ToneMode::Cross)
+        .map(|&tmode| (tmode, CrossMode::default()))
+        .collect();
+    if features.valid_tmodes.contains(&ToneMode::Cross) {
+        cases.extend(
+            features
+                .valid_cross_modes
+                .iter()
+                .map(|&cross| (ToneMode::Cross, cross)),
+        );
+    }
+
+    for (tmode, cross_mode) in cases {
+        let mut mem = Memory {
+            tmode,
+            cross_mode,
+            rtone,
+            ctone,
+            dtcs,
+            rx_dtcs,
+            ..base.clone()
+        };
+        if tmode == ToneMode::Tsql {
+            mem.rtone = ctone;
+        }
+        let what = format!("tone mode {:?} {:?}", tmode, cross_mode);
+        let read = roundtrip(radio, &what, &mem);
+        assert_eq!(read.tmode, tmode, "{}", what);
+        if tmode == ToneMode::Cross {
+            assert_eq!(read.cross_mode, cross_mode, "{}", what);
+        }
+
+        let used = (read.rtone, read.ctone, read.dtcs, read.rx_dtcs);
+        match (tmode, cross_mode) {
+            (ToneMode::Tone, _) => assert_eq!(used.0, rtone, "{}", what),
+            (ToneMode::Tsql, _) => assert_eq!(used.1, ctone, "{}", what),
+            (ToneMode::Dtcs, _) => assert_eq!(used.2, dtcs, "{}", what),
+            (ToneMode::Cross, CrossMode::ToneToTone) => {
+                assert_eq!((used.0, used.1), (rtone, ctone), "{}", what)
+            }
+            (ToneMode::Cross, CrossMode::ToneToDtcs) => {
+                assert_eq!((used.0, used.3), (rtone, rx_dtcs), "{}", what)
+            }
+            (ToneMode::Cross, CrossMode::DtcsToTone) => {
+                assert_eq!((used.2, used.1), (dtcs, ctone), "{}", what)
+            }
+            (ToneMode::Cross, CrossMode::DtcsToDtcs) => {
+                assert_eq!((used.2, used.3), (dtcs, rx_dtcs), "{}", what)
+            }
+            (ToneMode::Cross, CrossMode::DtcsToNone) => assert_eq!(used.2, dtcs, "{}", what),
+            (ToneMode::Cross, CrossMode::NoneToTone) => assert_eq!(used.1, ctone, "{}", what),
+            (ToneMode::Cross, CrossMode::NoneToDtcs) => {
+                assert_eq!(used.3, rx_dtcs, "{}", what)
+            }
+            _ => {}
+        }
+    }
+
+    if features.has_dtcs_polarity {
+        for &dtcs_polarity in &features.valid_dtcs_pols {
+            let mem = Memory {
+                tmode: ToneMode::Dtcs,
+                dtcs,
+                rx_dtcs: dtcs,
+                dtcs_polarity,
+                ..base.clone()
+            };
+            let what = format!("DTCS polarity {:?}", dtcs_polarity);
+            let read = roundtrip(radio, &what, &mem);
+            assert_eq!(read.dtcs_polarity, dtcs_polarity, "{}", what);
+        }
+    }
+}
@@ -1,71 +1,58 @@
 // Kenwood TH-D75 / TH-D74 radio driver
 // Reference: chirp/drivers/thd74.py
 
-use super::traits::{CloneModeRadio, Radio, RadioError, RadioResult, Status, StatusCallback};
+use super::bank::{Bank, BankModel, BankStyle};
+use super::kenwood::{self, SizeField, BLOCK_SIZE};
+use super::traits::{
+    BoxFuture, CloneModeRadio, Radio, RadioError, RadioResult, Status, StatusCallback,
+};
 use crate::bitwise::{read_u32_le, write_u32_le};
-use crate::core::{Memory, RadioFeatures, DTCS_CODES, TONES};
+use crate::core::{
+    CrossMode, Duplex, Memory, Mode, RadioFeatures, RadioSetting, RadioSettingGroup, SettingValue,
+    Skip, ToneMode, DTCS_CODES, TONES,
+};
+use crate::formats::d75;
 use crate::memmap::MemoryMap;
-use crate::serial::SerialPort;
-use std::time::{Duration, Instant};
+use crate::serial::{ConnectionProfile, Transport};
+use std::time::Duration;
 
 /// TH-D74/D75 memory size: 500KB
 const MEMSIZE: usize = 0x7A300;
 
-/// Block size for download/upload
-const BLOCK_SIZE: usize = 256;
-
-/// File header for .d74 files
-const D74_FILE_HEADER: &[u8] = b"MCP-D74\xFFV1.03\xFF\xFF\xFFTH-D74\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\x00\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF";
-
 /// Memory offsets
 const FLAGS_OFFSET: usize = 0x2000;
 const MEMORY_OFFSET: usize = 0x4000;
 const NAMES_OFFSET: usize = 0x10000;
 const GROUP_NAME_OFFSET: usize = 0x14800; // Bank/group names (16 bytes each)
+const GROUP_NAME_SIZE: usize = 16;
 
-/// Number of memories
-const NUM_MEMORIES: u32 = 1200;
+/// Number of memory groups (banks)
+const NUM_GROUPS: usize = 30;
 
-/// Duplex mode enumeration
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Duplex {
-    Simplex = 0, // 0b00 - split/simplex mode
-    Plus = 1,    // 0b01 - positive offset
-    Minus = 2,   // 0b10 - negative offset
-}
+/// Settings offsets (located by comparing MCP-D75 images)
+const DSTAR_MYCALL_OFFSET: usize = 0x02B1; // 8 chars, space padded
+const DSTAR_MEMO_OFFSET: usize = 0x02B9; // 4 chars, NUL padded
+const POWERON_MSG_OFFSET: usize = 0x10C0; // 16 chars, NUL padded
+const APRS_CALL_OFFSET: usize = 0x1200; // 6 chars, NUL padded
+const APRS_SSID_OFFSET: usize = 0x1206; // "-N" or "-NN", NUL padded
 
-impl Duplex {
-    fn from_bits(bits: u8) -> Self {
-        match bits & 0x03 {
-            0 => Duplex::Simplex,
-            1 => Duplex::Plus,
-            2 => Duplex::Minus,
-            _ => Duplex::Simplex, // 0b11 shouldn't occur, treat as simplex
-        }
-    }
+/// Characters allowed in callsigns
+const CALLSIGN_CHARSET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789/ ";
 
-    fn to_bits(self) -> u8 {
-        self as u8
-    }
+/// Characters allowed in the power-on message (printable ASCII)
+const MESSAGE_CHARSET: &str =
+    " !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~";
 
-    fn as_str(self) -> &'static str {
-        match self {
-            Duplex::Simplex => "",
-            Duplex::Plus => "+",
-            Duplex::Minus => "-",
-        }
-    }
+/// Number of memories
+const NUM_MEMORIES: u32 = 1200;
 
-    fn all() -> &'static [&'static str] {
-        &["", "+", "-"]
-    }
-}
+/// Programming cable: RTS/CTS flow control, DTR high, RTS low
+const CONNECTION: ConnectionProfile = ConnectionProfile::new(9600)
+    .with_hardware_flow()
+    .with_lines(true, false);
 
-impl std::fmt::Display for Duplex {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.as_str())
-    }
-}
+/// Duplex values in the order of the 2-bit field (0b11 is unused)
+const THD75_DUPLEXES: &[Duplex] = &[Duplex::Simplex, Duplex::Plus, Duplex::Minus];
 
 /// Tuning steps (kHz)
 const TUNE_STEPS: &[f32] = &[
@@ -74,10 +61,24 @@ const TUNE_STEPS: &[f32] = &[
 
 /// Cross modes
 #[allow(dead_code)]
-const CROSS_MODES: &[&str] = &["DTCS->", "Tone->DTCS", "DTCS->Tone", "Tone->Tone"];
+const CROSS_MODES: &[CrossMode] = &[
+    CrossMode::DtcsToNone,
+    CrossMode::ToneToDtcs,
+    CrossMode::DtcsToTone,
+    CrossMode::ToneToTone,
+];
 
 /// TH-D75 modes
-const THD75_MODES: &[&str] = &["FM", "DV", "AM", "LSB", "USB", "CW", "NFM", "DV"];
+const THD75_MODES: &[Mode] = &[
+    Mode::Fm,
+    Mode::Dv,
+    Mode::Am,
+    Mode::Lsb,
+    Mode::Usb,
+    Mode::Cw,
+    Mode::Nfm,
+    Mode::Dv,
+];
 
 /// Memory flags structure (4 bytes per memory at 0x2000)
 #[derive(Debug, Clone, Copy)]
@@ -96,7 +97,7 @@ impl MemoryFlags {
         }
     }
 
-    fn to_bytes(&self) -> [u8; 4] {
+    fn to_bytes(self) -> [u8; 4] {
         [
             self.band,
             if self.lockout { 0x80 } else { 0x00 },
@@ -192,7 +193,11 @@ impl RawMemory {
         // Bit 3: cross_mode
         // Bit 5: split
         // Bits 6-7: tone_mode/ctcss_mode flags
-        let duplex = Duplex::from_bits(data[10]); // Bits 0-1
+        // Bits 0-1 (0b11 shouldn't occur, treat as simplex)
+        let duplex = THD75_DUPLEXES
+            .get((data[10] & 0x03) as usize)
+            .copied()
+            .unwrap_or(Duplex::Simplex);
         let dtcs_mode = (data[10] >> 2) & 0x01;
         let cross_mode = (data[10] >> 3) & 0x01;
         let split = (data[10] & 0x20) != 0;
@@ -273,7 +278,11 @@ impl RawMemory {
         };
 
         // Byte 10: duplex in bits 0-1, other flags in higher bits
-        bytes[10] = self.duplex.to_bits() // Bits 0-1: duplex
+        let duplex_bits = THD75_DUPLEXES
+            .iter()
+            .position(|&d| d == self.duplex)
+            .unwrap_or(0) as u8;
+        bytes[10] = duplex_bits // Bits 0-1: duplex
             | ((self.dtcs_mode & 0x01) << 2)
             | ((self.cross_mode & 0x01) << 3)
             | (if self.split { 0x20 } else { 0x00 })
@@ -313,19 +322,27 @@ impl THD75Radio {
         }
     }
 
+    /// Create a driver for the TH-D74 (same memory layout as the TH-D75)
+    pub fn new_d74() -> Self {
+        Self {
+            model: "TH-D74".to_string(),
+            ..Self::new()
+        }
+    }
+
     /// Read bank/group names from memory map
     /// Returns vector of 30 bank names (16 bytes each, null-terminated)
-    pub fn get_bank_names(&self) -> RadioResult<Vec<String>> {
+    fn read_bank_names(&self) -> RadioResult<Vec<String>> {
         let mmap = self
             .mmap
             .as_ref()
             .ok_or_else(|| RadioError::Radio("Memory map not loaded".to_string()))?;
 
-        let mut names = Vec::with_capacity(30);
-        for i in 0..30 {
-            let offset = GROUP_NAME_OFFSET + (i * 16);
+        let mut names = Vec::with_capacity(NUM_GROUPS);
+        for i in 0..NUM_GROUPS {
+            let offset = GROUP_NAME_OFFSET + (i * GROUP_NAME_SIZE);
             let bytes = mmap
-                .get(offset, Some(16))
+                .get(offset, Some(GROUP_NAME_SIZE))
                 .map_err(|e| RadioError::Radio(format!("Failed to read bank name {}: {}", i, e)))?;
 
             // Convert to string, stopping at first null byte
@@ -345,35 +362,82 @@ impl THD75Radio {
         Ok(names)
     }
 
-    /// Get all non-empty memories from the radio
-    pub fn get_memories(&mut self) -> RadioResult<Vec<Memory>> {
-        tracing::info!(
-            "Decoding {} memory channels from downloaded data",
-            NUM_MEMORIES
-        );
-        let mut memories = Vec::new();
+    /// Read the flags of a memory
+    fn read_flags(&self, number: u32) -> RadioResult<MemoryFlags> {
+        if number >= NUM_MEMORIES {
+            return Err(RadioError::InvalidMemory(number));
+        }
+        let mmap = self
+            .mmap
+            .as_ref()
+            .ok_or_else(|| RadioError::Radio("Memory map not loaded".to_string()))?;
+        let data = mmap
+            .get(self.flags_offset(number), Some(4))
+            .map_err(|e| RadioError::Radio(e.to_string()))?;
+        Ok(MemoryFlags::from_bytes(data))
+    }
 
-        for channel in 0..NUM_MEMORIES {
-            let mem = match self.get_memory(channel)? {
-                Some(mem) => mem,
-                None => {
-                    // Create an empty memory entry for unused slots
-                    let mut empty_mem = Memory::new(channel);
-                    empty_mem.empty = true;
-                    empty_mem
-                }
-            };
-            memories.push(mem);
+    /// Move a memory to another group, leaving the rest of its flags alone
+    fn write_group(&mut self, number: u32, group: usize) -> RadioResult<()> {
+        if group >= NUM_GROUPS {
+            return Err(RadioError::Radio(format!("Invalid group: {}", group)));
+        }
+        if self.read_flags(number)?.band == 0xFF {
+            return Err(RadioError::Radio(format!("Memory #{} is empty", number)));
         }
 
-        let non_empty_count = memories.iter().filter(|m| !m.empty).count();
-        tracing::info!(
-            "Decoded {} memory channels ({} non-empty, {} empty)",
-            memories.len(),
-            non_empty_count,
-            memories.len() - non_empty_count
-        );
-        Ok(memories)
+        let offset = self.flags_offset(number) + 2;
+        let mmap = self
+            .mmap
+            .as_mut()
+            .ok_or_else(|| RadioError::Radio("Memory map not loaded".to_string()))?;
+        mmap.set_bytes(offset, &[group as u8])
+            .map_err(|e| RadioError::Radio(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Read a text field, stopping at the first NUL and dropping trailing spaces
+    fn read_text(&self, offset: usize, len: usize) -> RadioResult<String> {
+        let mmap = self
+            .mmap
+            .as_ref()
+            .ok_or_else(|| RadioError::Radio("Memory map not loaded".to_string()))?;
+        let bytes = mmap.get(offset, Some(len)).map_err(|e| {
+            RadioError::Radio(format!("Failed to read text at 0x{:X}: {}", offset, e))
+        })?;
+
+        let text: String = bytes
+            .iter()
+            .take_while(|&&b| b != 0 && b != 0xFF)
+            .map(|&b| b as char)
+            .collect();
+        Ok(text.trim_end().to_string())
+    }
+
+    /// Encode a text field, padding it to `len` bytes with `pad`
+    fn encode_text(len: usize, text: &str, pad: u8) -> RadioResult<Vec<u8>> {
+        if text.len() > len || !text.is_ascii() {
+            return Err(RadioError::InvalidSetting(format!(
+                "{:?} does not fit in {} ASCII characters",
+                text, len
+            )));
+        }
+        let mut bytes = vec![pad; len];
+        bytes[..text.len()].copy_from_slice(text.as_bytes());
+        Ok(bytes)
+    }
+
+    /// Write a text field, padding it to `len` bytes with `pad`
+    fn write_text(&mut self, offset: usize, len: usize, text: &str, pad: u8) -> RadioResult<()> {
+        let bytes = Self::encode_text(len, text, pad)?;
+        let mmap = self
+            .mmap
+            .as_mut()
+            .ok_or_else(|| RadioError::Radio("Memory map not loaded".to_string()))?;
+        mmap.set_bytes(offset, &bytes).map_err(|e| {
+            RadioError::Radio(format!("Failed to write text at 0x{:X}: {}", offset, e))
+        })?;
+        Ok(())
     }
 
     /// Encode all memories to a MemoryMap (reverse of get_memories)
@@ -382,9 +446,7 @@ impl THD75Radio {
         let mut data = vec![0xFFu8; MEMSIZE];
 
         // Initialize padding regions with 0x00
-        for i in 0..0x2000 {
-            data[i] = 0x00;
-        }
+        data[..0x2000].fill(0x00);
 
         // Process each memory (skip empty ones - they stay as 0xFF)
         for mem in memories {
@@ -449,225 +511,13 @@ impl THD75Radio {
         NAMES_OFFSET + (number as usize * 16)
     }
 
-    /// Read a block from the radio
-    async fn read_block(&self, port: &mut SerialPort, block: u16) -> RadioResult<Vec<u8>> {
-        // Send read command: "R" + block number (big-endian u16) + 0x0000
-        let mut cmd = vec![b'R'];
-        cmd.extend_from_slice(&block.to_be_bytes());
-        cmd.extend_from_slice(&[0x00, 0x00]);
-
-        if block == 0 {
-            tracing::debug!("read_block 0 - sending command: {:02X?}", cmd);
-        }
-
-        port.write_all(&cmd).await.map_err(|e| {
-            tracing::debug!("read_block {} - write failed: {}", block, e);
-            RadioError::Serial(e.to_string())
-        })?;
-
-        port.flush().await.ok();
-
-        // Read response header: "W" + block number + 0x0000
-        if block == 0 {
-            tracing::debug!("read_block 0 - waiting for header (5 bytes)");
-        }
-        let mut header = [0u8; 5];
-        port.read_exact(&mut header).await.map_err(|e| {
-            tracing::debug!("read_block {} - read_exact header failed: {}", block, e);
-            RadioError::Serial(e.to_string())
-        })?;
-
-        if block == 0 {
-            tracing::debug!("read_block 0 - got header: {:02X?}", header);
-        }
-
-        if header[0] != b'W' {
-            return Err(RadioError::InvalidResponse(format!(
-                "Expected 'W', got '{}'",
-                header[0] as char
-            )));
-        }
-
-        let response_block = u16::from_be_bytes([header[1], header[2]]);
-        if response_block != block {
-            return Err(RadioError::InvalidResponse(format!(
-                "Block mismatch: expected {}, got {}",
-                block, response_block
-            )));
-        }
-
-        // Read block data
-        let mut data = vec![0u8; BLOCK_SIZE];
-        port.read_exact(&mut data)
-            .await
-            .map_err(|e| RadioError::Serial(e.to_string()))?;
-
-        // Send ACK
-        port.write_all(&[0x06])
-            .await
-            .map_err(|e| RadioError::Serial(e.to_string()))?;
-
-        // Wait for ACK response
-        let mut ack = [0u8; 1];
-        port.read_exact(&mut ack)
-            .await
-            .map_err(|e| RadioError::Serial(e.to_string()))?;
-
-        if ack[0] != 0x06 {
-            return Err(RadioError::Nak);
-        }
-
-        Ok(data)
-    }
-
-    /// Write a block to the radio
-    async fn write_block(&self, port: &mut SerialPort, block: u16, data: &[u8]) -> RadioResult<()> {
-        // Send write command: "W" + block number + size + data
-        let mut cmd = vec![b'W'];
-        cmd.extend_from_slice(&block.to_be_bytes());
-
-        let size = if data.len() < BLOCK_SIZE {
-            data.len() as u16
-        } else {
-            0
-        };
-        cmd.extend_from_slice(&size.to_be_bytes());
-        cmd.extend_from_slice(data);
-
-        port.write_all(&cmd)
-            .await
-            .map_err(|e| RadioError::Serial(e.to_string()))?;
-
-        port.flush()
-            .await
-            .map_err(|e| RadioError::Serial(e.to_string()))?;
-
-        // Wait for ACK
-        let mut ack = [0u8; 1];
-        port.read_exact(&mut ack)
-            .await
-            .map_err(|e| RadioError::Serial(e.to_string()))?;
-
-        if ack[0] != 0x06 {
-            return Err(RadioError::Nak);
-        }
-
-        Ok(())
-    }
-
-    /// Send a command and get response
-    async fn command(&self, port: &mut SerialPort, cmd: &str) -> RadioResult<String> {
-        // Clear any stale data before sending command
-        port.clear_input().ok();
-
-        let cmd_bytes = format!("{}\r", cmd);
-        tracing::debug!("command - sending: {:?}", cmd);
-        port.write_all(cmd_bytes.as_bytes())
-            .await
-            .map_err(|e| RadioError::Serial(e.to_string()))?;
-
-        // Flush to ensure command is sent
-        port.flush().await.ok();
-
-        // Read until \r - use a small buffer and read more efficiently
-        let mut response = Vec::new();
-        let mut buffer = [0u8; 64];
-        let start = Instant::now();
-
-        while start.elapsed() < Duration::from_secs(2) {
-            match port.read(&mut buffer).await {
-                Ok(n) => {
-                    if n > 0 {
-                        for i in 0..n {
-                            response.push(buffer[i]);
-                            if buffer[i] == b'\r' {
-                                // Found terminator
-                                let result = String::from_utf8(response)
-                                    .map(|s| s.trim().to_string())
-                                    .map_err(|_| {
-                                        RadioError::InvalidResponse("Invalid UTF-8".to_string())
-                                    })?;
-                                tracing::debug!("command - received: {:?}", result);
-                                return Ok(result);
-                            }
-                        }
-                    } else {
-                        // No data yet, small delay before retry
-                        tokio::time::sleep(Duration::from_millis(10)).await;
-                    }
-                }
-                Err(_) => {
-                    tokio::time::sleep(Duration::from_millis(10)).await;
-                }
-            }
-        }
-
-        // Timeout - return what we got
-        let result = String::from_utf8(response)
-            .map(|s| s.trim().to_string())
-            .map_err(|_| RadioError::InvalidResponse("Invalid UTF-8".to_string()))?;
-
-        if result.is_empty() {
-            Err(RadioError::NoResponse)
-        } else {
-            tracing::debug!("command - received (incomplete): {:?}", result);
-            Ok(result)
-        }
-    }
-
-    /// Get radio ID
-    async fn get_id(&self, port: &mut SerialPort) -> RadioResult<String> {
-        tracing::debug!("get_id - sending ID command");
-
-        // Try up to 3 times if we get garbage
-        for attempt in 1..=3 {
-            let response = self.command(port, "ID").await.map_err(|e| {
-                tracing::debug!("get_id - command failed on attempt {}: {}", attempt, e);
-                e
-            })?;
-            tracing::debug!(
-                "get_id - got response on attempt {}: {:?}",
-                attempt,
-                response
-            );
-
-            if response.starts_with("ID ") {
-                return Ok(response.split_whitespace().nth(1).unwrap_or("").to_string());
-            } else if response == "?" && attempt < 3 {
-                // Radio confused, clear and retry
-                tracing::debug!("get_id - got '?', clearing and retrying");
-                port.clear_all().ok();
-                tokio::time::sleep(Duration::from_millis(200)).await;
-                continue;
-            }
-        }
-
-        tracing::debug!("get_id - all attempts failed");
-        Err(RadioError::NoResponse)
-    }
-
-    /// Detect baud rate
-    async fn detect_baud(&self, port: &mut SerialPort) -> RadioResult<String> {
-        // Note: serialport doesn't support runtime baud rate changes easily
-        // For now, we'll assume 9600 is set correctly at port opening
-        tracing::debug!("detect_baud - clearing input buffer");
-        port.clear_input()
-            .map_err(|e| RadioError::Serial(format!("Failed to clear buffer: {}", e)))?;
-
-        tracing::debug!("detect_baud - sending wake-up CRs");
-        port.write_all(b"\r\r")
-            .await
-            .map_err(|e| RadioError::Serial(format!("Failed to send wake-up: {}", e)))?;
-
-        // Wait a bit for radio to wake up
-        tokio::time::sleep(Duration::from_millis(100)).await;
-
-        // Clear any pending data
-        let mut buf = [0u8; 32];
-        let _ = port.read(&mut buf).await;
-
-        tracing::debug!("detect_baud - getting ID");
-        self.get_id(port).await
+    /// Identify a TH-D74/TH-D75 on a serial port using the `ID` command
+    pub fn probe(port_name: String) -> BoxFuture<'static, RadioResult<Option<String>>> {
+        kenwood::probe(
+            port_name,
+            CONNECTION,
+            &[("TH-D75", "TH-D75"), ("TH-D74", "TH-D74")],
+        )
     }
 
     /// Find tone index from frequency value
@@ -697,22 +547,21 @@ impl THD75Radio {
             .ok_or_else(|| RadioError::Radio(format!("Invalid tuning step: {}", step)))
     }
 
-    /// Find mode index from mode string
-    fn find_mode_index(mode: &str) -> RadioResult<u8> {
+    /// Find the radio's mode index
+    fn find_mode_index(mode: Mode) -> RadioResult<u8> {
         THD75_MODES
             .iter()
             .position(|&m| m == mode)
             .map(|idx| idx as u8)
-            .ok_or_else(|| RadioError::Radio(format!("Invalid mode: {}", mode)))
+            .ok_or_else(|| RadioError::Radio(format!("Unsupported mode: {}", mode)))
     }
 
-    /// Convert duplex string to Duplex enum
-    fn parse_duplex(duplex: &str) -> RadioResult<Duplex> {
-        match duplex {
-            "" => Ok(Duplex::Simplex),
-            "+" => Ok(Duplex::Plus),
-            "-" => Ok(Duplex::Minus),
-            _ => Err(RadioError::Radio(format!("Invalid duplex: {}", duplex))),
+    /// Check that the radio can store a duplex (no split or TX inhibit)
+    fn check_duplex(duplex: Duplex) -> RadioResult<Duplex> {
+        if THD75_DUPLEXES.contains(&duplex) {
+            Ok(duplex)
+        } else {
+            Err(RadioError::Radio(format!("Unsupported duplex: {}", duplex)))
         }
     }
 
@@ -731,11 +580,11 @@ impl THD75Radio {
         );
         let tuning_step = Self::find_tuning_step_index(mem.tuning_step)?;
         tracing::debug!("  -> tuning_step index={}", tuning_step);
-        let mode = Self::find_mode_index(&mem.mode)?;
-        let duplex = Self::parse_duplex(&mem.duplex)?;
+        let mode = Self::find_mode_index(mem.mode)?;
+        let duplex = Self::check_duplex(mem.duplex)?;
 
         // Determine narrow flag based on mode
-        let narrow = mem.mode == "NFM";
+        let narrow = mem.mode == Mode::Nfm;
 
         // Handle tones/DTCS
         let rtone = if mem.rtone > 0.0 {
@@ -757,11 +606,11 @@ impl THD75Radio {
         };
 
         // Parse tone mode flags
-        let (tone_mode, ctcss_mode, dtcs_mode, cross_mode) = match mem.tmode.as_str() {
-            "Tone" => (1, 0, 0, 0),
-            "TSQL" => (0, 1, 0, 0),
-            "DTCS" => (0, 0, 1, 0),
-            "Cross" => (0, 0, 0, 1),
+        let (tone_mode, ctcss_mode, dtcs_mode, cross_mode) = match mem.tmode {
+            ToneMode::Tone => (1, 0, 0, 0),
+            ToneMode::Tsql => (0, 1, 0, 0),
+            ToneMode::Dtcs => (0, 0, 1, 0),
+            ToneMode::Cross => (0, 0, 0, 1),
             _ => (0, 0, 0, 0),
         };
 
@@ -814,7 +663,7 @@ impl THD75Radio {
             } else {
                 MemoryFlags::detect_band(mem.freq) // Detect band from frequency
             },
-            lockout: mem.skip == "S",
+            lockout: mem.skip == Skip::Skip,
             group: mem.bank, // Use bank from memory
         };
 
@@ -852,11 +701,11 @@ impl THD75Radio {
 
         // Mode
         if (raw.mode as usize) < THD75_MODES.len() {
-            mem.mode = THD75_MODES[raw.mode as usize].to_string();
+            mem.mode = THD75_MODES[raw.mode as usize];
         }
 
         // Duplex
-        mem.duplex = raw.duplex.as_str().to_string();
+        mem.duplex = raw.duplex;
 
         // Tuning step
         if (raw.tuning_step as usize) < TUNE_STEPS.len() {
@@ -888,19 +737,19 @@ impl THD75Radio {
         // Mode 1 = DV/D-STAR, skip tone settings for those
         if raw.mode != 1 {
             if raw.tone_mode != 0 {
-                mem.tmode = "Tone".to_string();
+                mem.tmode = ToneMode::Tone;
             } else if raw.ctcss_mode != 0 {
-                mem.tmode = "TSQL".to_string();
+                mem.tmode = ToneMode::Tsql;
             } else if raw.dtcs_mode != 0 {
-                mem.tmode = "DTCS".to_string();
+                mem.tmode = ToneMode::Dtcs;
             } else if raw.cross_mode != 0 {
-                mem.tmode = "Cross".to_string();
+                mem.tmode = ToneMode::Cross;
             }
         }
 
         // Skip (lockout)
         if flags.lockout {
-            mem.skip = "S".to_string();
+            mem.skip = Skip::Skip;
         }
 
         // Bank/Group
@@ -960,18 +809,22 @@ impl Radio for THD75Radio {
         &self.model
     }
 
+    fn connection_profile(&self) -> ConnectionProfile {
+        CONNECTION
+    }
+
     fn get_features(&self) -> RadioFeatures {
         RadioFeatures {
             memory_bounds: (0, NUM_MEMORIES - 1),
-            valid_modes: THD75_MODES.iter().map(|s| s.to_string()).collect(),
+            valid_modes: THD75_MODES[..7].to_vec(),
             valid_tmodes: vec![
-                "".to_string(),
-                "Tone".to_string(),
-                "TSQL".to_string(),
-                "DTCS".to_string(),
-                "Cross".to_string(),
+                ToneMode::None,
+                ToneMode::Tone,
+                ToneMode::Tsql,
+                ToneMode::Dtcs,
+                ToneMode::Cross,
             ],
-            valid_duplexes: Duplex::all().iter().map(|s| s.to_string()).collect(),
+            valid_duplexes: THD75_DUPLEXES.to_vec(),
             valid_tuning_steps: TUNE_STEPS.to_vec(),
             valid_tones: TONES.to_vec(),
             valid_dtcs_codes: DTCS_CODES.to_vec(),
@@ -980,6 +833,7 @@ impl Radio for THD75Radio {
             has_dtcs: true,
             has_ctone: true,
             has_cross: true,
+            has_settings: true,
             ..Default::default()
         }
     }
@@ -1075,6 +929,213 @@ impl Radio for THD75Radio {
 
         Ok(())
     }
+
+    fn get_bank_names(&self) -> RadioResult<Vec<String>> {
+        self.read_bank_names()
+    }
+
+    fn bank_model(&mut self) -> Option<&mut dyn BankModel> {
+        Some(self)
+    }
+
+    fn get_settings(&self) -> RadioResult<RadioSettingGroup> {
+        let callsign = |value: String, len: usize| {
+            SettingValue::string(value, len).with_charset(CALLSIGN_CHARSET)
+        };
+
+        let aprs_call = self.read_text(APRS_CALL_OFFSET, 6)?;
+        let ssid = self.read_text(APRS_SSID_OFFSET, 3)?;
+        let mut aprs = RadioSettingGroup::new("aprs", "APRS");
+        aprs.add(
+            RadioSetting::new(
+                "aprs_mycall",
+                "My Callsign",
+                SettingValue::string(aprs_call + &ssid, 9)
+                    .with_charset("ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789-"),
+            )
+            .with_help("APRS callsign with optional SSID, e.g. N0CALL-7"),
+        );
+
+        let mut dstar = RadioSettingGroup::new("dstar", "D-STAR");
+        dstar.add(RadioSetting::new(
+            "dstar_mycall",
+            "My Callsign",
+            callsign(self.read_text(DSTAR_MYCALL_OFFSET, 8)?, 8),
+        ));
+        dstar.add(
+            RadioSetting::new(
+                "dstar_mycall_memo",
+                "Callsign Memo",
+                callsign(self.read_text(DSTAR_MEMO_OFFSET, 4)?, 4),
+            )
+            .with_help("Four character suffix sent after My Callsign"),
+        );
+
+        let mut display = RadioSettingGroup::new("display", "Display");
+        display.add(RadioSetting::new(
+            "poweron_msg",
+            "Power-On Message",
+            SettingValue::string(self.read_text(POWERON_MSG_OFFSET, 16)?, 16)
+                .with_charset(MESSAGE_CHARSET),
+        ));
+
+        let mut settings = RadioSettingGroup::new("settings", "Settings");
+        settings.add_group(aprs);
+        settings.add_group(dstar);
+        settings.add_group(display);
+        Ok(settings)
+    }
+
+    fn set_settings(&mut self, settings: &RadioSettingGroup) -> RadioResult<()> {
+        // Encode everything first so a bad value leaves the image untouched
+        let mut writes = Vec::new();
+        for setting in settings.iter() {
+            let text = setting.value.as_str().ok_or_else(|| {
+                RadioError::InvalidSetting(format!("{} must be a string", setting.name))
+            })?;
+            match setting.name.as_str() {
+                "aprs_mycall" => {
+                    let (call, ssid) = match text.find('-') {
+                        Some(pos) => text.split_at(pos),
+                        None => (text, ""),
+                    };
+                    if call.len() > 6 || ssid.len() > 3 {
+                        return Err(RadioError::InvalidSetting(format!(
+                            "Invalid APRS callsign: {}",
+                            text
+                        )));
+                    }
+                    writes.push((APRS_CALL_OFFSET, Self::encode_text(6, call, 0x00)?));
+                    writes.push((APRS_SSID_OFFSET, Self::encode_text(3, ssid, 0x00)?));
+                }
+                "dstar_mycall" => {
+                    writes.push((DSTAR_MYCALL_OFFSET, Self::encode_text(8, text, b' ')?))
+                }
+                "dstar_mycall_memo" => {
+                    writes.push((DSTAR_MEMO_OFFSET, Self::encode_text(4, text, 0x00)?))
+                }
+                "poweron_msg" => {
+                    writes.push((POWERON_MSG_OFFSET, Self::encode_text(16, text, 0x00)?))
+                }
+                name => {
+                    return Err(RadioError::InvalidSetting(format!(
+                        "Unknown setting: {}",
+                        name
+                    )))
+                }
+            }
+        }
+
+        let mmap = self
+            .mmap
+            .as_mut()
+            .ok_or_else(|| RadioError::Radio("Memory map not loaded".to_string()))?;
+        for (offset, bytes) in writes {
+            mmap.set_bytes(offset, &bytes).map_err(|e| {
+                RadioError::Radio(format!("Failed to write text at 0x{:X}: {}", offset, e))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Get all memory slots, with unused slots marked empty
+    fn get_memories(&mut self) -> RadioResult<Vec<Memory>> {
+        tracing::info!(
+            "Decoding {} memory channels from downloaded data",
+            NUM_MEMORIES
+        );
+        let mut memories = Vec::new();
+
+        for channel in 0..NUM_MEMORIES {
+            let mem = match self.get_memory(channel)? {
+                Some(mem) => mem,
+                None => {
+                    // Create an empty memory entry for unused slots
+                    let mut empty_mem = Memory::new(channel);
+                    empty_mem.empty = true;
+                    empty_mem
+                }
+            };
+            memories.push(mem);
+        }
+
+        let non_empty_count = memories.iter().filter(|m| !m.empty).count();
+        tracing::info!(
+            "Decoded {} memory channels ({} non-empty, {} empty)",
+            memories.len(),
+            non_empty_count,
+            memories.len() - non_empty_count
+        );
+        Ok(memories)
+    }
+}
+
+/// TH-D75 groups: every memory is in exactly one of 30 named groups
+impl BankModel for THD75Radio {
+    fn bank_style(&self) -> BankStyle {
+        BankStyle::Single
+    }
+
+    fn bank_name_length(&self) -> usize {
+        GROUP_NAME_SIZE
+    }
+
+    fn get_banks(&self) -> RadioResult<Vec<Bank>> {
+        Ok(self
+            .read_bank_names()?
+            .into_iter()
+            .enumerate()
+            .map(|(index, name)| Bank::new(index, name))
+            .collect())
+    }
+
+    /// Write a group name to GROUP_NAME_OFFSET (an empty name shows as "Bank N")
+    fn rename_bank(&mut self, bank: usize, name: &str) -> RadioResult<()> {
+        if bank >= NUM_GROUPS {
+            return Err(RadioError::Radio(format!("Invalid group: {}", bank)));
+        }
+        self.write_text(
+            GROUP_NAME_OFFSET + bank * GROUP_NAME_SIZE,
+            GROUP_NAME_SIZE,
+            name,
+            0x00,
+        )
+    }
+
+    fn get_memory_banks(&self, number: u32) -> RadioResult<Vec<usize>> {
+        let flags = self.read_flags(number)?;
+        if flags.band == 0xFF {
+            return Ok(Vec::new());
+        }
+        Ok(vec![flags.group as usize])
+    }
+
+    /// Move a memory into a group
+    fn add_memory_to_bank(&mut self, number: u32, bank: usize) -> RadioResult<()> {
+        self.write_group(number, bank)
+    }
+
+    /// The radio has no "no group", so the memory goes back to group 0
+    fn remove_memory_from_bank(&mut self, number: u32, bank: usize) -> RadioResult<()> {
+        if !self.get_memory_banks(number)?.contains(&bank) {
+            return Err(RadioError::Radio(format!(
+                "Memory #{} is not in group {}",
+                number, bank
+            )));
+        }
+        self.write_group(number, 0)
+    }
+
+    fn get_bank_memories(&self, bank: usize) -> RadioResult<Vec<u32>> {
+        let mut memories = Vec::new();
+        for number in 0..NUM_MEMORIES {
+            let flags = self.read_flags(number)?;
+            if flags.band != 0xFF && flags.group as usize == bank {
+                memories.push(number);
+            }
+        }
+        Ok(memories)
+    }
 }
 
 impl CloneModeRadio for THD75Radio {
@@ -1082,20 +1143,14 @@ impl CloneModeRadio for THD75Radio {
         MEMSIZE
     }
 
-    async fn sync_in(
+    async fn sync_in<T: Transport + ?Sized>(
         &mut self,
-        port: &mut SerialPort,
+        port: &mut T,
         status_fn: Option<StatusCallback>,
     ) -> RadioResult<MemoryMap> {
         // Detect baud and enter programming mode
-        self.detect_baud(port).await?;
-
-        tracing::debug!("Entering programming mode");
-        let response = self.command(port, "0M PROGRAM").await?;
-        tracing::debug!("Got response: {:?}", response);
-        if response != "0M" {
-            return Err(RadioError::NoResponse);
-        }
+        kenwood::wake(port).await?;
+        kenwood::enter_programming(port).await?;
 
         // Radio is now in programming mode and expecting us to switch to high speed
         // DO NOT read anything else - immediately switch baud rates
@@ -1125,7 +1180,7 @@ impl CloneModeRadio for THD75Radio {
             if block % 100 == 0 {
                 tracing::debug!("Reading block {}/{}", block, num_blocks);
             }
-            let block_data = self.read_block(port, block as u16).await?;
+            let block_data = kenwood::read_block(port, block as u16, SizeField::Word).await?;
             data.extend_from_slice(&block_data);
 
             if let Some(ref callback) = status_fn {
@@ -1140,13 +1195,7 @@ impl CloneModeRadio for THD75Radio {
         tracing::debug!("Block download complete");
 
         // End programming mode
-        tracing::debug!("Sending exit command");
-        port.write_all(b"E")
-            .await
-            .map_err(|e| RadioError::Serial(e.to_string()))?;
-
-        // Ensure command is sent before switching baud
-        port.flush().await.ok();
+        kenwood::exit_programming(port).await?;
 
         // Give radio time to process exit command
         tokio::time::sleep(Duration::from_millis(200)).await;
@@ -1179,19 +1228,15 @@ impl CloneModeRadio for THD75Radio {
         Ok(mmap)
     }
 
-    async fn sync_out(
+    async fn sync_out<T: Transport + ?Sized>(
         &mut self,
-        port: &mut SerialPort,
+        port: &mut T,
         mmap: &MemoryMap,
         status_fn: Option<StatusCallback>,
     ) -> RadioResult<()> {
         // Detect baud and enter programming mode
-        self.detect_baud(port).await?;
-
-        let response = self.command(port, "0M PROGRAM").await?;
-        if response != "0M" {
-            return Err(RadioError::NoResponse);
-        }
+        kenwood::wake(port).await?;
+        kenwood::enter_programming(port).await?;
 
         // Radio is now in programming mode and expecting us to switch to high speed
         // DO NOT read anything else - immediately switch baud rates
@@ -1222,7 +1267,7 @@ impl CloneModeRadio for THD75Radio {
                 .get(start, Some(BLOCK_SIZE))
                 .map_err(|e| RadioError::Radio(e.to_string()))?;
 
-            self.write_block(port, block as u16, block_data).await?;
+            kenwood::write_block(port, block as u16, block_data, SizeField::Word).await?;
 
             if let Some(ref callback) = status_fn {
                 let status = Status::new(
@@ -1235,13 +1280,7 @@ impl CloneModeRadio for THD75Radio {
         }
 
         // End programming mode
-        tracing::debug!("Sending exit command");
-        port.write_all(b"E")
-            .await
-            .map_err(|e| RadioError::Serial(e.to_string()))?;
-
-        // Ensure command is sent before switching baud
-        port.flush().await.ok();
+        kenwood::exit_programming(port).await?;
 
         // Give radio time to process exit command
         tokio::time::sleep(Duration::from_millis(200)).await;
@@ -1273,19 +1312,24 @@ impl CloneModeRadio for THD75Radio {
     }
 
     fn process_mmap(&mut self, mmap: &MemoryMap) -> RadioResult<()> {
-        self.mmap = Some(mmap.clone());
+        // An MCP file opened under another name still carries its header
+        if d75::has_header(mmap.get_packed()) {
+            let (image, _) =
+                d75::parse(mmap.get_packed()).map_err(|e| RadioError::Radio(e.to_string()))?;
+            self.mmap = Some(image);
+        } else {
+            self.mmap = Some(mmap.clone());
+        }
         Ok(())
     }
 
+    fn get_mmap(&self) -> Option<&MemoryMap> {
+        self.mmap.as_ref()
+    }
+
     fn match_model(data: &[u8], filename: &str) -> bool {
-        if filename.ends_with(".d74") || filename.ends_with(".d75") {
-            return true;
-        }
-        // Check for file header
-        if data.len() >= D74_FILE_HEADER.len() {
-            return data[..D74_FILE_HEADER.len()].starts_with(D74_FILE_HEADER);
-        }
-        false
+        // MCP-D74/D75 file, or raw clone image (as saved by sync_in)
+        d75::is_mcp_file(filename) || d75::has_header(data) || data.len() == MEMSIZE
     }
 }
 
@@ -1330,6 +1374,27 @@ mod tests {
         assert!(THD75Radio::match_model(&[], "test.d74"));
         assert!(THD75Radio::match_model(&[], "test.d75"));
         assert!(!THD75Radio::match_model(&[], "test.img"));
+        assert!(THD75Radio::match_model(&vec![0u8; MEMSIZE], "test.img"));
+    }
+
+    #[test]
+    fn test_mcp_header_under_img_name() {
+        crate::drivers::init_drivers();
+
+        let mut mem = Memory::new(5);
+        mem.freq = 145_500_000;
+        mem.name = "CALLING".to_string();
+        let image = THD75Radio::new().encode_memories(&[mem]).unwrap();
+        let mut data = d75::file_header("TH-D75").unwrap();
+        data.extend_from_slice(image.get_packed());
+
+        let info = crate::drivers::detect_image(&data, "backup.img").unwrap();
+        assert_eq!(info.model, "TH-D75");
+        let mut radio = info.create().unwrap().into_clone_mode().unwrap();
+        radio.load_mmap(&MemoryMap::new(data)).unwrap();
+        assert_eq!(radio.mmap().unwrap().len(), MEMSIZE);
+        let read = radio.get_memory(5).unwrap().unwrap();
+        assert_eq!((read.freq, read.name.as_str()), (145_500_000, "CALLING"));
     }
 
     #[test]
@@ -1387,7 +1452,7 @@ mod tests {
         assert_eq!(mem0.name, "APRS");
         assert_eq!(mem0.freq, 144_390_000);
         assert_eq!(mem0.offset, 600_000);
-        assert_eq!(mem0.mode, "FM");
+        assert_eq!(mem0.mode, Mode::Fm);
 
         // Test memory #3 - PhilMont W3QV (Tone mode)
         let mem3 = radio.get_memory(3).expect("Failed to get memory 3");
@@ -1397,8 +1462,8 @@ mod tests {
         assert_eq!(mem3.name, "PhilMont W3QV");
         assert_eq!(mem3.freq, 147_030_000);
         assert_eq!(mem3.offset, 600_000);
-        assert_eq!(mem3.duplex, "+");
-        assert_eq!(mem3.tmode, "Tone");
+        assert_eq!(mem3.duplex, Duplex::Plus);
+        assert_eq!(mem3.tmode, ToneMode::Tone);
         assert_eq!(mem3.rtone, 88.5);
         assert_eq!(mem3.ctone, 91.5);
 
@@ -1410,8 +1475,8 @@ mod tests {
         assert_eq!(mem32.name, "N3CB");
         assert_eq!(mem32.freq, 448_675_000);
         assert_eq!(mem32.offset, 5_000_000);
-        assert_eq!(mem32.mode, "FM");
-        assert_eq!(mem32.duplex, "-");
+        assert_eq!(mem32.mode, Mode::Fm);
+        assert_eq!(mem32.duplex, Duplex::Minus);
 
         // Test memory #40 - W3EOC (previously problematic)
         let mem40 = radio.get_memory(40).expect("Failed to get memory 40");
@@ -1421,8 +1486,8 @@ mod tests {
         assert_eq!(mem40.name, "W3EOC");
         assert_eq!(mem40.freq, 441_950_000);
         assert_eq!(mem40.offset, 5_000_000);
-        assert_eq!(mem40.duplex, "+");
-        assert_eq!(mem40.tmode, "Tone");
+        assert_eq!(mem40.duplex, Duplex::Plus);
+        assert_eq!(mem40.tmode, ToneMode::Tone);
         assert_eq!(mem40.ctone, 100.0);
 
         // Test memory #50 - KB3AJF
@@ -1433,7 +1498,7 @@ mod tests {
         assert_eq!(mem50.name, "KB3AJF");
         assert_eq!(mem50.freq, 447_975_000);
         assert_eq!(mem50.offset, 5_000_000);
-        assert_eq!(mem50.duplex, "-");
+        assert_eq!(mem50.duplex, Duplex::Minus);
 
         // Test that we get all memory slots with correct non-empty count
         let memories = radio.get_memories().expect("Failed to get memories");
@@ -1494,15 +1559,15 @@ mod tests {
         assert_eq!(mem1.name, "Eaglevi CQCQCQ");
         assert_eq!(mem1.freq, 445_018_750);
         assert_eq!(mem1.offset, 5_000_000);
-        assert_eq!(mem1.mode, "DV");
-        assert_eq!(mem1.duplex, "-");
+        assert_eq!(mem1.mode, Mode::Dv);
+        assert_eq!(mem1.duplex, Duplex::Minus);
         // D-STAR fields are empty for this memory
         assert_eq!(mem1.dv_urcall, "");
         assert_eq!(mem1.dv_rpt1call, "");
         assert_eq!(mem1.dv_rpt2call, "");
         assert_eq!(mem1.dv_code, 0);
         // Verify that DV memories have no tone mode
-        assert_eq!(mem1.tmode, "");
+        assert_eq!(mem1.tmode, ToneMode::None);
 
         // Test memory #102 - dmr clear (DV memory with populated D-STAR fields)
         let mem102 = radio.get_memory(102).expect("Failed to get memory 102");
@@ -1511,12 +1576,12 @@ mod tests {
         assert_eq!(mem102.number, 102);
         assert_eq!(mem102.name, "dmr clear");
         assert_eq!(mem102.freq, 438_287_500);
-        assert_eq!(mem102.mode, "DV");
+        assert_eq!(mem102.mode, Mode::Dv);
         // D-STAR fields should be populated (CHIRP CSV export bug didn't include these)
         assert_eq!(mem102.dv_urcall, "4000");
         assert_eq!(mem102.dv_rpt1call, "W3POG");
         // RPT2CALL might be empty or populated - just verify it's been parsed
-        assert_eq!(mem102.tmode, "");
+        assert_eq!(mem102.tmode, ToneMode::None);
     }
 
     #[test]
@@ -1623,8 +1688,8 @@ mod tests {
         // Test encoding a D-STAR memory with call signs
         let mut mem = Memory::new(100);
         mem.freq = 438_287_500;
-        mem.mode = "DV".to_string();
-        mem.duplex = "-".to_string();
+        mem.mode = Mode::Dv;
+        mem.duplex = Duplex::Minus;
         mem.offset = 5_000_000;
         mem.dv_urcall = "4000".to_string();
         mem.dv_rpt1call = "W3POG".to_string();
@@ -1695,15 +1760,104 @@ mod tests {
         assert!(THD75Radio::find_tuning_step_index(7.5).is_err());
 
         // Test mode lookup
-        assert_eq!(THD75Radio::find_mode_index("FM").unwrap(), 0);
-        assert_eq!(THD75Radio::find_mode_index("DV").unwrap(), 1);
-        assert_eq!(THD75Radio::find_mode_index("NFM").unwrap(), 6);
-        assert!(THD75Radio::find_mode_index("INVALID").is_err());
-
-        // Test duplex parsing
-        assert_eq!(THD75Radio::parse_duplex("").unwrap(), Duplex::Simplex);
-        assert_eq!(THD75Radio::parse_duplex("+").unwrap(), Duplex::Plus);
-        assert_eq!(THD75Radio::parse_duplex("-").unwrap(), Duplex::Minus);
-        assert!(THD75Radio::parse_duplex("invalid").is_err());
+        assert_eq!(THD75Radio::find_mode_index(Mode::Fm).unwrap(), 0);
+        assert_eq!(THD75Radio::find_mode_index(Mode::Dv).unwrap(), 1);
+        assert_eq!(THD75Radio::find_mode_index(Mode::Nfm).unwrap(), 6);
+        assert!(THD75Radio::find_mode_index(Mode::Wfm).is_err());
+
+        // Test duplex checking
+        assert_eq!(
+            THD75Radio::check_duplex(Duplex::Simplex).unwrap(),
+            Duplex::Simplex
+        );
+        assert_eq!(
+            THD75Radio::check_duplex(Duplex::Plus).unwrap(),
+            Duplex::Plus
+        );
+        assert_eq!(
+            THD75Radio::check_duplex(Duplex::Minus).unwrap(),
+            Duplex::Minus
+        );
+        assert!(THD75Radio::check_duplex(Duplex::Split).is_err());
+    }
+
+    #[test]
+    fn test_settings() {
+        let path =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("test_data/radio_dump.bin");
+        let mut radio = THD75Radio::new();
+        radio
+            .process_mmap(&MemoryMap::new(std::fs::read(path).unwrap()))
+            .unwrap();
+        assert!(radio.get_features().has_settings);
+
+        let mut settings = radio.get_settings().unwrap();
+        let value = |settings: &RadioSettingGroup, name: &str| {
+            settings.get(name).unwrap().value.to_string()
+        };
+        assert_eq!(value(&settings, "aprs_mycall"), "W3POG-7");
+        assert_eq!(value(&settings, "dstar_mycall"), "W3POG");
+        assert_eq!(value(&settings, "dstar_mycall_memo"), "D75");
+        assert_eq!(value(&settings, "poweron_msg"), "W3POG");
+
+        settings.set("aprs_mycall", "N0CALL-12").unwrap();
+        settings.set("dstar_mycall", "N0CALL").unwrap();
+        settings.set("poweron_msg", "Hello, world").unwrap();
+        assert!(settings.set("dstar_mycall", "n0call").is_err());
+        assert!(settings.set("poweron_msg", "This is far too long").is_err());
+        radio.set_settings(&settings).unwrap();
+
+        let mmap = radio.mmap.as_ref().unwrap();
+        assert_eq!(mmap.get(DSTAR_MYCALL_OFFSET, Some(8)).unwrap(), b"N0CALL  ");
+        assert_eq!(mmap.get(APRS_CALL_OFFSET, Some(9)).unwrap(), b"N0CALL-12");
+        assert_eq!(radio.get_settings().unwrap(), settings);
+
+        // A bad value later in the group must not leave earlier ones written
+        settings.set("dstar_mycall", "W3POG").unwrap();
+        settings.set("aprs_mycall", "N0CALLX-1").unwrap();
+        assert!(matches!(
+            radio.set_settings(&settings),
+            Err(RadioError::InvalidSetting(_))
+        ));
+        let mmap = radio.mmap.as_ref().unwrap();
+        assert_eq!(mmap.get(DSTAR_MYCALL_OFFSET, Some(8)).unwrap(), b"N0CALL  ");
+    }
+
+    #[test]
+    fn test_bank_model() {
+        let path =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("test_data/radio_dump.bin");
+        let mut radio = THD75Radio::new();
+        radio
+            .process_mmap(&MemoryMap::new(std::fs::read(path).unwrap()))
+            .unwrap();
+
+        let model = radio.bank_model().unwrap();
+        assert_eq!(model.bank_style(), BankStyle::Single);
+        assert_eq!(model.get_banks().unwrap().len(), NUM_GROUPS);
+
+        // Move the first used memory into group 7 and back out again
+        let number = (0..NUM_MEMORIES)
+            .find(|&n| !model.get_memory_banks(n).unwrap().is_empty())
+            .unwrap();
+        model.add_memory_to_bank(number, 7).unwrap();
+        assert_eq!(model.get_memory_banks(number).unwrap(), vec![7]);
+        assert!(model.get_bank_memories(7).unwrap().contains(&number));
+        assert!(model.remove_memory_from_bank(number, 8).is_err());
+        model.remove_memory_from_bank(number, 7).unwrap();
+        assert_eq!(model.get_memory_banks(number).unwrap(), vec![0]);
+        assert!(model.add_memory_to_bank(number, NUM_GROUPS).is_err());
+
+        // Renamed groups are stored in the image
+        model.rename_bank(3, "SATELLITES").unwrap();
+        assert!(model.rename_bank(3, "A NAME THAT IS TOO LONG").is_err());
+        let mut reloaded = THD75Radio::new();
+        reloaded.process_mmap(radio.get_mmap().unwrap()).unwrap();
+        assert_eq!(reloaded.get_bank_names().unwrap()[3], "SATELLITES");
+        assert_eq!(
+            reloaded.get_memory(number).unwrap().unwrap().bank,
+            0,
+            "group change should survive a reload"
+        );
     }
 }
@@ -0,0 +1,881 @@
+// Kenwood TM-V71A / TM-D710 radio driver
+// Reference: chirp/drivers/tmv71.py, chirp/drivers/tmv71_ll.py
+//
+// Same clone protocol as the TH-D74/D75 (see `kenwood`), at 9600 baud
+// throughout and with a one-byte size field in block headers. The image is
+// 127 blocks:
+//
+//   0x0E00  2 bytes per channel: band the channel opens on (0xFF = unused),
+//           lockout flag
+//   0x1700  16 bytes per channel: frequency, step, mode, tones, offset
+//   0x5780  16 bytes per VFO, same format as a channel
+//   0x5800  8 bytes per channel: name (6 characters, 0xFF padded)
+//
+// Channels 0-999 are the regular memories; the band flag records the
+// frequency range (VHF, 220 or UHF) each one is stored under. Program scan
+// edges L0-L9/U0-U9 and the two call channels follow at 1000-1031 and are
+// reached through their extended names.
+//
+// Each band keeps one VFO per frequency range it can tune: five on band A
+// (118, 144, 220, 300 and 430 MHz) and three on band B (144, 300 and 430 MHz).
+// They follow the channel records and are exposed as special channels
+// 1032-1039 ("VFO A 144", ...). A VFO is always in use and has no flags or
+// name, so it can be edited but not deleted.
+
+use super::image::{index_of, ImageAccess};
+use super::kenwood::{self, SizeField, BLOCK_SIZE};
+use super::traits::{
+    BoxFuture, CloneModeRadio, Radio, RadioError, RadioResult, Status, StatusCallback,
+};
+use crate::bitwise::{read_u32_le, write_u32_le};
+use crate::core::{
+    Duplex, Memory, Mode, RadioFeatures, Skip, ToneMode, DTCS_CODES, TONES, TUNING_STEPS,
+};
+use crate::memmap::MemoryMap;
+use crate::serial::{ConnectionProfile, Transport};
+
+/// Image size: 127 blocks
+const MEMSIZE: usize = 0x7F00;
+
+/// Memory offsets
+const FLAGS_OFFSET: usize = 0x0E00;
+const MEMORY_OFFSET: usize = 0x1700;
+const NAMES_OFFSET: usize = 0x5800;
+
+/// Bytes per channel in each table
+const FLAGS_SIZE: usize = 2;
+const MEMORY_SIZE: usize = 16;
+const NAME_SLOT_SIZE: usize = 8;
+
+/// Characters stored per name
+const NAME_LENGTH: usize = 6;
+
+/// Name characters
+const CHARSET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ 1234567890-/";
+
+/// Number of regular memories
+const NUM_MEMORIES: u32 = 1000;
+
+/// First special channel (program scan edges, then call channels)
+const SPECIAL_BASE: u32 = 1000;
+
+/// Call channels, one per frequency range
+const CALL_CHANNELS: &[(&str, u32)] = &[("C VHF", 1030), ("C UHF", 1031)];
+
+/// First VFO record, right after the call channels
+const VFO_BASE: u32 = 1032;
+
+/// VFOs in record order: band A's ranges, then band B's
+const VFOS: &[&str] = &[
+    "VFO A 118",
+    "VFO A 144",
+    "VFO A 220",
+    "VFO A 300",
+    "VFO A 430",
+    "VFO B 144",
+    "VFO B 300",
+    "VFO B 430",
+];
+
+/// Programming cable: RTS/CTS flow control, DTR high, RTS low
+const CONNECTION: ConnectionProfile = ConnectionProfile::new(9600)
+    .with_hardware_flow()
+    .with_lines(true, false);
+
+/// `ID` answers from radios with this memory layout, and their registered models
+const MODELS: &[(&str, &str)] = &[("TM-V71", "TM-V71A"), ("TM-D710", "TM-D710")];
+
+/// Modes in the order of the 2-bit field
+const TMV71_MODES: &[Mode] = &[Mode::Fm, Mode::Nfm, Mode::Am];
+
+/// Duplex values in the order of the 2-bit field
+const TMV71_DUPLEXES: &[Duplex] = &[Duplex::Simplex, Duplex::Plus, Duplex::Minus];
+
+/// Tone mode bits (byte 6)
+const TMODE_MASK: u8 = 0x70;
+const TMODE_TONE: u8 = 0x40;
+const TMODE_TSQL: u8 = 0x20;
+const TMODE_DTCS: u8 = 0x10;
+
+/// Band flag values (first flags byte)
+const BAND_VHF: u8 = 0x05;
+const BAND_220: u8 = 0x06;
+const BAND_UHF: u8 = 0x08;
+const BAND_UNUSED: u8 = 0xFF;
+
+/// Lockout bit (second flags byte)
+const LOCKOUT: u8 = 0x01;
+
+/// Band flag for a frequency
+fn band_flag(freq: u64) -> u8 {
+    match freq {
+        0..200_000_000 => BAND_VHF,
+        200_000_000..400_000_000 => BAND_220,
+        _ => BAND_UHF,
+    }
+}
+
+/// Extended name of a special channel
+fn special_name(number: u32) -> Option<String> {
+    if let Some(vfo) = number.checked_sub(VFO_BASE) {
+        return VFOS.get(vfo as usize).map(|name| name.to_string());
+    }
+    match number.checked_sub(SPECIAL_BASE)? {
+        index @ 0..20 => {
+            let edge = if index % 2 == 0 { 'L' } else { 'U' };
+            Some(format!("{}{}", edge, index / 2))
+        }
+        _ => CALL_CHANNELS
+            .iter()
+            .find(|(_, n)| *n == number)
+            .map(|(name, _)| name.to_string()),
+    }
+}
+
+/// Whether a channel number is one of the VFOs
+fn is_vfo(number: u32) -> bool {
+    (VFO_BASE..VFO_BASE + VFOS.len() as u32).contains(&number)
+}
+
+/// Channel number of a special channel's extended name
+pub fn special_number(name: &str) -> Option<u32> {
+    if let Some((_, number)) = CALL_CHANNELS.iter().find(|(n, _)| *n == name) {
+        return Some(*number);
+    }
+    if let Some(vfo) = VFOS.iter().position(|&n| n == name) {
+        return Some(VFO_BASE + vfo as u32);
+    }
+    let mut chars = name.chars();
+    let edge = match chars.next()? {
+        'L' => 0,
+        'U' => 1,
+        _ => return None,
+    };
+    let index: u32 = chars.as_str().parse().ok().filter(|&i| i < 10)?;
+    Some(SPECIAL_BASE + index * 2 + edge)
+}
+
+/// Kenwood TM-V71A / TM-D710 radio driver
+pub struct TMV71Radio {
+    pub mmap: Option<MemoryMap>,
+    model: String,
+}
+
+impl TMV71Radio {
+    pub fn new() -> Self {
+        Self {
+            mmap: None,
+            model: "TM-V71A".to_string(),
+        }
+    }
+
+    /// Create a driver for the TM-D710 (same memory layout as the TM-V71A)
+    pub fn new_d710() -> Self {
+        Self {
+            model: "TM-D710".to_string(),
+            ..Self::new()
+        }
+    }
+
+    /// Identify a TM-V71/TM-D710 on a serial port using the `ID` command
+    pub fn probe(port_name: String) -> BoxFuture<'static, RadioResult<Option<String>>> {
+        kenwood::probe(port_name, CONNECTION, MODELS)
+    }
+
+    /// Make sure the radio that answered `ID` uses this memory layout
+    fn check_id(id: &str) -> RadioResult<()> {
+        if MODELS.iter().any(|(answer, _)| *answer == id) {
+            Ok(())
+        } else {
+            Err(RadioError::Unsupported(format!(
+                "Radio identified as {:?}, not a TM-V71 or TM-D710",
+                id
+            )))
+        }
+    }
+
+    /// Resolve a memory to its channel number (regular or special)
+    fn channel(memory: &Memory) -> RadioResult<u32> {
+        if !memory.extd_number.is_empty() {
+            return special_number(&memory.extd_number).ok_or_else(|| {
+                RadioError::Radio(format!("Unknown channel: {}", memory.extd_number))
+            });
+        }
+        Self::check_channel(memory.number)
+    }
+
+    fn check_channel(number: u32) -> RadioResult<u32> {
+        if number < NUM_MEMORIES || special_name(number).is_some() {
+            Ok(number)
+        } else {
+            Err(RadioError::InvalidMemory(number))
+        }
+    }
+
+    fn flags_offset(number: u32) -> usize {
+        FLAGS_OFFSET + number as usize * FLAGS_SIZE
+    }
+
+    fn memory_offset(number: u32) -> usize {
+        MEMORY_OFFSET + number as usize * MEMORY_SIZE
+    }
+
+    fn name_offset(number: u32) -> usize {
+        NAMES_OFFSET + number as usize * NAME_SLOT_SIZE
+    }
+
+    /// Encode a name into its 0xFF-padded slot
+    fn encode_name(name: &str) -> RadioResult<[u8; NAME_SLOT_SIZE]> {
+        let name = name.trim_end().to_ascii_uppercase();
+        if let Some(c) = name.chars().find(|&c| !CHARSET.contains(c)) {
+            return Err(RadioError::Radio(format!(
+                "Unsupported character in name: {}",
+                c
+            )));
+        }
+        let mut slot = [0xFF; NAME_SLOT_SIZE];
+        for (byte, c) in slot.iter_mut().zip(name.bytes().take(NAME_LENGTH)) {
+            *byte = c;
+        }
+        Ok(slot)
+    }
+
+    /// Decode a used channel
+    fn decode_memory(number: u32, data: &[u8], flags: &[u8], name: &[u8]) -> RadioResult<Memory> {
+        let mut mem = Memory::new(number);
+        mem.extd_number = special_name(number).unwrap_or_default();
+        mem.freq = read_u32_le(&data[0..4]).map_err(|e| RadioError::Radio(e.to_string()))? as u64;
+        mem.offset =
+            read_u32_le(&data[10..14]).map_err(|e| RadioError::Radio(e.to_string()))? as u64;
+
+        mem.tuning_step = TUNING_STEPS
+            .get((data[4] & 0x0F) as usize)
+            .copied()
+            .unwrap_or(5.0);
+        mem.mode = TMV71_MODES
+            .get((data[5] & 0x03) as usize)
+            .copied()
+            .unwrap_or(Mode::Fm);
+        mem.duplex = TMV71_DUPLEXES
+            .get((data[6] & 0x03) as usize)
+            .copied()
+            .unwrap_or(Duplex::Simplex);
+        mem.tmode = match data[6] & TMODE_MASK {
+            TMODE_TONE => ToneMode::Tone,
+            TMODE_TSQL => ToneMode::Tsql,
+            TMODE_DTCS => ToneMode::Dtcs,
+            _ => ToneMode::None,
+        };
+
+        if let Some(&tone) = TONES.get(data[7] as usize) {
+            mem.rtone = tone;
+        }
+        if let Some(&tone) = TONES.get(data[8] as usize) {
+            mem.ctone = tone;
+        }
+        if let Some(&code) = DTCS_CODES.get(data[9] as usize) {
+            mem.dtcs = code;
+            mem.rx_dtcs = code;
+        }
+
+        if flags[1] & LOCKOUT != 0 {
+            mem.skip = Skip::Skip;
+        }
+
+        mem.name = name
+            .iter()
+            .take(NAME_LENGTH)
+            .take_while(|&&b| b != 0xFF && b != 0x00)
+            .map(|&b| b as char)
+            .collect::<String>()
+            .trim_end()
+            .to_string();
+
+        Ok(mem)
+    }
+
+    /// Encode a channel's 16-byte record
+    ///
+    /// Starts from `current` so the upper step/mode bits and the two trailing
+    /// bytes, which CHIRP leaves alone too, survive an edit.
+    fn encode_memory(mem: &Memory, current: &[u8]) -> RadioResult<[u8; MEMORY_SIZE]> {
+        let step = index_of(&TUNING_STEPS[..10], mem.tuning_step, "tuning step")?;
+        let mode = index_of(TMV71_MODES, mem.mode, "mode")?;
+        let duplex = index_of(TMV71_DUPLEXES, mem.duplex, "duplex")?;
+        let tmode = match mem.tmode {
+            ToneMode::None => 0,
+            ToneMode::Tone => TMODE_TONE,
+            ToneMode::Tsql => TMODE_TSQL,
+            ToneMode::Dtcs => TMODE_DTCS,
+            tmode => {
+                return Err(RadioError::Radio(format!(
+                    "Unsupported tone mode: {}",
+                    tmode
+                )))
+            }
+        };
+
+        let mut data = [0u8; MEMORY_SIZE];
+        data.copy_from_slice(current);
+        data[0..4].copy_from_slice(&write_u32_le(mem.freq as u32));
+        data[4] = (data[4] & 0xF0) | step;
+        data[5] = (data[5] & !0x03) | mode;
+        data[6] = (data[6] & !(TMODE_MASK | 0x03)) | tmode | duplex;
+        data[7] = index_of(&TONES, mem.rtone, "tone")?;
+        data[8] = index_of(&TONES, mem.ctone, "tone")?;
+        data[9] = index_of(&DTCS_CODES, mem.dtcs, "DTCS code")?;
+        data[10..14].copy_from_slice(&write_u32_le(mem.offset as u32));
+        Ok(data)
+    }
+}
+
+impl ImageAccess for TMV71Radio {
+    fn image(&self) -> Option<&MemoryMap> {
+        self.mmap.as_ref()
+    }
+
+    fn image_mut(&mut self) -> Option<&mut MemoryMap> {
+        self.mmap.as_mut()
+    }
+}
+
+impl Default for TMV71Radio {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Radio for TMV71Radio {
+    fn vendor(&self) -> &str {
+        "Kenwood"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn connection_profile(&self) -> ConnectionProfile {
+        CONNECTION
+    }
+
+    fn get_features(&self) -> RadioFeatures {
+        RadioFeatures {
+            memory_bounds: (0, NUM_MEMORIES - 1),
+            valid_modes: TMV71_MODES.to_vec(),
+            valid_tmodes: vec![
+                ToneMode::None,
+                ToneMode::Tone,
+                ToneMode::Tsql,
+                ToneMode::Dtcs,
+            ],
+            valid_duplexes: TMV71_DUPLEXES.to_vec(),
+            valid_tuning_steps: TUNING_STEPS[..10].to_vec(),
+            valid_tones: TONES.to_vec(),
+            valid_dtcs_codes: DTCS_CODES.to_vec(),
+            valid_name_length: NAME_LENGTH,
+            valid_characters: CHARSET.to_string(),
+            valid_bands: vec![(118_000_000, 524_000_000)],
+            valid_skips: vec![Skip::None, Skip::Skip],
+            valid_special_chans: (SPECIAL_BASE..VFO_BASE + VFOS.len() as u32)
+                .filter_map(special_name)
+                .collect(),
+            has_bank: false,
+            has_dtcs: true,
+            has_dtcs_polarity: false,
+            has_ctone: true,
+            has_cross: false,
+            has_tuning_step: true,
+            has_mode: true,
+            has_offset: true,
+            has_name: true,
+            can_delete: true,
+            ..Default::default()
+        }
+    }
+
+    fn get_memory(&mut self, number: u32) -> RadioResult<Option<Memory>> {
+        let number = Self::check_channel(number)?;
+
+        if is_vfo(number) {
+            let data = self.read(Self::memory_offset(number), MEMORY_SIZE)?;
+            if data == [0xFF; MEMORY_SIZE] {
+                return Ok(None);
+            }
+            return Self::decode_memory(number, data, &[0; FLAGS_SIZE], &[]).map(Some);
+        }
+
+        let flags = self.read(Self::flags_offset(number), FLAGS_SIZE)?;
+        if flags[0] == BAND_UNUSED {
+            return Ok(None);
+        }
+
+        let data = self.read(Self::memory_offset(number), MEMORY_SIZE)?;
+        let name = self.read(Self::name_offset(number), NAME_SLOT_SIZE)?;
+        Self::decode_memory(number, data, flags, name).map(Some)
+    }
+
+    fn set_memory(&mut self, memory: &Memory) -> RadioResult<()> {
+        let number = Self::channel(memory)?;
+
+        if is_vfo(number) {
+            if memory.empty {
+                return Err(RadioError::Unsupported(format!(
+                    "{} can't be deleted",
+                    special_name(number).unwrap_or_default()
+                )));
+            }
+            let current = self
+                .read(Self::memory_offset(number), MEMORY_SIZE)?
+                .to_vec();
+            let data = Self::encode_memory(memory, &current)?;
+            return self.write(Self::memory_offset(number), &data);
+        }
+
+        if memory.empty {
+            self.write(Self::flags_offset(number), &[BAND_UNUSED; FLAGS_SIZE])?;
+            self.write(Self::memory_offset(number), &[0xFF; MEMORY_SIZE])?;
+            return self.write(Self::name_offset(number), &[0xFF; NAME_SLOT_SIZE]);
+        }
+
+        let flags = self.read(Self::flags_offset(number), FLAGS_SIZE)?.to_vec();
+        let current = self.record_base(
+            Self::memory_offset(number),
+            MEMORY_SIZE,
+            flags[0] != BAND_UNUSED,
+        )?;
+        let data = Self::encode_memory(memory, &current)?;
+
+        let mut lockout = if flags[0] == BAND_UNUSED { 0 } else { flags[1] };
+        if memory.skip == Skip::Skip {
+            lockout |= LOCKOUT;
+        } else {
+            lockout &= !LOCKOUT;
+        }
+
+        let name = Self::encode_name(&memory.name)?;
+
+        self.write(Self::memory_offset(number), &data)?;
+        self.write(
+            Self::flags_offset(number),
+            &[band_flag(memory.freq), lockout],
+        )?;
+        self.write(Self::name_offset(number), &name)
+    }
+}
+
+impl CloneModeRadio for TMV71Radio {
+    fn get_memsize(&self) -> usize {
+        MEMSIZE
+    }
+
+    async fn sync_in<T: Transport + ?Sized>(
+        &mut self,
+        port: &mut T,
+        status_fn: Option<StatusCallback>,
+    ) -> RadioResult<MemoryMap> {
+        let id = kenwood::wake(port).await?;
+        tracing::debug!("Radio ID: {}", id);
+        Self::check_id(&id)?;
+        kenwood::enter_programming(port).await?;
+
+        let num_blocks = MEMSIZE / BLOCK_SIZE;
+        let mut data = Vec::with_capacity(MEMSIZE);
+        for block in 0..num_blocks {
+            let block_data = kenwood::read_block(port, block as u16, SizeField::Byte).await?;
+            data.extend_from_slice(&block_data);
+
+            if let Some(ref callback) = status_fn {
+                let status = Status::new(
+                    block + 1,
+                    num_blocks,
+                    format!("Downloading block {}/{}", block + 1, num_blocks),
+                );
+                callback(status.current, status.max, &status.message);
+            }
+        }
+
+        kenwood::exit_programming(port).await?;
+
+        let mmap = MemoryMap::new(data);
+        self.mmap = Some(mmap.clone());
+        Ok(mmap)
+    }
+
+    async fn sync_out<T: Transport + ?Sized>(
+        &mut self,
+        port: &mut T,
+        mmap: &MemoryMap,
+        status_fn: Option<StatusCallback>,
+    ) -> RadioResult<()> {
+        let id = kenwood::wake(port).await?;
+        tracing::debug!("Radio ID: {}", id);
+        Self::check_id(&id)?;
+        kenwood::enter_programming(port).await?;
+
+        let num_blocks = MEMSIZE / BLOCK_SIZE;
+        for block in 0..num_blocks {
+            let block_data = mmap
+                .get(block * BLOCK_SIZE, Some(BLOCK_SIZE))
+                .map_err(|e| RadioError::Radio(e.to_string()))?;
+            kenwood::write_block(port, block as u16, block_data, SizeField::Byte).await?;
+
+            if let Some(ref callback) = status_fn {
+                let status = Status::new(
+                    block + 1,
+                    num_blocks,
+                    format!("Uploading block {}/{}", block + 1, num_blocks),
+                );
+                callback(status.current, status.max, &status.message);
+            }
+        }
+
+        kenwood::exit_programming(port).await
+    }
+
+    fn process_mmap(&mut self, mmap: &MemoryMap) -> RadioResult<()> {
+        if mmap.len() < MEMSIZE {
+            return Err(RadioError::Radio(format!(
+                "Memory map too small: expected at least {} bytes, got {}",
+                MEMSIZE,
+                mmap.len()
+            )));
+        }
+        self.mmap = Some(mmap.clone());
+        Ok(())
+    }
+
+    fn get_mmap(&self) -> Option<&MemoryMap> {
+        self.mmap.as_ref()
+    }
+
+    /// The image has no model string, so check the band flag table: every
+    /// channel's first flag byte is a band code or the unused marker
+    fn match_model(data: &[u8], _filename: &str) -> bool {
+        data.len() == MEMSIZE
+            && data[FLAGS_OFFSET..Self::flags_offset(VFO_BASE)]
+                .iter()
+                .step_by(FLAGS_SIZE)
+                .all(|band| [BAND_VHF, BAND_220, BAND_UHF, BAND_UNUSED].contains(band))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drivers::test_image::{check_feature_roundtrip, TestImage};
+
+    fn blank_radio() -> TMV71Radio {
+        TestImage::new(MEMSIZE, 0xFF).load(TMV71Radio::new())
+    }
+
+    /// Image with four channels laid out by hand from CHIRP's tmv71 memory format
+    ///
+    /// The bytes are written out here rather than produced by `encode_memory`, so
+    /// decoding is checked against the format and not just against the encoder.
+    fn fixture_radio() -> TMV71Radio {
+        let mut image = TestImage::new(MEMSIZE, 0xFF);
+        let mut put = |number: usize, flags: [u8; 2], record: [u8; 16], name: &[u8; 8]| {
+            image
+                .set_entry(0x0E00, 2, number, &flags)
+                .set_entry(0x1700, 16, number, &record)
+                .set_entry(0x5800, 8, number, name);
+        };
+
+        // 146.520 FM simplex, 5 kHz step; 0x20 in the step byte is an unknown bit
+        put(
+            0,
+            [0x05, 0x00],
+            [
+                0xC0, 0xB7, 0xBB, 0x08, 0x20, 0x00, 0x00, 0x08, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0xA5, 0x5A,
+            ],
+            b"CALL\xFF\xFF\xFF\xFF",
+        );
+        // 442.250 NFM, +5 MHz, tone 100.0, 25 kHz step, locked out
+        put(
+            5,
+            [0x08, 0x01],
+            [
+                0x10, 0x33, 0x5C, 0x1A, 0x06, 0x01, 0x41, 0x0C, 0x0C, 0x00, 0x40, 0x4B, 0x4C, 0x00,
+                0x00, 0x00,
+            ],
+            b"W6CX\xFF\xFF\xFF\xFF",
+        );
+        // 223.500 FM, -1.6 MHz, DTCS 754, 20 kHz step
+        put(
+            999,
+            [0x06, 0x00],
+            [
+                0xE0, 0x56, 0x52, 0x0D, 0x05, 0x00, 0x12, 0x00, 0x00, 0x67, 0x00, 0x6A, 0x18, 0x00,
+                0x00, 0x00,
+            ],
+            b"220RPT\xFF\xFF",
+        );
+        // VHF call channel
+        put(
+            1030,
+            [0x05, 0x00],
+            [
+                0xC0, 0xB7, 0xBB, 0x08, 0x00, 0x00, 0x00, 0x08, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00,
+            ],
+            &[0xFF; 8],
+        );
+
+        image.load(TMV71Radio::new())
+    }
+
+    #[test]
+    fn test_decode_fixture() {
+        let mut radio = fixture_radio();
+        let numbers: Vec<u32> = radio
+            .get_memories()
+            .unwrap()
+            .iter()
+            .map(|m| m.number)
+            .collect();
+        assert_eq!(numbers, vec![0, 5, 999]);
+
+        let call = radio.get_memory(0).unwrap().unwrap();
+        assert_eq!((call.freq, call.name.as_str()), (146_520_000, "CALL"));
+        assert_eq!((call.mode, call.duplex), (Mode::Fm, Duplex::Simplex));
+        assert_eq!((call.tmode, call.tuning_step), (ToneMode::None, 5.0));
+        assert_eq!(call.skip, Skip::None);
+
+        let rpt = radio.get_memory(5).unwrap().unwrap();
+        assert_eq!((rpt.freq, rpt.name.as_str()), (442_250_000, "W6CX"));
+        assert_eq!((rpt.duplex, rpt.offset), (Duplex::Plus, 5_000_000));
+        assert_eq!((rpt.tmode, rpt.rtone), (ToneMode::Tone, 100.0));
+        assert_eq!((rpt.mode, rpt.tuning_step), (Mode::Nfm, 25.0));
+        assert_eq!(rpt.skip, Skip::Skip);
+
+        let dtcs = radio.get_memory(999).unwrap().unwrap();
+        assert_eq!((dtcs.freq, dtcs.name.as_str()), (223_500_000, "220RPT"));
+        assert_eq!((dtcs.duplex, dtcs.offset), (Duplex::Minus, 1_600_000));
+        assert_eq!((dtcs.tmode, dtcs.dtcs), (ToneMode::Dtcs, 754));
+        assert_eq!(dtcs.tuning_step, 20.0);
+
+        let vhf_call = radio.get_memory(1030).unwrap().unwrap();
+        assert_eq!(vhf_call.extd_number, "C VHF");
+        assert_eq!(vhf_call.freq, 146_520_000);
+
+        // Editing a channel keeps the bytes this driver doesn't decode
+        let mut edited = call.clone();
+        edited.freq = 147_000_000;
+        edited.name = "SIMPLX".to_string();
+        radio.set_memory(&edited).unwrap();
+        let data = radio.mmap.as_ref().unwrap().get_packed();
+        assert_eq!(&data[0x1700..0x1705], &[0xC0, 0x0A, 0xC3, 0x08, 0x20]);
+        assert_eq!(&data[0x170E..0x1710], &[0xA5, 0x5A]);
+        assert_eq!(&data[0x5800..0x5808], b"SIMPLX\xFF\xFF");
+    }
+
+    #[test]
+    fn test_memory_roundtrip() {
+        let mut radio = blank_radio();
+        assert!(radio.get_memory(0).unwrap().is_none());
+
+        let mut mem = Memory::new(999);
+        mem.freq = 146_940_000;
+        mem.name = "w6cx".to_string();
+        mem.duplex = Duplex::Minus;
+        mem.offset = 600_000;
+        mem.tmode = ToneMode::Tone;
+        mem.rtone = 100.0;
+        mem.tuning_step = 10.0;
+        mem.mode = Mode::Nfm;
+        mem.skip = Skip::Skip;
+        radio.set_memory(&mem).unwrap();
+
+        let data = radio.mmap.as_ref().unwrap().get_packed();
+        assert_eq!(
+            &data[0x0E00 + 999 * 2..0x0E00 + 1000 * 2],
+            &[BAND_VHF, LOCKOUT]
+        );
+        assert_eq!(
+            &data[0x5800 + 999 * 8..0x5800 + 999 * 8 + 8],
+            b"W6CX\xFF\xFF\xFF\xFF"
+        );
+
+        let read = radio.get_memory(999).unwrap().unwrap();
+        assert_eq!(read.freq, 146_940_000);
+        assert_eq!(read.name, "W6CX");
+        assert_eq!((read.duplex, read.offset), (Duplex::Minus, 600_000));
+        assert_eq!(read.tmode, ToneMode::Tone);
+        assert_eq!(read.rtone, 100.0);
+        assert_eq!(read.tuning_step, 10.0);
+        assert_eq!(read.mode, Mode::Nfm);
+        assert_eq!(read.skip, Skip::Skip);
+
+        let mut uhf = Memory::new(1);
+        uhf.freq = 446_000_000;
+        uhf.tmode = ToneMode::Dtcs;
+        uhf.dtcs = 754;
+        radio.set_memory(&uhf).unwrap();
+        let read = radio.get_memory(1).unwrap().unwrap();
+        assert_eq!(read.tmode, ToneMode::Dtcs);
+        assert_eq!(read.dtcs, 754);
+        assert_eq!(radio.mmap.as_ref().unwrap().get_packed()[0x0E02], BAND_UHF);
+
+        radio.delete_memory(999).unwrap();
+        assert!(radio.get_memory(999).unwrap().is_none());
+        assert_eq!(radio.get_memories().unwrap().len(), 1);
+
+        uhf.name = "Caf\u{e9}".to_string();
+        assert!(radio.set_memory(&uhf).is_err());
+        uhf.name = "W6/X-1".to_string();
+        radio.set_memory(&uhf).unwrap();
+        uhf.duplex = Duplex::Split;
+        assert!(radio.set_memory(&uhf).is_err());
+        assert!(matches!(
+            radio.get_memory(1040),
+            Err(RadioError::InvalidMemory(1040))
+        ));
+    }
+
+    #[test]
+    fn test_feature_roundtrip() {
+        let mut radio = blank_radio();
+        let mut base = Memory::new(10);
+        base.freq = 146_520_000;
+        check_feature_roundtrip(&mut radio, &base);
+        base.freq = 446_000_000;
+        check_feature_roundtrip(&mut radio, &base);
+    }
+
+    #[test]
+    fn test_special_channels() {
+        assert_eq!(special_name(1000).as_deref(), Some("L0"));
+        assert_eq!(special_name(1019).as_deref(), Some("U9"));
+        assert_eq!(special_name(1031).as_deref(), Some("C UHF"));
+        assert_eq!(special_name(1025), None);
+        assert_eq!(special_number("U3"), Some(1007));
+        assert_eq!(special_number("C VHF"), Some(1030));
+        assert_eq!(special_number("L10"), None);
+        assert_eq!(special_name(1032).as_deref(), Some("VFO A 118"));
+        assert_eq!(special_name(1039).as_deref(), Some("VFO B 430"));
+        assert_eq!(special_name(1040), None);
+        assert_eq!(special_number("VFO B 144"), Some(1037));
+
+        let mut radio = blank_radio();
+        let features = radio.get_features();
+        assert_eq!(features.valid_special_chans.len(), 30);
+
+        let mut call = Memory::new(0);
+        call.extd_number = "C UHF".to_string();
+        call.freq = 446_000_000;
+        radio.set_memory(&call).unwrap();
+        let read = radio.get_memory(1031).unwrap().unwrap();
+        assert_eq!(read.extd_number, "C UHF");
+        assert_eq!(read.freq, 446_000_000);
+        assert!(radio.get_memory(0).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_vfos() {
+        // Band B's UHF VFO on 445.000 NFM, tone 100.0; the flags and names
+        // tables stop before the VFOs, so nothing else is set
+        let mut radio = TestImage::new(MEMSIZE, 0xFF)
+            .set_entry(
+                0x1700,
+                16,
+                1039,
+                &[
+                    0x40, 0x29, 0x86, 0x1A, 0x05, 0x01, 0x40, 0x0C, 0x0C, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00,
+                ],
+            )
+            .load(TMV71Radio::new());
+
+        let vfo = radio.get_memory(1039).unwrap().unwrap();
+        assert_eq!(vfo.extd_number, "VFO B 430");
+        assert_eq!((vfo.freq, vfo.mode), (445_000_000, Mode::Nfm));
+        assert_eq!((vfo.tmode, vfo.rtone), (ToneMode::Tone, 100.0));
+        // An erased VFO record reads as empty
+        assert!(radio.get_memory(1032).unwrap().is_none());
+        // VFOs aren't regular memories
+        assert!(radio.get_memories().unwrap().is_empty());
+
+        let mut edited = vfo.clone();
+        edited.number = 0;
+        edited.freq = 446_500_000;
+        edited.name = "IGNORED".to_string();
+        radio.set_memory(&edited).unwrap();
+        let data = radio.mmap.as_ref().unwrap().get_packed();
+        assert_eq!(&data[0x1700 + 1039 * 16..][..4], &[0xA0, 0x0C, 0x9D, 0x1A]);
+        assert_eq!(&data[0x5800..0x5808], &[0xFF; 8]);
+        assert_eq!(radio.get_memory(1039).unwrap().unwrap().freq, 446_500_000);
+
+        edited.empty = true;
+        assert!(matches!(
+            radio.set_memory(&edited),
+            Err(RadioError::Unsupported(_))
+        ));
+    }
+
+    /// Radio on a TCP port that answers `ID` with `answer`; returns everything it received
+    fn fake_radio(answer: &'static [u8]) -> (String, std::thread::JoinHandle<Vec<u8>>) {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port_name = format!("tcp://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (mut conn, _) = listener.accept().unwrap();
+            conn.set_read_timeout(Some(std::time::Duration::from_secs(5)))
+                .unwrap();
+            let mut received = Vec::new();
+            let mut buf = [0u8; 64];
+            while let Ok(n @ 1..) = conn.read(&mut buf) {
+                received.extend_from_slice(&buf[..n]);
+                if received.ends_with(b"ID\r") {
+                    conn.write_all(answer).unwrap();
+                }
+            }
+            received
+        });
+        (port_name, server)
+    }
+
+    #[tokio::test]
+    async fn test_probe() {
+        // A TM-V71A answers "ID TM-V71", which is registered as the TM-V71A
+        let (port_name, server) = fake_radio(b"ID TM-V71\r");
+        let model = TMV71Radio::probe(port_name).await.unwrap();
+        assert_eq!(model.as_deref(), Some("TM-V71A"));
+        assert_eq!(server.join().unwrap(), b"ID\r");
+
+        crate::drivers::init_drivers();
+        assert!(crate::drivers::get_driver("Kenwood", "TM-V71A").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_sync_rejects_other_radio() {
+        let (port_name, server) = fake_radio(b"ID TH-D75\r");
+        let mut port = CONNECTION
+            .with_timeout(std::time::Duration::from_millis(200))
+            .open(&port_name)
+            .unwrap();
+        let result = TMV71Radio::new().sync_in(port.as_mut(), None).await;
+        assert!(matches!(result, Err(RadioError::Unsupported(_))));
+        drop(port);
+
+        // Never asked a TH-D75 to enter programming mode
+        let received = server.join().unwrap();
+        assert!(received.ends_with(b"ID\r"));
+        assert!(!received.windows(2).any(|w| w == b"0M"));
+    }
+
+    #[test]
+    fn test_match_model() {
+        assert!(TMV71Radio::match_model(&vec![0xFF; MEMSIZE], "tmv71.img"));
+        let radio = fixture_radio();
+        assert!(TMV71Radio::match_model(
+            radio.mmap.as_ref().unwrap().get_packed(),
+            "tmv71.img"
+        ));
+        assert!(!TMV71Radio::match_model(&[0xFF; 0x1808], "uv5r.img"));
+        // Same size, but a zeroed image from some other radio
+        assert!(!TMV71Radio::match_model(&vec![0x00; MEMSIZE], "other.img"));
+        assert_eq!(TMV71Radio::new_d710().model(), "TM-D710");
+    }
+}
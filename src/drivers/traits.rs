@@ -1,10 +1,13 @@
 // Radio driver traits
 // Reference: chirp/chirp_common.py lines 1240-1500
 
-use crate::core::{Memory, RadioFeatures};
+use super::bank::BankModel;
+use crate::core::{Memory, RadioFeatures, RadioSettingGroup, SettingError};
 use crate::memmap::MemoryMap;
-use crate::serial::SerialPort;
+use crate::serial::{ConnectionProfile, Transport};
 use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -30,6 +33,9 @@ pub enum RadioError {
     #[error("Unsupported operation: {0}")]
     Unsupported(String),
 
+    #[error("Invalid setting: {0}")]
+    InvalidSetting(String),
+
     #[error("Radio error: {0}")]
     Radio(String),
 }
@@ -40,6 +46,12 @@ impl From<crate::serial::SerialError> for RadioError {
     }
 }
 
+impl From<SettingError> for RadioError {
+    fn from(err: SettingError) -> Self {
+        RadioError::InvalidSetting(err.to_string())
+    }
+}
+
 impl From<crate::bitwise::bcd::BcdError> for RadioError {
     fn from(err: crate::bitwise::bcd::BcdError) -> Self {
         RadioError::InvalidResponse(format!("BCD decode error: {}", err))
@@ -51,6 +63,9 @@ pub type RadioResult<T> = std::result::Result<T, RadioError>;
 /// Progress callback for download/upload operations
 pub type StatusCallback = Box<dyn Fn(usize, usize, &str) + Send + Sync>;
 
+/// Boxed future returned by the object-safe driver traits
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
 /// Base trait for all radio drivers
 pub trait Radio: Send {
     /// Get the radio vendor name
@@ -62,11 +77,22 @@ pub trait Radio: Send {
     /// Get the radio's feature set
     fn get_features(&self) -> RadioFeatures;
 
+    /// Get the serial settings the programming cable needs
+    fn connection_profile(&self) -> ConnectionProfile {
+        ConnectionProfile::default()
+    }
+
     /// Get a printable name for this radio
     fn get_name(&self) -> String {
         format!("{} {}", self.vendor(), self.model())
     }
 
+    /// Get the sub-model detected in the loaded image (e.g. "BF-F8HP"), if the
+    /// driver tells variants apart
+    fn variant(&self) -> Option<&str> {
+        None
+    }
+
     /// Get a memory from the radio
     /// Returns None if the memory is empty
     fn get_memory(&mut self, number: u32) -> RadioResult<Option<Memory>>;
@@ -80,6 +106,32 @@ pub trait Radio: Send {
         self.set_memory(&mem)
     }
 
+    /// Get bank/group names (empty for radios without named banks)
+    fn get_bank_names(&self) -> RadioResult<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    /// Get the radio's bank model, if it has editable banks
+    fn bank_model(&mut self) -> Option<&mut dyn BankModel> {
+        None
+    }
+
+    /// Get the radio's non-memory settings
+    fn get_settings(&self) -> RadioResult<RadioSettingGroup> {
+        Err(RadioError::Unsupported(format!(
+            "{} has no editable settings",
+            self.get_name()
+        )))
+    }
+
+    /// Write back settings returned by `get_settings` (and since edited)
+    fn set_settings(&mut self, _settings: &RadioSettingGroup) -> RadioResult<()> {
+        Err(RadioError::Unsupported(format!(
+            "{} has no editable settings",
+            self.get_name()
+        )))
+    }
+
     /// Get all memories from the radio
     fn get_memories(&mut self) -> RadioResult<Vec<Memory>> {
         let features = self.get_features();
@@ -105,17 +157,17 @@ pub trait CloneModeRadio: Radio {
 
     /// Download the radio's memory map
     /// This initiates a radio-to-PC clone operation
-    async fn sync_in(
+    async fn sync_in<T: Transport + ?Sized>(
         &mut self,
-        port: &mut SerialPort,
+        port: &mut T,
         status_fn: Option<StatusCallback>,
     ) -> RadioResult<MemoryMap>;
 
     /// Upload a memory map to the radio
     /// This initiates a PC-to-radio clone operation
-    async fn sync_out(
+    async fn sync_out<T: Transport + ?Sized>(
         &mut self,
-        port: &mut SerialPort,
+        port: &mut T,
         mmap: &MemoryMap,
         status_fn: Option<StatusCallback>,
     ) -> RadioResult<()>;
@@ -123,12 +175,95 @@ pub trait CloneModeRadio: Radio {
     /// Process the memory map after loading from file
     fn process_mmap(&mut self, mmap: &MemoryMap) -> RadioResult<()>;
 
+    /// Get the currently loaded memory map, if any
+    fn get_mmap(&self) -> Option<&MemoryMap>;
+
     /// Check if this driver matches a given file
     fn match_model(data: &[u8], filename: &str) -> bool
     where
         Self: Sized;
 }
 
+/// Object-safe view of a `CloneModeRadio`
+///
+/// `CloneModeRadio` uses async fns and can't be boxed, so the registry hands
+/// out `Box<dyn DynCloneModeRadio>` instead. Every clone-mode driver gets this
+/// for free through the blanket impl below.
+pub trait DynCloneModeRadio: Radio {
+    /// Size of the radio's memory map in bytes
+    fn memsize(&self) -> usize;
+
+    /// Download the radio's memory map (see `CloneModeRadio::sync_in`)
+    fn download<'a>(
+        &'a mut self,
+        port: &'a mut dyn Transport,
+        status_fn: Option<StatusCallback>,
+    ) -> BoxFuture<'a, RadioResult<MemoryMap>>;
+
+    /// Upload a memory map to the radio (see `CloneModeRadio::sync_out`)
+    fn upload<'a>(
+        &'a mut self,
+        port: &'a mut dyn Transport,
+        mmap: &'a MemoryMap,
+        status_fn: Option<StatusCallback>,
+    ) -> BoxFuture<'a, RadioResult<()>>;
+
+    /// Load a memory map into the driver (see `CloneModeRadio::process_mmap`)
+    fn load_mmap(&mut self, mmap: &MemoryMap) -> RadioResult<()>;
+
+    /// Get the currently loaded memory map, if any
+    fn mmap(&self) -> Option<&MemoryMap>;
+}
+
+impl<T: CloneModeRadio> DynCloneModeRadio for T {
+    fn memsize(&self) -> usize {
+        self.get_memsize()
+    }
+
+    fn download<'a>(
+        &'a mut self,
+        port: &'a mut dyn Transport,
+        status_fn: Option<StatusCallback>,
+    ) -> BoxFuture<'a, RadioResult<MemoryMap>> {
+        Box::pin(self.sync_in(port, status_fn))
+    }
+
+    fn upload<'a>(
+        &'a mut self,
+        port: &'a mut dyn Transport,
+        mmap: &'a MemoryMap,
+        status_fn: Option<StatusCallback>,
+    ) -> BoxFuture<'a, RadioResult<()>> {
+        Box::pin(self.sync_out(port, mmap, status_fn))
+    }
+
+    fn load_mmap(&mut self, mmap: &MemoryMap) -> RadioResult<()> {
+        self.process_mmap(mmap)
+    }
+
+    fn mmap(&self) -> Option<&MemoryMap> {
+        self.get_mmap()
+    }
+}
+
+/// Trait for radios programmed one channel at a time over a live link (e.g. CI-V)
+pub trait CommandModeRadio: Radio {
+    /// Read every memory channel from the radio
+    fn download<'a>(
+        &'a mut self,
+        port: &'a mut dyn Transport,
+        status_fn: Option<StatusCallback>,
+    ) -> BoxFuture<'a, RadioResult<Vec<Memory>>>;
+
+    /// Write the given memories to the radio (empty memories are erased)
+    fn upload<'a>(
+        &'a mut self,
+        port: &'a mut dyn Transport,
+        memories: &'a [Memory],
+        status_fn: Option<StatusCallback>,
+    ) -> BoxFuture<'a, RadioResult<()>>;
+}
+
 /// Status information for progress reporting
 #[derive(Debug, Clone)]
 pub struct Status {
@@ -1,17 +1,28 @@
 // Baofeng UV-5R radio driver
 // Reference: chirp/drivers/uv5r.py
 
-use super::traits::{CloneModeRadio, Radio, RadioError, RadioResult, StatusCallback};
+use super::traits::{BoxFuture, CloneModeRadio, Radio, RadioError, RadioResult, StatusCallback};
 use crate::bitwise::{bcd_to_int, int_to_bcd};
-use crate::core::{Memory, PowerLevel, RadioFeatures, DTCS_CODES, TONES};
+use crate::core::{
+    CrossMode, DtcsPolarity, Duplex, Memory, Mode, PowerLevel, RadioFeatures, RadioSetting,
+    RadioSettingGroup, SettingValue, Skip, ToneMode, DTCS_CODES, TONES,
+};
 use crate::memmap::MemoryMap;
-use crate::serial::SerialPort;
+use crate::serial::{ConnectionProfile, Transport};
 use std::time::Duration;
 use tokio::time::timeout;
 
 /// UV-5R memory map size: 6152 bytes (base) up to 8192 bytes with aux block
 const MEMSIZE: usize = 0x1808; // 6152 bytes
 
+/// Auxiliary block (radio 0x1EC0-0x2000), stored after the main block in images
+pub(crate) const AUX_START: usize = 0x1EC0;
+pub(crate) const AUX_END: usize = 0x2000;
+
+/// Firmware version string in the aux block (file offset)
+const VERSION_OFFSET: usize = 0x1838;
+const VERSION_LENGTH: usize = 14;
+
 /// Number of memory channels
 const NUM_MEMORIES: u32 = 128;
 
@@ -33,28 +44,438 @@ const BLOCK_SIZE: usize = 0x40;
 /// Block size for writing (16 bytes)
 const WRITE_BLOCK_SIZE: usize = 0x10;
 
+/// Clone cables don't use flow control or DTR/RTS signaling
+const CONNECTION: ConnectionProfile = ConnectionProfile::new(9600);
+
 /// Model identification magic bytes (UV-5R variant 291)
-const UV5R_MODEL_291: &[u8] = b"\x50\xBB\xFF\x20\x12\x07\x25";
+pub(crate) const UV5R_MODEL_291: &[u8] = b"\x50\xBB\xFF\x20\x12\x07\x25";
 
 /// Model identification magic bytes (original UV-5R)
-const UV5R_MODEL_ORIG: &[u8] = b"\x50\xBB\xFF\x01\x25\x98\x4D";
+pub(crate) const UV5R_MODEL_ORIG: &[u8] = b"\x50\xBB\xFF\x01\x25\x98\x4D";
+
+/// Model identification magic bytes (newer UV-5R and BF-F8HP firmware)
+pub(crate) const UV5R_MODEL_A58: &[u8] = b"\x50\xBB\xFF\x20\x14\x04\x13";
+
+/// Model identification magic bytes (UV-82 family)
+pub(crate) const UV5R_MODEL_UV82: &[u8] = b"\x50\xBB\xFF\x20\x13\x01\x05";
 
 /// CTCSS tone encoding threshold (values >= this are CTCSS, < this are DTCS)
 const TONE_CTCSS_THRESHOLD: u16 = 0x0258;
 
-/// Power levels: High (4W), Low (1W)
+/// Power levels of dual-power radios: High (4W), Low (1W)
 const POWER_LEVELS: &[(&str, f32)] = &[("High", 4.0), ("Low", 1.0)];
 
+/// Transmit bands of the UV-5R and UV-82
+const BANDS: &[(u64, u64)] = &[
+    (136_000_000, 174_000_000), // VHF: 136-174 MHz
+    (400_000_000, 520_000_000), // UHF: 400-520 MHz
+];
+
+/// Upper band of radios whose ident reports 220 MHz instead of UHF
+const BAND_220: (u64, u64) = (220_000_000, 260_000_000);
+
+/// Ident byte with the radio's upper band (image offset 3), and its 220 MHz value
+const IDENT_UPPER_BAND: usize = 3;
+const UPPER_BAND_220: u8 = 0x02;
+
+/// Model name appended after the aux block, NUL padded, when the firmware
+/// version doesn't identify the radio (CHIRP's `append_model`)
+const MODEL_TRAILER_OFFSET: usize = MEMSIZE + (AUX_END - AUX_START);
+const MODEL_TRAILER_SIZE: usize = 8;
+
+/// A member of the UV-5R family, told apart by its firmware version string
+#[derive(Debug, PartialEq)]
+pub struct Variant {
+    /// Sub-model name, reported in image metadata
+    pub name: &'static str,
+    /// Firmware version substrings that identify this variant
+    basetypes: &'static [&'static str],
+    /// Power levels in `lowpower` order
+    power_levels: &'static [(&'static str, f32)],
+    /// Valid transmit bands (Hz), VHF then UHF
+    bands: &'static [(u64, u64)],
+    /// Handshake magics this variant's firmware answers (CHIRP's `_idents`)
+    magics: &'static [&'static [u8]],
+}
+
+/// The plain UV-5R, also used when the version string is missing or unknown
+///
+/// The UV-5RA and other rebadges run this firmware and report as UV-5R.
+pub const UV5R: Variant = Variant {
+    name: "UV-5R",
+    basetypes: &["BFS", "BFB", "N5R-2", "N5R2", "N5RV", "BTS", "D5R2", "B5R2"],
+    power_levels: POWER_LEVELS,
+    bands: BANDS,
+    magics: &[UV5R_MODEL_291, UV5R_MODEL_ORIG, UV5R_MODEL_A58],
+};
+
+/// Known variants, most specific first (version strings can contain several basetypes)
+/// Reference: BASETYPE_* lists in chirp/drivers/uv5r.py
+pub const VARIANTS: &[Variant] = &[
+    Variant {
+        name: "BF-F8HP",
+        basetypes: &["BFP3V3 F", "N5R-3", "N5R3", "F5R3", "BFT"],
+        power_levels: &[("High", 8.0), ("Med", 4.0), ("Low", 1.0)],
+        bands: &[(130_000_000, 180_000_000), (400_000_000, 520_000_000)],
+        magics: &[UV5R_MODEL_291, UV5R_MODEL_A58],
+    },
+    Variant {
+        name: "UV-82HP",
+        basetypes: &["N82-3", "N823"],
+        power_levels: &[("High", 7.0), ("Med", 4.0), ("Low", 1.0)],
+        bands: &[(136_000_000, 175_000_000), (400_000_000, 521_000_000)],
+        magics: &[UV5R_MODEL_UV82],
+    },
+    Variant {
+        name: "UV-82",
+        basetypes: &["US2S2", "B82S", "BF82", "N82-2", "N822"],
+        power_levels: &[("High", 5.0), ("Low", 1.0)],
+        bands: BANDS,
+        magics: &[UV5R_MODEL_UV82],
+    },
+    UV5R,
+];
+
+impl Variant {
+    /// Identify the variant of an image
+    ///
+    /// The firmware version string decides. Images from radios whose version
+    /// doesn't say carry the model `sync_in` appended after the aux block.
+    pub fn detect(data: &[u8]) -> &'static Variant {
+        Self::from_version(data)
+            .or_else(|| Self::from_trailer(data))
+            .unwrap_or(&UV5R)
+    }
+
+    fn from_version(data: &[u8]) -> Option<&'static Variant> {
+        let version = firmware_version(data)?;
+        let variant = VARIANTS
+            .iter()
+            .find(|variant| variant.basetypes.iter().any(|b| version.contains(b)));
+        if variant.is_none() {
+            tracing::debug!("Unknown UV-5R firmware {:?}", version);
+        }
+        variant
+    }
+
+    fn from_trailer(data: &[u8]) -> Option<&'static Variant> {
+        let raw = data.get(MODEL_TRAILER_OFFSET..MODEL_TRAILER_OFFSET + MODEL_TRAILER_SIZE)?;
+        let name: String = raw
+            .iter()
+            .take_while(|&&b| b != 0)
+            .map(|&b| b as char)
+            .collect();
+        VARIANTS.iter().find(|variant| variant.name == name)
+    }
+
+    /// Variant implied by the magic the radio answered in the handshake
+    ///
+    /// Only a magic answered by a single variant identifies the radio. The
+    /// UV-82 and UV-82HP share theirs, as do the UV-5R and BF-F8HP, and
+    /// their power levels differ, so those aren't guessed.
+    fn from_magic(magic: &[u8]) -> Option<&'static Variant> {
+        let mut candidates = VARIANTS
+            .iter()
+            .filter(|variant| variant.magics.contains(&magic));
+        let variant = candidates.next()?;
+        if candidates.next().is_some() {
+            tracing::debug!("UV-5R magic {:02X?} is shared by several models", magic);
+            return None;
+        }
+        Some(variant)
+    }
+
+    /// Transmit bands of an image's radio, with the upper band from its ident
+    fn bands(&self, data: &[u8]) -> Vec<(u64, u64)> {
+        let has_ident = data.len() >= 8 && data[0] == 0xAA && data[7] == 0xDD;
+        if has_ident && data[IDENT_UPPER_BAND] == UPPER_BAND_220 {
+            vec![self.bands[0], BAND_220]
+        } else {
+            self.bands.to_vec()
+        }
+    }
+
+    /// Power level for a `lowpower` value (out-of-range values clamp to the lowest)
+    fn power_level(&self, lowpower: u8) -> PowerLevel {
+        let (label, watts) =
+            self.power_levels[(lowpower as usize).min(self.power_levels.len() - 1)];
+        PowerLevel::from_watts(label, watts)
+    }
+
+    /// `lowpower` value of the level nearest to `watts`
+    fn lowpower(&self, watts: f32) -> u8 {
+        let mut best = 0;
+        for (i, (_, level)) in self.power_levels.iter().enumerate() {
+            if (level - watts).abs() < (self.power_levels[best].1 - watts).abs() {
+                best = i;
+            }
+        }
+        best as u8
+    }
+}
+
+/// Firmware version from the aux block of an image, if it has one
+pub fn firmware_version(data: &[u8]) -> Option<String> {
+    let raw = data.get(VERSION_OFFSET..VERSION_OFFSET + VERSION_LENGTH)?;
+    let version: String = raw
+        .iter()
+        .take_while(|&&b| b != 0xFF && b != 0x00)
+        .map(|&b| b as char)
+        .collect();
+    let version = version.trim().to_string();
+    (!version.is_empty()).then_some(version)
+}
+
 /// Valid character set for channel names
-const UV5R_CHARSET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789!@#$%^&*()+-=[]:\";'<>?,./";
+const UV5R_CHARSET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ 0123456789!@#$%^&*()+-=[]:\";'<>?,./";
 
 /// Memory skip ranges during upload (these ranges should not be written)
 /// (start, end) pairs
-const UPLOAD_SKIP_RANGES: &[(usize, usize)] = &[
+pub(crate) const UPLOAD_SKIP_RANGES: &[(usize, usize)] = &[
     (0x0CF8, 0x0D08), // Skip range 1
     (0x0DF8, 0x0E08), // Skip range 2
 ];
 
+/// Settings block base address (CHIRP `#seekto 0x0E28`)
+const SETTINGS_BASE: usize = 0x0E28;
+
+/// Settings block size (one byte per field)
+const SETTINGS_SIZE: usize = 0x30;
+
+/// "OFF" followed by levels 1-10 (VOX, repeater tail settings)
+const OFF_1_TO_10: &[&str] = &["OFF", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10"];
+
+/// Backlight colors
+const LED_COLORS: &[&str] = &["Off", "Blue", "Orange", "Purple"];
+
+/// Display modes for the A and B sides
+const DISPLAY_MODES: &[&str] = &["Frequency", "Channel", "Name"];
+
+/// How a setting is stored in its byte of the settings block
+#[derive(Debug, Clone, Copy)]
+enum SettingKind {
+    /// `min + raw * step`, up to `max`
+    Integer { min: i64, max: i64, step: i64 },
+    /// Index into a list of options
+    List(&'static [&'static str]),
+    /// Non-zero is on
+    Boolean,
+}
+
+/// One field of the settings block
+struct SettingDef {
+    name: &'static str,
+    label: &'static str,
+    /// Offset from SETTINGS_BASE
+    offset: usize,
+    /// Bits of the byte used by this field
+    mask: u8,
+    kind: SettingKind,
+}
+
+const fn setting(
+    name: &'static str,
+    label: &'static str,
+    offset: usize,
+    kind: SettingKind,
+) -> SettingDef {
+    SettingDef {
+        name,
+        label,
+        offset,
+        mask: 0xFF,
+        kind,
+    }
+}
+
+const fn integer(min: i64, max: i64, step: i64) -> SettingKind {
+    SettingKind::Integer { min, max, step }
+}
+
+/// Settings shown on the basic page (layout from chirp/drivers/uv5r.py)
+const BASIC_SETTINGS: &[SettingDef] = &[
+    setting("squelch", "Carrier Squelch Level", 0, integer(0, 9, 1)),
+    setting(
+        "save",
+        "Battery Saver",
+        3,
+        SettingKind::List(&["OFF", "1:1", "1:2", "1:3", "1:4"]),
+    ),
+    setting("vox", "VOX Sensitivity", 4, SettingKind::List(OFF_1_TO_10)),
+    setting("abr", "Backlight Timeout", 6, integer(0, 24, 1)),
+    setting("tdr", "Dual Watch", 7, SettingKind::Boolean),
+    setting("beep", "Beep", 8, SettingKind::Boolean),
+    setting(
+        "timeout",
+        "Timeout Timer (seconds)",
+        9,
+        integer(15, 600, 15),
+    ),
+    setting(
+        "voice",
+        "Voice",
+        14,
+        SettingKind::List(&["Off", "English", "Chinese"]),
+    ),
+    setting(
+        "mdfa",
+        "Display Mode (A)",
+        21,
+        SettingKind::List(DISPLAY_MODES),
+    ),
+    setting(
+        "mdfb",
+        "Display Mode (B)",
+        22,
+        SettingKind::List(DISPLAY_MODES),
+    ),
+    setting("autolk", "Automatic Key Lock", 24, SettingKind::Boolean),
+    setting(
+        "wtled",
+        "Standby LED Color",
+        29,
+        SettingKind::List(LED_COLORS),
+    ),
+    setting("rxled", "RX LED Color", 30, SettingKind::List(LED_COLORS)),
+    setting("txled", "TX LED Color", 31, SettingKind::List(LED_COLORS)),
+    setting(
+        "ponmsg",
+        "Power-On Message",
+        38,
+        SettingKind::List(&["Full", "Message"]),
+    ),
+    setting("roger", "Roger Beep", 39, SettingKind::Boolean),
+    setting(
+        "workmode",
+        "Work Mode",
+        44,
+        SettingKind::List(&["Frequency", "Channel"]),
+    ),
+    setting("keylock", "Keypad Lock", 45, SettingKind::Boolean),
+];
+
+/// Settings shown on the advanced page
+const ADVANCED_SETTINGS: &[SettingDef] = &[
+    setting(
+        "dtmfst",
+        "DTMF Sidetone",
+        16,
+        SettingKind::List(&["OFF", "DT-ST", "ANI-ST", "DT+ANI"]),
+    ),
+    SettingDef {
+        mask: 0x03,
+        ..setting(
+            "screv",
+            "Scan Resume",
+            18,
+            SettingKind::List(&["TO", "CO", "SE"]),
+        )
+    },
+    setting(
+        "pttid",
+        "When to send PTT ID",
+        19,
+        SettingKind::List(&["OFF", "BOT", "EOT", "BOTH"]),
+    ),
+    setting("pttlt", "PTT ID Delay", 20, integer(0, 50, 1)),
+    setting("bcl", "Busy Channel Lockout", 23, SettingKind::Boolean),
+    setting(
+        "almod",
+        "Alarm Mode",
+        32,
+        SettingKind::List(&["Site", "Tone", "Code"]),
+    ),
+    setting(
+        "tdrab",
+        "Dual Watch TX Priority",
+        34,
+        SettingKind::List(&["Off", "A", "B"]),
+    ),
+    setting(
+        "ste",
+        "Squelch Tail Eliminate (HT to HT)",
+        35,
+        SettingKind::Boolean,
+    ),
+    setting(
+        "rpste",
+        "Squelch Tail Eliminate (repeater)",
+        36,
+        SettingKind::List(OFF_1_TO_10),
+    ),
+    setting(
+        "rptrl",
+        "STE Repeater Delay",
+        37,
+        SettingKind::List(OFF_1_TO_10),
+    ),
+];
+
+impl SettingDef {
+    /// Build the setting from its raw byte
+    /// Out-of-range values (e.g. an erased 0xFF byte) fall back to the first option
+    fn decode(&self, byte: u8) -> RadioSetting {
+        let raw = (byte & self.mask) as i64;
+        let value = match self.kind {
+            SettingKind::Integer { min, max, step } => {
+                let mut value = min + raw * step;
+                if value > max {
+                    tracing::warn!(
+                        "UV-5R setting {} has invalid value {:#04x}",
+                        self.name,
+                        byte
+                    );
+                    value = min;
+                }
+                SettingValue::integer(value, min, max).with_step(step)
+            }
+            SettingKind::List(options) => {
+                let mut index = raw as usize;
+                if index >= options.len() {
+                    tracing::warn!(
+                        "UV-5R setting {} has invalid value {:#04x}",
+                        self.name,
+                        byte
+                    );
+                    index = 0;
+                }
+                SettingValue::list(options, index)
+            }
+            SettingKind::Boolean => SettingValue::boolean(raw != 0),
+        };
+        RadioSetting::new(self.name, self.label, value)
+    }
+
+    /// Merge a setting's value back into its raw byte
+    ///
+    /// The value is checked against this definition, not the bounds it carries.
+    fn encode(&self, setting: &RadioSetting, byte: u8) -> RadioResult<u8> {
+        let wrong_type =
+            || RadioError::InvalidSetting(format!("{} has the wrong value type", self.name));
+        let out_of_range = |value: String| {
+            RadioError::InvalidSetting(format!("Invalid value for {}: {}", self.name, value))
+        };
+        let raw = match self.kind {
+            SettingKind::Integer { min, max, step } => {
+                let value = setting.value.as_integer().ok_or_else(wrong_type)?;
+                if value < min || value > max || (value - min) % step != 0 {
+                    return Err(out_of_range(value.to_string()));
+                }
+                ((value - min) / step) as u8
+            }
+            SettingKind::List(options) => {
+                let index = setting.value.as_index().ok_or_else(wrong_type)?;
+                if index >= options.len() {
+                    return Err(out_of_range(format!("option {}", index)));
+                }
+                index as u8
+            }
+            SettingKind::Boolean => setting.value.as_bool().ok_or_else(wrong_type)? as u8,
+        };
+        Ok((byte & !self.mask) | (raw & self.mask))
+    }
+}
+
 /// Raw memory structure (16 bytes per channel)
 ///
 /// Byte layout:
@@ -74,7 +495,7 @@ struct RawMemory {
     txtone: u16,  // Same as rxtone
     isuhf: bool,  // Band indicator (VHF=false, UHF=true)
     scode: u8,    // PTT ID code (0-15)
-    lowpower: u8, // Power level (0=High, 1=Low, 2=Mid on tri-power variants, stored in bits 0-1 of byte 14)
+    lowpower: u8, // Power level index (High, Low or High, Mid, Low on tri-power variants, stored in bits 0-1 of byte 14)
     wide: bool,   // Bandwidth: true=FM (25kHz), false=NFM (12.5kHz)
     bcl: bool,    // Busy channel lockout
     scan: bool,   // Scan enable
@@ -177,7 +598,7 @@ impl RawMemory {
 ///   → 14652000 (divide by 10)
 ///   → BCD encoding: 0x01 0x46 0x52 0x00 (little-endian)
 /// ```
-
+///
 /// Convert BCD-encoded u32 to frequency in Hz
 fn bcd_to_freq(bcd: u32) -> u64 {
     // Convert BCD to integer (treats u32 as 4-byte BCD array)
@@ -291,6 +712,7 @@ pub struct UV5RRadio {
     pub mmap: Option<MemoryMap>,
     vendor: String,
     model: String,
+    variant: &'static Variant,
 }
 
 impl UV5RRadio {
@@ -300,9 +722,18 @@ impl UV5RRadio {
             mmap: None,
             vendor: "Baofeng".to_string(),
             model: "UV-5R".to_string(),
+            variant: &UV5R,
         }
     }
 
+    fn bands(&self) -> Vec<(u64, u64)> {
+        self.mmap
+            .as_ref()
+            .map_or(self.variant.bands.to_vec(), |mmap| {
+                self.variant.bands(mmap.get_packed())
+            })
+    }
+
     /// Calculate memory offset for a given channel number
     fn memory_offset(&self, number: u32) -> usize {
         MEMORY_BASE + (number as usize * MEMORY_SIZE)
@@ -397,6 +828,19 @@ impl UV5RRadio {
         })?;
         Ok(())
     }
+
+    /// Read the settings block from memory map
+    fn read_settings_block(&self) -> RadioResult<Vec<u8>> {
+        let mmap = self
+            .mmap
+            .as_ref()
+            .ok_or(RadioError::Radio("No memory map loaded".to_string()))?;
+
+        let data = mmap
+            .get(SETTINGS_BASE, Some(SETTINGS_SIZE))
+            .map_err(|e| RadioError::Radio(format!("Failed to read settings block: {}", e)))?;
+        Ok(data.to_vec())
+    }
 }
 
 impl Default for UV5RRadio {
@@ -406,7 +850,12 @@ impl Default for UV5RRadio {
 }
 
 /// Decode raw memory structure to Memory
-fn decode_memory(number: u32, raw: &RawMemory, name: &str) -> RadioResult<Memory> {
+fn decode_memory(
+    number: u32,
+    raw: &RawMemory,
+    name: &str,
+    variant: &Variant,
+) -> RadioResult<Memory> {
     let mut mem = Memory::new(number);
 
     // Decode frequency
@@ -415,14 +864,14 @@ fn decode_memory(number: u32, raw: &RawMemory, name: &str) -> RadioResult<Memory
     // Decode duplex and offset
     if raw.txfreq == 0xFFFFFFFF {
         // TX inhibited
-        mem.duplex = "off".to_string();
+        mem.duplex = Duplex::Off;
         mem.offset = 0;
     } else {
         let tx_freq = bcd_to_freq(raw.txfreq);
 
         if tx_freq == mem.freq {
             // Simplex
-            mem.duplex = String::new();
+            mem.duplex = Duplex::Simplex;
             mem.offset = 0;
         } else {
             // Calculate offset
@@ -431,49 +880,31 @@ fn decode_memory(number: u32, raw: &RawMemory, name: &str) -> RadioResult<Memory
             // Check if this is split (large frequency difference)
             if diff > 70_000_000 {
                 // Split operation (TX freq stored directly)
-                mem.duplex = "split".to_string();
+                mem.duplex = Duplex::Split;
                 mem.offset = tx_freq;
             } else if tx_freq > mem.freq {
                 // Positive offset
-                mem.duplex = "+".to_string();
+                mem.duplex = Duplex::Plus;
                 mem.offset = diff;
             } else {
                 // Negative offset
-                mem.duplex = "-".to_string();
+                mem.duplex = Duplex::Minus;
                 mem.offset = diff;
             }
         }
     }
 
     // Mode: FM or NFM based on wide flag
-    mem.mode = if raw.wide {
-        "FM".to_string()
-    } else {
-        "NFM".to_string()
-    };
+    mem.mode = if raw.wide { Mode::Fm } else { Mode::Nfm };
 
-    // Power level (lowpower: 0=High, 1=Low, 2=Mid on tri-power variants)
-    // Standard UV-5R uses: 0=High (4W), 1=Low (1W)
-    let power_index = match raw.lowpower {
-        0 => 0, // High
-        1 => 1, // Low
-        2 => 1, // Mid (treat as Low on standard UV-5R, only exists on tri-power variants)
-        _ => 0, // Invalid, default to High
-    };
-    mem.power = Some(PowerLevel::from_watts(
-        POWER_LEVELS[power_index].0,
-        POWER_LEVELS[power_index].1,
-    ));
+    // Power level (lowpower indexes the variant's levels)
+    mem.power = Some(variant.power_level(raw.lowpower));
 
     // Decode tone modes
     decode_tone_mode(raw, &mut mem)?;
 
     // Skip flag (scan enabled = don't skip, scan disabled = skip)
-    mem.skip = if raw.scan {
-        String::new()
-    } else {
-        "S".to_string()
-    };
+    mem.skip = if raw.scan { Skip::None } else { Skip::Skip };
 
     // Name
     mem.name = name.to_string();
@@ -507,46 +938,43 @@ fn decode_tone_mode(raw: &RawMemory, mem: &mut Memory) -> RadioResult<()> {
     match (&tx_tone_type, &rx_tone_type) {
         (ToneType::None, ToneType::None) => {
             // No tones
-            mem.tmode = String::new();
+            mem.tmode = ToneMode::None;
         }
         (ToneType::Ctcss(freq), ToneType::None) => {
             // TX CTCSS only (Tone mode)
-            mem.tmode = "Tone".to_string();
+            mem.tmode = ToneMode::Tone;
             mem.rtone = *freq;
         }
         (ToneType::Ctcss(freq), ToneType::Ctcss(_)) => {
             // TX and RX CTCSS (TSQL mode)
-            mem.tmode = "TSQL".to_string();
+            mem.tmode = ToneMode::Tsql;
             mem.rtone = *freq;
             mem.ctone = *freq; // In TSQL, both use same tone
         }
         (ToneType::Dtcs(code, pol), ToneType::None)
         | (ToneType::Dtcs(code, pol), ToneType::Dtcs(_, _)) => {
             // DTCS mode
-            mem.tmode = "DTCS".to_string();
+            mem.tmode = ToneMode::Dtcs;
             mem.dtcs = *code;
             mem.rx_dtcs = *code;
 
             // Set polarity
-            mem.dtcs_polarity = match pol {
-                'N' => "NN".to_string(),
-                'R' => "RN".to_string(), // TX reversed
-                _ => "NN".to_string(),
-            };
+            // Only TX polarity is stored ('R' = TX reversed)
+            mem.dtcs_polarity = DtcsPolarity::from_chars(*pol, 'N');
         }
         (ToneType::None, ToneType::Ctcss(freq)) => {
             // RX CTCSS only (TSQL-R mode)
-            mem.tmode = "TSQL-R".to_string();
+            mem.tmode = ToneMode::TsqlR;
             mem.ctone = *freq;
         }
         (ToneType::None, ToneType::Dtcs(code, _)) => {
             // RX DTCS only (DTCS-R mode)
-            mem.tmode = "DTCS-R".to_string();
+            mem.tmode = ToneMode::DtcsR;
             mem.rx_dtcs = *code;
         }
         (ToneType::Ctcss(_), ToneType::Dtcs(_, _)) | (ToneType::Dtcs(_, _), ToneType::Ctcss(_)) => {
             // Cross mode
-            mem.tmode = "Cross".to_string();
+            mem.tmode = ToneMode::Cross;
 
             if matches!(tx_tone_type, ToneType::Ctcss(_)) {
                 mem.rtone = if let ToneType::Ctcss(f) = tx_tone_type {
@@ -554,7 +982,7 @@ fn decode_tone_mode(raw: &RawMemory, mem: &mut Memory) -> RadioResult<()> {
                 } else {
                     88.5
                 };
-                mem.cross_mode = "Tone->DTCS".to_string();
+                mem.cross_mode = CrossMode::ToneToDtcs;
                 mem.rx_dtcs = if let ToneType::Dtcs(c, _) = rx_tone_type {
                     c
                 } else {
@@ -566,7 +994,7 @@ fn decode_tone_mode(raw: &RawMemory, mem: &mut Memory) -> RadioResult<()> {
                 } else {
                     23
                 };
-                mem.cross_mode = "DTCS->Tone".to_string();
+                mem.cross_mode = CrossMode::DtcsToTone;
                 mem.ctone = if let ToneType::Ctcss(f) = rx_tone_type {
                     f
                 } else {
@@ -588,14 +1016,20 @@ enum ToneType {
 }
 
 /// Encode Memory structure to RawMemory
-fn encode_memory(mem: &Memory) -> RadioResult<RawMemory> {
+fn encode_memory(mem: &Memory, variant: &Variant, bands: &[(u64, u64)]) -> RadioResult<RawMemory> {
     // Validate frequency is in valid bands
-    if !((136_000_000..=174_000_000).contains(&mem.freq)
-        || (400_000_000..=520_000_000).contains(&mem.freq))
+    if !bands
+        .iter()
+        .any(|(low, high)| (*low..=*high).contains(&mem.freq))
     {
+        let bands: Vec<String> = bands
+            .iter()
+            .map(|(low, high)| format!("{}-{} MHz", low / 1_000_000, high / 1_000_000))
+            .collect();
         return Err(RadioError::Radio(format!(
-            "Frequency {} Hz is outside valid bands (136-174 MHz or 400-520 MHz)",
-            mem.freq
+            "Frequency {} Hz is outside valid bands ({})",
+            mem.freq,
+            bands.join(" or ")
         )));
     }
 
@@ -603,18 +1037,12 @@ fn encode_memory(mem: &Memory) -> RadioResult<RawMemory> {
     let rxfreq = freq_to_bcd(mem.freq);
 
     // Encode TX frequency based on duplex
-    let txfreq = match mem.duplex.as_str() {
-        "off" => 0xFFFFFFFF, // TX inhibited
-        "" => rxfreq,        // Simplex
-        "+" => freq_to_bcd(mem.freq + mem.offset),
-        "-" => freq_to_bcd(mem.freq.saturating_sub(mem.offset)),
-        "split" => freq_to_bcd(mem.offset), // In split mode, offset is the TX freq
-        _ => {
-            return Err(RadioError::Radio(format!(
-                "Invalid duplex mode: {}",
-                mem.duplex
-            )))
-        }
+    let txfreq = match mem.duplex {
+        Duplex::Off => 0xFFFFFFFF, // TX inhibited
+        Duplex::Simplex => rxfreq,
+        Duplex::Plus => freq_to_bcd(mem.freq + mem.offset),
+        Duplex::Minus => freq_to_bcd(mem.freq.saturating_sub(mem.offset)),
+        Duplex::Split => freq_to_bcd(mem.offset), // In split mode, offset is the TX freq
     };
 
     // Encode tone modes
@@ -624,24 +1052,16 @@ fn encode_memory(mem: &Memory) -> RadioResult<RawMemory> {
     let isuhf = mem.freq >= 300_000_000;
 
     // Power level
-    let lowpower = if let Some(ref power) = mem.power {
-        // Match power level (High=0, Low=1)
-        // Note: Some UV-5R variants support 3 levels (High=0, Mid=1, Low=2)
-        // but standard UV-5R only has 2 levels (High=0, Low=1)
-        if power.watts() < 2.5 {
-            1 // Low (1W)
-        } else {
-            0 // High (4W)
-        }
-    } else {
-        0 // Default to high
+    let lowpower = match mem.power {
+        Some(ref power) => variant.lowpower(power.watts()),
+        None => 0, // Default to high
     };
 
     // Mode (wide = FM, narrow = NFM)
-    let wide = mem.mode == "FM";
+    let wide = mem.mode == Mode::Fm;
 
     // Scan flag (empty skip = scan enabled)
-    let scan = mem.skip.is_empty();
+    let scan = mem.skip == Skip::None;
 
     Ok(RawMemory {
         rxfreq,
@@ -663,75 +1083,69 @@ fn encode_tone_mode(mem: &Memory) -> RadioResult<(u16, u16)> {
     let txtone: u16;
     let rxtone: u16;
 
-    match mem.tmode.as_str() {
-        "" => {
+    let tx_polarity = mem.dtcs_polarity.tx();
+    let rx_polarity = mem.dtcs_polarity.rx();
+
+    match mem.tmode {
+        ToneMode::None => {
             // No tones
             txtone = 0;
             rxtone = 0;
         }
-        "Tone" => {
+        ToneMode::Tone => {
             // TX CTCSS only
             txtone = tone_to_u16(mem.rtone);
             rxtone = 0;
         }
-        "TSQL" => {
+        ToneMode::Tsql => {
             // TX and RX CTCSS (same tone)
             // Note: For TSQL, both use ctone (not rtone)
             txtone = tone_to_u16(mem.ctone);
             rxtone = tone_to_u16(mem.ctone);
         }
-        "TSQL-R" => {
+        ToneMode::TsqlR => {
             // RX CTCSS only
             txtone = 0;
             rxtone = tone_to_u16(mem.ctone);
         }
-        "DTCS" => {
+        ToneMode::Dtcs => {
             // DTCS mode
-            // Parse polarity (e.g., "NN", "NR", "RN", "RR")
-            let tx_polarity = mem.dtcs_polarity.chars().next().unwrap_or('N');
-            let rx_polarity = mem.dtcs_polarity.chars().nth(1).unwrap_or('N');
-
             txtone = dtcs_to_u16(mem.dtcs, tx_polarity)?;
             rxtone = dtcs_to_u16(mem.rx_dtcs, rx_polarity)?;
         }
-        "DTCS-R" => {
+        ToneMode::DtcsR => {
             // RX DTCS only
-            let rx_polarity = mem.dtcs_polarity.chars().nth(1).unwrap_or('N');
             txtone = 0;
             rxtone = dtcs_to_u16(mem.rx_dtcs, rx_polarity)?;
         }
-        "Cross" => {
+        ToneMode::Cross => {
             // Cross mode
-            match mem.cross_mode.as_str() {
-                "Tone->DTCS" => {
+            match mem.cross_mode {
+                CrossMode::ToneToDtcs => {
                     txtone = tone_to_u16(mem.rtone);
-                    let rx_polarity = mem.dtcs_polarity.chars().nth(1).unwrap_or('N');
                     rxtone = dtcs_to_u16(mem.rx_dtcs, rx_polarity)?;
                 }
-                "DTCS->Tone" => {
-                    let tx_polarity = mem.dtcs_polarity.chars().next().unwrap_or('N');
+                CrossMode::DtcsToTone => {
                     txtone = dtcs_to_u16(mem.dtcs, tx_polarity)?;
                     rxtone = tone_to_u16(mem.ctone);
                 }
-                "Tone->Tone" => {
+                CrossMode::ToneToTone => {
                     txtone = tone_to_u16(mem.rtone);
                     rxtone = tone_to_u16(mem.ctone);
                 }
-                "DTCS->" => {
-                    let tx_polarity = mem.dtcs_polarity.chars().next().unwrap_or('N');
+                CrossMode::DtcsToNone => {
                     txtone = dtcs_to_u16(mem.dtcs, tx_polarity)?;
                     rxtone = 0;
                 }
-                "->DTCS" => {
+                CrossMode::NoneToDtcs => {
                     txtone = 0;
-                    let rx_polarity = mem.dtcs_polarity.chars().nth(1).unwrap_or('N');
                     rxtone = dtcs_to_u16(mem.rx_dtcs, rx_polarity)?;
                 }
-                "->Tone" => {
+                CrossMode::NoneToTone => {
                     txtone = 0;
                     rxtone = tone_to_u16(mem.ctone);
                 }
-                _ => {
+                CrossMode::DtcsToDtcs | CrossMode::ToneToNone => {
                     return Err(RadioError::Radio(format!(
                         "Unsupported cross mode: {}",
                         mem.cross_mode
@@ -739,12 +1153,6 @@ fn encode_tone_mode(mem: &Memory) -> RadioResult<(u16, u16)> {
                 }
             }
         }
-        _ => {
-            return Err(RadioError::Radio(format!(
-                "Unsupported tone mode: {}",
-                mem.tmode
-            )))
-        }
     }
 
     Ok((txtone, rxtone))
@@ -760,32 +1168,27 @@ impl Radio for UV5RRadio {
         &self.model
     }
 
+    fn connection_profile(&self) -> ConnectionProfile {
+        CONNECTION
+    }
+
     fn get_features(&self) -> RadioFeatures {
         RadioFeatures {
             memory_bounds: (0, NUM_MEMORIES - 1),
-            valid_modes: vec!["FM".to_string(), "NFM".to_string()],
+            valid_modes: vec![Mode::Fm, Mode::Nfm],
             valid_tmodes: vec![
-                String::new(),
-                "Tone".to_string(),
-                "TSQL".to_string(),
-                "DTCS".to_string(),
-                "Cross".to_string(),
-            ],
-            valid_duplexes: vec![
-                String::new(),
-                "+".to_string(),
-                "-".to_string(),
-                "split".to_string(),
-                "off".to_string(),
+                ToneMode::None,
+                ToneMode::Tone,
+                ToneMode::Tsql,
+                ToneMode::Dtcs,
+                ToneMode::Cross,
             ],
+            valid_duplexes: Duplex::ALL.to_vec(),
             valid_tuning_steps: vec![2.5, 5.0, 6.25, 10.0, 12.5, 20.0, 25.0, 50.0],
             valid_tones: TONES.to_vec(),
             valid_dtcs_codes: DTCS_CODES.to_vec(),
             valid_name_length: 7,
-            valid_bands: vec![
-                (136_000_000, 174_000_000), // VHF: 136-174 MHz
-                (400_000_000, 520_000_000), // UHF: 400-520 MHz
-            ],
+            valid_bands: self.bands(),
             has_bank: false,
             has_dtcs: true,
             has_ctone: true,
@@ -796,8 +1199,11 @@ impl Radio for UV5RRadio {
             has_name: true,
             has_comment: false,
             has_variable_power: true,
+            has_settings: true,
             valid_characters: UV5R_CHARSET.to_string(),
-            valid_power_levels: POWER_LEVELS
+            valid_power_levels: self
+                .variant
+                .power_levels
                 .iter()
                 .map(|(label, watts)| PowerLevel::from_watts(*label, *watts))
                 .collect(),
@@ -818,7 +1224,7 @@ impl Radio for UV5RRadio {
         }
 
         let name = self.read_name(number)?;
-        let mem = decode_memory(number, &raw, &name)?;
+        let mem = decode_memory(number, &raw, &name, self.variant)?;
 
         // Also check if frequency is 0 (invalid BCD decode) - treat as empty
         if mem.freq == 0 {
@@ -837,7 +1243,7 @@ impl Radio for UV5RRadio {
             return Err(RadioError::InvalidMemory(memory.number));
         }
 
-        let raw = encode_memory(memory)?;
+        let raw = encode_memory(memory, self.variant, &self.bands())?;
         self.write_raw_memory(memory.number, &raw)?;
         self.write_name(memory.number, &memory.name)?;
         Ok(())
@@ -886,6 +1292,49 @@ impl Radio for UV5RRadio {
         }
         Ok(memories)
     }
+
+    fn variant(&self) -> Option<&str> {
+        Some(self.variant.name)
+    }
+
+    fn get_settings(&self) -> RadioResult<RadioSettingGroup> {
+        let block = self.read_settings_block()?;
+        let page = |name: &str, label: &str, defs: &[SettingDef]| {
+            let mut group = RadioSettingGroup::new(name, label);
+            for def in defs {
+                group.add(def.decode(block[def.offset]));
+            }
+            group
+        };
+
+        let mut settings = RadioSettingGroup::new("settings", "Settings");
+        settings.add_group(page("basic", "Basic Settings", BASIC_SETTINGS));
+        settings.add_group(page("advanced", "Advanced Settings", ADVANCED_SETTINGS));
+        Ok(settings)
+    }
+
+    fn set_settings(&mut self, settings: &RadioSettingGroup) -> RadioResult<()> {
+        // Encode into a copy so a bad value leaves the image untouched
+        let mut block = self.read_settings_block()?;
+        for setting in settings.iter() {
+            let def = BASIC_SETTINGS
+                .iter()
+                .chain(ADVANCED_SETTINGS)
+                .find(|def| def.name == setting.name)
+                .ok_or_else(|| {
+                    RadioError::InvalidSetting(format!("Unknown setting: {}", setting.name))
+                })?;
+            block[def.offset] = def.encode(setting, block[def.offset])?;
+        }
+
+        let mmap = self
+            .mmap
+            .as_mut()
+            .ok_or(RadioError::Radio("No memory map loaded".to_string()))?;
+        mmap.set_bytes(SETTINGS_BASE, &block)
+            .map_err(|e| RadioError::Radio(format!("Failed to write settings block: {}", e)))?;
+        Ok(())
+    }
 }
 
 /// Implementation of CloneModeRadio trait for UV-5R
@@ -894,13 +1343,13 @@ impl CloneModeRadio for UV5RRadio {
         MEMSIZE
     }
 
-    async fn sync_in(
+    async fn sync_in<T: Transport + ?Sized>(
         &mut self,
-        port: &mut SerialPort,
+        port: &mut T,
         status_fn: Option<StatusCallback>,
     ) -> RadioResult<MemoryMap> {
         // Perform handshake to establish communication
-        let ident = self.do_handshake(port).await?;
+        let (magic, ident) = self.do_handshake(port).await?;
 
         // CHIRP file format: ident bytes go at the BEGINNING as an 8-byte header
         // The radio sends them during handshake, and CHIRP places them at 0x0000-0x0007
@@ -918,9 +1367,12 @@ impl CloneModeRadio for UV5RRadio {
             }
         }
 
+        // Main block plus the aux block
+        let total = MEMSIZE + (AUX_END - AUX_START);
+
         // Progress callback for header
         if let Some(ref cb) = status_fn {
-            cb(0, 0x1808, "Downloading from radio");
+            cb(0, total, "Downloading from radio");
         }
 
         // Read ALL memory from 0x0000 to 0x1800
@@ -935,7 +1387,7 @@ impl CloneModeRadio for UV5RRadio {
 
             // Progress callback
             if let Some(ref cb) = status_fn {
-                cb(addr + 8, end_addr + 8, "Downloading from radio");
+                cb(addr + 8, total, "Downloading from radio");
             }
         }
 
@@ -944,6 +1396,40 @@ impl CloneModeRadio for UV5RRadio {
             data.push(0xFF);
         }
 
+        // Aux block: power-on message and firmware version (not uploaded)
+        for addr in (AUX_START..AUX_END).step_by(BLOCK_SIZE) {
+            let block = self.read_block(port, addr as u16, BLOCK_SIZE as u8).await?;
+            data.extend_from_slice(&block);
+
+            if let Some(ref cb) = status_fn {
+                cb(data.len(), total, "Downloading from radio");
+            }
+        }
+        self.variant = match Variant::from_version(&data) {
+            Some(variant) => variant,
+            None => match Variant::from_magic(magic) {
+                Some(variant) => {
+                    // Keep what the handshake told us where a later load will find it
+                    let mut model = variant.name.as_bytes().to_vec();
+                    model.resize(MODEL_TRAILER_SIZE, 0);
+                    data.extend_from_slice(&model);
+                    variant
+                }
+                None => {
+                    tracing::warn!(
+                        "UV-5R model not identified by firmware or handshake, using {}",
+                        UV5R.name
+                    );
+                    &UV5R
+                }
+            },
+        };
+        tracing::info!(
+            "UV-5R firmware {:?}: {}",
+            firmware_version(&data),
+            self.variant.name
+        );
+
         // DEBUG: Save downloaded data to file for inspection
         if let Err(e) = std::fs::write("/tmp/uv5r_download_raw.bin", &data) {
             tracing::warn!("Failed to save debug file: {}", e);
@@ -954,9 +1440,9 @@ impl CloneModeRadio for UV5RRadio {
         Ok(MemoryMap::new(data))
     }
 
-    async fn sync_out(
+    async fn sync_out<T: Transport + ?Sized>(
         &mut self,
-        port: &mut SerialPort,
+        port: &mut T,
         mmap: &MemoryMap,
         status_fn: Option<StatusCallback>,
     ) -> RadioResult<()> {
@@ -1017,18 +1503,28 @@ impl CloneModeRadio for UV5RRadio {
             )));
         }
 
+        self.variant = Variant::detect(mmap.get_packed());
         self.mmap = Some(mmap.clone());
         Ok(())
     }
 
+    fn get_mmap(&self) -> Option<&MemoryMap> {
+        self.mmap.as_ref()
+    }
+
     fn match_model(data: &[u8], filename: &str) -> bool {
-        // Check file extension
-        if !filename.ends_with(".dat") && !filename.ends_with(".uv5") {
+        // Check size
+        if data.len() < 0x1808 || data.len() > 0x2000 {
             return false;
         }
 
-        // Check size
-        if data.len() < 0x1808 || data.len() > 0x2000 {
+        // Images saved by sync_in start with the 8-byte radio ident (AA .. DD)
+        if data[0] == 0xAA && data[7] == 0xDD {
+            return true;
+        }
+
+        // Otherwise require a known extension plus a plausible first channel
+        if !filename.ends_with(".dat") && !filename.ends_with(".uv5") {
             return false;
         }
 
@@ -1060,21 +1556,31 @@ impl UV5RRadio {
     /// Perform handshake with radio
     ///
     /// The handshake sequence is:
-    /// 1. Send magic bytes (try both variants)
+    /// 1. Send magic bytes (try each family's magic)
     /// 2. Wait for ACK (0x06)
     /// 3. Send 0x02
     /// 4. Read ident (8 bytes ending with 0xDD)
     /// 5. Send ACK (0x06)
     /// 6. Wait for ACK (0x06)
-    async fn do_handshake(&self, port: &mut SerialPort) -> RadioResult<Vec<u8>> {
-        // Try both magic sequences
-        let magics = [UV5R_MODEL_291, UV5R_MODEL_ORIG];
+    ///
+    /// Returns the magic the radio answered and its ident.
+    async fn do_handshake<T: Transport + ?Sized>(
+        &self,
+        port: &mut T,
+    ) -> RadioResult<(&'static [u8], Vec<u8>)> {
+        // Try each magic sequence
+        let magics = [
+            UV5R_MODEL_291,
+            UV5R_MODEL_ORIG,
+            UV5R_MODEL_A58,
+            UV5R_MODEL_UV82,
+        ];
 
-        for magic in magics.iter() {
+        for magic in magics {
             tracing::debug!("Trying magic sequence: {:02X?}", magic);
 
             // Send magic byte-by-byte with delay
-            for &byte in *magic {
+            for &byte in magic {
                 port.write(&[byte]).await?;
                 tokio::time::sleep(Duration::from_millis(10)).await;
             }
@@ -1116,15 +1622,8 @@ impl UV5RRadio {
                         match timeout(Duration::from_secs(1), port.read_exact(&mut ack2_buf)).await
                         {
                             Ok(Ok(())) if ack2_buf[0] == 0x06 => {
-                                tracing::info!(
-                                    "Handshake successful with {} magic",
-                                    if magic == &UV5R_MODEL_291 {
-                                        "291"
-                                    } else {
-                                        "ORIG"
-                                    }
-                                );
-                                return Ok(ident);
+                                tracing::info!("Handshake successful with magic {:02X?}", magic);
+                                return Ok((magic, ident));
                             }
                             _ => {}
                         }
@@ -1140,13 +1639,40 @@ impl UV5RRadio {
         Err(RadioError::NoResponse)
     }
 
+    /// Identify a UV-5R on a serial port by running the clone handshake
+    ///
+    /// This puts the radio into clone mode, so it must run after read-only probes.
+    /// The protocol has no command to leave clone mode: a radio identified this
+    /// way has to be power-cycled before it can be downloaded or uploaded.
+    /// Returns the model, or None if no magic sequence was acknowledged.
+    pub fn probe(port_name: String) -> BoxFuture<'static, RadioResult<Option<String>>> {
+        Box::pin(async move {
+            let mut port = CONNECTION
+                .with_timeout(Duration::from_millis(500))
+                .open(&port_name)?;
+
+            match Self::new().do_handshake(&mut port).await {
+                Ok((_, ident)) => {
+                    tracing::debug!("probe: UV-5R ident {:02X?}", ident);
+                    Ok(Some("UV-5R".to_string()))
+                }
+                Err(_) => Ok(None),
+            }
+        })
+    }
+
     /// Read a block of data from the radio
     ///
     /// Protocol:
     /// - Send: "S" + addr (u16 BE) + size (u8)
     /// - Receive: "X" + addr (u16 BE) + size (u8) + data
     /// - Send: ACK (0x06)
-    async fn read_block(&self, port: &mut SerialPort, addr: u16, size: u8) -> RadioResult<Vec<u8>> {
+    async fn read_block<T: Transport + ?Sized>(
+        &self,
+        port: &mut T,
+        addr: u16,
+        size: u8,
+    ) -> RadioResult<Vec<u8>> {
         // Send read command
         let cmd = [b'S', (addr >> 8) as u8, (addr & 0xFF) as u8, size];
         port.write(&cmd).await?;
@@ -1210,7 +1736,12 @@ impl UV5RRadio {
     /// Protocol:
     /// - Send: "X" + addr (u16 BE) + size (u8) + data
     /// - Receive: ACK (0x06)
-    async fn write_block(&self, port: &mut SerialPort, addr: u16, data: &[u8]) -> RadioResult<()> {
+    async fn write_block<T: Transport + ?Sized>(
+        &self,
+        port: &mut T,
+        addr: u16,
+        data: &[u8],
+    ) -> RadioResult<()> {
         // Send write command
         let mut cmd = vec![
             b'X',
@@ -1249,6 +1780,7 @@ impl UV5RRadio {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::serial::mock::MockSerialPort;
 
     #[test]
     fn test_bcd_encoding() {
@@ -1345,15 +1877,15 @@ mod tests {
         let mut mem = Memory::new(0);
         mem.freq = 146_520_000;
         mem.offset = 0;
-        mem.duplex = String::new();
-        mem.mode = "FM".to_string();
+        mem.duplex = Duplex::Simplex;
+        mem.mode = Mode::Fm;
         mem.rtone = 88.5;
-        mem.tmode = "Tone".to_string();
+        mem.tmode = ToneMode::Tone;
         mem.power = Some(PowerLevel::from_watts("High", 4.0));
         mem.name = "TEST".to_string();
 
-        let raw = encode_memory(&mem).unwrap();
-        let decoded = decode_memory(0, &raw, "TEST").unwrap();
+        let raw = encode_memory(&mem, &UV5R, BANDS).unwrap();
+        let decoded = decode_memory(0, &raw, "TEST", &UV5R).unwrap();
 
         assert_eq!(decoded.freq, mem.freq);
         assert_eq!(decoded.duplex, mem.duplex);
@@ -1369,13 +1901,13 @@ mod tests {
         let mut mem = Memory::new(1);
         mem.freq = 146_520_000;
         mem.offset = 600_000;
-        mem.duplex = "+".to_string();
-        mem.mode = "NFM".to_string();
+        mem.duplex = Duplex::Plus;
+        mem.mode = Mode::Nfm;
         mem.power = Some(PowerLevel::from_watts("Low", 1.0));
         mem.name = "RPTR".to_string();
 
-        let raw = encode_memory(&mem).unwrap();
-        let decoded = decode_memory(1, &raw, "RPTR").unwrap();
+        let raw = encode_memory(&mem, &UV5R, BANDS).unwrap();
+        let decoded = decode_memory(1, &raw, "RPTR", &UV5R).unwrap();
 
         assert_eq!(decoded.freq, mem.freq);
         assert_eq!(decoded.duplex, mem.duplex);
@@ -1393,17 +1925,17 @@ mod tests {
         // Test DTCS tone mode
         let mut mem = Memory::new(2);
         mem.freq = 446_000_000;
-        mem.duplex = String::new();
-        mem.mode = "FM".to_string();
-        mem.tmode = "DTCS".to_string();
+        mem.duplex = Duplex::Simplex;
+        mem.mode = Mode::Fm;
+        mem.tmode = ToneMode::Dtcs;
         mem.dtcs = 23;
         mem.rx_dtcs = 23;
-        mem.dtcs_polarity = "NN".to_string();
+        mem.dtcs_polarity = DtcsPolarity::NN;
 
-        let raw = encode_memory(&mem).unwrap();
-        let decoded = decode_memory(2, &raw, "").unwrap();
+        let raw = encode_memory(&mem, &UV5R, BANDS).unwrap();
+        let decoded = decode_memory(2, &raw, "", &UV5R).unwrap();
 
-        assert_eq!(decoded.tmode, "DTCS");
+        assert_eq!(decoded.tmode, ToneMode::Dtcs);
         assert_eq!(decoded.dtcs, 23);
         assert_eq!(decoded.rx_dtcs, 23);
     }
@@ -1413,14 +1945,14 @@ mod tests {
         // Test TX inhibit (duplex = "off")
         let mut mem = Memory::new(3);
         mem.freq = 146_520_000;
-        mem.duplex = "off".to_string();
-        mem.mode = "FM".to_string();
+        mem.duplex = Duplex::Off;
+        mem.mode = Mode::Fm;
 
-        let raw = encode_memory(&mem).unwrap();
+        let raw = encode_memory(&mem, &UV5R, BANDS).unwrap();
         assert_eq!(raw.txfreq, 0xFFFFFFFF);
 
-        let decoded = decode_memory(3, &raw, "").unwrap();
-        assert_eq!(decoded.duplex, "off");
+        let decoded = decode_memory(3, &raw, "", &UV5R).unwrap();
+        assert_eq!(decoded.duplex, Duplex::Off);
     }
 
     #[test]
@@ -1436,6 +1968,14 @@ mod tests {
         assert!(UV5RRadio::match_model(&data, "radio.uv5"));
     }
 
+    #[test]
+    fn test_match_model_ident_header() {
+        // Raw image with ident header matches regardless of extension
+        let mut data = vec![0xFFu8; 0x1948];
+        data[..8].copy_from_slice(&[0xAA, 0x30, 0x76, 0x04, 0x00, 0x05, 0x20, 0xDD]);
+        assert!(UV5RRadio::match_model(&data, "dump.img"));
+    }
+
     #[test]
     fn test_match_model_invalid() {
         let mut data = vec![0xFFu8; 0x1808];
@@ -1520,18 +2060,18 @@ mod tests {
         // Test Cross mode: Tone->DTCS
         let mut mem = Memory::new(10);
         mem.freq = 146_520_000;
-        mem.mode = "FM".to_string();
-        mem.tmode = "Cross".to_string();
-        mem.cross_mode = "Tone->DTCS".to_string();
+        mem.mode = Mode::Fm;
+        mem.tmode = ToneMode::Cross;
+        mem.cross_mode = CrossMode::ToneToDtcs;
         mem.rtone = 100.0;
         mem.rx_dtcs = 23;
-        mem.dtcs_polarity = "NN".to_string();
+        mem.dtcs_polarity = DtcsPolarity::NN;
 
-        let raw = encode_memory(&mem).unwrap();
-        let decoded = decode_memory(10, &raw, "").unwrap();
+        let raw = encode_memory(&mem, &UV5R, BANDS).unwrap();
+        let decoded = decode_memory(10, &raw, "", &UV5R).unwrap();
 
-        assert_eq!(decoded.tmode, "Cross");
-        assert_eq!(decoded.cross_mode, "Tone->DTCS");
+        assert_eq!(decoded.tmode, ToneMode::Cross);
+        assert_eq!(decoded.cross_mode, CrossMode::ToneToDtcs);
         assert_eq!(decoded.rtone, 100.0);
         assert_eq!(decoded.rx_dtcs, 23);
     }
@@ -1541,19 +2081,19 @@ mod tests {
         // Test TSQL (TX and RX use same tone)
         let mut mem = Memory::new(11);
         mem.freq = 146_520_000;
-        mem.mode = "FM".to_string();
-        mem.tmode = "TSQL".to_string();
+        mem.mode = Mode::Fm;
+        mem.tmode = ToneMode::Tsql;
         mem.rtone = 123.0;
         mem.ctone = 123.0;
 
-        let raw = encode_memory(&mem).unwrap();
+        let raw = encode_memory(&mem, &UV5R, BANDS).unwrap();
 
         // In TSQL mode, both TX and RX should use the same tone
         assert_eq!(raw.txtone, tone_to_u16(123.0));
         assert_eq!(raw.rxtone, tone_to_u16(123.0));
 
-        let decoded = decode_memory(11, &raw, "").unwrap();
-        assert_eq!(decoded.tmode, "TSQL");
+        let decoded = decode_memory(11, &raw, "", &UV5R).unwrap();
+        assert_eq!(decoded.tmode, ToneMode::Tsql);
         assert_eq!(decoded.rtone, 123.0);
     }
 
@@ -1562,9 +2102,9 @@ mod tests {
         // Test invalid frequency (out of band)
         let mut mem = Memory::new(0);
         mem.freq = 200_000_000; // Invalid (between VHF and UHF)
-        mem.mode = "FM".to_string();
+        mem.mode = Mode::Fm;
 
-        let result = encode_memory(&mem);
+        let result = encode_memory(&mem, &UV5R, BANDS);
         assert!(result.is_err());
     }
 
@@ -1592,16 +2132,274 @@ mod tests {
         let mut mem = Memory::new(5);
         mem.freq = 146_520_000; // RX
         mem.offset = 446_000_000; // TX (stored in offset for split mode)
-        mem.duplex = "split".to_string();
-        mem.mode = "FM".to_string();
+        mem.duplex = Duplex::Split;
+        mem.mode = Mode::Fm;
 
-        let raw = encode_memory(&mem).unwrap();
+        let raw = encode_memory(&mem, &UV5R, BANDS).unwrap();
         assert_eq!(bcd_to_freq(raw.rxfreq), 146_520_000);
         assert_eq!(bcd_to_freq(raw.txfreq), 446_000_000);
 
-        let decoded = decode_memory(5, &raw, "").unwrap();
-        assert_eq!(decoded.duplex, "split");
+        let decoded = decode_memory(5, &raw, "", &UV5R).unwrap();
+        assert_eq!(decoded.duplex, Duplex::Split);
         assert_eq!(decoded.freq, 146_520_000);
         assert_eq!(decoded.offset, 446_000_000);
     }
+
+    #[tokio::test]
+    async fn test_handshake_over_mock() {
+        let ident = [0xAA, 0x30, 0x76, 0x04, 0x00, 0x05, 0x20, 0xDD];
+        let mut port = MockSerialPort::new();
+        port.push_read_data(&[0x06]);
+        port.push_read_data(&ident);
+        port.push_read_data(&[0x06]);
+
+        let radio = UV5RRadio::new();
+        assert_eq!(
+            radio.do_handshake(&mut port).await.unwrap(),
+            (UV5R_MODEL_291, ident.to_vec())
+        );
+        assert!(port.was_written(UV5R_MODEL_291));
+        assert!(port.get_written_data().ends_with(&[0x02, 0x06]));
+    }
+
+    fn load_test_image() -> UV5RRadio {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("test_data/Baofeng_UV-5R_20260211.img");
+        let data = std::fs::read(path).expect("Failed to read UV-5R test image");
+        let mut radio = UV5RRadio::new();
+        radio
+            .process_mmap(&MemoryMap::new(data[..MEMSIZE].to_vec()))
+            .unwrap();
+        radio
+    }
+
+    #[test]
+    fn test_get_settings() {
+        let radio = load_test_image();
+        assert!(radio.get_features().has_settings);
+
+        let settings = radio.get_settings().unwrap();
+        let value = |name: &str| settings.get(name).unwrap().value.to_string();
+        assert_eq!(value("squelch"), "3");
+        assert_eq!(value("save"), "1:3");
+        assert_eq!(value("vox"), "OFF");
+        assert_eq!(value("abr"), "5");
+        assert_eq!(value("beep"), "On");
+        assert_eq!(value("timeout"), "60");
+        assert_eq!(value("voice"), "English");
+        assert_eq!(value("mdfa"), "Name");
+        assert_eq!(value("wtled"), "Purple");
+        assert_eq!(value("ponmsg"), "Message");
+        assert_eq!(value("dtmfst"), "DT+ANI");
+        assert_eq!(value("rpste"), "5");
+        assert_eq!(settings.iter().count(), 28);
+    }
+
+    #[test]
+    fn test_set_settings_roundtrip() {
+        let mut radio = load_test_image();
+        let original = radio.mmap.clone().unwrap();
+
+        // Writing back unchanged settings leaves the image untouched
+        let mut settings = radio.get_settings().unwrap();
+        radio.set_settings(&settings).unwrap();
+        assert_eq!(radio.mmap.as_ref().unwrap(), &original);
+
+        settings.set("squelch", "7").unwrap();
+        settings.set("timeout", "180").unwrap();
+        settings.set("screv", "SE").unwrap();
+        settings.set("roger", "on").unwrap();
+        radio.set_settings(&settings).unwrap();
+
+        let block = radio.read_settings_block().unwrap();
+        assert_eq!(block[0], 7);
+        assert_eq!(block[9], 11);
+        assert_eq!(block[18], 2);
+        assert_eq!(block[39], 1);
+        assert_eq!(radio.get_settings().unwrap(), settings);
+
+        assert!(settings.set("squelch", "10").is_err());
+        assert!(settings.set("timeout", "100").is_err());
+        assert!(settings.set("voice", "Klingon").is_err());
+
+        // Values built directly skip `set`'s checks, so the driver checks them
+        // against its own definitions, and a valid value ahead of a bad one
+        // isn't written either
+        let original = radio.mmap.clone().unwrap();
+        for (name, value) in [
+            ("squelch", SettingValue::integer(12, 0, 255)),
+            ("timeout", SettingValue::integer(20, 0, 600)),
+            ("voice", SettingValue::list(&["A", "B", "C", "D"], 3)),
+        ] {
+            let mut bad = RadioSettingGroup::new("settings", "Settings");
+            bad.add(RadioSetting::new(
+                "beep",
+                "Beep",
+                SettingValue::boolean(false),
+            ));
+            bad.add(RadioSetting::new(name, name, value));
+            assert!(matches!(
+                radio.set_settings(&bad),
+                Err(RadioError::InvalidSetting(_))
+            ));
+        }
+        assert_eq!(radio.mmap.as_ref().unwrap(), &original);
+
+        let mut unknown = RadioSettingGroup::new("settings", "Settings");
+        unknown.add(RadioSetting::new(
+            "bogus",
+            "Bogus",
+            SettingValue::boolean(true),
+        ));
+        assert!(matches!(
+            radio.set_settings(&unknown),
+            Err(RadioError::InvalidSetting(_))
+        ));
+    }
+
+    /// Test image including the aux block, with its firmware version replaced
+    fn image_with_version(version: &[u8]) -> Vec<u8> {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("test_data/Baofeng_UV-5R_20260211.img");
+        let data = std::fs::read(path).expect("Failed to read UV-5R test image");
+        let mut image = data[..MEMSIZE + (AUX_END - AUX_START)].to_vec();
+        if !version.is_empty() {
+            let field = &mut image[VERSION_OFFSET..VERSION_OFFSET + VERSION_LENGTH];
+            field.fill(0xFF);
+            field[..version.len()].copy_from_slice(version);
+        }
+        image
+    }
+
+    #[test]
+    fn test_variant_detection() {
+        let image = image_with_version(b"");
+        assert_eq!(firmware_version(&image).as_deref(), Some("N5R2407BFB297"));
+        assert_eq!(Variant::detect(&image), &UV5R);
+
+        // Images without the aux block can't be told apart
+        assert_eq!(Variant::detect(&image[..MEMSIZE]), &UV5R);
+
+        assert_eq!(
+            Variant::detect(&image_with_version(b"BFP3V3 F")).name,
+            "BF-F8HP"
+        );
+        assert_eq!(
+            Variant::detect(&image_with_version(b"N823")).name,
+            "UV-82HP"
+        );
+        assert_eq!(
+            Variant::detect(&image_with_version(b"BF82017")).name,
+            "UV-82"
+        );
+        assert_eq!(Variant::detect(&image_with_version(b"XYZ123")), &UV5R);
+
+        // A blank version falls back to the model appended after the aux block
+        let mut image = image_with_version(b"\xFF");
+        assert_eq!(Variant::detect(&image), &UV5R);
+        image.extend_from_slice(b"UV-82HP\0");
+        assert_eq!(Variant::detect(&image).name, "UV-82HP");
+        let (version, trailer) = image.split_at_mut(MODEL_TRAILER_OFFSET);
+        trailer.copy_from_slice(b"UV-82\0\0\0");
+        assert_eq!(Variant::detect(version), &UV5R);
+        assert_eq!(Variant::detect(&image).name, "UV-82");
+    }
+
+    #[test]
+    fn test_variant_from_magic() {
+        assert_eq!(Variant::from_magic(UV5R_MODEL_ORIG), Some(&UV5R));
+        // Shared by models with different power levels
+        assert_eq!(Variant::from_magic(UV5R_MODEL_UV82), None);
+        assert_eq!(Variant::from_magic(UV5R_MODEL_291), None);
+        assert_eq!(Variant::from_magic(UV5R_MODEL_A58), None);
+        assert_eq!(Variant::from_magic(b"\x50\xBB\xFF\x00\x00\x00\x00"), None);
+    }
+
+    #[test]
+    fn test_variant_bands() {
+        let mut radio = UV5RRadio::new();
+        radio
+            .process_mmap(&MemoryMap::new(image_with_version(b"N82-3")))
+            .unwrap();
+        assert_eq!(radio.variant(), Some("UV-82HP"));
+        assert_eq!(
+            radio.get_features().valid_bands,
+            vec![(136_000_000, 175_000_000), (400_000_000, 521_000_000)]
+        );
+
+        let mut mem = Memory::new(100);
+        mem.freq = 520_500_000;
+        radio.set_memory(&mem).unwrap();
+        radio
+            .process_mmap(&MemoryMap::new(image_with_version(b"BFS")))
+            .unwrap();
+        assert!(radio.set_memory(&mem).is_err());
+    }
+
+    #[test]
+    fn test_ident_upper_band() {
+        let mut image = image_with_version(b"");
+        assert_eq!(UV5R.bands(&image), BANDS);
+
+        // Ident byte 3 reports a VHF/220 MHz radio
+        image[IDENT_UPPER_BAND] = UPPER_BAND_220;
+        let mut radio = UV5RRadio::new();
+        radio.process_mmap(&MemoryMap::new(image)).unwrap();
+        assert_eq!(
+            radio.get_features().valid_bands,
+            vec![(136_000_000, 174_000_000), BAND_220]
+        );
+
+        let mut mem = Memory::new(100);
+        mem.freq = 223_500_000;
+        radio.set_memory(&mem).unwrap();
+        assert_eq!(radio.get_memory(100).unwrap().unwrap().freq, 223_500_000);
+        mem.freq = 446_000_000;
+        assert!(radio.set_memory(&mem).is_err());
+    }
+
+    #[test]
+    fn test_tri_power_variant() {
+        let mut radio = UV5RRadio::new();
+        radio
+            .process_mmap(&MemoryMap::new(image_with_version(b"N5R-340")))
+            .unwrap();
+        assert_eq!(radio.variant(), Some("BF-F8HP"));
+        let features = radio.get_features();
+        assert_eq!(features.valid_power_levels.len(), 3);
+        assert_eq!(features.valid_bands[0], (130_000_000, 180_000_000));
+
+        let mut mem = Memory::new(100);
+        mem.freq = 146_520_000;
+        for (watts, lowpower, label) in [(4.0, 1, "Med"), (8.0, 0, "High"), (0.5, 2, "Low")] {
+            mem.power = Some(PowerLevel::from_watts("Any", watts));
+            radio.set_memory(&mem).unwrap();
+            assert_eq!(radio.read_raw_memory(100).unwrap().lowpower, lowpower);
+            assert_eq!(
+                radio
+                    .get_memory(100)
+                    .unwrap()
+                    .unwrap()
+                    .power
+                    .unwrap()
+                    .label(),
+                label
+            );
+        }
+
+        // A 4 W UV-5R memory lands on the nearest level, and a tri-power low
+        // setting reads back as low on a dual-power radio
+        mem.power = Some(PowerLevel::from_watts("High", 4.0));
+        assert_eq!(
+            encode_memory(&mem, &VARIANTS[0], BANDS).unwrap().lowpower,
+            1
+        );
+        assert_eq!(encode_memory(&mem, &UV5R, BANDS).unwrap().lowpower, 0);
+        let raw = RawMemory {
+            lowpower: 2,
+            ..encode_memory(&mem, &UV5R, BANDS).unwrap()
+        };
+        let decoded = decode_memory(100, &raw, "", &UV5R).unwrap();
+        assert_eq!(decoded.power.unwrap().label(), "Low");
+    }
 }
@@ -0,0 +1,1042 @@
+// Quansheng UV-K5 radio driver
+// Reference: chirp/drivers/uvk5.py
+//
+// Every packet is framed as
+//
+//   AB CD <len:u16 LE> <payload ^ key> <crc:u16 LE ^ key> DC BA
+//
+// where the payload starts with a command id (u16 LE) and its own length,
+// the CRC is CRC-16/XMODEM over the plain payload, and the XOR key is a
+// fixed 16-byte table applied from the first payload byte. The radio doesn't
+// fill in a CRC on its replies, so those aren't checked.
+//
+// EEPROM is read and written in 0x80-byte chunks. Uploads stop at 0x1D00;
+// the calibration data above that is never written.
+//
+// Image layout:
+//
+//   0x0000  16 bytes per channel (200 memories, then the VFOs)
+//   0x0D60  1 attribute byte per channel: scan lists, compander, free flag,
+//           band (0x0F = unused)
+//   0x0F50  16 bytes per channel: name (10 characters used)
+
+use super::bank::{Bank, BankModel, BankStyle};
+use super::image::{channel_index, ImageAccess};
+use super::traits::{CloneModeRadio, Radio, RadioError, RadioResult, Status, StatusCallback};
+use crate::core::{
+    CrossMode, DtcsPolarity, Duplex, Memory, Mode, PowerLevel, RadioFeatures, Skip, ToneMode,
+    DTCS_CODES, TONES,
+};
+use crate::memmap::MemoryMap;
+use crate::serial::{ConnectionProfile, Transport};
+
+/// EEPROM image size
+const MEMSIZE: usize = 0x2000;
+
+/// Uploads stop here; calibration follows
+const PROG_SIZE: usize = 0x1D00;
+
+/// EEPROM transfer chunk
+const CHUNK_SIZE: usize = 0x80;
+
+const CONNECTION: ConnectionProfile = ConnectionProfile::new(38400);
+
+/// Packet framing
+const HEADER: [u8; 2] = [0xAB, 0xCD];
+const FOOTER: [u8; 2] = [0xDC, 0xBA];
+
+/// Payload obfuscation key
+const XOR_KEY: [u8; 16] = [
+    0x16, 0x6C, 0x14, 0xE6, 0x2E, 0x91, 0x0D, 0x40, 0x21, 0x35, 0xD5, 0x40, 0x13, 0x03, 0xE9, 0x80,
+];
+
+/// Session id sent with every command
+const SESSION: [u8; 4] = [0x6A, 0x39, 0x57, 0x64];
+
+/// Command ids
+const CMD_HELLO: u16 = 0x0514;
+const CMD_HELLO_REPLY: u16 = 0x0515;
+const CMD_READ: u16 = 0x051B;
+const CMD_READ_REPLY: u16 = 0x051C;
+const CMD_WRITE: u16 = 0x051D;
+const CMD_WRITE_REPLY: u16 = 0x051E;
+const CMD_RESET: u16 = 0x05DD;
+
+/// Memory offsets
+const MEMORY_OFFSET: usize = 0x0000;
+const ATTR_OFFSET: usize = 0x0D60;
+const NAMES_OFFSET: usize = 0x0F50;
+
+const MEMORY_SIZE: usize = 16;
+const NAME_SLOT_SIZE: usize = 16;
+const NAME_LENGTH: usize = 10;
+
+const NUM_MEMORIES: u32 = 200;
+
+/// Attribute bits
+const SCANLIST1: u8 = 0x80;
+const SCANLIST2: u8 = 0x40;
+const COMPANDER_MASK: u8 = 0x30;
+const IS_FREE: u8 = 0x08;
+const BAND_MASK: u8 = 0x07;
+
+/// Attribute byte of an unused channel: free, band 7
+const ATTR_UNUSED: u8 = IS_FREE | BAND_MASK;
+
+/// Band codes by frequency range (Hz)
+const BANDS: &[(u64, u64)] = &[
+    (50_000_000, 76_000_000),
+    (108_000_000, 136_000_000),
+    (136_000_000, 200_000_000),
+    (200_000_000, 300_000_000),
+    (350_000_000, 400_000_000),
+    (400_000_000, 470_000_000),
+    (470_000_000, 600_000_000),
+];
+
+const MODES: &[Mode] = &[Mode::Fm, Mode::Am, Mode::Usb];
+
+const STEPS: &[f32] = &[2.5, 5.0, 6.25, 10.0, 12.5, 25.0, 8.33];
+
+const POWER_LEVELS: &[(&str, f32)] = &[("Low", 1.5), ("Mid", 3.0), ("High", 5.0)];
+
+/// Characters the radio displays in names
+const CHARSET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789 !\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+
+/// CRC-16/XMODEM (poly 0x1021, init 0)
+pub fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0u16, |crc, &byte| {
+        let mut crc = crc ^ ((byte as u16) << 8);
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+/// XOR a payload with the key (the operation is its own inverse)
+fn obfuscate(data: &mut [u8]) {
+    for (i, byte) in data.iter_mut().enumerate() {
+        *byte ^= XOR_KEY[i % XOR_KEY.len()];
+    }
+}
+
+/// Frame a payload for the wire
+pub fn frame(payload: &[u8]) -> Vec<u8> {
+    let mut body = payload.to_vec();
+    body.extend_from_slice(&crc16(payload).to_le_bytes());
+    obfuscate(&mut body);
+
+    let mut packet = HEADER.to_vec();
+    packet.extend_from_slice(&(payload.len() as u16).to_le_bytes());
+    packet.extend_from_slice(&body);
+    packet.extend_from_slice(&FOOTER);
+    packet
+}
+
+/// Payload of a command: id, length, arguments
+fn command(id: u16, args: &[u8]) -> Vec<u8> {
+    let mut payload = id.to_le_bytes().to_vec();
+    payload.extend_from_slice(&(args.len() as u16).to_le_bytes());
+    payload.extend_from_slice(args);
+    payload
+}
+
+async fn send<T: Transport + ?Sized>(port: &mut T, payload: &[u8]) -> RadioResult<()> {
+    port.write_all(&frame(payload))
+        .await
+        .map_err(|e| RadioError::Serial(e.to_string()))?;
+    port.flush().await.ok();
+    Ok(())
+}
+
+/// Read one packet and return its payload, checking it's a reply of type `id`
+async fn receive<T: Transport + ?Sized>(port: &mut T, id: u16) -> RadioResult<Vec<u8>> {
+    let mut header = [0u8; 4];
+    port.read_exact(&mut header)
+        .await
+        .map_err(|e| RadioError::Serial(e.to_string()))?;
+    if header[..2] != HEADER {
+        return Err(RadioError::InvalidResponse(format!(
+            "Bad packet header: {:02X?}",
+            header
+        )));
+    }
+
+    let len = u16::from_le_bytes([header[2], header[3]]) as usize;
+    let mut body = vec![0u8; len + 4];
+    port.read_exact(&mut body)
+        .await
+        .map_err(|e| RadioError::Serial(e.to_string()))?;
+    if body[len + 2..] != FOOTER {
+        return Err(RadioError::InvalidResponse(format!(
+            "Bad packet footer: {:02X?}",
+            &body[len + 2..]
+        )));
+    }
+
+    body.truncate(len);
+    obfuscate(&mut body);
+    if body.len() < 4 || u16::from_le_bytes([body[0], body[1]]) != id {
+        return Err(RadioError::InvalidResponse(format!(
+            "Expected reply 0x{:04X}, got {:02X?}",
+            id,
+            &body[..body.len().min(4)]
+        )));
+    }
+    Ok(body)
+}
+
+/// Say hello and return the firmware version
+async fn hello<T: Transport + ?Sized>(port: &mut T) -> RadioResult<String> {
+    send(port, &command(CMD_HELLO, &SESSION)).await?;
+    let reply = receive(port, CMD_HELLO_REPLY).await?;
+    let version: String = reply[4..]
+        .iter()
+        .take(16)
+        .take_while(|&&b| b != 0)
+        .map(|&b| b as char)
+        .collect();
+    tracing::debug!("UV-K5 firmware: {}", version);
+    Ok(version)
+}
+
+async fn read_chunk<T: Transport + ?Sized>(port: &mut T, offset: usize) -> RadioResult<Vec<u8>> {
+    let mut args = (offset as u16).to_le_bytes().to_vec();
+    args.extend_from_slice(&[CHUNK_SIZE as u8, 0]);
+    args.extend_from_slice(&SESSION);
+    send(port, &command(CMD_READ, &args)).await?;
+
+    let reply = receive(port, CMD_READ_REPLY).await?;
+    if reply.len() < 8 + CHUNK_SIZE || u16::from_le_bytes([reply[4], reply[5]]) as usize != offset {
+        return Err(RadioError::InvalidResponse(format!(
+            "Bad reply reading 0x{:04X}",
+            offset
+        )));
+    }
+    Ok(reply[8..8 + CHUNK_SIZE].to_vec())
+}
+
+async fn write_chunk<T: Transport + ?Sized>(
+    port: &mut T,
+    offset: usize,
+    data: &[u8],
+) -> RadioResult<()> {
+    let mut args = (offset as u16).to_le_bytes().to_vec();
+    args.extend_from_slice(&[data.len() as u8, 1]);
+    args.extend_from_slice(&SESSION);
+    args.extend_from_slice(data);
+    send(port, &command(CMD_WRITE, &args)).await?;
+
+    let reply = receive(port, CMD_WRITE_REPLY).await?;
+    if reply.len() < 6 || u16::from_le_bytes([reply[4], reply[5]]) as usize != offset {
+        return Err(RadioError::Nak);
+    }
+    Ok(())
+}
+
+/// CTCSS/DCS setting of one direction
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Code {
+    None,
+    Tone(f32),
+    Dtcs(u16, char),
+}
+
+impl Code {
+    fn decode(flag: u8, index: u8) -> Self {
+        let index = index as usize;
+        match flag {
+            1 => TONES.get(index).map_or(Code::None, |&t| Code::Tone(t)),
+            2 | 3 => DTCS_CODES.get(index).map_or(Code::None, |&c| {
+                Code::Dtcs(c, if flag == 3 { 'R' } else { 'N' })
+            }),
+            _ => Code::None,
+        }
+    }
+
+    /// (flag, index) pair
+    fn encode(self) -> RadioResult<(u8, u8)> {
+        let position = |found: Option<usize>, what: &str| {
+            found
+                .map(|i| i as u8)
+                .ok_or_else(|| RadioError::Radio(format!("Unsupported {}", what)))
+        };
+        Ok(match self {
+            Code::None => (0, 0),
+            Code::Tone(tone) => (1, position(TONES.iter().position(|&t| t == tone), "tone")?),
+            Code::Dtcs(code, polarity) => (
+                if polarity == 'R' { 3 } else { 2 },
+                position(DTCS_CODES.iter().position(|&c| c == code), "DTCS code")?,
+            ),
+        })
+    }
+}
+
+/// Set the tone fields of a memory from the TX and RX codes
+fn decode_tones(mem: &mut Memory, tx: Code, rx: Code) {
+    let mut tx_pol = 'N';
+    let mut rx_pol = 'N';
+    mem.tmode = match (tx, rx) {
+        (Code::None, Code::None) => ToneMode::None,
+        (Code::Tone(t), Code::None) => {
+            mem.rtone = t;
+            ToneMode::Tone
+        }
+        (Code::Tone(t), Code::Tone(r)) if t == r => {
+            mem.rtone = t;
+            mem.ctone = r;
+            ToneMode::Tsql
+        }
+        (Code::Dtcs(t, tp), Code::Dtcs(r, rp)) if t == r => {
+            mem.dtcs = t;
+            mem.rx_dtcs = r;
+            (tx_pol, rx_pol) = (tp, rp);
+            ToneMode::Dtcs
+        }
+        _ => {
+            mem.cross_mode = match (tx, rx) {
+                (Code::Tone(t), Code::Tone(r)) => {
+                    (mem.rtone, mem.ctone) = (t, r);
+                    CrossMode::ToneToTone
+                }
+                (Code::Tone(t), Code::Dtcs(r, rp)) => {
+                    (mem.rtone, mem.rx_dtcs, rx_pol) = (t, r, rp);
+                    CrossMode::ToneToDtcs
+                }
+                (Code::Dtcs(t, tp), Code::Tone(r)) => {
+                    (mem.dtcs, tx_pol, mem.ctone) = (t, tp, r);
+                    CrossMode::DtcsToTone
+                }
+                (Code::Dtcs(t, tp), Code::Dtcs(r, rp)) => {
+                    (mem.dtcs, tx_pol, mem.rx_dtcs, rx_pol) = (t, tp, r, rp);
+                    CrossMode::DtcsToDtcs
+                }
+                (Code::Dtcs(t, tp), Code::None) => {
+                    (mem.dtcs, tx_pol) = (t, tp);
+                    CrossMode::DtcsToNone
+                }
+                (Code::None, Code::Tone(r)) => {
+                    mem.ctone = r;
+                    CrossMode::NoneToTone
+                }
+                (Code::None, Code::Dtcs(r, rp)) => {
+                    (mem.rx_dtcs, rx_pol) = (r, rp);
+                    CrossMode::NoneToDtcs
+                }
+                (Code::Tone(_), Code::None) | (Code::None, Code::None) => {
+                    unreachable!("decoded as Tone or no tone above")
+                }
+            };
+            ToneMode::Cross
+        }
+    };
+    mem.dtcs_polarity = DtcsPolarity::from_chars(tx_pol, rx_pol);
+}
+
+/// TX and RX codes for a memory's tone settings
+fn encode_tones(mem: &Memory) -> RadioResult<(Code, Code)> {
+    let tx_dtcs = |code| Code::Dtcs(code, mem.dtcs_polarity.tx());
+    let rx_dtcs = |code| Code::Dtcs(code, mem.dtcs_polarity.rx());
+    Ok(match mem.tmode {
+        ToneMode::None => (Code::None, Code::None),
+        ToneMode::Tone => (Code::Tone(mem.rtone), Code::None),
+        ToneMode::Tsql => (Code::Tone(mem.ctone), Code::Tone(mem.ctone)),
+        ToneMode::Dtcs => (tx_dtcs(mem.dtcs), rx_dtcs(mem.dtcs)),
+        ToneMode::Cross => match mem.cross_mode {
+            CrossMode::ToneToTone => (Code::Tone(mem.rtone), Code::Tone(mem.ctone)),
+            CrossMode::ToneToDtcs => (Code::Tone(mem.rtone), rx_dtcs(mem.rx_dtcs)),
+            CrossMode::DtcsToTone => (tx_dtcs(mem.dtcs), Code::Tone(mem.ctone)),
+            CrossMode::DtcsToDtcs => (tx_dtcs(mem.dtcs), rx_dtcs(mem.rx_dtcs)),
+            CrossMode::DtcsToNone => (tx_dtcs(mem.dtcs), Code::None),
+            CrossMode::NoneToTone => (Code::None, Code::Tone(mem.ctone)),
+            CrossMode::NoneToDtcs => (Code::None, rx_dtcs(mem.rx_dtcs)),
+            // Stored the same way as Tone, so not offered as a cross mode
+            CrossMode::ToneToNone => (Code::Tone(mem.rtone), Code::None),
+        },
+        tmode => {
+            return Err(RadioError::Radio(format!(
+                "Unsupported tone mode: {}",
+                tmode
+            )))
+        }
+    })
+}
+
+/// Quansheng UV-K5 radio driver
+pub struct UVK5Radio {
+    pub mmap: Option<MemoryMap>,
+}
+
+impl UVK5Radio {
+    pub fn new() -> Self {
+        Self { mmap: None }
+    }
+
+    fn attr(&self, index: usize) -> RadioResult<u8> {
+        Ok(self.read(ATTR_OFFSET + index, 1)?[0])
+    }
+
+    fn is_used(&self, index: usize) -> RadioResult<bool> {
+        let freq = self.read(MEMORY_OFFSET + index * MEMORY_SIZE, 4)?;
+        Ok(freq != [0xFF; 4] && freq != [0x00; 4])
+    }
+
+    fn decode_memory(&self, number: u32, index: usize) -> RadioResult<Memory> {
+        let data = self.read(MEMORY_OFFSET + index * MEMORY_SIZE, MEMORY_SIZE)?;
+        let mut mem = Memory::new(number);
+
+        mem.freq = u32::from_le_bytes([data[0], data[1], data[2], data[3]]) as u64 * 10;
+        mem.offset = u32::from_le_bytes([data[4], data[5], data[6], data[7]]) as u64 * 10;
+        mem.duplex = match data[11] & 0x0F {
+            1 if mem.offset > 0 => Duplex::Plus,
+            2 if mem.offset > 0 => Duplex::Minus,
+            _ => Duplex::Simplex,
+        };
+
+        let tx = Code::decode(data[10] >> 4, data[9]);
+        let rx = Code::decode(data[10] & 0x0F, data[8]);
+        decode_tones(&mut mem, tx, rx);
+
+        mem.mode = match MODES.get((data[11] >> 4) as usize) {
+            Some(Mode::Fm) if data[12] & 0x02 != 0 => Mode::Nfm,
+            Some(&mode) => mode,
+            None => Mode::Fm,
+        };
+        let (label, watts) = POWER_LEVELS[(((data[12] >> 2) & 0x03) as usize).min(2)];
+        mem.power = Some(PowerLevel::from_watts(label, watts));
+        mem.tuning_step = STEPS.get(data[14] as usize).copied().unwrap_or(2.5);
+
+        if self.attr(index)? & (SCANLIST1 | SCANLIST2) == 0 {
+            mem.skip = Skip::Skip;
+        }
+
+        let name = self.read(NAMES_OFFSET + index * NAME_SLOT_SIZE, NAME_LENGTH)?;
+        mem.name = name
+            .iter()
+            .take_while(|&&b| b != 0x00 && b != 0xFF)
+            .map(|&b| b as char)
+            .collect::<String>()
+            .trim_end()
+            .to_string();
+
+        Ok(mem)
+    }
+
+    /// Encode a channel's 16-byte record on top of `current`
+    ///
+    /// The busy-lock and reverse bits next to the power field and the DTMF and
+    /// scrambler bytes aren't exposed here, so they keep the radio's values.
+    fn encode_memory(mem: &Memory, current: &[u8]) -> RadioResult<[u8; MEMORY_SIZE]> {
+        let mut data = [0u8; MEMORY_SIZE];
+        data.copy_from_slice(current);
+
+        data[0..4].copy_from_slice(&((mem.freq / 10) as u32).to_le_bytes());
+        let (shift, offset) = match mem.duplex {
+            Duplex::Simplex => (0, 0),
+            Duplex::Plus => (1, mem.offset),
+            Duplex::Minus => (2, mem.offset),
+            duplex => return Err(RadioError::Radio(format!("Unsupported duplex: {}", duplex))),
+        };
+        data[4..8].copy_from_slice(&((offset / 10) as u32).to_le_bytes());
+
+        let (tx, rx) = encode_tones(mem)?;
+        let (tx_flag, tx_index) = tx.encode()?;
+        let (rx_flag, rx_index) = rx.encode()?;
+        data[8] = rx_index;
+        data[9] = tx_index;
+        data[10] = (tx_flag << 4) | rx_flag;
+
+        let (modulation, narrow) = match mem.mode {
+            Mode::Nfm => (0, 0x02),
+            mode => match MODES.iter().position(|&m| m == mode) {
+                Some(index) => (index as u8, 0),
+                None => return Err(RadioError::Radio(format!("Unsupported mode: {}", mode))),
+            },
+        };
+        data[11] = (modulation << 4) | shift;
+
+        let power = match &mem.power {
+            Some(power) => POWER_LEVELS
+                .iter()
+                .rposition(|(_, watts)| power.watts() >= *watts)
+                .unwrap_or(0) as u8,
+            None => 2,
+        };
+        data[12] = (data[12] & 0xF1) | (power << 2) | narrow;
+        data[14] = match STEPS.iter().position(|&s| s == mem.tuning_step) {
+            Some(index) => index as u8,
+            None => {
+                return Err(RadioError::Radio(format!(
+                    "Unsupported tuning step: {}",
+                    mem.tuning_step
+                )))
+            }
+        };
+
+        Ok(data)
+    }
+
+    fn encode_name(name: &str) -> RadioResult<[u8; NAME_SLOT_SIZE]> {
+        let name = name.trim_end();
+        if let Some(c) = name.chars().find(|&c| !CHARSET.contains(c)) {
+            return Err(RadioError::Radio(format!(
+                "Unsupported character in name: {}",
+                c
+            )));
+        }
+        let mut slot = [0u8; NAME_SLOT_SIZE];
+        for (byte, c) in slot.iter_mut().zip(name.bytes().take(NAME_LENGTH)) {
+            *byte = c;
+        }
+        Ok(slot)
+    }
+
+    fn set_scanlist(&mut self, index: usize, list: usize, member: bool) -> RadioResult<()> {
+        let mask = match list {
+            0 => SCANLIST1,
+            1 => SCANLIST2,
+            _ => return Err(RadioError::Radio(format!("Invalid scan list: {}", list))),
+        };
+        let attr = self.attr(index)?;
+        let attr = if member { attr | mask } else { attr & !mask };
+        self.write(ATTR_OFFSET + index, &[attr])
+    }
+}
+
+impl ImageAccess for UVK5Radio {
+    fn image(&self) -> Option<&MemoryMap> {
+        self.mmap.as_ref()
+    }
+
+    fn image_mut(&mut self) -> Option<&mut MemoryMap> {
+        self.mmap.as_mut()
+    }
+}
+
+impl Default for UVK5Radio {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Radio for UVK5Radio {
+    fn vendor(&self) -> &str {
+        "Quansheng"
+    }
+
+    fn model(&self) -> &str {
+        "UV-K5"
+    }
+
+    fn connection_profile(&self) -> ConnectionProfile {
+        CONNECTION
+    }
+
+    fn get_features(&self) -> RadioFeatures {
+        RadioFeatures {
+            memory_bounds: (1, NUM_MEMORIES),
+            valid_modes: vec![Mode::Fm, Mode::Nfm, Mode::Am, Mode::Usb],
+            valid_tmodes: vec![
+                ToneMode::None,
+                ToneMode::Tone,
+                ToneMode::Tsql,
+                ToneMode::Dtcs,
+                ToneMode::Cross,
+            ],
+            valid_cross_modes: vec![
+                CrossMode::ToneToTone,
+                CrossMode::ToneToDtcs,
+                CrossMode::DtcsToTone,
+                CrossMode::DtcsToDtcs,
+                CrossMode::DtcsToNone,
+                CrossMode::NoneToTone,
+                CrossMode::NoneToDtcs,
+            ],
+            valid_duplexes: vec![Duplex::Simplex, Duplex::Plus, Duplex::Minus],
+            valid_tuning_steps: STEPS.to_vec(),
+            valid_tones: TONES.to_vec(),
+            valid_dtcs_codes: DTCS_CODES.to_vec(),
+            valid_dtcs_pols: vec![
+                DtcsPolarity::NN,
+                DtcsPolarity::NR,
+                DtcsPolarity::RN,
+                DtcsPolarity::RR,
+            ],
+            valid_power_levels: POWER_LEVELS
+                .iter()
+                .map(|(label, watts)| PowerLevel::from_watts(*label, *watts))
+                .collect(),
+            valid_name_length: NAME_LENGTH,
+            valid_characters: CHARSET.to_string(),
+            valid_bands: vec![(50_000_000, 76_000_000), (108_000_000, 600_000_000)],
+            valid_skips: vec![Skip::None, Skip::Skip],
+            has_bank: true,
+            has_dtcs: true,
+            has_rx_dtcs: true,
+            has_dtcs_polarity: true,
+            has_ctone: true,
+            has_cross: true,
+            has_tuning_step: true,
+            has_mode: true,
+            has_offset: true,
+            has_name: true,
+            can_delete: true,
+            ..Default::default()
+        }
+    }
+
+    fn get_memory(&mut self, number: u32) -> RadioResult<Option<Memory>> {
+        let index = channel_index(number, NUM_MEMORIES)?;
+        if !self.is_used(index)? {
+            return Ok(None);
+        }
+        self.decode_memory(number, index).map(Some)
+    }
+
+    fn set_memory(&mut self, memory: &Memory) -> RadioResult<()> {
+        let index = channel_index(memory.number, NUM_MEMORIES)?;
+        let mem_offset = MEMORY_OFFSET + index * MEMORY_SIZE;
+        let name_offset = NAMES_OFFSET + index * NAME_SLOT_SIZE;
+
+        if memory.empty {
+            self.write(mem_offset, &[0xFF; MEMORY_SIZE])?;
+            self.write(ATTR_OFFSET + index, &[ATTR_UNUSED])?;
+            return self.write(name_offset, &[0xFF; NAME_SLOT_SIZE]);
+        }
+
+        let band = BANDS
+            .iter()
+            .position(|(low, high)| (*low..*high).contains(&memory.freq))
+            .ok_or_else(|| {
+                RadioError::Radio(format!(
+                    "Frequency {} Hz is outside the radio's bands",
+                    memory.freq
+                ))
+            })? as u8;
+
+        let used = self.is_used(index)?;
+        let current = self.record_base(mem_offset, MEMORY_SIZE, used)?;
+        let data = Self::encode_memory(memory, &current)?;
+        let name = Self::encode_name(&memory.name)?;
+
+        // Skipped channels are in no scan list; others keep their lists
+        // (new channels go in list 1)
+        let lists = if used {
+            self.attr(index)? & (SCANLIST1 | SCANLIST2)
+        } else {
+            0
+        };
+        let lists = match (memory.skip, lists) {
+            (Skip::Skip, _) => 0,
+            (_, 0) => SCANLIST1,
+            (_, lists) => lists,
+        };
+        // The compander setting isn't exposed, so a used channel keeps it
+        let compander = if used {
+            self.attr(index)? & COMPANDER_MASK
+        } else {
+            0
+        };
+
+        self.write(mem_offset, &data)?;
+        self.write(name_offset, &name)?;
+        self.write(
+            ATTR_OFFSET + index,
+            &[lists | compander | (band & BAND_MASK)],
+        )
+    }
+
+    fn bank_model(&mut self) -> Option<&mut dyn BankModel> {
+        Some(self)
+    }
+}
+
+/// UV-K5 scan lists: two fixed lists, a channel can be in either or both
+impl BankModel for UVK5Radio {
+    fn bank_style(&self) -> BankStyle {
+        BankStyle::Multiple
+    }
+
+    fn bank_name_length(&self) -> usize {
+        0
+    }
+
+    fn get_banks(&self) -> RadioResult<Vec<Bank>> {
+        Ok(vec![
+            Bank::new(0, "Scan list 1"),
+            Bank::new(1, "Scan list 2"),
+        ])
+    }
+
+    fn rename_bank(&mut self, _bank: usize, _name: &str) -> RadioResult<()> {
+        Err(RadioError::Unsupported(
+            "UV-K5 scan lists can't be renamed".to_string(),
+        ))
+    }
+
+    fn get_memory_banks(&self, number: u32) -> RadioResult<Vec<usize>> {
+        let index = channel_index(number, NUM_MEMORIES)?;
+        if !self.is_used(index)? {
+            return Ok(Vec::new());
+        }
+        let attr = self.attr(index)?;
+        Ok([SCANLIST1, SCANLIST2]
+            .iter()
+            .enumerate()
+            .filter(|(_, &mask)| attr & mask != 0)
+            .map(|(list, _)| list)
+            .collect())
+    }
+
+    fn add_memory_to_bank(&mut self, number: u32, bank: usize) -> RadioResult<()> {
+        let index = channel_index(number, NUM_MEMORIES)?;
+        if !self.is_used(index)? {
+            return Err(RadioError::Radio(format!("Memory #{} is empty", number)));
+        }
+        self.set_scanlist(index, bank, true)
+    }
+
+    fn remove_memory_from_bank(&mut self, number: u32, bank: usize) -> RadioResult<()> {
+        if !self.get_memory_banks(number)?.contains(&bank) {
+            return Err(RadioError::Radio(format!(
+                "Memory #{} is not in scan list {}",
+                number,
+                bank + 1
+            )));
+        }
+        self.set_scanlist(number as usize - 1, bank, false)
+    }
+
+    fn get_bank_memories(&self, bank: usize) -> RadioResult<Vec<u32>> {
+        let mut memories = Vec::new();
+        for number in 1..=NUM_MEMORIES {
+            if self.get_memory_banks(number)?.contains(&bank) {
+                memories.push(number);
+            }
+        }
+        Ok(memories)
+    }
+}
+
+impl CloneModeRadio for UVK5Radio {
+    fn get_memsize(&self) -> usize {
+        MEMSIZE
+    }
+
+    async fn sync_in<T: Transport + ?Sized>(
+        &mut self,
+        port: &mut T,
+        status_fn: Option<StatusCallback>,
+    ) -> RadioResult<MemoryMap> {
+        hello(port).await?;
+
+        let mut data = Vec::with_capacity(MEMSIZE);
+        for offset in (0..MEMSIZE).step_by(CHUNK_SIZE) {
+            data.extend_from_slice(&read_chunk(port, offset).await?);
+
+            if let Some(ref callback) = status_fn {
+                let status = Status::new(data.len(), MEMSIZE, "Cloning from radio".to_string());
+                callback(status.current, status.max, &status.message);
+            }
+        }
+
+        let mmap = MemoryMap::new(data);
+        self.mmap = Some(mmap.clone());
+        Ok(mmap)
+    }
+
+    async fn sync_out<T: Transport + ?Sized>(
+        &mut self,
+        port: &mut T,
+        mmap: &MemoryMap,
+        status_fn: Option<StatusCallback>,
+    ) -> RadioResult<()> {
+        hello(port).await?;
+
+        for offset in (0..PROG_SIZE).step_by(CHUNK_SIZE) {
+            let chunk = mmap
+                .get(offset, Some(CHUNK_SIZE))
+                .map_err(|e| RadioError::Radio(e.to_string()))?;
+            write_chunk(port, offset, chunk).await?;
+
+            if let Some(ref callback) = status_fn {
+                let status = Status::new(
+                    offset + CHUNK_SIZE,
+                    PROG_SIZE,
+                    "Cloning to radio".to_string(),
+                );
+                callback(status.current, status.max, &status.message);
+            }
+        }
+
+        // Restart the radio so it loads the new EEPROM contents
+        send(port, &command(CMD_RESET, &[])).await
+    }
+
+    fn process_mmap(&mut self, mmap: &MemoryMap) -> RadioResult<()> {
+        if mmap.len() < MEMSIZE {
+            return Err(RadioError::Radio(format!(
+                "Memory map too small: expected at least {} bytes, got {}",
+                MEMSIZE,
+                mmap.len()
+            )));
+        }
+        self.mmap = Some(mmap.clone());
+        Ok(())
+    }
+
+    fn get_mmap(&self) -> Option<&MemoryMap> {
+        self.mmap.as_ref()
+    }
+
+    fn match_model(data: &[u8], _filename: &str) -> bool {
+        data.len() == MEMSIZE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drivers::test_image::{check_feature_roundtrip, TestImage};
+    use crate::serial::mock::MockSerialPort;
+
+    fn blank_radio() -> UVK5Radio {
+        TestImage::new(MEMSIZE, 0xFF).load(UVK5Radio::new())
+    }
+
+    /// 145.500 FM, high power, 12.5 kHz step
+    const SIMPLEX_RECORD: [u8; MEMORY_SIZE] = [
+        0xF0, 0x03, 0xDE, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x08, 0x00, 0x04,
+        0x00,
+    ];
+
+    #[test]
+    fn test_scan_list_attr_bits() {
+        // Erased EEPROM, then three channels' records and attribute bytes
+        let mut image = TestImage::new(MEMSIZE, 0xFF);
+        for index in 0..3 {
+            image.set_entry(MEMORY_OFFSET, MEMORY_SIZE, index, &SIMPLEX_RECORD);
+        }
+        image
+            // Scan list 2 only, compander 2, and a stale free flag
+            .set(ATTR_OFFSET, &[SCANLIST2 | 0x20 | IS_FREE | 2])
+            // In no scan list
+            .set(ATTR_OFFSET + 1, &[2])
+            // A zeroed frequency marks the channel unused like 0xFF does
+            .set_entry(MEMORY_OFFSET, MEMORY_SIZE, 2, &[0x00; 4]);
+        let mut radio = image.load(UVK5Radio::new());
+
+        let listed = radio.get_memory(1).unwrap().unwrap();
+        assert_eq!(listed.skip, Skip::None);
+        let unlisted = radio.get_memory(2).unwrap().unwrap();
+        assert_eq!(unlisted.skip, Skip::Skip);
+        assert!(radio.get_memory(3).unwrap().is_none());
+        let model = radio.bank_model().unwrap();
+        assert_eq!(model.get_memory_banks(1).unwrap(), vec![1]);
+        assert!(model.get_memory_banks(2).unwrap().is_empty());
+
+        // Moving to UHF changes the band code and clears the free flag, but
+        // keeps the list and compander bits
+        let mut moved = listed.clone();
+        moved.freq = 435_000_000;
+        radio.set_memory(&moved).unwrap();
+        assert_eq!(radio.attr(0).unwrap(), SCANLIST2 | 0x20 | 5);
+
+        // Skip drops both lists, and un-skipping falls back to list 1
+        moved.skip = Skip::Skip;
+        radio.set_memory(&moved).unwrap();
+        assert_eq!(radio.attr(0).unwrap(), 0x20 | 5);
+        let mut unskipped = unlisted.clone();
+        unskipped.skip = Skip::None;
+        radio.set_memory(&unskipped).unwrap();
+        assert_eq!(radio.attr(1).unwrap(), SCANLIST1 | 2);
+    }
+
+    #[test]
+    fn test_record_quirks() {
+        let mut image = TestImage::new(MEMSIZE, 0xFF);
+        image
+            // 446.00625 NFM, low power, 6.25 kHz step. Transmits tone 88.5
+            // (flag 1, index 8) and receives DCS 754 inverted (flag 3, index
+            // 0x67). Busy lock and reverse are on, and the DTMF and scrambler
+            // bytes are set.
+            .set_entry(
+                MEMORY_OFFSET,
+                MEMORY_SIZE,
+                4,
+                &[
+                    0x31, 0x8D, 0xA8, 0x02, 0x00, 0x00, 0x00, 0x00, 0x67, 0x08, 0x13, 0x00, 0x13,
+                    0x5A, 0x02, 0x03,
+                ],
+            )
+            .set(ATTR_OFFSET + 4, &[SCANLIST1 | 5])
+            // NUL-terminated, with the rest of the slot still erased
+            .set_entry(NAMES_OFFSET, NAME_SLOT_SIZE, 4, b"Net\0")
+            // Second copy whose name runs straight into the 0xFF padding
+            .copy_entry(MEMORY_OFFSET, MEMORY_SIZE, 4, 5)
+            .set(ATTR_OFFSET + 5, &[SCANLIST1 | 5])
+            .set_entry(NAMES_OFFSET, NAME_SLOT_SIZE, 5, b"Ch6");
+        let mut radio = image.load(UVK5Radio::new());
+
+        let mem = radio.get_memory(5).unwrap().unwrap();
+        assert_eq!(mem.freq, 446_006_250);
+        assert_eq!(
+            (mem.tmode, mem.cross_mode),
+            (ToneMode::Cross, CrossMode::ToneToDtcs)
+        );
+        assert_eq!((mem.rtone, mem.rx_dtcs), (88.5, 754));
+        assert_eq!(mem.dtcs_polarity, DtcsPolarity::NR);
+        assert_eq!((mem.mode, mem.tuning_step), (Mode::Nfm, 6.25));
+        assert_eq!(mem.power.as_ref().unwrap().label(), "Low");
+        assert_eq!(mem.name, "Net");
+        assert_eq!(radio.get_memory(6).unwrap().unwrap().name, "Ch6");
+
+        let mut edited = mem.clone();
+        edited.rx_dtcs = 23;
+        edited.name = "Net 2".to_string();
+        radio.set_memory(&edited).unwrap();
+        let record = radio
+            .read(MEMORY_OFFSET + 4 * MEMORY_SIZE, MEMORY_SIZE)
+            .unwrap();
+        // Code index changes, the inverted flag and the 0xF1 bits stay
+        assert_eq!(&record[8..11], &[0x00, 0x08, 0x13]);
+        assert_eq!(&record[12..], &[0x13, 0x5A, 0x02, 0x03]);
+        let name = radio
+            .read(NAMES_OFFSET + 4 * NAME_SLOT_SIZE, NAME_SLOT_SIZE)
+            .unwrap();
+        assert_eq!(&name[..6], b"Net 2\0");
+        assert!(name[6..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_feature_roundtrip() {
+        let mut radio = blank_radio();
+        let mut base = Memory::new(1);
+        base.freq = 145_500_000;
+        check_feature_roundtrip(&mut radio, &base);
+        base.freq = 446_000_000;
+        check_feature_roundtrip(&mut radio, &base);
+
+        // The radio has no reverse modes; coercion drops them
+        let mut reverse = Memory::new(1);
+        reverse.freq = 446_000_000;
+        reverse.tmode = ToneMode::TsqlR;
+        assert!(radio.set_memory(&reverse).is_err());
+        let (coerced, _) = radio.get_features().coerce_memory(&reverse);
+        assert_eq!(coerced.tmode, ToneMode::None);
+    }
+
+    #[tokio::test]
+    async fn test_framing() {
+        assert_eq!(crc16(b"123456789"), 0x31C3);
+
+        let payload = command(CMD_HELLO, &SESSION);
+        let packet = frame(&payload);
+        assert_eq!(&packet[..4], &[0xAB, 0xCD, 0x08, 0x00]);
+        assert_eq!(&packet[4..6], &[0x14 ^ 0x16, 0x05 ^ 0x6C]);
+        assert_eq!(&packet[packet.len() - 2..], &FOOTER);
+
+        let mut port = MockSerialPort::new();
+        port.push_read_data(&packet);
+        assert_eq!(receive(&mut port, CMD_HELLO).await.unwrap(), payload);
+
+        let mut port = MockSerialPort::new();
+        port.push_read_data(&packet);
+        assert!(matches!(
+            receive(&mut port, CMD_READ_REPLY).await,
+            Err(RadioError::InvalidResponse(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sync_in_over_mock() {
+        let image: Vec<u8> = (0..MEMSIZE).map(|i| (i / 3) as u8).collect();
+
+        let mut port = MockSerialPort::new();
+        let mut version = b"\x15\x05\x10\x00k5_2.01.26".to_vec();
+        version.resize(20, 0);
+        port.push_read_data(&frame(&version));
+        for offset in (0..MEMSIZE).step_by(CHUNK_SIZE) {
+            let mut args = (offset as u16).to_le_bytes().to_vec();
+            args.extend_from_slice(&[CHUNK_SIZE as u8, 0]);
+            args.extend_from_slice(&image[offset..offset + CHUNK_SIZE]);
+            port.push_read_data(&frame(&command(CMD_READ_REPLY, &args)));
+        }
+
+        let mut radio = UVK5Radio::new();
+        let mmap = radio.sync_in(&mut port, None).await.unwrap();
+        assert_eq!(mmap.get_packed(), &image[..]);
+        assert!(port.was_written(&frame(&command(CMD_HELLO, &SESSION))));
+        assert!(UVK5Radio::match_model(mmap.get_packed(), "k5.img"));
+        assert!(!UVK5Radio::match_model(&image[..0x1808], "uv5r.img"));
+    }
+
+    #[test]
+    fn test_memory_roundtrip() {
+        let mut radio = blank_radio();
+        assert!(radio.get_memory(1).unwrap().is_none());
+
+        let mut mem = Memory::new(200);
+        mem.freq = 145_612_500;
+        mem.name = "Repeater 1".to_string();
+        mem.duplex = Duplex::Minus;
+        mem.offset = 600_000;
+        mem.tmode = ToneMode::Cross;
+        mem.cross_mode = CrossMode::ToneToDtcs;
+        mem.rtone = 88.5;
+        mem.rx_dtcs = 754;
+        mem.dtcs_polarity = DtcsPolarity::NR;
+        mem.mode = Mode::Nfm;
+        mem.tuning_step = 12.5;
+        mem.power = Some(PowerLevel::from_watts("Mid", 3.0));
+        radio.set_memory(&mem).unwrap();
+
+        let read = radio.get_memory(200).unwrap().unwrap();
+        assert_eq!(read.freq, 145_612_500);
+        assert_eq!(read.name, "Repeater 1");
+        assert_eq!((read.duplex, read.offset), (Duplex::Minus, 600_000));
+        assert_eq!(
+            (read.tmode, read.cross_mode),
+            (ToneMode::Cross, CrossMode::ToneToDtcs)
+        );
+        assert_eq!((read.rtone, read.rx_dtcs), (88.5, 754));
+        assert_eq!(read.dtcs_polarity, DtcsPolarity::NR);
+        assert_eq!(read.mode, Mode::Nfm);
+        assert_eq!(read.tuning_step, 12.5);
+        assert_eq!(read.power.unwrap().label(), "Mid");
+        assert_eq!(read.skip, Skip::None);
+
+        // New channels join scan list 1; band 2 is 136-200 MHz
+        assert_eq!(radio.attr(199).unwrap(), SCANLIST1 | 2);
+        let model = radio.bank_model().unwrap();
+        model.add_memory_to_bank(200, 1).unwrap();
+        assert_eq!(model.get_memory_banks(200).unwrap(), vec![0, 1]);
+        assert_eq!(model.get_bank_memories(1).unwrap(), vec![200]);
+
+        mem.skip = Skip::Skip;
+        mem.tmode = ToneMode::Tsql;
+        mem.ctone = 100.0;
+        radio.set_memory(&mem).unwrap();
+        let read = radio.get_memory(200).unwrap().unwrap();
+        assert_eq!(read.skip, Skip::Skip);
+        assert_eq!((read.tmode, read.ctone), (ToneMode::Tsql, 100.0));
+
+        radio.delete_memory(200).unwrap();
+        assert!(radio.get_memory(200).unwrap().is_none());
+        assert_eq!(radio.attr(199).unwrap(), ATTR_UNUSED);
+
+        mem.freq = 30_000_000;
+        assert!(radio.set_memory(&mem).is_err());
+        mem.freq = 446_000_000;
+        mem.name = "Caf\u{e9}".to_string();
+        assert!(radio.set_memory(&mem).is_err());
+        mem.name = "Cafe".to_string();
+        mem.tuning_step = 7.5;
+        assert!(radio.set_memory(&mem).is_err());
+    }
+}
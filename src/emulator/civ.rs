@@ -0,0 +1,394 @@
+// Emulated Icom CI-V bus with one transceiver (IC-9700 by default)
+// Reference: src/serial/civ_protocol.rs (the host side of the same protocol)
+//
+// Frames are FE FE <dst> <src> <cmd> [sub] [data] FD. The radio answers
+// frames addressed to it with FB (OK), FA (NG) or a data reply. A CT-17
+// style level converter echoes every byte the host sends back onto the bus.
+
+use super::{Emulator, Fault, Line};
+use crate::bitwise::bcd;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::ops::RangeInclusive;
+
+const PREAMBLE: u8 = 0xFE;
+const END: u8 = 0xFD;
+const OK: u8 = 0xFB;
+const NG: u8 = 0xFA;
+
+const CMD_READ_ID: u8 = 0x19;
+const CMD_MEMORY: u8 = 0x1A;
+
+/// Body of an erase command (and of an empty slot in a read reply)
+const EMPTY_MARKER: u8 = 0xFF;
+
+/// Simulated CI-V transceiver holding per-band memory tables
+pub struct CivBusSimulator {
+    model_code: u8,
+    baud_rate: u32,
+    echo: bool,
+    bands: u8,
+    channels: RangeInclusive<u16>,
+    memories: BTreeMap<u8, BTreeMap<u16, Vec<u8>>>,
+    input: Vec<u8>,
+    output: VecDeque<u8>,
+    faults: HashMap<(u8, u16), Fault>,
+    writes: Vec<(u8, u16)>,
+    erases: Vec<(u8, u16)>,
+}
+
+impl CivBusSimulator {
+    /// Create a radio at `model_code` with IC-9700 defaults:
+    /// 19200 baud, bands 1-3, channels 1-99, no echo
+    pub fn new(model_code: u8) -> Self {
+        Self {
+            model_code,
+            baud_rate: 19200,
+            echo: false,
+            bands: 3,
+            channels: 1..=99,
+            memories: BTreeMap::new(),
+            input: Vec::new(),
+            output: VecDeque::new(),
+            faults: HashMap::new(),
+            writes: Vec::new(),
+            erases: Vec::new(),
+        }
+    }
+
+    /// Echo host frames back like a CT-17 style interface
+    pub fn with_echo(mut self, echo: bool) -> Self {
+        self.echo = echo;
+        self
+    }
+
+    /// Set the CI-V baud rate
+    pub fn with_baud_rate(mut self, baud_rate: u32) -> Self {
+        self.baud_rate = baud_rate;
+        self
+    }
+
+    /// Set the number of bands (memory banks 1..=bands)
+    pub fn with_bands(mut self, bands: u8) -> Self {
+        self.bands = bands;
+        self
+    }
+
+    /// Set the valid memory channel numbers
+    pub fn with_channels(mut self, channels: RangeInclusive<u16>) -> Self {
+        self.channels = channels;
+        self
+    }
+
+    /// Store a memory body (everything after bank and channel in a 0x1A 0x00 frame)
+    pub fn set_memory(&mut self, band: u8, channel: u16, body: Vec<u8>) {
+        self.memories.entry(band).or_default().insert(channel, body);
+    }
+
+    /// Get a stored memory body, None if the slot is empty
+    pub fn memory(&self, band: u8, channel: u16) -> Option<&[u8]> {
+        self.memories
+            .get(&band)
+            .and_then(|table| table.get(&channel))
+            .map(|body| body.as_slice())
+    }
+
+    /// Number of non-empty slots in a band
+    pub fn memory_count(&self, band: u8) -> usize {
+        self.memories.get(&band).map_or(0, |table| table.len())
+    }
+
+    /// Misbehave the next time the host reads or writes this slot
+    pub fn inject_fault(&mut self, band: u8, channel: u16, fault: Fault) {
+        self.faults.insert((band, channel), fault);
+    }
+
+    /// Slots written by the host, in order
+    pub fn writes(&self) -> &[(u8, u16)] {
+        &self.writes
+    }
+
+    /// Slots erased by the host, in order
+    pub fn erases(&self) -> &[(u8, u16)] {
+        &self.erases
+    }
+
+    /// Build a frame from this radio to `dst`
+    fn frame(&self, dst: u8, payload: &[u8]) -> Vec<u8> {
+        let mut frame = vec![PREAMBLE, PREAMBLE, dst, self.model_code];
+        frame.extend_from_slice(payload);
+        frame.push(END);
+        frame
+    }
+
+    /// Queue a reply frame to the controller
+    fn reply(&mut self, dst: u8, payload: &[u8]) {
+        let frame = self.frame(dst, payload);
+        self.output.extend(frame);
+    }
+
+    /// Pull complete frames out of the input buffer
+    fn process_frames(&mut self) {
+        while let Some(end) = self.input.iter().position(|&b| b == END) {
+            let frame: Vec<u8> = self.input.drain(..=end).collect();
+            // Drop any noise before the preamble
+            let Some(start) = frame.windows(2).position(|w| w == [PREAMBLE, PREAMBLE]) else {
+                continue;
+            };
+            let frame = &frame[start..];
+            // FE FE dst src cmd FD at minimum
+            if frame.len() < 6 {
+                continue;
+            }
+            let (dst, src) = (frame[2], frame[3]);
+            if dst != self.model_code {
+                // Someone else's frame (or the echo probe); ignore it
+                continue;
+            }
+            let body = frame[4..frame.len() - 1].to_vec();
+            self.handle_command(src, &body);
+        }
+    }
+
+    fn handle_command(&mut self, src: u8, body: &[u8]) {
+        match body {
+            [CMD_READ_ID, 0x00] => {
+                let id = self.model_code;
+                self.reply(src, &[CMD_READ_ID, 0x00, id]);
+            }
+            [CMD_MEMORY, 0x00, band, ch_hi, ch_lo, rest @ ..] => {
+                let channel = bcd::bcd_to_int_be(&[*ch_hi, *ch_lo]).ok().map(|c| c as u16);
+                match channel {
+                    Some(channel)
+                        if (1..=self.bands).contains(band) && self.channels.contains(&channel) =>
+                    {
+                        self.handle_memory(src, *band, channel, [*ch_hi, *ch_lo], rest)
+                    }
+                    _ => self.reply(src, &[NG]),
+                }
+            }
+            _ => self.reply(src, &[NG]),
+        }
+    }
+
+    fn handle_memory(&mut self, src: u8, band: u8, channel: u16, ch_bcd: [u8; 2], body: &[u8]) {
+        let fault = self.faults.remove(&(band, channel));
+        match fault {
+            Some(Fault::Timeout) => return,
+            Some(Fault::Nak) => return self.reply(src, &[NG]),
+            _ => {}
+        }
+
+        let payload = match body {
+            // Read
+            [] => {
+                let mut payload = vec![CMD_MEMORY, 0x00, band, ch_bcd[0], ch_bcd[1]];
+                match self.memory(band, channel) {
+                    Some(stored) => payload.extend_from_slice(stored),
+                    None => payload.push(EMPTY_MARKER),
+                }
+                payload
+            }
+            // Erase
+            [EMPTY_MARKER] => {
+                if let Some(table) = self.memories.get_mut(&band) {
+                    table.remove(&channel);
+                }
+                self.erases.push((band, channel));
+                vec![OK]
+            }
+            // Write
+            _ => {
+                self.set_memory(band, channel, body.to_vec());
+                self.writes.push((band, channel));
+                vec![OK]
+            }
+        };
+
+        let mut frame = self.frame(src, &payload);
+        if let Some(Fault::ShortRead(n)) = fault {
+            frame.truncate(n);
+        }
+        self.output.extend(frame);
+    }
+}
+
+impl Emulator for CivBusSimulator {
+    fn receive(&mut self, data: &[u8], line: &Line) {
+        if line.baud_rate.is_some_and(|baud| baud != self.baud_rate) {
+            tracing::trace!(
+                "emulator: dropped {} bytes at {:?} baud",
+                data.len(),
+                line.baud_rate
+            );
+            return;
+        }
+
+        if self.echo {
+            self.output.extend(data);
+        }
+        self.input.extend_from_slice(data);
+        self.process_frames();
+    }
+
+    fn output(&mut self) -> &mut VecDeque<u8> {
+        &mut self.output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Memory, Mode};
+    use crate::drivers::icomciv::{IcomCivRadio, IC705, IC9700};
+    use crate::drivers::{CommandModeRadio, RadioError};
+    use crate::emulator::EmulatedPort;
+
+    fn memory(number: u32, band: u8, freq: u64, name: &str) -> Memory {
+        let mut mem = Memory::new(number);
+        mem.freq = freq;
+        mem.name = name.to_string();
+        mem.mode = Mode::Fm;
+        mem.band = Some(band);
+        mem
+    }
+
+    fn port(radio: CivBusSimulator) -> EmulatedPort<CivBusSimulator> {
+        EmulatedPort::new(radio).with_baud_rate(19200)
+    }
+
+    async fn roundtrip(echo: bool) {
+        let mut port = port(CivBusSimulator::new(0xA2).with_echo(echo));
+        let memories = vec![
+            memory(1, 1, 146_520_000, "CALL"),
+            memory(42, 2, 446_000_000, "UHF"),
+            memory(99, 3, 1_293_000_000, "23CM"),
+        ];
+
+        IcomCivRadio::new(&IC9700)
+            .upload(&mut port, &memories, None)
+            .await
+            .unwrap();
+        assert_eq!(port.radio().writes(), &[(1, 1), (2, 42), (3, 99)]);
+        assert_eq!(port.radio().memory_count(2), 1);
+
+        let downloaded = IcomCivRadio::new(&IC9700)
+            .download(&mut port, None)
+            .await
+            .unwrap();
+        assert_eq!(downloaded.len(), 3 * 99);
+        let used: Vec<_> = downloaded.iter().filter(|m| !m.empty).collect();
+        assert_eq!(used.len(), 3);
+        for (got, want) in used.iter().zip(&memories) {
+            assert_eq!(got.number, want.number);
+            assert_eq!(got.band, want.band);
+            assert_eq!(got.freq, want.freq);
+            assert_eq!(got.name.trim(), want.name);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_roundtrip() {
+        roundtrip(false).await;
+    }
+
+    #[tokio::test]
+    async fn test_roundtrip_with_echo() {
+        roundtrip(true).await;
+    }
+
+    #[tokio::test]
+    async fn test_single_bank_profile() {
+        let mut port = port(CivBusSimulator::new(0xA4).with_bands(1));
+        let mut mem = memory(10, 1, 7_074_000, "FT8");
+        mem.mode = Mode::Usb;
+        mem.band = None;
+
+        IcomCivRadio::new(&IC705)
+            .upload(&mut port, std::slice::from_ref(&mem), None)
+            .await
+            .unwrap();
+        assert_eq!(port.radio().writes(), &[(1, 10)]);
+        assert_eq!(port.radio().memory(1, 10).map(|body| body.len()), Some(64));
+
+        let downloaded = IcomCivRadio::new(&IC705)
+            .download(&mut port, None)
+            .await
+            .unwrap();
+        assert_eq!(downloaded.len(), 99);
+        let got = &downloaded[9];
+        assert_eq!((got.freq, got.mode, got.band), (7_074_000, Mode::Usb, None));
+    }
+
+    #[tokio::test]
+    async fn test_erase() {
+        let mut port = port(CivBusSimulator::new(0xA2));
+        let mem = memory(5, 1, 145_000_000, "GONE");
+        IcomCivRadio::new(&IC9700)
+            .upload(&mut port, std::slice::from_ref(&mem), None)
+            .await
+            .unwrap();
+        assert!(port.radio().memory(1, 5).is_some());
+
+        let mut erased = Memory::new_empty(5);
+        erased.band = Some(1);
+        IcomCivRadio::new(&IC9700)
+            .upload(&mut port, &[erased], None)
+            .await
+            .unwrap();
+        assert_eq!(port.radio().erases(), &[(1, 5)]);
+        assert!(port.radio().memory(1, 5).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_ng_reply() {
+        let mut radio = CivBusSimulator::new(0xA2);
+        radio.inject_fault(2, 7, Fault::Nak);
+        let mut port = port(radio);
+
+        let result = IcomCivRadio::new(&IC9700)
+            .upload(&mut port, &[memory(7, 2, 440_000_000, "NG")], None)
+            .await;
+        assert!(matches!(result, Err(RadioError::Radio(_))), "{:?}", result);
+        assert!(port.radio().writes().is_empty());
+    }
+
+    #[test]
+    fn test_addressing_and_baud() {
+        let mut radio = CivBusSimulator::new(0xA2);
+        let mut line = Line {
+            baud_rate: Some(9600),
+            dtr: false,
+            rts: false,
+        };
+        let read_id = [PREAMBLE, PREAMBLE, 0xA2, 0xE0, CMD_READ_ID, 0x00, END];
+
+        radio.receive(&read_id, &line);
+        assert!(radio.output().is_empty());
+
+        line.baud_rate = Some(19200);
+        // Frames for another radio are ignored
+        radio.receive(
+            &[PREAMBLE, PREAMBLE, 0x94, 0xE0, CMD_READ_ID, 0x00, END],
+            &line,
+        );
+        assert!(radio.output().is_empty());
+
+        radio.receive(&read_id, &line);
+        assert_eq!(
+            radio.output().drain(..).collect::<Vec<_>>(),
+            vec![PREAMBLE, PREAMBLE, 0xE0, 0xA2, CMD_READ_ID, 0x00, 0xA2, END]
+        );
+
+        // Channel 0 is out of range
+        radio.receive(
+            &[
+                PREAMBLE, PREAMBLE, 0xA2, 0xE0, CMD_MEMORY, 0x00, 1, 0x00, 0x00, END,
+            ],
+            &line,
+        );
+        assert_eq!(
+            radio.output().drain(..).collect::<Vec<_>>(),
+            vec![PREAMBLE, PREAMBLE, 0xE0, 0xA2, NG, END]
+        );
+    }
+}
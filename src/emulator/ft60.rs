@@ -0,0 +1,217 @@
+// Emulated Yaesu FT-60R
+// Reference: src/drivers/ft60.rs (the host side of the same protocol)
+//
+// The clone cable is a single wire, so every byte the host sends comes back
+// as an echo. When the user starts a clone from the radio, it sends an 8-byte
+// header and then 64-byte chunks, each one only after the host's ACK. In
+// receive mode it ACKs the header and every chunk, and anything the host sends
+// before reading that ACK overruns the radio's buffer and ends the transfer.
+
+use super::{Emulator, Line};
+use crate::drivers::ft60::{CHUNK_SIZE, HEADER_SIZE, MEMSIZE};
+use crate::memmap::MemoryMap;
+use std::collections::VecDeque;
+
+const BAUD_RATE: u32 = 9600;
+const ACK: u8 = 0x06;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Clone receive mode, next chunk goes at this offset
+    Receive(usize),
+    /// Sending to the host, next chunk starts at this offset
+    Send(usize),
+    /// Host overran the receive buffer; ignore everything
+    Failed,
+}
+
+/// Software FT-60R backed by a memory map of the whole image
+pub struct FT60Emulator {
+    mmap: MemoryMap,
+    state: State,
+    input: Vec<u8>,
+    output: VecDeque<u8>,
+    /// Bytes queued for the host so far
+    queued: usize,
+    /// Position of the last ACK in the queued byte count
+    last_ack: Option<usize>,
+}
+
+impl FT60Emulator {
+    /// Create an emulated FT-60R in clone receive mode
+    ///
+    /// The image is padded with 0xFF to the radio's size.
+    pub fn new(mmap: MemoryMap) -> Self {
+        let mut data = mmap.get_packed().to_vec();
+        data.resize(MEMSIZE, 0xFF);
+        Self {
+            mmap: MemoryMap::new(data),
+            state: State::Receive(0),
+            input: Vec::new(),
+            output: VecDeque::new(),
+            queued: 0,
+            last_ack: None,
+        }
+    }
+
+    /// Press the radio's clone send button
+    pub fn start_send(&mut self) {
+        self.input.clear();
+        self.state = State::Send(HEADER_SIZE);
+        let header = self.mmap.get_packed()[..HEADER_SIZE].to_vec();
+        self.reply(&header);
+    }
+
+    /// Get the radio's memory
+    pub fn mmap(&self) -> &MemoryMap {
+        &self.mmap
+    }
+
+    /// Check if the host sent data before reading an ACK
+    pub fn overrun(&self) -> bool {
+        self.state == State::Failed
+    }
+
+    fn reply(&mut self, bytes: &[u8]) {
+        self.output.extend(bytes);
+        self.queued += bytes.len();
+    }
+
+    fn ack(&mut self) {
+        self.last_ack = Some(self.queued);
+        self.reply(&[ACK]);
+    }
+
+    /// Check if the host hasn't read the last ACK yet
+    fn ack_unread(&self) -> bool {
+        self.last_ack
+            .is_some_and(|pos| self.queued - self.output.len() <= pos)
+    }
+
+    fn receive_byte(&mut self, byte: u8, pos: usize) {
+        if self.input.is_empty() && self.ack_unread() {
+            tracing::warn!(
+                "emulator: host sent data at 0x{:04X} before reading the ACK",
+                pos
+            );
+            self.state = State::Failed;
+            return;
+        }
+
+        self.input.push(byte);
+        let len = if pos == 0 {
+            HEADER_SIZE
+        } else {
+            CHUNK_SIZE.min(MEMSIZE - pos)
+        };
+        if self.input.len() == len {
+            let chunk = std::mem::take(&mut self.input);
+            // Chunks never run past MEMSIZE
+            self.mmap.set_bytes(pos, &chunk).ok();
+            self.state = State::Receive(pos + len);
+            self.ack();
+        }
+    }
+
+    fn send_next(&mut self, byte: u8, pos: usize) {
+        if byte != ACK {
+            tracing::warn!(
+                "emulator: expected ACK at 0x{:04X}, got 0x{:02X}",
+                pos,
+                byte
+            );
+            self.state = State::Receive(0);
+            return;
+        }
+        if pos >= MEMSIZE {
+            self.state = State::Receive(0);
+            return;
+        }
+        let end = (pos + CHUNK_SIZE).min(MEMSIZE);
+        let chunk = self.mmap.get_packed()[pos..end].to_vec();
+        self.reply(&chunk);
+        self.state = State::Send(end);
+    }
+}
+
+impl Emulator for FT60Emulator {
+    fn receive(&mut self, data: &[u8], line: &Line) {
+        if line.baud_rate.is_some_and(|baud| baud != BAUD_RATE) {
+            tracing::trace!(
+                "emulator: dropped {} bytes at {:?} baud",
+                data.len(),
+                line.baud_rate
+            );
+            return;
+        }
+
+        for &byte in data {
+            // The cable echoes before the radio sees anything
+            self.reply(&[byte]);
+            match self.state {
+                State::Receive(pos) if pos < MEMSIZE => self.receive_byte(byte, pos),
+                State::Send(pos) => self.send_next(byte, pos),
+                State::Receive(_) | State::Failed => {}
+            }
+        }
+    }
+
+    fn output(&mut self) -> &mut VecDeque<u8> {
+        &mut self.output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_image() -> Vec<u8> {
+        (0..MEMSIZE).map(|i| (i * 31 + i / 256) as u8).collect()
+    }
+
+    #[test]
+    fn test_send_waits_for_ack() {
+        let image = test_image();
+        let line = Line {
+            baud_rate: Some(BAUD_RATE),
+            dtr: false,
+            rts: false,
+        };
+        let mut emulator = FT60Emulator::new(MemoryMap::new(image.clone()));
+        emulator.start_send();
+        assert_eq!(emulator.output().drain(..).collect::<Vec<_>>(), image[..8]);
+
+        // Nothing more until the ACK, which is echoed ahead of the next chunk
+        assert!(emulator.output().is_empty());
+        emulator.receive(&[ACK], &line);
+        let reply: Vec<u8> = emulator.output().drain(..).collect();
+        assert_eq!(reply[0], ACK);
+        assert_eq!(&reply[1..], &image[8..8 + CHUNK_SIZE]);
+    }
+
+    #[test]
+    fn test_chunk_before_ack_rejected() {
+        let image = test_image();
+        let line = Line {
+            baud_rate: Some(BAUD_RATE),
+            dtr: false,
+            rts: false,
+        };
+
+        // Header and first chunk in one go, without waiting for the ACK
+        let mut emulator = FT60Emulator::new(MemoryMap::new_with_size(0));
+        emulator.receive(&image[..HEADER_SIZE + CHUNK_SIZE], &line);
+        assert!(emulator.overrun());
+        let mut expected = image[..HEADER_SIZE + CHUNK_SIZE].to_vec();
+        expected.insert(HEADER_SIZE, ACK);
+        assert_eq!(emulator.output().drain(..).collect::<Vec<_>>(), expected);
+
+        // The same bytes with the echo and ACK read in between are fine
+        let mut emulator = FT60Emulator::new(MemoryMap::new_with_size(0));
+        emulator.receive(&image[..HEADER_SIZE], &line);
+        assert_eq!(emulator.output().drain(..).next_back(), Some(ACK));
+        emulator.receive(&image[HEADER_SIZE..HEADER_SIZE + CHUNK_SIZE], &line);
+        assert!(!emulator.overrun());
+        assert_eq!(emulator.output().back(), Some(&ACK));
+    }
+}
@@ -0,0 +1,217 @@
+// Software radios for testing drivers without hardware
+//
+// Each emulator implements the radio side of a wire protocol. Wrap one in an
+// `EmulatedPort` to hand it straight to a driver as a `Transport`, or run it
+// behind a socket/pty with `serve`.
+
+pub mod civ;
+pub mod ft60;
+pub mod thd75;
+pub mod uv5r;
+
+pub use civ::CivBusSimulator;
+pub use ft60::FT60Emulator;
+pub use thd75::THD75Emulator;
+pub use uv5r::{UV5REmulator, UV5RFirmware};
+
+use crate::drivers::traits::BoxFuture;
+use crate::serial::comm::{Result, SerialError};
+use crate::serial::Transport;
+use std::collections::VecDeque;
+use std::io::{self, Read, Write};
+use std::time::Duration;
+
+/// Misbehaviour an emulator can be told to inject
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// Reject the transfer with a NAK (0x15)
+    Nak,
+    /// Send only the first N bytes of the reply, then go quiet
+    ShortRead(usize),
+    /// Don't answer at all
+    Timeout,
+}
+
+/// Host-side line settings seen by the emulated radio
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line {
+    /// Baud rate, or None when the link can't carry it (plain sockets/ptys)
+    pub baud_rate: Option<u32>,
+    pub dtr: bool,
+    pub rts: bool,
+}
+
+/// Radio side of an emulated link
+pub trait Emulator {
+    /// Handle bytes sent by the host
+    fn receive(&mut self, data: &[u8], line: &Line);
+
+    /// Bytes queued for the host
+    fn output(&mut self) -> &mut VecDeque<u8>;
+}
+
+/// In-process `Transport` connected to an emulated radio
+pub struct EmulatedPort<E: Emulator> {
+    radio: E,
+    line: Line,
+    timeout: Duration,
+    name: String,
+}
+
+impl<E: Emulator> EmulatedPort<E> {
+    /// Connect to the given emulator at 9600 baud
+    pub fn new(radio: E) -> Self {
+        Self {
+            radio,
+            line: Line {
+                baud_rate: Some(9600),
+                dtr: false,
+                rts: false,
+            },
+            timeout: Duration::from_secs(2),
+            name: "emulator".to_string(),
+        }
+    }
+
+    /// Start the host side at a different baud rate
+    pub fn with_baud_rate(mut self, baud_rate: u32) -> Self {
+        self.line.baud_rate = Some(baud_rate);
+        self
+    }
+
+    /// Get the emulated radio
+    pub fn radio(&self) -> &E {
+        &self.radio
+    }
+
+    /// Get the emulated radio mutably (e.g. to inject faults mid-test)
+    pub fn radio_mut(&mut self) -> &mut E {
+        &mut self.radio
+    }
+
+    /// Disconnect and return the emulated radio
+    pub fn into_radio(self) -> E {
+        self.radio
+    }
+
+    /// Current host line settings
+    pub fn line(&self) -> Line {
+        self.line
+    }
+}
+
+impl<E: Emulator> Transport for EmulatedPort<E> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Reads never block: an empty queue is reported as a timeout straight away
+    fn read<'a>(&'a mut self, buf: &'a mut [u8]) -> BoxFuture<'a, Result<usize>> {
+        Box::pin(async move {
+            let output = self.radio.output();
+            if output.is_empty() {
+                return Err(SerialError::Timeout(self.timeout));
+            }
+            let n = buf.len().min(output.len());
+            for (dst, src) in buf.iter_mut().zip(output.drain(..n)) {
+                *dst = src;
+            }
+            Ok(n)
+        })
+    }
+
+    fn write<'a>(&'a mut self, buf: &'a [u8]) -> BoxFuture<'a, Result<usize>> {
+        Box::pin(async move {
+            self.radio.receive(buf, &self.line);
+            Ok(buf.len())
+        })
+    }
+
+    fn flush(&mut self) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn clear_input(&mut self) -> Result<()> {
+        self.radio.output().clear();
+        Ok(())
+    }
+
+    fn clear_output(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn set_dtr(&mut self, level: bool) -> Result<()> {
+        self.line.dtr = level;
+        Ok(())
+    }
+
+    fn set_rts(&mut self, level: bool) -> Result<()> {
+        self.line.rts = level;
+        Ok(())
+    }
+
+    fn set_baud_rate(&mut self, baud_rate: u32) -> Result<()> {
+        self.line.baud_rate = Some(baud_rate);
+        Ok(())
+    }
+}
+
+/// Run an emulator over a byte stream (TCP socket, pty master, ...) until it closes
+///
+/// Line settings don't travel over a plain stream, so the emulator sees no baud
+/// rate and skips any baud checks.
+pub fn serve<E: Emulator, S: Read + Write>(radio: &mut E, mut stream: S) -> io::Result<()> {
+    let line = Line {
+        baud_rate: None,
+        dtr: true,
+        rts: false,
+    };
+    let mut buf = [0u8; 1024];
+    loop {
+        let n = match stream.read(&mut buf) {
+            Ok(0) => return Ok(()),
+            Ok(n) => n,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        radio.receive(&buf[..n], &line);
+
+        let reply: Vec<u8> = radio.output().drain(..).collect();
+        if !reply.is_empty() {
+            stream.write_all(&reply)?;
+            stream.flush()?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memmap::MemoryMap;
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+
+    #[test]
+    fn test_serve_over_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let (conn, _) = listener.accept().unwrap();
+            let mut radio = THD75Emulator::new(MemoryMap::new_with_size(256));
+            serve(&mut radio, conn).unwrap();
+        });
+
+        let mut client = TcpStream::connect(addr).unwrap();
+        client.write_all(b"ID\r").unwrap();
+        let mut reply = [0u8; 10];
+        client.read_exact(&mut reply).unwrap();
+        assert_eq!(&reply, b"ID TH-D75\r");
+
+        drop(client);
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,338 @@
+// Emulated Kenwood TH-D75/TH-D74
+// Reference: src/drivers/thd75.rs (the host side of the same protocol)
+//
+// Command mode (9600 baud): CR-terminated text commands, `ID` and
+// `0M PROGRAM`. Program mode (57600 baud): binary `R`/`W` 256-byte blocks,
+// `E` returns to command mode.
+
+use super::{Emulator, Fault, Line};
+use crate::memmap::MemoryMap;
+use std::collections::{HashMap, VecDeque};
+
+const BLOCK_SIZE: usize = 256;
+const COMMAND_BAUD: u32 = 9600;
+const PROGRAM_BAUD: u32 = 57600;
+const ACK: u8 = 0x06;
+const NAK: u8 = 0x15;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Command,
+    Program,
+}
+
+/// Software TH-D75 backed by a memory map
+pub struct THD75Emulator {
+    mmap: MemoryMap,
+    model: String,
+    mode: Mode,
+    input: Vec<u8>,
+    output: VecDeque<u8>,
+    /// Reply to send once the host ACKs a block we sent
+    pending_ack: Option<u8>,
+    faults: HashMap<u16, Fault>,
+    written: Vec<u16>,
+}
+
+impl THD75Emulator {
+    /// Create an emulated TH-D75 with the given memory contents
+    pub fn new(mmap: MemoryMap) -> Self {
+        Self {
+            mmap,
+            model: "TH-D75".to_string(),
+            mode: Mode::Command,
+            input: Vec::new(),
+            output: VecDeque::new(),
+            pending_ack: None,
+            faults: HashMap::new(),
+            written: Vec::new(),
+        }
+    }
+
+    /// Report a different model in the `ID` reply (e.g. "TH-D74")
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// Misbehave the next time the host reads or writes `block`
+    pub fn inject_fault(&mut self, block: u16, fault: Fault) {
+        self.faults.insert(block, fault);
+    }
+
+    /// Get the radio's memory
+    pub fn mmap(&self) -> &MemoryMap {
+        &self.mmap
+    }
+
+    /// Blocks written by the host, in order
+    pub fn written_blocks(&self) -> &[u16] {
+        &self.written
+    }
+
+    /// Check if the radio is in programming mode
+    pub fn in_program_mode(&self) -> bool {
+        self.mode == Mode::Program
+    }
+
+    fn reply(&mut self, bytes: &[u8]) {
+        self.output.extend(bytes);
+    }
+
+    /// Handle complete CR-terminated commands
+    fn process_commands(&mut self) {
+        while self.mode == Mode::Command {
+            let Some(end) = self.input.iter().position(|&b| b == b'\r') else {
+                return;
+            };
+            let line: Vec<u8> = self.input.drain(..=end).collect();
+            let command = String::from_utf8_lossy(&line[..end]).trim().to_string();
+            tracing::trace!("emulator: command {:?}", command);
+
+            match command.as_str() {
+                "ID" => {
+                    let reply = format!("ID {}\r", self.model);
+                    self.reply(reply.as_bytes());
+                }
+                "0M PROGRAM" => {
+                    self.reply(b"0M\r");
+                    self.mode = Mode::Program;
+                }
+                _ => self.reply(b"?\r"),
+            }
+        }
+    }
+
+    /// Handle complete binary frames; returns false when more input is needed
+    fn process_frame(&mut self) -> bool {
+        if let Some(reply) = self.pending_ack {
+            let Some(&byte) = self.input.first() else {
+                return false;
+            };
+            self.input.remove(0);
+            self.pending_ack = None;
+            self.reply(&[if byte == ACK { reply } else { NAK }]);
+            return true;
+        }
+
+        match self.input.first() {
+            None => false,
+            Some(b'R') => {
+                if self.input.len() < 5 {
+                    return false;
+                }
+                let block = u16::from_be_bytes([self.input[1], self.input[2]]);
+                self.input.drain(..5);
+                self.read_block(block);
+                true
+            }
+            Some(b'W') => {
+                if self.input.len() < 5 {
+                    return false;
+                }
+                let size = match u16::from_be_bytes([self.input[3], self.input[4]]) {
+                    0 => BLOCK_SIZE,
+                    n => n as usize,
+                };
+                if self.input.len() < 5 + size {
+                    return false;
+                }
+                let block = u16::from_be_bytes([self.input[1], self.input[2]]);
+                let data: Vec<u8> = self.input.drain(..5 + size).skip(5).collect();
+                self.write_block(block, &data);
+                true
+            }
+            Some(b'E') => {
+                self.input.remove(0);
+                self.mode = Mode::Command;
+                true
+            }
+            Some(_) => {
+                // Line noise
+                self.input.remove(0);
+                true
+            }
+        }
+    }
+
+    fn read_block(&mut self, block: u16) {
+        let start = block as usize * BLOCK_SIZE;
+        let data = match self.mmap.get(start, Some(BLOCK_SIZE)) {
+            Ok(data) if data.len() == BLOCK_SIZE => data.to_vec(),
+            _ => return self.reply(&[NAK]),
+        };
+
+        let mut frame = vec![b'W'];
+        frame.extend_from_slice(&block.to_be_bytes());
+        frame.extend_from_slice(&[0x00, 0x00]);
+
+        match self.faults.remove(&block) {
+            Some(Fault::Timeout) => {}
+            Some(Fault::ShortRead(n)) => {
+                frame.extend_from_slice(&data);
+                frame.truncate(n);
+                self.reply(&frame);
+            }
+            Some(Fault::Nak) => {
+                frame.extend_from_slice(&data);
+                self.reply(&frame);
+                self.pending_ack = Some(NAK);
+            }
+            None => {
+                frame.extend_from_slice(&data);
+                self.reply(&frame);
+                self.pending_ack = Some(ACK);
+            }
+        }
+    }
+
+    fn write_block(&mut self, block: u16, data: &[u8]) {
+        match self.faults.remove(&block) {
+            // A write reply is a single ACK, so a short read is no reply at all
+            Some(Fault::Timeout) | Some(Fault::ShortRead(_)) => {}
+            Some(Fault::Nak) => self.reply(&[NAK]),
+            None => {
+                let start = block as usize * BLOCK_SIZE;
+                if start + data.len() > self.mmap.len() {
+                    return self.reply(&[NAK]);
+                }
+                // Bounds checked above
+                self.mmap.set_bytes(start, data).ok();
+                self.written.push(block);
+                self.reply(&[ACK]);
+            }
+        }
+    }
+}
+
+impl Emulator for THD75Emulator {
+    fn receive(&mut self, data: &[u8], line: &Line) {
+        let expected = match self.mode {
+            Mode::Command => COMMAND_BAUD,
+            Mode::Program => PROGRAM_BAUD,
+        };
+        if line.baud_rate.is_some_and(|baud| baud != expected) {
+            // Wrong speed: the radio only sees garbage
+            tracing::trace!(
+                "emulator: dropped {} bytes at {:?} baud",
+                data.len(),
+                line.baud_rate
+            );
+            return;
+        }
+
+        for &byte in data {
+            self.input.push(byte);
+            // Mode can change mid-buffer (e.g. "0M PROGRAM\r" then binary), so
+            // process after every byte
+            match self.mode {
+                Mode::Command => self.process_commands(),
+                Mode::Program => while self.process_frame() {},
+            }
+        }
+    }
+
+    fn output(&mut self) -> &mut VecDeque<u8> {
+        &mut self.output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drivers::thd75::THD75Radio;
+    use crate::drivers::{CloneModeRadio, RadioError};
+    use crate::emulator::EmulatedPort;
+
+    fn test_image() -> MemoryMap {
+        let path =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("test_data/radio_dump.bin");
+        MemoryMap::new(std::fs::read(path).expect("Failed to read radio_dump.bin"))
+    }
+
+    #[tokio::test]
+    async fn test_sync_in() {
+        let image = test_image();
+        let mut port = EmulatedPort::new(THD75Emulator::new(image.clone()));
+
+        let mut radio = THD75Radio::new();
+        let mmap = radio.sync_in(&mut port, None).await.unwrap();
+
+        assert_eq!(mmap, image);
+        assert!(!port.radio().in_program_mode());
+        assert_eq!(port.line().baud_rate, Some(9600));
+    }
+
+    #[tokio::test]
+    async fn test_sync_out() {
+        let mut image = test_image();
+        let mut port = EmulatedPort::new(THD75Emulator::new(MemoryMap::new_with_size(image.len())));
+
+        image.set_bytes(0x10000, b"EMULATED").unwrap();
+        let mut radio = THD75Radio::new();
+        radio.sync_out(&mut port, &image, None).await.unwrap();
+
+        let emulator = port.into_radio();
+        let blocks = image.len() / BLOCK_SIZE;
+        assert_eq!(emulator.written_blocks().len(), blocks - 2);
+        assert_eq!(emulator.mmap().get(0x10000, Some(8)).unwrap(), b"EMULATED");
+        // The last two blocks are never written
+        let tail = (blocks - 2) * BLOCK_SIZE;
+        assert!(emulator
+            .mmap()
+            .get(tail, None)
+            .unwrap()
+            .iter()
+            .all(|&b| b == 0));
+    }
+
+    #[tokio::test]
+    async fn test_injected_faults() {
+        let mut emulator = THD75Emulator::new(test_image());
+        emulator.inject_fault(3, Fault::Nak);
+        let mut port = EmulatedPort::new(emulator);
+        let result = THD75Radio::new().sync_in(&mut port, None).await;
+        assert!(matches!(result, Err(RadioError::Nak)));
+
+        for fault in [Fault::ShortRead(100), Fault::Timeout] {
+            let mut emulator = THD75Emulator::new(test_image());
+            emulator.inject_fault(1, fault);
+            let mut port = EmulatedPort::new(emulator);
+            let result = THD75Radio::new().sync_in(&mut port, None).await;
+            assert!(matches!(result, Err(RadioError::Serial(_))), "{:?}", fault);
+        }
+    }
+
+    #[test]
+    fn test_baud_and_commands() {
+        let mut emulator =
+            THD75Emulator::new(MemoryMap::new_with_size(BLOCK_SIZE)).with_model("TH-D74");
+        let mut line = Line {
+            baud_rate: Some(9600),
+            dtr: true,
+            rts: false,
+        };
+
+        emulator.receive(b"ID\r", &line);
+        assert_eq!(
+            emulator.output().drain(..).collect::<Vec<_>>(),
+            b"ID TH-D74\r"
+        );
+
+        emulator.receive(b"0M PROGRAM\r", &line);
+        assert!(emulator.in_program_mode());
+        emulator.output().clear();
+
+        // Still at 9600: the block request is lost
+        emulator.receive(b"R\x00\x00\x00\x00", &line);
+        assert!(emulator.output().is_empty());
+
+        line.baud_rate = Some(57600);
+        emulator.receive(b"R\x00\x00\x00\x00", &line);
+        assert_eq!(emulator.output().len(), 5 + BLOCK_SIZE);
+        emulator.output().clear();
+        emulator.receive(&[ACK, b'E'], &line);
+        assert_eq!(emulator.output().drain(..).collect::<Vec<_>>(), vec![ACK]);
+        assert!(!emulator.in_program_mode());
+    }
+}
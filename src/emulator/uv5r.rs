@@ -0,0 +1,473 @@
+// Emulated Baofeng UV-5R
+// Reference: src/drivers/uv5r.rs (the host side of the same protocol)
+//
+// Handshake: 7-byte magic -> ACK, 0x02 -> ident (8 or 12 bytes ending in
+// 0xDD), ACK -> ACK. Clone mode: "S" addr size -> "X" addr size data, and
+// "X" addr size data -> ACK. Everything runs at 9600 baud.
+
+use super::{Emulator, Fault, Line};
+use crate::drivers::uv5r::{
+    AUX_END, AUX_START, UPLOAD_SKIP_RANGES, UV5R_MODEL_291, UV5R_MODEL_A58, UV5R_MODEL_ORIG,
+    UV5R_MODEL_UV82,
+};
+use crate::memmap::MemoryMap;
+use std::collections::{HashMap, VecDeque};
+
+const BAUD_RATE: u32 = 9600;
+const ACK: u8 = 0x06;
+const NAK: u8 = 0x15;
+
+/// Image files carry the ident in front of the radio memory
+const IMAGE_HEADER_SIZE: usize = 8;
+
+/// Main block of an image (radio 0x0000-0x1800); the aux block follows it
+const MAIN_SIZE: usize = 0x1800;
+
+/// Firmware family, which decides the magic the radio answers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UV5RFirmware {
+    /// BFB291 and later, `UV5R_MODEL_291`
+    Model291,
+    /// Original firmware, `UV5R_MODEL_ORIG`
+    Orig,
+    /// Newer UV-5R and BF-F8HP firmware, `UV5R_MODEL_A58`
+    A58,
+    /// UV-82 family, `UV5R_MODEL_UV82`
+    UV82,
+}
+
+impl UV5RFirmware {
+    fn magic(self) -> &'static [u8] {
+        match self {
+            UV5RFirmware::Model291 => UV5R_MODEL_291,
+            UV5RFirmware::Orig => UV5R_MODEL_ORIG,
+            UV5RFirmware::A58 => UV5R_MODEL_A58,
+            UV5RFirmware::UV82 => UV5R_MODEL_UV82,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Waiting for the magic
+    Idle,
+    /// Magic ACKed, waiting for 0x02
+    IdentRequest,
+    /// Ident sent, waiting for the host's ACK
+    IdentAck,
+    /// Block reads and writes
+    Clone,
+}
+
+/// Software UV-5R backed by a memory map of radio addresses
+pub struct UV5REmulator {
+    mmap: MemoryMap,
+    firmware: UV5RFirmware,
+    ident: Vec<u8>,
+    state: State,
+    input: Vec<u8>,
+    output: VecDeque<u8>,
+    faults: HashMap<u16, Fault>,
+    writes: Vec<(u16, usize)>,
+    skip_range_writes: Vec<(u16, usize)>,
+}
+
+impl UV5REmulator {
+    /// Create an emulated UV-5R with the given radio memory (address 0 first)
+    ///
+    /// Memory past the end reads as 0xFF, up to the radio's 8 KiB.
+    pub fn new(mmap: MemoryMap) -> Self {
+        let mut data = mmap.get_packed().to_vec();
+        if data.len() < AUX_END {
+            data.resize(AUX_END, 0xFF);
+        }
+        Self {
+            mmap: MemoryMap::new(data),
+            firmware: UV5RFirmware::Model291,
+            ident: vec![0xAA, 0x30, 0x76, 0x04, 0x00, 0x05, 0x20, 0xDD],
+            state: State::Idle,
+            input: Vec::new(),
+            output: VecDeque::new(),
+            faults: HashMap::new(),
+            writes: Vec::new(),
+            skip_range_writes: Vec::new(),
+        }
+    }
+
+    /// Create an emulated UV-5R from a CHIRP image (ident header, main block,
+    /// then the aux block if present)
+    pub fn from_image(image: &MemoryMap) -> Self {
+        let data = image.get_packed();
+        let header = &data[..IMAGE_HEADER_SIZE.min(data.len())];
+        let body = data.get(IMAGE_HEADER_SIZE..).unwrap_or_default();
+
+        let mut memory = body[..MAIN_SIZE.min(body.len())].to_vec();
+        if let Some(aux) = body.get(MAIN_SIZE..) {
+            memory.resize(AUX_START, 0xFF);
+            memory.extend_from_slice(&aux[..aux.len().min(AUX_END - AUX_START)]);
+        }
+        Self::new(MemoryMap::new(memory)).with_ident(header.to_vec())
+    }
+
+    /// Answer the other firmware family's magic
+    pub fn with_firmware(mut self, firmware: UV5RFirmware) -> Self {
+        self.firmware = firmware;
+        self
+    }
+
+    /// Send a different ident (8 or 12 bytes, normally ending in 0xDD)
+    pub fn with_ident(mut self, ident: Vec<u8>) -> Self {
+        self.ident = ident;
+        self
+    }
+
+    /// Misbehave the next time the host reads or writes `addr`
+    pub fn inject_fault(&mut self, addr: u16, fault: Fault) {
+        self.faults.insert(addr, fault);
+    }
+
+    /// Get the radio's memory (radio addresses, no image header)
+    pub fn mmap(&self) -> &MemoryMap {
+        &self.mmap
+    }
+
+    /// Every write as (radio address, length), in order
+    pub fn writes(&self) -> &[(u16, usize)] {
+        &self.writes
+    }
+
+    /// Writes that touched an upload skip range
+    pub fn skip_range_writes(&self) -> &[(u16, usize)] {
+        &self.skip_range_writes
+    }
+
+    /// Check if the handshake has completed
+    pub fn in_clone_mode(&self) -> bool {
+        self.state == State::Clone
+    }
+
+    fn reply(&mut self, bytes: &[u8]) {
+        self.output.extend(bytes);
+    }
+
+    /// `UPLOAD_SKIP_RANGES` are image offsets; convert to radio addresses
+    fn in_skip_range(addr: usize, len: usize) -> bool {
+        UPLOAD_SKIP_RANGES.iter().any(|&(start, end)| {
+            let start = start - IMAGE_HEADER_SIZE;
+            let end = end - IMAGE_HEADER_SIZE;
+            addr < end && addr + len > start
+        })
+    }
+
+    /// Handle complete input; returns false when more bytes are needed
+    fn process(&mut self) -> bool {
+        match self.state {
+            State::Idle => {
+                let magic = self.firmware.magic();
+                if self.input.ends_with(magic) {
+                    self.input.clear();
+                    self.reply(&[ACK]);
+                    self.state = State::IdentRequest;
+                    return true;
+                }
+                // Keep only what could still become the magic
+                let keep = magic.len() - 1;
+                if self.input.len() > keep {
+                    self.input.drain(..self.input.len() - keep);
+                }
+                false
+            }
+            State::IdentRequest | State::IdentAck => {
+                let Some(&byte) = self.input.first() else {
+                    return false;
+                };
+                self.input.remove(0);
+                match (self.state, byte) {
+                    (State::IdentRequest, 0x02) => {
+                        let ident = self.ident.clone();
+                        self.reply(&ident);
+                        self.state = State::IdentAck;
+                    }
+                    (State::IdentAck, ACK) => {
+                        self.reply(&[ACK]);
+                        self.state = State::Clone;
+                    }
+                    _ => self.state = State::Idle,
+                }
+                true
+            }
+            State::Clone => match self.input.first() {
+                None => false,
+                Some(b'S') => {
+                    if self.input.len() < 4 {
+                        return false;
+                    }
+                    let addr = u16::from_be_bytes([self.input[1], self.input[2]]);
+                    let size = self.input[3];
+                    self.input.drain(..4);
+                    self.read_block(addr, size);
+                    true
+                }
+                Some(b'X') => {
+                    if self.input.len() < 4 {
+                        return false;
+                    }
+                    let size = self.input[3] as usize;
+                    if self.input.len() < 4 + size {
+                        return false;
+                    }
+                    let addr = u16::from_be_bytes([self.input[1], self.input[2]]);
+                    let data: Vec<u8> = self.input.drain(..4 + size).skip(4).collect();
+                    self.write_block(addr, &data);
+                    true
+                }
+                Some(_) => {
+                    // Host ACKs after each read and line noise need no reply
+                    self.input.remove(0);
+                    true
+                }
+            },
+        }
+    }
+
+    fn read_block(&mut self, addr: u16, size: u8) {
+        let data = match self.mmap.get(addr as usize, Some(size as usize)) {
+            Ok(data) if data.len() == size as usize => data.to_vec(),
+            _ => return self.reply(&[NAK]),
+        };
+
+        let mut frame = vec![b'X'];
+        frame.extend_from_slice(&addr.to_be_bytes());
+        frame.push(size);
+        frame.extend_from_slice(&data);
+
+        match self.faults.remove(&addr) {
+            Some(Fault::Timeout) => {}
+            Some(Fault::Nak) => self.reply(&[NAK]),
+            Some(Fault::ShortRead(n)) => {
+                frame.truncate(n);
+                self.reply(&frame);
+            }
+            None => self.reply(&frame),
+        }
+    }
+
+    fn write_block(&mut self, addr: u16, data: &[u8]) {
+        match self.faults.remove(&addr) {
+            // A write reply is a single ACK, so a short read is no reply at all
+            Some(Fault::Timeout) | Some(Fault::ShortRead(_)) => return,
+            Some(Fault::Nak) => return self.reply(&[NAK]),
+            None => {}
+        }
+
+        let start = addr as usize;
+        if start + data.len() > self.mmap.len() {
+            return self.reply(&[NAK]);
+        }
+
+        if Self::in_skip_range(start, data.len()) {
+            tracing::warn!(
+                "emulator: write to {:04X} ({} bytes) hits an upload skip range",
+                addr,
+                data.len()
+            );
+            self.skip_range_writes.push((addr, data.len()));
+        }
+
+        // Bounds checked above
+        self.mmap.set_bytes(start, data).ok();
+        self.writes.push((addr, data.len()));
+        self.reply(&[ACK]);
+    }
+}
+
+impl Emulator for UV5REmulator {
+    fn receive(&mut self, data: &[u8], line: &Line) {
+        if line.baud_rate.is_some_and(|baud| baud != BAUD_RATE) {
+            tracing::trace!(
+                "emulator: dropped {} bytes at {:?} baud",
+                data.len(),
+                line.baud_rate
+            );
+            return;
+        }
+
+        for &byte in data {
+            self.input.push(byte);
+            while self.process() {}
+        }
+    }
+
+    fn output(&mut self) -> &mut VecDeque<u8> {
+        &mut self.output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drivers::uv5r::UV5RRadio;
+    use crate::drivers::{CloneModeRadio, Radio, RadioError};
+    use crate::emulator::EmulatedPort;
+
+    /// Radio memory downloaded by the driver (0x0000-0x1800)
+    const RADIO_MEMSIZE: usize = 0x1800;
+
+    fn test_image() -> MemoryMap {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("test_data/Baofeng_UV-5R_20260211.img");
+        let data = std::fs::read(path).expect("Failed to read UV-5R test image");
+        MemoryMap::new(data[..IMAGE_HEADER_SIZE + RADIO_MEMSIZE].to_vec())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_sync_in_291() {
+        let image = test_image();
+        let mut port = EmulatedPort::new(UV5REmulator::from_image(&image));
+
+        let mmap = UV5RRadio::new().sync_in(&mut port, None).await.unwrap();
+
+        assert_eq!(
+            mmap.get(0, Some(8)).unwrap(),
+            image.get(0, Some(8)).unwrap()
+        );
+        assert_eq!(
+            mmap.get(IMAGE_HEADER_SIZE, Some(RADIO_MEMSIZE)).unwrap(),
+            image.get(IMAGE_HEADER_SIZE, Some(RADIO_MEMSIZE)).unwrap()
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_sync_in_orig_12_byte_ident() {
+        let ident = vec![
+            0xAA, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0xDD,
+        ];
+        let emulator = UV5REmulator::new(MemoryMap::new_with_size(RADIO_MEMSIZE))
+            .with_firmware(UV5RFirmware::Orig)
+            .with_ident(ident.clone());
+        let mut port = EmulatedPort::new(emulator);
+
+        let mmap = UV5RRadio::new().sync_in(&mut port, None).await.unwrap();
+
+        assert!(port.radio().in_clone_mode());
+        assert_eq!(mmap.get(0, Some(8)).unwrap(), &ident[..8]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_sync_in_aux_block_variant() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("test_data/Baofeng_UV-5R_20260211.img");
+        let mut data = std::fs::read(path).expect("Failed to read UV-5R test image");
+        data.truncate(IMAGE_HEADER_SIZE + MAIN_SIZE + (AUX_END - AUX_START));
+        // Firmware version of a UV-82HP (image offset 0x1838)
+        data[0x1838..0x1846].copy_from_slice(b"N82-3\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF");
+        let image = MemoryMap::new(data);
+
+        let emulator = UV5REmulator::from_image(&image).with_firmware(UV5RFirmware::UV82);
+        let mut port = EmulatedPort::new(emulator);
+        let mut radio = UV5RRadio::new();
+        let mmap = radio.sync_in(&mut port, None).await.unwrap();
+
+        assert_eq!(mmap.get_packed(), image.get_packed());
+        assert_eq!(radio.variant(), Some("UV-82HP"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_sync_in_without_aux_block() {
+        // No aux block, so the radio reports no firmware version
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("test_data/Baofeng_UV-5R_20260211.img");
+        let mut data = std::fs::read(path).expect("Failed to read UV-5R test image");
+        data.truncate(IMAGE_HEADER_SIZE + MAIN_SIZE);
+        let image = MemoryMap::new(data);
+
+        // Only the original firmware's magic names a single model, and the
+        // download keeps it
+        let emulator = UV5REmulator::from_image(&image).with_firmware(UV5RFirmware::Orig);
+        let mut port = EmulatedPort::new(emulator);
+        let mut radio = UV5RRadio::new();
+        let mmap = radio.sync_in(&mut port, None).await.unwrap();
+        assert_eq!(radio.variant(), Some("UV-5R"));
+        assert!(mmap.get_packed().ends_with(b"UV-5R\0\0\0"));
+
+        let mut loaded = UV5RRadio::new();
+        loaded.process_mmap(&mmap).unwrap();
+        assert_eq!(loaded.variant(), Some("UV-5R"));
+        let stored_len = mmap.len();
+
+        // The UV-82 magic doesn't tell the UV-82 and UV-82HP apart, nor the
+        // A58 magic the UV-5R and BF-F8HP, so nothing is stored
+        for firmware in [UV5RFirmware::UV82, UV5RFirmware::A58] {
+            let emulator = UV5REmulator::from_image(&image).with_firmware(firmware);
+            let mut port = EmulatedPort::new(emulator);
+            let mmap = radio.sync_in(&mut port, None).await.unwrap();
+            assert_eq!(radio.variant(), Some("UV-5R"));
+            assert_eq!(mmap.len(), stored_len - 8);
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_sync_out_respects_skip_ranges() {
+        let image = test_image();
+        let blank = MemoryMap::new(vec![0xFF; RADIO_MEMSIZE]);
+        let mut port = EmulatedPort::new(UV5REmulator::new(blank));
+
+        UV5RRadio::new()
+            .sync_out(&mut port, &image, None)
+            .await
+            .unwrap();
+
+        let emulator = port.into_radio();
+        assert!(emulator.skip_range_writes().is_empty());
+        assert_eq!(emulator.writes()[0], (0x0000, 0x10));
+
+        // Everything outside the skip ranges lands at the right radio address
+        for addr in 0..RADIO_MEMSIZE {
+            if UV5REmulator::in_skip_range(addr, 1) {
+                assert_eq!(emulator.mmap().get(addr, Some(1)).unwrap(), &[0xFF]);
+            } else {
+                assert_eq!(
+                    emulator.mmap().get(addr, Some(1)).unwrap(),
+                    image.get(addr + IMAGE_HEADER_SIZE, Some(1)).unwrap(),
+                    "radio address {:04X}",
+                    addr
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_skip_range_write_flagged() {
+        let mut emulator = UV5REmulator::new(MemoryMap::new_with_size(RADIO_MEMSIZE));
+        let line = Line {
+            baud_rate: Some(9600),
+            dtr: false,
+            rts: false,
+        };
+        emulator.receive(UV5R_MODEL_291, &line);
+        emulator.receive(&[0x02, ACK], &line);
+        assert!(emulator.in_clone_mode());
+        emulator.output().clear();
+
+        let mut frame = vec![b'X', 0x0C, 0xF0, 0x10];
+        frame.extend_from_slice(&[0x55; 0x10]);
+        emulator.receive(&frame, &line);
+
+        assert_eq!(emulator.output().drain(..).collect::<Vec<_>>(), vec![ACK]);
+        assert_eq!(emulator.skip_range_writes(), &[(0x0CF0, 0x10)]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_write_nak() {
+        let mut emulator = UV5REmulator::new(MemoryMap::new_with_size(RADIO_MEMSIZE));
+        emulator.inject_fault(0x0020, Fault::Nak);
+        let mut port = EmulatedPort::new(emulator);
+
+        let result = UV5RRadio::new()
+            .sync_out(&mut port, &test_image(), None)
+            .await;
+
+        assert!(matches!(result, Err(RadioError::InvalidResponse(_))));
+        assert_eq!(port.radio().writes().len(), 2);
+    }
+}
@@ -1,9 +1,15 @@
 //! CSV file format handler for import/export
-
-use crate::core::Memory;
-use std::fs::File;
-use std::io::{BufRead, BufReader, Write};
+//!
+//! Reads and writes the CSV layout Python CHIRP uses (RFC 4180, CRLF line
+//! endings), plus Bank and Band columns. Columns are matched by header name,
+//! so files with a subset of columns or a different order import too.
+
+use crate::core::{is_valid_dtcs, is_valid_tone, Memory, PowerLevel};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
 use std::path::Path;
+use std::str::FromStr;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -20,13 +26,86 @@ pub enum CsvError {
 
 pub type Result<T> = std::result::Result<T, CsvError>;
 
+/// A CSV row that wasn't imported
+#[derive(Debug, Clone)]
+pub struct RejectedRow {
+    /// Line the row starts on
+    pub line: usize,
+    pub reason: String,
+}
+
+/// A field that was replaced by a default during import
+#[derive(Debug, Clone)]
+pub struct FieldNote {
+    /// Line the row starts on
+    pub line: usize,
+    /// Memory location from the row
+    pub location: u32,
+    /// CSV column name
+    pub column: &'static str,
+    pub message: String,
+}
+
+/// Outcome of a CSV import
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    /// Imported memories, in file order
+    pub memories: Vec<Memory>,
+    /// Rows that were skipped
+    pub rejected: Vec<RejectedRow>,
+    /// Fields of imported memories that fell back to a default
+    pub notes: Vec<FieldNote>,
+}
+
+impl ImportReport {
+    /// True if every row imported without changes
+    pub fn is_clean(&self) -> bool {
+        self.rejected.is_empty() && self.notes.is_empty()
+    }
+
+    /// One-line summary, e.g. "Imported 12 rows, rejected 1, 2 fields defaulted"
+    pub fn summary(&self) -> String {
+        format!(
+            "Imported {} rows, rejected {}, {} fields defaulted",
+            self.memories.len(),
+            self.rejected.len(),
+            self.notes.len()
+        )
+    }
+}
+
+impl fmt::Display for ImportReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.summary())?;
+        for row in &self.rejected {
+            write!(f, "\nLine {}: rejected: {}", row.line, row.reason)?;
+        }
+        for note in &self.notes {
+            write!(
+                f,
+                "\nLine {} (memory #{}): {}: {}",
+                note.line, note.location, note.column, note.message
+            )?;
+        }
+        Ok(())
+    }
+}
+
 /// Export memories to CSV file
 pub fn export_csv(filename: impl AsRef<Path>, memories: &[Memory]) -> Result<()> {
-    let mut file = File::create(filename)?;
+    fs::write(filename, format_csv(memories))?;
+    Ok(())
+}
+
+/// Import memories from CSV file
+pub fn import_csv(filename: impl AsRef<Path>) -> Result<ImportReport> {
+    parse_csv(&fs::read_to_string(filename)?)
+}
 
-    // Write header
-    let header = Memory::CSV_HEADER.join(",");
-    writeln!(file, "{}", header)?;
+/// Format memories as CSV text (CRLF line endings, quoted only where needed)
+pub fn format_csv(memories: &[Memory]) -> String {
+    let mut text = String::new();
+    write_record(&mut text, Memory::CSV_HEADER.iter().copied());
 
     // Write each non-empty memory as a CSV row
     for mem in memories {
@@ -35,77 +114,226 @@ pub fn export_csv(filename: impl AsRef<Path>, memories: &[Memory]) -> Result<()>
             continue;
         }
         let row = mem.to_csv();
-        writeln!(file, "{}", row.join(","))?;
+        write_record(&mut text, row.iter().map(String::as_str));
     }
 
-    Ok(())
+    text
 }
 
-/// Import memories from CSV file
-pub fn import_csv(filename: impl AsRef<Path>) -> Result<Vec<Memory>> {
-    let file = File::open(filename)?;
-    let reader = BufReader::new(file);
-    let mut memories = Vec::new();
-    let mut lines = reader.lines();
+/// Parse CSV text into memories
+///
+/// Rows that can't be imported are skipped and listed in the report along
+/// with any fields that fell back to a default; only an unreadable file or a
+/// missing header is an error.
+pub fn parse_csv(text: &str) -> Result<ImportReport> {
+    let mut report = ImportReport::default();
+    let mut records = read_records(text)?.into_iter();
 
     // Read header line
-    let header_line = lines
+    let (_, headers) = records
         .next()
-        .ok_or_else(|| CsvError::InvalidFormat("Empty CSV file".to_string()))??;
-
-    let headers: Vec<String> = header_line
-        .split(',')
-        .map(|s| s.trim().to_string())
-        .collect();
+        .ok_or_else(|| CsvError::InvalidFormat("Empty CSV file".to_string()))?;
 
     // Build column index map (column name -> position)
-    let mut column_map = std::collections::HashMap::new();
+    let mut column_map = HashMap::new();
     for (idx, header) in headers.iter().enumerate() {
-        column_map.insert(header.clone(), idx);
+        column_map.insert(header.trim().to_string(), idx);
+    }
+    if !column_map.contains_key("Location") {
+        return Err(CsvError::InvalidFormat("No Location column".to_string()));
     }
 
-    // Read data lines
-    for (line_num, line_result) in lines.enumerate() {
-        let line = line_result?;
-        if line.trim().is_empty() {
-            continue; // Skip empty lines
+    // First line each location was seen on, to catch duplicates. Multi-band
+    // radios number each band from zero, so the band is part of the key.
+    let mut seen = HashMap::new();
+
+    // Read data records
+    for (line, fields) in records {
+        let mut notes = Vec::new();
+        let result = parse_csv_line_flexible(&fields, &column_map, line, &mut notes).and_then(
+            |mem| match (seen.get(&(mem.number, mem.band)), mem.band) {
+                (Some(first), Some(band)) => Err(format!(
+                    "Location {} in band {} already used on line {}",
+                    mem.number, band, first
+                )),
+                (Some(first), None) => Err(format!(
+                    "Location {} already used on line {}",
+                    mem.number, first
+                )),
+                (None, _) => Ok(mem),
+            },
+        );
+
+        match result {
+            Ok(mem) => {
+                seen.insert((mem.number, mem.band), line);
+                report.memories.push(mem);
+                report.notes.extend(notes);
+            }
+            Err(reason) => {
+                tracing::warn!("Skipping line {}: {}", line, reason);
+                report.rejected.push(RejectedRow { line, reason });
+            }
         }
+    }
 
-        match parse_csv_line_flexible(&line, &column_map, line_num + 2) {
-            Ok(mem) => memories.push(mem),
-            Err(e) => {
-                tracing::warn!("Skipping line {}: {}", line_num + 2, e);
-                // Continue importing other memories instead of failing completely
+    Ok(report)
+}
+
+/// Append one record, quoting fields that contain a delimiter, quote or line break
+fn write_record<'a>(text: &mut String, fields: impl Iterator<Item = &'a str>) {
+    for (i, field) in fields.enumerate() {
+        if i > 0 {
+            text.push(',');
+        }
+        if field.contains([',', '"', '\r', '\n']) {
+            text.push('"');
+            text.push_str(&field.replace('"', "\"\""));
+            text.push('"');
+        } else {
+            text.push_str(field);
+        }
+    }
+    text.push_str("\r\n");
+}
+
+/// Split CSV text into records, each with the line number it starts on
+///
+/// Follows RFC 4180: fields may be quoted, quoted fields may contain commas,
+/// line breaks and doubled quotes. A leading UTF-8 BOM is ignored, as are
+/// blank lines. Lines may end in CRLF, LF or CR.
+pub(crate) fn read_records(text: &str) -> Result<Vec<(usize, Vec<String>)>> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut start = 1;
+
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quoted = false,
+                _ => {
+                    if c == '\n' || (c == '\r' && chars.peek() != Some(&'\n')) {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => quoted = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' | '\n' => {
+                if c == '\r' && chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                record.push(std::mem::take(&mut field));
+                let record = std::mem::take(&mut record);
+                if record.iter().any(|f| !f.is_empty()) {
+                    records.push((start, record));
+                }
+                line += 1;
+                start = line;
             }
+            _ => field.push(c),
         }
     }
 
-    Ok(memories)
+    if quoted {
+        return Err(CsvError::Parse(format!(
+            "Line {}: Unterminated quoted field",
+            start
+        )));
+    }
+    record.push(field);
+    if record.iter().any(|f| !f.is_empty()) {
+        records.push((start, record));
+    }
+
+    Ok(records)
 }
 
-/// Parse a single CSV line into a Memory struct (flexible column mapping)
+/// Collects notes about fields in one row that fell back to a default
+struct RowContext {
+    line: usize,
+    location: u32,
+    notes: Vec<FieldNote>,
+}
+
+impl RowContext {
+    fn note(&mut self, column: &'static str, message: String) {
+        self.notes.push(FieldNote {
+            line: self.line,
+            location: self.location,
+            column,
+            message,
+        });
+    }
+
+    /// Parse a numeric field, using `default` if it's empty, unparseable or invalid
+    fn parse_or<T: FromStr + fmt::Display + Copy>(
+        &mut self,
+        column: &'static str,
+        value: &str,
+        default: T,
+        valid: impl Fn(T) -> bool,
+    ) -> T {
+        if value.is_empty() {
+            return default;
+        }
+        match value.parse() {
+            Ok(parsed) if valid(parsed) => parsed,
+            _ => {
+                self.note(
+                    column,
+                    format!("Invalid value \"{}\", using {}", value, default),
+                );
+                default
+            }
+        }
+    }
+}
+
+/// Parse a single CSV record into a Memory struct (flexible column mapping)
+///
+/// Errors in required or enumerated fields reject the row; bad numeric
+/// fields fall back to the Memory defaults and are noted in `notes`.
 fn parse_csv_line_flexible(
-    line: &str,
-    column_map: &std::collections::HashMap<String, usize>,
+    fields: &[String],
+    column_map: &HashMap<String, usize>,
     line_num: usize,
-) -> Result<Memory> {
-    let fields: Vec<&str> = line.split(',').map(|s| s.trim()).collect();
-
+    notes: &mut Vec<FieldNote>,
+) -> std::result::Result<Memory, String> {
     // Helper to get field by column name
     let get_field = |name: &str| -> Option<&str> {
         column_map
             .get(name)
             .and_then(|&idx| fields.get(idx))
-            .copied()
+            .map(|field| field.trim())
     };
 
     // Location is required
-    let number: u32 = get_field("Location")
-        .ok_or_else(|| CsvError::Parse(format!("Line {}: Missing Location column", line_num)))?
+    let location = get_field("Location").ok_or("Missing Location")?;
+    let number: u32 = location
         .parse()
-        .map_err(|_| CsvError::Parse(format!("Line {}: Invalid location number", line_num)))?;
+        .map_err(|_| format!("Invalid location number \"{}\"", location))?;
 
     let mut mem = Memory::new(number);
+    let mut row = RowContext {
+        line: line_num,
+        location: number,
+        notes: Vec::new(),
+    };
 
     // Optional fields - use defaults if not present
     if let Some(name) = get_field("Name") {
@@ -113,62 +341,72 @@ fn parse_csv_line_flexible(
     }
 
     if let Some(freq) = get_field("Frequency") {
-        mem.freq = parse_frequency(freq)?;
+        mem.freq = parse_frequency(freq).map_err(|_| format!("Invalid frequency \"{}\"", freq))?;
     }
+    if mem.freq == 0 {
+        return Err("Missing frequency".to_string());
+    }
+
+    // Enumerated fields must use CHIRP's spellings (case-insensitive)
+    let invalid = |e: crate::core::memory::MemoryError| e.to_string();
 
     if let Some(duplex) = get_field("Duplex") {
-        mem.duplex = duplex.to_string();
+        mem.duplex = duplex.parse().map_err(invalid)?;
     }
 
     if let Some(offset) = get_field("Offset") {
-        mem.offset = parse_frequency(offset)?;
+        mem.offset =
+            parse_frequency(offset).map_err(|_| format!("Invalid offset \"{}\"", offset))?;
     }
 
     if let Some(tmode) = get_field("Tone") {
-        mem.tmode = tmode.to_string();
+        mem.tmode = tmode.parse().map_err(invalid)?;
     }
 
     if let Some(rtone) = get_field("rToneFreq") {
-        mem.rtone = rtone.parse().unwrap_or(88.5);
+        mem.rtone = row.parse_or("rToneFreq", rtone, mem.rtone, is_valid_tone);
     }
 
     if let Some(ctone) = get_field("cToneFreq") {
-        mem.ctone = ctone.parse().unwrap_or(88.5);
+        mem.ctone = row.parse_or("cToneFreq", ctone, mem.ctone, is_valid_tone);
     }
 
     if let Some(dtcs) = get_field("DtcsCode") {
-        mem.dtcs = dtcs.parse().unwrap_or(23);
+        mem.dtcs = row.parse_or("DtcsCode", dtcs, mem.dtcs, is_valid_dtcs);
     }
 
     if let Some(pol) = get_field("DtcsPolarity") {
-        mem.dtcs_polarity = pol.to_string();
+        mem.dtcs_polarity = pol.parse().map_err(invalid)?;
     }
 
     if let Some(rx_dtcs) = get_field("RxDtcsCode") {
-        mem.rx_dtcs = rx_dtcs.parse().unwrap_or(23);
+        mem.rx_dtcs = row.parse_or("RxDtcsCode", rx_dtcs, mem.rx_dtcs, is_valid_dtcs);
     }
 
     if let Some(cross) = get_field("CrossMode") {
-        mem.cross_mode = cross.to_string();
+        mem.cross_mode = cross.parse().map_err(invalid)?;
     }
 
     if let Some(mode) = get_field("Mode") {
-        mem.mode = mode.to_string();
+        mem.mode = mode.parse().map_err(invalid)?;
     }
 
     if let Some(tstep) = get_field("TStep") {
-        mem.tuning_step = tstep.parse().unwrap_or(5.0);
+        mem.tuning_step = row.parse_or("TStep", tstep, mem.tuning_step, |step| step > 0.0);
     }
 
     if let Some(skip) = get_field("Skip") {
-        mem.skip = skip.to_string();
+        mem.skip = skip.parse().map_err(invalid)?;
     }
 
     if let Some(power) = get_field("Power") {
         if !power.is_empty() {
-            use crate::core::PowerLevel;
-            if let Ok(p) = PowerLevel::parse(power) {
-                mem.power = Some(p);
+            match PowerLevel::parse(power) {
+                Ok(p) => mem.power = Some(p),
+                Err(_) => row.note(
+                    "Power",
+                    format!("Invalid value \"{}\", using the radio's default", power),
+                ),
             }
         }
     }
@@ -191,43 +429,40 @@ fn parse_csv_line_flexible(
     }
 
     if let Some(dvcode) = get_field("DVCODE") {
-        mem.dv_code = dvcode.parse().unwrap_or(0);
+        mem.dv_code = row.parse_or("DVCODE", dvcode, mem.dv_code, |_| true);
     }
 
     // Bank
     if let Some(bank) = get_field("Bank") {
-        mem.bank = bank.parse().unwrap_or(0);
+        mem.bank = row.parse_or("Bank", bank, mem.bank, |_| true);
     }
 
     // Band (for multi-band radios like IC-9700)
     if let Some(band_str) = get_field("Band") {
         if !band_str.is_empty() {
             mem.band = band_str.parse().ok();
+            if mem.band.is_none() {
+                row.note("Band", format!("Invalid value \"{}\", ignored", band_str));
+            }
         }
     }
 
-    // Mark as non-empty if it has a valid frequency
-    mem.empty = mem.freq == 0;
+    mem.empty = false;
+    notes.append(&mut row.notes);
 
     Ok(mem)
 }
 
 /// Parse frequency from MHz string to Hz
 fn parse_frequency(freq_str: &str) -> Result<u64> {
-    if freq_str.is_empty() || freq_str == "0" {
-        return Ok(0);
-    }
-
-    let freq_mhz: f64 = freq_str
-        .parse()
-        .map_err(|_| CsvError::Parse(format!("Invalid frequency: {}", freq_str)))?;
-
-    Ok((freq_mhz * 1_000_000.0) as u64)
+    Memory::parse_freq(freq_str)
+        .map_err(|_| CsvError::Parse(format!("Invalid frequency: {}", freq_str)))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::{DtcsPolarity, Duplex, Mode, Skip, ToneMode};
     use tempfile::NamedTempFile;
 
     #[test]
@@ -236,36 +471,36 @@ mod tests {
         let mut mem1 = Memory::new(1);
         mem1.name = "Test 1".to_string();
         mem1.freq = 146_520_000;
-        mem1.mode = "FM".to_string();
+        mem1.mode = Mode::Fm;
         mem1.bank = 0;
 
         let mut mem2 = Memory::new(2);
         mem2.name = "Test 2".to_string();
         mem2.freq = 147_330_000;
-        mem2.duplex = "+".to_string();
+        mem2.duplex = Duplex::Plus;
         mem2.offset = 600_000;
-        mem2.tmode = "Tone".to_string();
+        mem2.tmode = ToneMode::Tone;
         mem2.rtone = 100.0;
         mem2.bank = 1;
 
         let memories = vec![mem1, mem2];
 
-        // Export to CSV (uses official CHIRP format - 21 columns, no Bank)
+        // Export to CSV
         let temp_file = NamedTempFile::new().unwrap();
         export_csv(temp_file.path(), &memories)?;
 
         // Import back
-        let imported = import_csv(temp_file.path())?;
+        let imported = import_csv(temp_file.path())?.memories;
 
-        // Verify - note that Bank is NOT preserved since it's not in official CHIRP CSV
+        // Verify
         assert_eq!(imported.len(), 2);
         assert_eq!(imported[0].name, "Test 1");
         assert_eq!(imported[0].freq, 146_520_000);
-        assert_eq!(imported[0].bank, 0); // Bank preserved (was 0)
+        assert_eq!(imported[0].bank, 0);
         assert_eq!(imported[1].name, "Test 2");
         assert_eq!(imported[1].freq, 147_330_000);
-        assert_eq!(imported[1].duplex, "+");
-        assert_eq!(imported[1].bank, 0); // Bank NOT preserved (defaults to 0)
+        assert_eq!(imported[1].duplex, Duplex::Plus);
+        assert_eq!(imported[1].bank, 1);
 
         Ok(())
     }
@@ -276,6 +511,164 @@ mod tests {
         assert_eq!(parse_frequency("441.950")?, 441_950_000);
         assert_eq!(parse_frequency("0")?, 0);
         assert_eq!(parse_frequency("")?, 0);
+        assert_eq!(parse_frequency("446.00625")?, 446_006_250);
+        Ok(())
+    }
+
+    /// Exported by Python CHIRP (csv module, excel dialect)
+    const UPSTREAM_CSV: &str = "\
+Location,Name,Frequency,Duplex,Offset,Tone,rToneFreq,cToneFreq,DtcsCode,DtcsPolarity,RxDtcsCode,CrossMode,Mode,TStep,Skip,Power,Comment,URCALL,RPT1CALL,RPT2CALL,DVCODE\r
+1,W6CX,146.940000,-,0.600000,Tone,100.0,88.5,023,NN,023,Tone->Tone,FM,5.00,,50W,\"Mt Diablo, Walnut Creek\",,,,\r
+2,KJ6KO B,145.230000,-,0.600000,,88.5,88.5,023,NN,023,Tone->Tone,DV,5.00,,50W,,CQCQCQ,KJ6KO  B,KJ6KO  G,0\r
+3,NOAA 1,162.550000,,0.000000,,88.5,88.5,023,NN,023,Tone->Tone,FM,12.50,S,,\"Weather \"\"WX1\"\"\",,,,\r
+4,GMRS 15,462.550000,+,5.000000,DTCS,88.5,88.5,131,RN,131,Tone->Tone,NFM,6.25,,5W,,,,,\r
+";
+
+    #[test]
+    fn test_import_upstream_csv() -> Result<()> {
+        let report = parse_csv(UPSTREAM_CSV)?;
+        assert!(report.is_clean(), "{}", report);
+        let imported = report.memories;
+        assert_eq!(imported.len(), 4);
+
+        assert_eq!(imported[0].comment, "Mt Diablo, Walnut Creek");
+        assert_eq!(imported[0].rtone, 100.0);
+        assert!(imported[0].dv_urcall.is_empty());
+
+        assert_eq!(imported[1].mode, Mode::Dv);
+        assert_eq!(imported[1].dv_urcall, "CQCQCQ");
+        assert_eq!(imported[1].dv_rpt1call, "KJ6KO  B");
+        assert_eq!(imported[1].dv_rpt2call, "KJ6KO  G");
+
+        assert_eq!(imported[2].comment, "Weather \"WX1\"");
+        assert_eq!(imported[2].skip, Skip::Skip);
+        assert_eq!(imported[3].dtcs, 131);
+        assert_eq!(imported[3].dtcs_polarity, DtcsPolarity::RN);
+
+        // Re-export and import again: nothing lost
+        let text = format_csv(&imported);
+        assert!(text.contains(",\"Mt Diablo, Walnut Creek\","));
+        assert!(text.contains(",\"Weather \"\"WX1\"\"\","));
+        let reimported = parse_csv(&text)?.memories;
+        assert_eq!(reimported.len(), 4);
+        for (a, b) in imported.iter().zip(&reimported) {
+            assert_eq!(a.to_csv(), b.to_csv());
+        }
+
+        // D-STAR fields survive outside DV mode too
+        let mut fm = imported[1].clone();
+        fm.mode = Mode::Fm;
+        fm.dv_code = 3;
+        let fm = parse_csv(&format_csv(&[fm]))?.memories.remove(0);
+        assert_eq!(
+            (fm.dv_urcall.as_str(), fm.dv_rpt1call.as_str(), fm.dv_code),
+            ("CQCQCQ", "KJ6KO  B", 3)
+        );
+        assert!(imported[0].to_csv()[17..21].iter().all(String::is_empty));
+
+        // Our extra columns come after CHIRP's, so CHIRP's rows are a prefix of ours
+        let upstream_row = UPSTREAM_CSV.lines().nth(2).unwrap().trim_end();
+        assert!(text.lines().nth(2).unwrap().starts_with(upstream_row));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_quoting_roundtrip() -> Result<()> {
+        let mut mem = Memory::new(7);
+        mem.freq = 147_330_000;
+        mem.name = "Rpt, \"A\"".to_string();
+        mem.comment = "Line one\nLine two, with comma".to_string();
+        mem.bank = 3;
+
+        let text = format_csv(&[mem]);
+        let imported = parse_csv(&text)?.memories;
+        assert_eq!(imported.len(), 1);
+        assert_eq!(imported[0].name, "Rpt, \"A\"");
+        assert_eq!(imported[0].comment, "Line one\nLine two, with comma");
+        assert_eq!(imported[0].bank, 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_report() -> Result<()> {
+        let csv_content = "\
+Location,Name,Frequency,Tone,rToneFreq,DtcsCode,Mode,Power\r
+1,Good,146.520000,Tone,100.0,023,FM,5W\r
+2,Bad tone,147.000000,Tone,abc,999,FM,lots\r
+3,No freq,,,,,FM,\r
+4,Bad mode,446.000000,,,,XYZ,\r
+1,Duplicate,146.550000,,,,FM,\r
+x,Bad location,146.580000,,,,FM,\r
+";
+        let report = parse_csv(csv_content)?;
+
+        let imported: Vec<u32> = report.memories.iter().map(|m| m.number).collect();
+        assert_eq!(imported, vec![1, 2]);
+        assert_eq!(report.memories[1].rtone, 88.5);
+        assert_eq!(report.memories[1].dtcs, 23);
+        assert!(report.memories[1].power.is_none());
+
+        let rejected: Vec<usize> = report.rejected.iter().map(|r| r.line).collect();
+        assert_eq!(rejected, vec![4, 5, 6, 7]);
+        assert!(report.rejected[2].reason.contains("line 2"));
+
+        let columns: Vec<&str> = report.notes.iter().map(|n| n.column).collect();
+        assert_eq!(columns, vec!["rToneFreq", "DtcsCode", "Power"]);
+        assert!(report.notes.iter().all(|n| n.line == 3 && n.location == 2));
+
+        assert!(!report.is_clean());
+        assert_eq!(
+            report.summary(),
+            "Imported 2 rows, rejected 4, 3 fields defaulted"
+        );
+        assert!(report
+            .to_string()
+            .contains("Line 3 (memory #2): rToneFreq: Invalid value \"abc\", using 88.5"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_multiband_locations() -> Result<()> {
+        // IC-9700 export: every band starts at Location 1
+        let csv_content = "\
+Location,Name,Frequency,Mode,Band\r
+1,2m Call,146.520000,FM,1\r
+1,70cm Call,446.000000,FM,2\r
+2,23cm Call,1294.500000,FM,3\r
+1,Duplicate,446.500000,FM,2\r
+";
+        let report = parse_csv(csv_content)?;
+
+        let imported: Vec<(u32, Option<u8>)> =
+            report.memories.iter().map(|m| (m.number, m.band)).collect();
+        assert_eq!(imported, vec![(1, Some(1)), (1, Some(2)), (2, Some(3))]);
+        assert_eq!(report.rejected.len(), 1);
+        assert_eq!(report.rejected[0].line, 5);
+        assert!(report.rejected[0].reason.contains("band 2"));
+        assert!(report.rejected[0].reason.contains("line 3"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_records() -> Result<()> {
+        // BOM, LF endings, blank lines and a quoted field spanning lines
+        let records = read_records("\u{feff}A,B\n\n1,\"x\r\ny\"\n2,\"\"\n")?;
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0], (1, vec!["A".to_string(), "B".to_string()]));
+        assert_eq!(records[1], (3, vec!["1".to_string(), "x\r\ny".to_string()]));
+        assert_eq!(records[2], (5, vec!["2".to_string(), String::new()]));
+
+        let imported = parse_csv("\u{feff}Location,Name,Frequency\r\n1,Test,146.520000")?.memories;
+        assert_eq!(imported[0].name, "Test");
+
+        assert!(matches!(
+            read_records("A,B\n1,\"open\n"),
+            Err(CsvError::Parse(_))
+        ));
         Ok(())
     }
 
@@ -287,17 +680,17 @@ mod tests {
         let temp_file = NamedTempFile::new().unwrap();
         std::fs::write(temp_file.path(), csv_content).unwrap();
 
-        let imported = import_csv(temp_file.path())?;
+        let imported = import_csv(temp_file.path())?.memories;
 
         assert_eq!(imported.len(), 2);
         assert_eq!(imported[0].name, "Test");
         assert_eq!(imported[0].freq, 146_520_000);
-        assert_eq!(imported[0].mode, "FM");
-        assert_eq!(imported[0].duplex, ""); // Should be default
+        assert_eq!(imported[0].mode, Mode::Fm);
+        assert_eq!(imported[0].duplex, Duplex::Simplex); // Should be default
         assert_eq!(imported[0].bank, 0); // Should be default
 
         assert_eq!(imported[1].name, "Test2");
-        assert_eq!(imported[1].mode, "NFM");
+        assert_eq!(imported[1].mode, Mode::Nfm);
 
         Ok(())
     }
@@ -310,13 +703,13 @@ mod tests {
         let temp_file = NamedTempFile::new().unwrap();
         std::fs::write(temp_file.path(), csv_content).unwrap();
 
-        let imported = import_csv(temp_file.path())?;
+        let imported = import_csv(temp_file.path())?.memories;
 
         assert_eq!(imported.len(), 1);
         assert_eq!(imported[0].number, 5);
         assert_eq!(imported[0].name, "Reversed");
         assert_eq!(imported[0].freq, 146_520_000);
-        assert_eq!(imported[0].mode, "FM");
+        assert_eq!(imported[0].mode, Mode::Fm);
 
         Ok(())
     }
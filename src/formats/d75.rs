@@ -0,0 +1,141 @@
+// Kenwood MCP-D74 / MCP-D75 programming software files (.d74, .d75)
+// Reference: chirp/drivers/thd74.py (D74_FILE_HEADER, load_mmap, save_mmap)
+//
+// An MCP file is a 256-byte header followed by the raw radio image, the same
+// bytes `THD75Radio::process_mmap` takes. The header names the software and
+// radio model; the rest of it is 0xFF padding.
+
+use super::metadata::Metadata;
+use crate::memmap::MemoryMap;
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum D75Error {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Not an MCP-D74/D75 file")]
+    InvalidHeader,
+
+    #[error("MCP files can't hold a {0} image")]
+    UnsupportedModel(String),
+}
+
+pub type Result<T> = std::result::Result<T, D75Error>;
+
+/// Size of the MCP file header
+pub const HEADER_SIZE: usize = 0x100;
+
+/// Header written by MCP-D74
+pub const D74_FILE_HEADER: &[u8] = b"MCP-D74\xFFV1.03\xFF\xFF\xFFTH-D74\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\x00\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF";
+
+/// Header written by MCP-D75
+pub const D75_FILE_HEADER: &[u8] = b"MCP-D75\xFFV1.03\xFF\xFF\xFFTH-D75\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\x00\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF";
+
+/// Software name prefix shared by both headers
+const MAGIC: &[u8] = b"MCP-D7";
+
+/// Model name field (0xFF padded)
+const MODEL_RANGE: std::ops::Range<usize> = 16..32;
+
+/// Check whether data starts with an MCP header
+pub fn has_header(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+/// Check whether a filename has an MCP extension (.d74 or .d75)
+pub fn is_mcp_file(filename: impl AsRef<Path>) -> bool {
+    filename
+        .as_ref()
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("d74") || ext.eq_ignore_ascii_case("d75"))
+}
+
+/// Build the padded file header for a radio model
+pub fn file_header(model: &str) -> Result<Vec<u8>> {
+    let header = match model {
+        "TH-D74" => D74_FILE_HEADER,
+        "TH-D75" => D75_FILE_HEADER,
+        _ => return Err(D75Error::UnsupportedModel(model.to_string())),
+    };
+    let mut data = header.to_vec();
+    data.resize(HEADER_SIZE, 0xFF);
+    Ok(data)
+}
+
+/// Split MCP file contents into the radio image and its metadata
+pub fn parse(data: &[u8]) -> Result<(MemoryMap, Metadata)> {
+    if !has_header(data) || data.len() < HEADER_SIZE {
+        return Err(D75Error::InvalidHeader);
+    }
+
+    let model: String = data[MODEL_RANGE]
+        .iter()
+        .take_while(|&&b| b != 0xFF && b != 0x00)
+        .map(|&b| b as char)
+        .collect();
+    let metadata = Metadata::new("Kenwood", model);
+
+    Ok((MemoryMap::new(data[HEADER_SIZE..].to_vec()), metadata))
+}
+
+/// Load a .d74/.d75 file
+pub fn load_d75(filename: impl AsRef<Path>) -> Result<(MemoryMap, Metadata)> {
+    parse(&fs::read(filename)?)
+}
+
+/// Save a radio image as a .d74/.d75 file (the header follows `metadata.model`)
+pub fn save_d75(filename: impl AsRef<Path>, mmap: &MemoryMap, metadata: &Metadata) -> Result<()> {
+    let mut data = file_header(&metadata.model)?;
+    data.extend_from_slice(mmap.get_packed());
+    fs::write(filename, data)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_roundtrip() {
+        let image: Vec<u8> = (0..0x1000).map(|i| i as u8).collect();
+        let file = NamedTempFile::new().unwrap();
+
+        let metadata = Metadata::new("Kenwood", "TH-D75");
+        save_d75(file.path(), &MemoryMap::new(image.clone()), &metadata).unwrap();
+
+        let raw = fs::read(file.path()).unwrap();
+        assert_eq!(raw.len(), HEADER_SIZE + image.len());
+        assert!(raw.starts_with(D75_FILE_HEADER));
+        assert!(raw[D75_FILE_HEADER.len()..HEADER_SIZE]
+            .iter()
+            .all(|&b| b == 0xFF));
+
+        let (mmap, metadata) = load_d75(file.path()).unwrap();
+        assert_eq!(mmap.get_packed(), &image[..]);
+        assert_eq!(metadata.vendor, "Kenwood");
+        assert_eq!(metadata.model, "TH-D75");
+    }
+
+    #[test]
+    fn test_header() {
+        let mut data = file_header("TH-D74").unwrap();
+        data.extend_from_slice(&[0u8; 16]);
+        let (mmap, metadata) = parse(&data).unwrap();
+        assert_eq!(metadata.model, "TH-D74");
+        assert_eq!(mmap.get_packed().len(), 16);
+
+        assert!(matches!(parse(b"MCP-D75"), Err(D75Error::InvalidHeader)));
+        assert!(matches!(parse(&[0u8; 0x200]), Err(D75Error::InvalidHeader)));
+        assert!(matches!(
+            file_header("UV-5R"),
+            Err(D75Error::UnsupportedModel(_))
+        ));
+        assert!(is_mcp_file("club.D75"));
+        assert!(!is_mcp_file("club.img"));
+    }
+}
@@ -0,0 +1,362 @@
+// Icom .icf clone files (CS-xxxx programming software)
+// Reference: chirp/drivers/icf.py (read_file, write_file)
+//
+// An .icf file is text. The first line is the radio's model bytes in hex,
+// followed by optional "#Key=Value" properties and then data lines:
+//
+//   AAAALLDDDD...     4-digit address, length, payload (images up to 64K)
+//   AAAAAAAALLDD...   8-digit address for larger images
+//
+// A line may carry one extra byte after the payload: a checksum in the Icom
+// clone-frame style (two's complement of the address, length and data bytes).
+// It is checked when present and written back if the file had it.
+
+use crate::memmap::MemoryMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum IcfError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid model header: {0:?}")]
+    InvalidModel(String),
+
+    #[error("Line {line}: {reason}")]
+    InvalidLine { line: usize, reason: String },
+
+    #[error("Line {line}: checksum {actual:02X} doesn't match {expected:02X}")]
+    Checksum {
+        line: usize,
+        expected: u8,
+        actual: u8,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, IcfError>;
+
+/// Payload bytes per data line when writing
+const LINE_SIZE: usize = 0x20;
+
+/// Largest image accepted (1 MiB, well past any Icom radio's)
+const MAX_IMAGE_SIZE: usize = 0x100000;
+
+/// Contents of an .icf file
+#[derive(Debug, Clone)]
+pub struct IcfFile {
+    /// Radio model bytes from the header line
+    pub model: Vec<u8>,
+    /// "#Key=Value" properties, in file order
+    pub properties: Vec<(String, String)>,
+    /// Radio image (gaps between data lines are 0xFF)
+    pub mmap: MemoryMap,
+    /// Data lines carry a checksum byte
+    pub checksums: bool,
+}
+
+impl IcfFile {
+    pub fn new(model: impl Into<Vec<u8>>, mmap: MemoryMap) -> Self {
+        Self {
+            model: model.into(),
+            properties: vec![("MapRev".to_string(), "1".to_string())],
+            mmap,
+            checksums: false,
+        }
+    }
+
+    /// Set a property, replacing any existing value
+    pub fn with_property(mut self, key: &str, value: impl Into<String>) -> Self {
+        let value = value.into();
+        match self.properties.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) => *v = value,
+            None => self.properties.push((key.to_string(), value)),
+        }
+        self
+    }
+
+    /// Write a checksum byte on every data line
+    pub fn with_checksums(mut self) -> Self {
+        self.checksums = true;
+        self
+    }
+
+    /// Look up a property
+    pub fn property(&self, key: &str) -> Option<&str> {
+        self.properties
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Parse .icf text
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut lines = text.lines().map(str::trim).enumerate();
+
+        let (_, header) = lines
+            .find(|(_, line)| !line.is_empty())
+            .ok_or_else(|| IcfError::InvalidModel(String::new()))?;
+        let model = decode_hex(header)
+            .filter(|model| !model.is_empty())
+            .ok_or_else(|| IcfError::InvalidModel(header.to_string()))?;
+
+        let mut file = Self {
+            model,
+            properties: Vec::new(),
+            mmap: MemoryMap::new_empty(),
+            checksums: false,
+        };
+        let mut data = Vec::new();
+        let mut addr_len = None;
+
+        for (index, line) in lines {
+            let line_num = index + 1;
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(property) = line.strip_prefix('#') {
+                let (key, value) = property.split_once('=').unwrap_or((property, ""));
+                file.properties
+                    .push((key.trim().to_string(), value.trim().to_string()));
+                continue;
+            }
+
+            let (address, payload, checksum) = parse_data_line(line, line_num, &mut addr_len)?;
+            if let Some(actual) = checksum {
+                let expected = line_checksum(address, &payload);
+                if actual != expected {
+                    return Err(IcfError::Checksum {
+                        line: line_num,
+                        expected,
+                        actual,
+                    });
+                }
+                file.checksums = true;
+            }
+
+            let end = address + payload.len();
+            if end > MAX_IMAGE_SIZE {
+                return Err(IcfError::InvalidLine {
+                    line: line_num,
+                    reason: format!(
+                        "Address 0x{:X} is past the {} byte image limit",
+                        address, MAX_IMAGE_SIZE
+                    ),
+                });
+            }
+            if data.len() < end {
+                data.resize(end, 0xFF);
+            }
+            data[address..end].copy_from_slice(&payload);
+        }
+
+        file.mmap = MemoryMap::new(data);
+        Ok(file)
+    }
+}
+
+impl fmt::Display for IcfFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\r\n", encode_hex(&self.model))?;
+        for (key, value) in &self.properties {
+            write!(f, "#{}={}\r\n", key, value)?;
+        }
+
+        let data = self.mmap.get_packed();
+        let wide = data.len() > 0x10000;
+        for (i, chunk) in data.chunks(LINE_SIZE).enumerate() {
+            let address = i * LINE_SIZE;
+            if wide {
+                write!(f, "{:08X}", address)?;
+            } else {
+                write!(f, "{:04X}", address)?;
+            }
+            write!(f, "{:02X}{}", chunk.len(), encode_hex(chunk))?;
+            if self.checksums {
+                write!(f, "{:02X}", line_checksum(address, chunk))?;
+            }
+            f.write_str("\r\n")?;
+        }
+        Ok(())
+    }
+}
+
+/// Checksum of a data line: two's complement of the address, length and data bytes
+pub fn line_checksum(address: usize, data: &[u8]) -> u8 {
+    let header = (address as u32).to_be_bytes();
+    let sum = header
+        .iter()
+        .chain(&[data.len() as u8])
+        .chain(data)
+        .fold(0u8, |sum, &b| sum.wrapping_add(b));
+    sum.wrapping_neg()
+}
+
+/// Split a data line into address, payload and optional checksum
+///
+/// The address width is picked on the first data line and kept for the rest
+/// of the file; guessing per line would misread 8-digit lines whose third
+/// address byte happens to match the line length.
+fn parse_data_line(
+    line: &str,
+    line_num: usize,
+    addr_len: &mut Option<usize>,
+) -> Result<(usize, Vec<u8>, Option<u8>)> {
+    let invalid = |reason: &str| IcfError::InvalidLine {
+        line: line_num,
+        reason: reason.to_string(),
+    };
+    let bytes = decode_hex(line).ok_or_else(|| invalid("not a hex data line"))?;
+
+    // Try a 2-byte address first, then a 4-byte one
+    let width = match *addr_len {
+        Some(width) => width,
+        None => [2, 4]
+            .into_iter()
+            .find(|&width| split_data_line(&bytes, width).is_some())
+            .ok_or_else(|| invalid("length doesn't match the data"))?,
+    };
+    let parsed =
+        split_data_line(&bytes, width).ok_or_else(|| invalid("length doesn't match the data"))?;
+    *addr_len = Some(width);
+    Ok(parsed)
+}
+
+/// Split a decoded data line with an `addr_len`-byte address
+fn split_data_line(bytes: &[u8], addr_len: usize) -> Option<(usize, Vec<u8>, Option<u8>)> {
+    let length = *bytes.get(addr_len)?;
+    let start = addr_len + 1;
+    let end = start + length as usize;
+    let checksum = match bytes.len().checked_sub(end)? {
+        0 => None,
+        1 => Some(bytes[end]),
+        _ => return None,
+    };
+    let address = bytes[..addr_len]
+        .iter()
+        .fold(0usize, |addr, &b| (addr << 8) | b as usize);
+    Some((address, bytes[start..end].to_vec(), checksum))
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn encode_hex(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+/// Load an .icf file
+pub fn load_icf(filename: impl AsRef<Path>) -> Result<IcfFile> {
+    IcfFile::parse(&fs::read_to_string(filename)?)
+}
+
+/// Save an .icf file
+pub fn save_icf(filename: impl AsRef<Path>, icf: &IcfFile) -> Result<()> {
+    fs::write(filename, icf.to_string())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+33220001\r
+#Comment=Club repeaters\r
+#MapRev=1\r
+#EtcData=000006\r
+000010000102030405060708090A0B0C0D0E0F\r
+00200441424344\r
+";
+
+    #[test]
+    fn test_parse() {
+        let icf = IcfFile::parse(SAMPLE).unwrap();
+        assert_eq!(icf.model, vec![0x33, 0x22, 0x00, 0x01]);
+        assert_eq!(icf.property("Comment"), Some("Club repeaters"));
+        assert_eq!(icf.property("EtcData"), Some("000006"));
+        assert!(!icf.checksums);
+
+        let data = icf.mmap.get_packed();
+        assert_eq!(data.len(), 0x24);
+        assert_eq!(data[0x0F], 0x0F);
+        assert_eq!(&data[0x10..0x20], &[0xFF; 16]);
+        assert_eq!(&data[0x20..0x24], b"ABCD");
+    }
+
+    #[test]
+    fn test_roundtrip_with_checksums() {
+        let image: Vec<u8> = (0..0x50).map(|i| (i * 7) as u8).collect();
+        let icf = IcfFile::new([0x44, 0x71, 0x00, 0x01], MemoryMap::new(image.clone()))
+            .with_property("Comment", "test")
+            .with_checksums();
+        let text = icf.to_string();
+        assert!(text.starts_with("44710001\r\n#MapRev=1\r\n#Comment=test\r\n"));
+
+        let parsed = IcfFile::parse(&text).unwrap();
+        assert!(parsed.checksums);
+        assert_eq!(parsed.mmap.get_packed(), &image[..]);
+        assert_eq!(parsed.to_string(), text);
+
+        // Corrupt one payload byte on the first data line
+        let bad = text.replacen("\r\n0000200007", "\r\n0000200107", 1);
+        assert!(matches!(
+            IcfFile::parse(&bad),
+            Err(IcfError::Checksum { line: 4, .. })
+        ));
+    }
+
+    #[test]
+    fn test_roundtrip_wide() {
+        // Over 64K, so every line uses an 8-digit address
+        let image: Vec<u8> = (0..0x10040).map(|i| (i % 251) as u8).collect();
+        let icf = IcfFile::new([0x39, 0x49, 0x00, 0x01], MemoryMap::new(image.clone()));
+        let text = icf.to_string();
+        assert!(text.contains("\r\n0001002020"));
+
+        // 00002200 20 ... also reads as a valid 4-digit line of 0x22 bytes
+        assert!(text.contains("\r\n0000220020"));
+        let parsed = IcfFile::parse(&text).unwrap();
+        assert_eq!(parsed.mmap.get_packed(), &image[..]);
+        assert_eq!(parsed.to_string(), text);
+    }
+
+    #[test]
+    fn test_invalid_lines() {
+        assert!(matches!(
+            IcfFile::parse("XYZ\r\n"),
+            Err(IcfError::InvalidModel(_))
+        ));
+        assert!(matches!(
+            IcfFile::parse("33220001\r\n00001000010203040506\r\n"),
+            Err(IcfError::InvalidLine { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_address_limit() {
+        // An 8-digit address near 4 GiB must not size the image
+        assert!(matches!(
+            IcfFile::parse("33220001\r\nFFFFFFF00401020304\r\n"),
+            Err(IcfError::InvalidLine { line: 2, reason }) if reason.contains("limit")
+        ));
+
+        // The last byte below the limit is fine
+        let text = "33220001\r\n000FFFFF01AA\r\n";
+        let parsed = IcfFile::parse(text).unwrap();
+        assert_eq!(parsed.mmap.len(), MAX_IMAGE_SIZE);
+        assert_eq!(
+            parsed.mmap.get(MAX_IMAGE_SIZE - 1, Some(1)).unwrap(),
+            &[0xAA]
+        );
+    }
+}
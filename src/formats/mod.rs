@@ -1,8 +1,14 @@
 // File format handlers
 pub mod csv;
+pub mod d75;
+pub mod icf;
 pub mod img;
 pub mod metadata;
+pub mod repeaters;
 
-pub use csv::{export_csv, import_csv, CsvError};
+pub use csv::{export_csv, format_csv, import_csv, parse_csv, CsvError, ImportReport};
+pub use d75::{load_d75, save_d75, D75Error};
+pub use icf::{load_icf, save_icf, IcfError, IcfFile};
 pub use img::{load_img, save_img, ImgError};
 pub use metadata::Metadata;
+pub use repeaters::{import_repeaters, RepeaterError};
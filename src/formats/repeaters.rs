@@ -0,0 +1,641 @@
+// RepeaterBook and RadioReference export files
+// Reference: chirp/sources/repeaterbook.py (RepeaterBook result fields)
+//
+// Converts downloaded exports (no live API) into memories:
+//
+//   RepeaterBook JSON   {"count": N, "results": [{"Frequency": ..., "Input Freq": ...}]}
+//   RepeaterBook CSV    the same field names as the JSON, one column each
+//   RadioReference CSV  "Frequency Output", "Frequency Input", "Tone", "Alpha Tag", ...
+//
+// Memories are numbered from 0 in file order. The result is a CSV-style
+// `ImportReport`; for JSON the "line" of a rejected entry is its position in
+// the results list, counting from 1.
+
+use super::csv::{read_records, CsvError, FieldNote, ImportReport, RejectedRow};
+use crate::core::{
+    is_valid_dtcs, is_valid_tone, CrossMode, DtcsPolarity, Duplex, Memory, Mode, ToneMode,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum RepeaterError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("{0}")]
+    Csv(#[from] CsvError),
+
+    #[error("Not a RepeaterBook or RadioReference export")]
+    UnknownFormat,
+}
+
+pub type Result<T> = std::result::Result<T, RepeaterError>;
+
+/// Where an export file came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExportSource {
+    RepeaterBook,
+    RadioReference,
+}
+
+/// Largest repeater offset; inputs further away are stored as split
+const MAX_OFFSET: u64 = 50_000_000;
+
+/// One export row, field name -> value
+type Row = HashMap<String, String>;
+
+/// Rows with the line (or JSON entry) each came from
+type Rows = Vec<(usize, Row)>;
+
+/// Import a RepeaterBook (JSON or CSV) or RadioReference (CSV) export
+pub fn import_repeaters(filename: impl AsRef<Path>) -> Result<ImportReport> {
+    parse_repeaters(&fs::read_to_string(filename)?)
+}
+
+/// Parse export text, detecting where it came from
+pub fn parse_repeaters(text: &str) -> Result<ImportReport> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    if text.trim_start().starts_with(['{', '[']) {
+        let (rows, skipped) = json_rows(text)?;
+        let mut report = convert(ExportSource::RepeaterBook, rows);
+        report.rejected.extend(skipped);
+        report.rejected.sort_by_key(|row| row.line);
+        return Ok(report);
+    }
+
+    let (headers, rows) = csv_rows(text)?;
+    let source = detect(&headers).ok_or(RepeaterError::UnknownFormat)?;
+    Ok(convert(source, rows))
+}
+
+/// Duplex and offset for a repeater's output (radio RX) and input (radio TX)
+pub fn duplex_for(output: u64, input: u64) -> (Duplex, u64) {
+    if input == 0 || input == output {
+        (Duplex::Simplex, 0)
+    } else if input.abs_diff(output) > MAX_OFFSET {
+        (Duplex::Split, input)
+    } else if input > output {
+        (Duplex::Plus, input - output)
+    } else {
+        (Duplex::Minus, output - input)
+    }
+}
+
+/// Squelch settings from a tone column
+#[derive(Debug, Clone, PartialEq)]
+enum Squelch {
+    None,
+    Tone(f32),
+    /// DCS code and polarity ('N' or 'R')
+    Dcs(u16, char),
+    /// P25 network access code
+    Nac(String),
+}
+
+/// Parse a tone column: "100.0", "100.0 PL", "D023N", "023 DPL", "293 NAC", "CSQ"
+fn parse_squelch(text: &str) -> Option<Squelch> {
+    let text = text.trim().to_uppercase();
+    if text.is_empty() || text == "CSQ" {
+        return Some(Squelch::None);
+    }
+    if let Some(nac) = text.strip_suffix("NAC") {
+        return Some(Squelch::Nac(nac.trim().trim_start_matches('$').to_string()));
+    }
+
+    let dcs = text
+        .strip_suffix("DPL")
+        .map(|code| (code.trim(), 'N'))
+        .or_else(|| {
+            let code = text.strip_prefix('D')?;
+            match code.strip_suffix(['N', 'I', 'R']) {
+                Some(digits) if code.ends_with('N') => Some((digits, 'N')),
+                Some(digits) => Some((digits, 'R')),
+                None => Some((code, 'N')),
+            }
+        });
+    if let Some((code, polarity)) = dcs {
+        return code
+            .parse()
+            .ok()
+            .filter(|&code| is_valid_dtcs(code))
+            .map(|code| Squelch::Dcs(code, polarity));
+    }
+
+    text.trim_end_matches("PL")
+        .trim()
+        .parse()
+        .ok()
+        .filter(|&tone| is_valid_tone(tone))
+        .map(Squelch::Tone)
+}
+
+/// Set tone mode and tones from the uplink (radio TX) and downlink (radio RX) squelch
+fn apply_squelch(mem: &mut Memory, uplink: &Squelch, downlink: &Squelch) {
+    let polarity = |tx: char, rx: char| DtcsPolarity::from_chars(tx, rx);
+    let cross = |mem: &mut Memory, mode: CrossMode| {
+        mem.tmode = ToneMode::Cross;
+        mem.cross_mode = mode;
+    };
+
+    match (uplink, downlink) {
+        (Squelch::Tone(tx), Squelch::Tone(rx)) if tx == rx => {
+            mem.tmode = ToneMode::Tsql;
+            mem.rtone = *tx;
+            mem.ctone = *rx;
+        }
+        (Squelch::Tone(tx), Squelch::Tone(rx)) => {
+            cross(mem, CrossMode::ToneToTone);
+            mem.rtone = *tx;
+            mem.ctone = *rx;
+        }
+        (Squelch::Tone(tx), Squelch::Dcs(rx, rx_pol)) => {
+            cross(mem, CrossMode::ToneToDtcs);
+            mem.rtone = *tx;
+            mem.rx_dtcs = *rx;
+            mem.dtcs_polarity = polarity('N', *rx_pol);
+        }
+        (Squelch::Tone(tx), _) => {
+            mem.tmode = ToneMode::Tone;
+            mem.rtone = *tx;
+        }
+        (Squelch::Dcs(tx, tx_pol), Squelch::Dcs(rx, rx_pol)) if tx != rx => {
+            cross(mem, CrossMode::DtcsToDtcs);
+            mem.dtcs = *tx;
+            mem.rx_dtcs = *rx;
+            mem.dtcs_polarity = polarity(*tx_pol, *rx_pol);
+        }
+        (Squelch::Dcs(tx, tx_pol), Squelch::Tone(rx)) => {
+            cross(mem, CrossMode::DtcsToTone);
+            mem.dtcs = *tx;
+            mem.ctone = *rx;
+            mem.dtcs_polarity = polarity(*tx_pol, 'N');
+        }
+        (Squelch::Dcs(tx, tx_pol), Squelch::Dcs(_, rx_pol)) => {
+            mem.tmode = ToneMode::Dtcs;
+            mem.dtcs = *tx;
+            mem.rx_dtcs = *tx;
+            mem.dtcs_polarity = polarity(*tx_pol, *rx_pol);
+        }
+        (Squelch::Dcs(tx, tx_pol), _) => {
+            cross(mem, CrossMode::DtcsToNone);
+            mem.dtcs = *tx;
+            mem.dtcs_polarity = polarity(*tx_pol, 'N');
+        }
+        (_, Squelch::Tone(rx)) => {
+            cross(mem, CrossMode::NoneToTone);
+            mem.ctone = *rx;
+        }
+        (_, Squelch::Dcs(rx, rx_pol)) => {
+            cross(mem, CrossMode::NoneToDtcs);
+            mem.rx_dtcs = *rx;
+            mem.dtcs_polarity = polarity('N', *rx_pol);
+        }
+        _ => {}
+    }
+}
+
+/// D-STAR repeater calls for a callsign: module letter by band, gateway "G"
+fn dstar_calls(callsign: &str, freq: u64) -> (String, String) {
+    let module = match freq {
+        0..=200_000_000 => 'C',
+        200_000_001..=1_000_000_000 => 'B',
+        _ => 'A',
+    };
+    let call = callsign.to_uppercase();
+    (format!("{:<7}{}", call, module), format!("{:<7}G", call))
+}
+
+/// Which export a CSV header row came from
+fn detect(headers: &[String]) -> Option<ExportSource> {
+    let has = |name: &str| headers.iter().any(|h| h == name);
+    if has("Frequency Output") || has("Alpha Tag") {
+        Some(ExportSource::RadioReference)
+    } else if has("Input Freq") {
+        Some(ExportSource::RepeaterBook)
+    } else {
+        None
+    }
+}
+
+/// Rows of a RepeaterBook JSON export
+fn json_rows(text: &str) -> Result<(Rows, Vec<RejectedRow>)> {
+    let json: Value = serde_json::from_str(text)?;
+    let results = match &json {
+        Value::Array(results) => results,
+        Value::Object(object) => match object.get("results") {
+            Some(Value::Array(results)) => results,
+            _ => return Err(RepeaterError::UnknownFormat),
+        },
+        _ => return Err(RepeaterError::UnknownFormat),
+    };
+
+    let mut rows = Vec::new();
+    let mut skipped = Vec::new();
+    for (i, result) in results.iter().enumerate() {
+        let Some(object) = result.as_object() else {
+            tracing::warn!("Skipping entry {}: not an object", i + 1);
+            skipped.push(RejectedRow {
+                line: i + 1,
+                reason: format!("Expected an object, got {}", result),
+            });
+            continue;
+        };
+        let row = object
+            .iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    Value::String(s) => s.clone(),
+                    Value::Null => String::new(),
+                    other => other.to_string(),
+                };
+                (key.clone(), value)
+            })
+            .collect();
+        rows.push((i + 1, row));
+    }
+    Ok((rows, skipped))
+}
+
+/// Header and rows of a CSV export, rows keyed by header name
+fn csv_rows(text: &str) -> Result<(Vec<String>, Rows)> {
+    let mut records = read_records(text)?.into_iter();
+    let (_, headers) = records.next().ok_or(RepeaterError::UnknownFormat)?;
+    let headers: Vec<String> = headers.iter().map(|h| h.trim().to_string()).collect();
+
+    let rows = records
+        .map(|(line, fields)| {
+            let row = headers.iter().cloned().zip(fields).collect();
+            (line, row)
+        })
+        .collect();
+    Ok((headers, rows))
+}
+
+/// First non-empty value among alternative column names
+fn field<'a>(row: &'a Row, names: &[&str]) -> &'a str {
+    names
+        .iter()
+        .filter_map(|name| row.get(*name))
+        .map(|value| value.trim())
+        .find(|value| !value.is_empty())
+        .unwrap_or("")
+}
+
+/// "Yes" flag columns
+fn flag(row: &Row, name: &str) -> bool {
+    field(row, &[name]).eq_ignore_ascii_case("yes")
+}
+
+/// Convert rows into memories numbered from 0
+fn convert(source: ExportSource, rows: Rows) -> ImportReport {
+    let mut report = ImportReport::default();
+
+    for (line, row) in rows {
+        let number = report.memories.len() as u32;
+        let mut notes = Vec::new();
+        let result = match source {
+            ExportSource::RepeaterBook => repeaterbook_memory(number, &row, &mut notes),
+            ExportSource::RadioReference => radioreference_memory(number, &row, &mut notes),
+        };
+
+        match result {
+            Ok(mem) => {
+                report.memories.push(mem);
+                report
+                    .notes
+                    .extend(notes.into_iter().map(|(column, message)| FieldNote {
+                        line,
+                        location: number,
+                        column,
+                        message,
+                    }));
+            }
+            Err(reason) => {
+                tracing::warn!("Skipping entry {}: {}", line, reason);
+                report.rejected.push(RejectedRow { line, reason });
+            }
+        }
+    }
+
+    report
+}
+
+/// Frequencies and tones shared by both exports
+fn base_memory(
+    number: u32,
+    output: &str,
+    input: &str,
+    tones: [(&'static str, &str); 2],
+    notes: &mut Vec<(&'static str, String)>,
+) -> std::result::Result<(Memory, Option<String>), String> {
+    let freq = Memory::parse_freq(output)
+        .ok()
+        .filter(|&freq| freq > 0)
+        .ok_or_else(|| format!("Invalid output frequency \"{}\"", output))?;
+    let input_freq = match Memory::parse_freq(input) {
+        Ok(input_freq) => input_freq,
+        Err(_) => {
+            notes.push((
+                "Input",
+                format!("Invalid value \"{}\", using simplex", input),
+            ));
+            0
+        }
+    };
+
+    let mut mem = Memory::new(number);
+    mem.freq = freq;
+    (mem.duplex, mem.offset) = duplex_for(freq, input_freq);
+
+    let [uplink, downlink] = tones.map(|(column, text)| {
+        parse_squelch(text).unwrap_or_else(|| {
+            notes.push((column, format!("Unrecognized tone \"{}\", ignored", text)));
+            Squelch::None
+        })
+    });
+    apply_squelch(&mut mem, &uplink, &downlink);
+
+    let nac = [uplink, downlink]
+        .into_iter()
+        .find_map(|squelch| match squelch {
+            Squelch::Nac(nac) => Some(nac),
+            _ => None,
+        });
+    Ok((mem, nac))
+}
+
+/// Join non-empty comment parts
+fn join_comment(parts: &[&str]) -> String {
+    parts
+        .iter()
+        .filter(|part| !part.is_empty())
+        .copied()
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Memory for a RepeaterBook result
+fn repeaterbook_memory(
+    number: u32,
+    row: &Row,
+    notes: &mut Vec<(&'static str, String)>,
+) -> std::result::Result<Memory, String> {
+    let status = field(row, &["Operational Status", "Op Status"]);
+    if status.eq_ignore_ascii_case("off-air") {
+        return Err("Repeater is off the air".to_string());
+    }
+
+    let (mut mem, nac) = base_memory(
+        number,
+        field(row, &["Frequency", "Output Freq"]),
+        field(row, &["Input Freq"]),
+        [
+            ("PL", field(row, &["PL", "Uplink Tone"])),
+            ("TSQ", field(row, &["TSQ", "Downlink Tone"])),
+        ],
+        notes,
+    )?;
+
+    let callsign = field(row, &["Callsign", "Call"]);
+    mem.name = callsign.to_string();
+
+    // Analog FM wins on mixed-mode repeaters since every radio can use it
+    mem.mode = if flag(row, "FM Analog") || row.get("FM Analog").is_none() {
+        match field(row, &["FM Bandwidth"]) {
+            bw if bw.starts_with("12.5") => Mode::Nfm,
+            _ => Mode::Fm,
+        }
+    } else if flag(row, "D-Star") {
+        Mode::Dv
+    } else if flag(row, "DMR") {
+        Mode::Dmr
+    } else if flag(row, "APCO P-25") {
+        Mode::P25
+    } else if flag(row, "System Fusion") {
+        Mode::Dn
+    } else {
+        return Err("No supported mode".to_string());
+    };
+
+    if mem.mode == Mode::Dv {
+        mem.tmode = ToneMode::None;
+        mem.dv_urcall = "CQCQCQ".to_string();
+        (mem.dv_rpt1call, mem.dv_rpt2call) = dstar_calls(callsign, mem.freq);
+    }
+
+    let nac = match mem.mode {
+        Mode::P25 => nac.or_else(|| Some(field(row, &["P-25 NAC"]).to_string())),
+        _ => None,
+    }
+    .filter(|nac| !nac.is_empty())
+    .map(|nac| format!("NAC {}", nac));
+    mem.comment = join_comment(&[
+        field(row, &["Nearest City", "Location"]),
+        field(row, &["Landmark"]),
+        nac.as_deref().unwrap_or(""),
+    ]);
+
+    Ok(mem)
+}
+
+/// Memory for a RadioReference frequency
+fn radioreference_memory(
+    number: u32,
+    row: &Row,
+    notes: &mut Vec<(&'static str, String)>,
+) -> std::result::Result<Memory, String> {
+    // One column for both directions: a CTCSS tone is only sent, while a
+    // DCS code is used on the input and the output alike
+    let tone = field(row, &["PL Tone", "Tone"]);
+    let downlink = match parse_squelch(tone) {
+        Some(Squelch::Dcs(..)) => tone,
+        _ => "",
+    };
+    let (mut mem, nac) = base_memory(
+        number,
+        field(row, &["Frequency Output", "Frequency"]),
+        field(row, &["Frequency Input"]),
+        [("Tone", tone), ("Tone", downlink)],
+        notes,
+    )?;
+
+    let mode = field(row, &["Mode"]);
+    mem.mode = match mode.to_uppercase().as_str() {
+        "FM" | "" => Mode::Fm,
+        "FMN" => Mode::Nfm,
+        "AM" => Mode::Am,
+        "P25" => Mode::P25,
+        "DMR" => Mode::Dmr,
+        _ => return Err(format!("Unsupported mode \"{}\"", mode)),
+    };
+
+    let callsign = field(row, &["FCC Callsign", "Callsign"]);
+    let alpha_tag = field(row, &["Alpha Tag"]);
+    mem.name = if alpha_tag.is_empty() {
+        callsign.to_string()
+    } else {
+        alpha_tag.to_string()
+    };
+
+    let nac = nac.map(|nac| format!("NAC {}", nac));
+    mem.comment = join_comment(&[
+        field(row, &["Description"]),
+        if alpha_tag.is_empty() { "" } else { callsign },
+        nac.as_deref().unwrap_or(""),
+    ]);
+
+    Ok(mem)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplex_and_tones() {
+        assert_eq!(
+            duplex_for(146_940_000, 146_340_000),
+            (Duplex::Minus, 600_000)
+        );
+        assert_eq!(
+            duplex_for(442_900_000, 447_900_000),
+            (Duplex::Plus, 5_000_000)
+        );
+        assert_eq!(duplex_for(146_520_000, 0), (Duplex::Simplex, 0));
+        assert_eq!(
+            duplex_for(145_800_000, 435_000_000),
+            (Duplex::Split, 435_000_000)
+        );
+
+        assert_eq!(parse_squelch("100.0"), Some(Squelch::Tone(100.0)));
+        assert_eq!(parse_squelch("136.5 PL"), Some(Squelch::Tone(136.5)));
+        assert_eq!(parse_squelch("D023N"), Some(Squelch::Dcs(23, 'N')));
+        assert_eq!(parse_squelch("d754i"), Some(Squelch::Dcs(754, 'R')));
+        assert_eq!(parse_squelch("411 DPL"), Some(Squelch::Dcs(411, 'N')));
+        assert_eq!(parse_squelch("$293 NAC"), Some(Squelch::Nac("293".into())));
+        assert_eq!(parse_squelch("CSQ"), Some(Squelch::None));
+        assert_eq!(parse_squelch("bogus"), None);
+
+        let mut mem = Memory::new(0);
+        apply_squelch(&mut mem, &Squelch::Tone(100.0), &Squelch::Dcs(23, 'N'));
+        assert_eq!(mem.tmode, ToneMode::Cross);
+        assert_eq!(mem.cross_mode, CrossMode::ToneToDtcs);
+        assert_eq!(mem.rx_dtcs, 23);
+
+        // DCS on the input only, and on both
+        let mut mem = Memory::new(0);
+        apply_squelch(&mut mem, &Squelch::Dcs(411, 'R'), &Squelch::None);
+        assert_eq!(mem.tmode, ToneMode::Cross);
+        assert_eq!(mem.cross_mode, CrossMode::DtcsToNone);
+        assert_eq!(mem.dtcs, 411);
+        assert_eq!(mem.dtcs_polarity, DtcsPolarity::RN);
+
+        let mut mem = Memory::new(0);
+        apply_squelch(&mut mem, &Squelch::Dcs(23, 'N'), &Squelch::Dcs(23, 'R'));
+        assert_eq!(mem.tmode, ToneMode::Dtcs);
+        assert_eq!((mem.dtcs, mem.rx_dtcs), (23, 23));
+        assert_eq!(mem.dtcs_polarity, DtcsPolarity::NR);
+    }
+
+    #[test]
+    fn test_repeaterbook_json_non_object() {
+        let json = r#"[
+            {"Frequency": "146.94000", "Input Freq": "146.34000", "Callsign": "W6CX",
+             "Operational Status": "On-air", "FM Analog": "Yes"},
+            "146.52000",
+            null,
+            {"Frequency": "", "Callsign": "NOFREQ"}
+        ]"#;
+        let report = parse_repeaters(json).unwrap();
+        assert_eq!(report.memories.len(), 1);
+        let lines: Vec<usize> = report.rejected.iter().map(|row| row.line).collect();
+        assert_eq!(lines, vec![2, 3, 4]);
+        assert!(report.rejected[0].reason.contains("object"));
+    }
+
+    #[test]
+    fn test_repeaterbook_json() {
+        let json = r#"{"count": 4, "results": [
+            {"Frequency": "146.94000", "Input Freq": "146.34000", "PL": "100.0", "TSQ": "",
+             "Nearest City": "Walnut Creek", "Landmark": "Mt Diablo", "Callsign": "W6CX",
+             "Operational Status": "On-air", "FM Analog": "Yes", "D-Star": "No"},
+            {"Frequency": "442.90000", "Input Freq": "447.90000", "PL": "D023N", "TSQ": "D023N",
+             "Nearest City": "Oakland", "Landmark": "", "Callsign": "K6LNK",
+             "Operational Status": "On-air", "FM Analog": "Yes", "D-Star": "No"},
+            {"Frequency": "145.23000", "Input Freq": "144.63000", "PL": "", "TSQ": "",
+             "Nearest City": "San Jose", "Landmark": "", "Callsign": "KJ6KO",
+             "Operational Status": "On-air", "FM Analog": "No", "D-Star": "Yes"},
+            {"Frequency": "147.00000", "Input Freq": "147.60000", "PL": "", "TSQ": "",
+             "Nearest City": "Nowhere", "Callsign": "N0AIR",
+             "Operational Status": "Off-air", "FM Analog": "Yes"}
+        ]}"#;
+        let report = parse_repeaters(json).unwrap();
+        assert_eq!(report.memories.len(), 3);
+        assert_eq!(report.rejected.len(), 1);
+        assert_eq!(report.rejected[0].line, 4);
+
+        let w6cx = &report.memories[0];
+        assert_eq!(w6cx.number, 0);
+        assert_eq!(w6cx.name, "W6CX");
+        assert_eq!(w6cx.comment, "Walnut Creek, Mt Diablo");
+        assert_eq!((w6cx.duplex, w6cx.offset), (Duplex::Minus, 600_000));
+        assert_eq!(w6cx.tmode, ToneMode::Tone);
+        assert_eq!(w6cx.rtone, 100.0);
+
+        let k6lnk = &report.memories[1];
+        assert_eq!(k6lnk.duplex, Duplex::Plus);
+        assert_eq!(k6lnk.tmode, ToneMode::Dtcs);
+        assert_eq!(k6lnk.dtcs, 23);
+
+        let dstar = &report.memories[2];
+        assert_eq!(dstar.mode, Mode::Dv);
+        assert_eq!(dstar.dv_rpt1call, "KJ6KO  C");
+        assert_eq!(dstar.dv_rpt2call, "KJ6KO  G");
+        assert_eq!(dstar.dv_urcall, "CQCQCQ");
+    }
+
+    #[test]
+    fn test_radioreference_csv() {
+        let csv = "\
+\"Frequency Output\",\"Frequency Input\",\"FCC Callsign\",\"Agency/Category\",\"Description\",\"Alpha Tag\",\"PL Tone\",\"Mode\",\"Class Station Code\",\"Tag\"\r
+\"155.47500\",\"\",\"\",\"Interop\",\"National Law Enforcement, Simplex\",\"NLEEC\",\"156.7 PL\",\"FM\",\"MO\",\"Interop\"\r
+\"453.35000\",\"458.35000\",\"WQAB123\",\"County\",\"Fire Dispatch\",\"FIRE DISP\",\"023 DPL\",\"FMN\",\"RM\",\"Fire Dispatch\"\r
+\"851.01250\",\"806.01250\",\"WQXY999\",\"County\",\"Sheriff\",\"SO P25\",\"$293 NAC\",\"P25\",\"RM\",\"Law Dispatch\"\r
+\"460.12500\",\"\",\"\",\"County\",\"Encrypted\",\"ENC\",\"\",\"DE\",\"FB2\",\"Law Tac\"\r
+";
+        let report = parse_repeaters(csv).unwrap();
+        assert_eq!(report.memories.len(), 3);
+        assert_eq!(report.rejected[0].line, 5);
+
+        let nleec = &report.memories[0];
+        assert_eq!(nleec.name, "NLEEC");
+        assert_eq!(nleec.comment, "National Law Enforcement, Simplex");
+        assert_eq!(nleec.duplex, Duplex::Simplex);
+        assert_eq!(nleec.tmode, ToneMode::Tone);
+        assert_eq!(nleec.rtone, 156.7);
+
+        let fire = &report.memories[1];
+        assert_eq!(fire.mode, Mode::Nfm);
+        assert_eq!((fire.duplex, fire.offset), (Duplex::Plus, 5_000_000));
+        assert_eq!(fire.tmode, ToneMode::Dtcs);
+        assert_eq!(fire.comment, "Fire Dispatch, WQAB123");
+
+        let p25 = &report.memories[2];
+        assert_eq!(p25.mode, Mode::P25);
+        assert_eq!((p25.duplex, p25.offset), (Duplex::Minus, 45_000_000));
+        assert_eq!(p25.tmode, ToneMode::None);
+        assert_eq!(p25.comment, "Sheriff, WQXY999, NAC 293");
+
+        assert!(matches!(
+            parse_repeaters("Location,Name\n1,Test\n"),
+            Err(RepeaterError::UnknownFormat)
+        ));
+    }
+}
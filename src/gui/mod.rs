@@ -2,6 +2,5 @@
 // Provides a traditional desktop application experience
 
 pub mod qt_gui;
-pub mod radio_ops;
 
 pub use qt_gui::run_qt_app;
@@ -1,9 +1,12 @@
 //! Qt-based GUI for CHIRP-RS
 //! Provides a traditional desktop application experience using Qt Widgets
 
-use crate::core::Memory;
-use crate::drivers::{get_driver, init_drivers, list_drivers};
-use crate::formats::load_img;
+use crate::core::{Duplex, Memory, Mode, RadioFeatures, ToneMode};
+use crate::drivers::{
+    detect_image, get_driver, group_by_bank, init_drivers, list_drivers, BankModel, DetectError,
+    DynCloneModeRadio,
+};
+use crate::formats::{d75, load_d75, load_img};
 use cpp::cpp;
 use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
@@ -33,6 +36,7 @@ cpp! {{
     #include <QtWidgets/QDialogButtonBox>
     #include <QtWidgets/QFormLayout>
     #include <QtWidgets/QLineEdit>
+    #include <QtWidgets/QInputDialog>
     #include <QtWidgets/QComboBox>
     #include <QtWidgets/QSpinBox>
     #include <QtWidgets/QProgressDialog>
@@ -73,6 +77,7 @@ cpp! {{
         const char* save_file(const char* path);
         const char* export_to_csv(const char* path);
         const char* import_from_csv(const char* path);
+        const char* get_import_report(int* out_clean);
         void new_file();
         const char* get_current_filename();
         const char* get_current_filepath();
@@ -86,6 +91,7 @@ cpp! {{
         const char* get_vendors();
         const char* get_models_for_vendor(const char* vendor);
         const char* get_serial_ports();
+        const char* probe_radio(const char* port);
         const char* get_ctcss_tones();
         const char* get_bank_names();
         const char* get_radio_features();
@@ -101,6 +107,7 @@ cpp! {{
         const char* delete_memory_at(size_t row);
         void copy_memory_at(size_t row);
         const char* paste_memory_at(size_t row);
+        const char* get_paste_report();
         int has_clipboard_memory();
 
         // Multi-band support
@@ -117,6 +124,7 @@ cpp! {{
         size_t get_bank_count();
         uint8_t get_bank_number_by_index(size_t index);
         const char* get_bank_name_by_number(uint8_t bank_num);
+        const char* rename_bank(uint8_t bank_num, const char* name);
         size_t get_bank_memory_count(uint8_t bank_num);
         RowData get_memory_by_bank_row(uint8_t bank_num, size_t row);
         intptr_t get_global_index_from_bank_row(uint8_t bank_num, size_t row);
@@ -357,8 +365,10 @@ cpp! {{
         // Create model dropdown (populated when vendor changes)
         QComboBox* modelCombo = new QComboBox();
 
-        // Create port dropdown
+        // Create port dropdown (editable so tcp:// and rfc2217:// URLs can be typed)
         QComboBox* portCombo = new QComboBox();
+        portCombo->setEditable(true);
+        portCombo->lineEdit()->setPlaceholderText("Device, tcp://host:port or rfc2217://host:port");
         QString portsStr = QString::fromUtf8(get_serial_ports());
         QStringList ports = portsStr.split(",", Qt::SkipEmptyParts);
         if (ports.isEmpty()) {
@@ -409,9 +419,39 @@ cpp! {{
         layout->addRow("Vendor:", vendorCombo);
         layout->addRow("Model:", modelCombo);
 
+        // Detect radio button: probe the selected port and fill in vendor/model
+        QPushButton* detectBtn = new QPushButton("Detect");
+        QObject::connect(detectBtn, &QPushButton::clicked,
+            [vendorCombo, modelCombo, portCombo, parent]() {
+                QString port = portCombo->currentText();
+                if (port.isEmpty() || port == "(No ports found)") {
+                    QMessageBox::warning(parent, "No Port Selected",
+                        "Please select a serial port first");
+                    return;
+                }
+
+                QApplication::setOverrideCursor(Qt::WaitCursor);
+                QString found = QString::fromUtf8(probe_radio(port.toUtf8().constData()));
+                QApplication::restoreOverrideCursor();
+
+                QStringList parts = found.split(",");
+                if (parts.count() != 2) {
+                    QMessageBox::warning(parent, "No Radio Detected",
+                        "No supported radio answered on " + port + ".\n\n"
+                        "Please check that the radio is powered on and connected,\n"
+                        "or select the vendor and model manually.");
+                    return;
+                }
+
+                // Setting the vendor repopulates the model list
+                vendorCombo->setCurrentIndex(vendorCombo->findText(parts[0]));
+                modelCombo->setCurrentIndex(modelCombo->findText(parts[1]));
+            });
+
         QHBoxLayout* portLayout = new QHBoxLayout();
         portLayout->addWidget(portCombo);
         portLayout->addWidget(refreshBtn);
+        portLayout->addWidget(detectBtn);
         layout->addRow("Port:", portLayout);
 
         // Add buttons
@@ -537,8 +577,10 @@ cpp! {{
         // Create model dropdown (populated when vendor changes)
         QComboBox* modelCombo = new QComboBox();
 
-        // Create port dropdown
+        // Create port dropdown (editable so tcp:// and rfc2217:// URLs can be typed)
         QComboBox* portCombo = new QComboBox();
+        portCombo->setEditable(true);
+        portCombo->lineEdit()->setPlaceholderText("Device, tcp://host:port or rfc2217://host:port");
         QString portsStr = QString::fromUtf8(get_serial_ports());
         QStringList ports = portsStr.split(",", Qt::SkipEmptyParts);
         if (ports.isEmpty()) {
@@ -1008,6 +1050,7 @@ struct AppState {
     is_modified: bool,
     mmap: Option<crate::memmap::MemoryMap>,
     bank_names: Vec<String>,
+    /// Copied memory, kept across file loads so channels can be pasted into another radio
     clipboard: Option<Memory>,
     /// Band organization for multi-band radios (band_num -> memory indices)
     band_groups: std::collections::HashMap<u8, Vec<usize>>,
@@ -1025,6 +1068,12 @@ struct AppState {
 /// This keeps data alive while Qt is displaying it
 static MEMORY_DATA: Mutex<Option<AppState>> = Mutex::new(None);
 
+/// Report text and clean flag from the last CSV import
+static IMPORT_REPORT: Mutex<Option<(String, bool)>> = Mutex::new(None);
+
+/// Changes made to the last pasted memory to fit the loaded radio
+static PASTE_REPORT: Mutex<Option<String>> = Mutex::new(None);
+
 /// Download progress state
 #[derive(Clone)]
 struct DownloadProgress {
@@ -1096,7 +1145,7 @@ fn memory_to_row_strings(mem: &Memory, bank_names: &[String]) -> Vec<String> {
         String::new()
     };
 
-    let (tmode, tone_str, urcall, rpt1, rpt2) = if mem.mode == "DV" {
+    let (tmode, tone_str, urcall, rpt1, rpt2) = if mem.mode == Mode::Dv {
         // D-STAR mode: show D-STAR fields
         (
             String::new(),
@@ -1107,18 +1156,21 @@ fn memory_to_row_strings(mem: &Memory, bank_names: &[String]) -> Vec<String> {
         )
     } else {
         // FM/other modes: show tone fields
-        let tone_val = if mem.tmode.contains("TSQL") || mem.tmode == "Cross" {
+        let tone_val = if matches!(
+            mem.tmode,
+            ToneMode::Tsql | ToneMode::TsqlR | ToneMode::Cross
+        ) {
             mem.ctone
         } else {
             mem.rtone
         };
-        let tone_str = if !mem.tmode.is_empty() && tone_val > 0.0 {
+        let tone_str = if mem.tmode != ToneMode::None && tone_val > 0.0 {
             format!("{:.1}", tone_val)
         } else {
             String::new()
         };
         (
-            mem.tmode.clone(),
+            mem.tmode.to_string(),
             tone_str,
             String::new(),
             String::new(),
@@ -1142,9 +1194,9 @@ fn memory_to_row_strings(mem: &Memory, bank_names: &[String]) -> Vec<String> {
         mem.number.to_string(),
         freq_str,
         mem.name.clone(),
-        mem.duplex.clone(),
+        mem.duplex.to_string(),
         offset_str,
-        mem.mode.clone(),
+        mem.mode.to_string(),
         format!("{}", mem.tuning_step), // Tuning step in kHz
         tmode,
         tone_str,
@@ -1400,6 +1452,55 @@ pub unsafe extern "C" fn get_bank_name_by_number(bank_num: u8) -> *const c_char
     CString::new(bank_name).unwrap().into_raw()
 }
 
+/// FFI: Rename a bank/group through the radio's bank model
+/// Returns NULL on success, or error message on failure
+#[no_mangle]
+pub unsafe extern "C" fn rename_bank(bank_num: u8, name: *const c_char) -> *const c_char {
+    let name_str = CStr::from_ptr(name).to_string_lossy().into_owned();
+
+    let mut data = MEMORY_DATA.lock().unwrap();
+    let state = match data.as_mut() {
+        Some(s) => s,
+        None => return CString::new("No data loaded").unwrap().into_raw(),
+    };
+
+    // The edits are replayed on a scratch driver, so a memory that fails
+    // leaves the loaded image as it was
+    let mut radio = match state_radio(state) {
+        Ok(Some(radio)) => radio,
+        Ok(None) => {
+            return CString::new("Bank names can only be edited in a loaded radio image")
+                .unwrap()
+                .into_raw()
+        }
+        Err(err_msg) => {
+            tracing::error!("rename_bank: {}", err_msg);
+            return CString::new(err_msg).unwrap().into_raw();
+        }
+    };
+    let model = match radio.bank_model() {
+        Some(model) => model,
+        None => {
+            return CString::new("This radio has no editable banks")
+                .unwrap()
+                .into_raw()
+        }
+    };
+    if let Err(e) = model.rename_bank(bank_num as usize, &name_str) {
+        tracing::error!("rename_bank: {}", e);
+        return CString::new(e.to_string()).unwrap().into_raw();
+    }
+    if let Ok(banks) = model.get_banks() {
+        state.bank_names = banks.into_iter().map(|bank| bank.name).collect();
+    }
+
+    state.mmap = radio.mmap().cloned();
+    state.is_modified = true;
+
+    // Return NULL to indicate success
+    std::ptr::null()
+}
+
 /// FFI: Get number of memories in a specific bank
 #[no_mangle]
 pub extern "C" fn get_bank_memory_count(bank_num: u8) -> usize {
@@ -1528,7 +1629,7 @@ fn set_memory_data(memories: Vec<Memory>, bank_names: Vec<String>) {
     let (band_groups, band_display_names) = build_band_info(&memories);
 
     // Build bank/group organization
-    let bank_groups = build_bank_info(&memories);
+    let bank_groups = build_bank_info(&memories, None);
 
     let mut data = MEMORY_DATA.lock().unwrap();
     *data = Some(AppState {
@@ -1538,7 +1639,7 @@ fn set_memory_data(memories: Vec<Memory>, bank_names: Vec<String>) {
         is_modified: false,
         mmap: None,
         bank_names,
-        clipboard: None,
+        clipboard: data.as_mut().and_then(|s| s.clipboard.take()),
         band_groups,
         band_display_names,
         bank_groups,
@@ -1547,11 +1648,28 @@ fn set_memory_data(memories: Vec<Memory>, bank_names: Vec<String>) {
     });
 }
 
-/// Build bank/group information from memories
-/// Returns a HashMap mapping bank numbers to vectors of memory indices
-fn build_bank_info(memories: &[Memory]) -> std::collections::HashMap<u8, Vec<usize>> {
+/// Build bank/group information through the radio's bank model
+/// Returns a HashMap mapping bank numbers to vectors of memory indices. A memory
+/// can be listed under several banks; radios without a bank model fall back to
+/// each memory's own bank number.
+fn build_bank_info(
+    memories: &[Memory],
+    bank_model: Option<&mut dyn BankModel>,
+) -> std::collections::HashMap<u8, Vec<usize>> {
     let mut bank_groups = std::collections::HashMap::new();
 
+    if let Some(model) = bank_model {
+        match group_by_bank(model, memories) {
+            Ok(groups) => {
+                return groups
+                    .into_iter()
+                    .map(|(bank, indices)| (bank as u8, indices))
+                    .collect();
+            }
+            Err(e) => tracing::warn!("build_bank_info: failed to read banks: {}", e),
+        }
+    }
+
     // Group memories by bank number (skip empty memories)
     for (idx, mem) in memories.iter().enumerate() {
         if !mem.empty {
@@ -1565,6 +1683,64 @@ fn build_bank_info(memories: &[Memory]) -> std::collections::HashMap<u8, Vec<usi
     bank_groups
 }
 
+/// Create a clone-mode driver for a radio through the driver registry
+fn create_clone_driver(vendor: &str, model: &str) -> Result<Box<dyn DynCloneModeRadio>, String> {
+    let driver_info =
+        get_driver(vendor, model).ok_or_else(|| format!("Unknown radio: {} {}", vendor, model))?;
+    driver_info
+        .create()
+        .and_then(|driver| driver.into_clone_mode())
+        .ok_or_else(|| format!("Unsupported radio model: {}", driver_info.model))
+}
+
+/// Look up a radio's features through the driver registry (defaults if unknown)
+fn radio_features(vendor: &str, model: &str) -> RadioFeatures {
+    get_driver(vendor, model)
+        .and_then(|info| info.create())
+        .map(|driver| driver.radio().get_features())
+        .unwrap_or_default()
+}
+
+/// Create a radio's driver with a memory map loaded (None for command-mode radios)
+fn mmap_radio(
+    vendor: &str,
+    model: &str,
+    mmap: &crate::memmap::MemoryMap,
+) -> Option<Box<dyn DynCloneModeRadio>> {
+    let mut radio = create_clone_driver(vendor, model).ok()?;
+    radio.load_mmap(mmap).ok()?;
+    Some(radio)
+}
+
+/// Create the loaded radio's driver with the current memory edits applied
+/// Returns None for radios without an image, or the memory the driver rejected
+fn state_radio(state: &AppState) -> Result<Option<Box<dyn DynCloneModeRadio>>, String> {
+    let mut radio = match (&state.radio_vendor, &state.radio_model, &state.mmap) {
+        (Some(vendor), Some(model), Some(mmap)) => match mmap_radio(vendor, model, mmap) {
+            Some(radio) => radio,
+            None => return Ok(None),
+        },
+        _ => return Ok(None),
+    };
+    for mem in state.memories.iter().filter(|mem| !mem.empty) {
+        radio
+            .set_memory(mem)
+            .map_err(|e| format!("Failed to update memory #{}: {}", mem.number, e))?;
+    }
+    Ok(Some(radio))
+}
+
+/// Read bank names through a radio's driver
+fn radio_bank_names(radio: Option<&dyn DynCloneModeRadio>) -> Vec<String> {
+    match radio {
+        Some(radio) => radio
+            .get_bank_names()
+            .unwrap_or_else(|_| (0..30).map(|i| format!("Bank {}", i)).collect()),
+        // Command-mode radios have no memory map, so no bank names
+        None => vec![],
+    }
+}
+
 /// Clear all memory data
 fn clear_memory_data() {
     let mut data = MEMORY_DATA.lock().unwrap();
@@ -1575,7 +1751,7 @@ fn clear_memory_data() {
         is_modified: false,
         mmap: None,
         bank_names: (0..10).map(|i| format!("Bank {}", i)).collect(),
-        clipboard: None,
+        clipboard: data.as_mut().and_then(|s| s.clipboard.take()),
         band_groups: std::collections::HashMap::new(),
         band_display_names: std::collections::HashMap::new(),
         bank_groups: std::collections::HashMap::new(),
@@ -1602,8 +1778,13 @@ pub unsafe extern "C" fn load_file(path: *const c_char) -> *const c_char {
 
     tracing::debug!("load_file called: {}", path.display());
 
-    // Load the .img file
-    let (mmap, metadata) = match load_img(&path) {
+    // Load the .img (or Kenwood MCP .d74/.d75) file
+    let loaded = if d75::is_mcp_file(&path) {
+        load_d75(&path).map_err(|e| e.to_string())
+    } else {
+        load_img(&path).map_err(|e| e.to_string())
+    };
+    let (mmap, metadata) = match loaded {
         Ok(data) => data,
         Err(e) => {
             let err_msg = format!("Failed to load file: {}", e);
@@ -1612,64 +1793,59 @@ pub unsafe extern "C" fn load_file(path: *const c_char) -> *const c_char {
         }
     };
 
-    // Determine which driver to use from metadata
-    let vendor = &metadata.vendor;
-    let model = &metadata.model;
-
-    // Get the appropriate driver
-    let driver_info = match get_driver(vendor, model) {
-        Some(info) => info,
-        None => {
-            let err_msg = format!("Unknown radio: {} {}", vendor, model);
-            tracing::error!("load_file: {}", err_msg);
-            return CString::new(err_msg).unwrap().into_raw();
-        }
-    };
-
-    // Parse memories and bank names from the memmap
-    let (memories, bank_names) = if driver_info.model == "TH-D75" || driver_info.model == "TH-D74" {
-        use crate::drivers::thd75::THD75Radio;
-        let mut radio = THD75Radio::new();
-        radio.mmap = Some(mmap.clone());
-
-        let mems = match radio.get_memories() {
-            Ok(mems) => mems,
-            Err(e) => {
-                let err_msg = format!("Failed to parse memories: {}", e);
-                tracing::error!("load_file: {}", err_msg);
-                return CString::new(err_msg).unwrap().into_raw();
+    // Determine which driver to use from metadata, or detect it for raw dumps
+    let (vendor, model) = if !metadata.vendor.is_empty() {
+        (metadata.vendor.clone(), metadata.model.clone())
+    } else {
+        let detected = match detect_image(mmap.get_packed(), path_str) {
+            Ok(info) => info,
+            Err(DetectError::Ambiguous(candidates)) => {
+                tracing::warn!(
+                    "load_file: {}, using {}",
+                    DetectError::Ambiguous(candidates.clone()),
+                    candidates[0].full_name()
+                );
+                candidates[0].clone()
             }
-        };
-
-        let names = radio
-            .get_bank_names()
-            .unwrap_or_else(|_| (0..10).map(|i| format!("Bank {}", i)).collect());
-
-        (mems, names)
-    } else if driver_info.model == "UV-5R" {
-        use crate::drivers::{uv5r::UV5RRadio, Radio};
-        let mut radio = UV5RRadio::new();
-        radio.mmap = Some(mmap.clone());
-
-        let mems = match radio.get_memories() {
-            Ok(mems) => mems,
             Err(e) => {
-                let err_msg = format!("Failed to parse memories: {}", e);
+                let err_msg = format!("Unable to identify radio image: {}", e);
                 tracing::error!("load_file: {}", err_msg);
                 return CString::new(err_msg).unwrap().into_raw();
             }
         };
+        tracing::info!("load_file: detected {}", detected.full_name());
+        (detected.vendor, detected.model)
+    };
 
-        // UV-5R doesn't have banks
-        let names = Vec::new();
+    // Get the appropriate driver from the registry
+    let mut radio = match create_clone_driver(&vendor, &model) {
+        Ok(radio) => radio,
+        Err(err_msg) => {
+            tracing::error!("load_file: {}", err_msg);
+            return CString::new(err_msg).unwrap().into_raw();
+        }
+    };
 
-        (mems, names)
-    } else {
-        let err_msg = format!("Unsupported radio model: {}", driver_info.model);
+    // Parse memories and bank names from the memmap
+    if let Err(e) = radio.load_mmap(&mmap) {
+        let err_msg = format!("Failed to process memory map: {}", e);
         tracing::error!("load_file: {}", err_msg);
         return CString::new(err_msg).unwrap().into_raw();
+    }
+
+    let memories = match radio.get_memories() {
+        Ok(mems) => mems,
+        Err(e) => {
+            let err_msg = format!("Failed to parse memories: {}", e);
+            tracing::error!("load_file: {}", err_msg);
+            return CString::new(err_msg).unwrap().into_raw();
+        }
     };
 
+    let bank_names = radio
+        .get_bank_names()
+        .unwrap_or_else(|_| (0..10).map(|i| format!("Bank {}", i)).collect());
+
     tracing::info!(
         "File loaded successfully: {} memories from {} {}",
         memories.len(),
@@ -1692,7 +1868,7 @@ pub unsafe extern "C" fn load_file(path: *const c_char) -> *const c_char {
     let (band_groups, band_display_names) = build_band_info(&memories);
 
     // Build bank/group organization
-    let bank_groups = build_bank_info(&memories);
+    let bank_groups = build_bank_info(&memories, radio.bank_model());
 
     // Update global state
     let mut data = MEMORY_DATA.lock().unwrap();
@@ -1703,12 +1879,12 @@ pub unsafe extern "C" fn load_file(path: *const c_char) -> *const c_char {
         is_modified: false,
         mmap: Some(mmap),
         bank_names,
-        clipboard: None,
+        clipboard: data.as_mut().and_then(|s| s.clipboard.take()),
         band_groups,
         band_display_names,
         bank_groups,
-        radio_vendor: Some(vendor.to_string()),
-        radio_model: Some(model.to_string()),
+        radio_vendor: Some(vendor),
+        radio_model: Some(model),
     });
 
     // Return NULL to indicate success
@@ -1751,8 +1927,7 @@ pub unsafe extern "C" fn save_file(path: *const c_char) -> *const c_char {
         return CString::new(err_msg).unwrap().into_raw();
     }
 
-    use crate::drivers::{CloneModeRadio, Radio};
-    use crate::formats::{save_img, Metadata};
+    use crate::formats::{save_d75, save_img, Metadata};
 
     // Get the mmap - must have been loaded from file or download
     let base_mmap = match &state.mmap {
@@ -1774,77 +1949,52 @@ pub unsafe extern "C" fn save_file(path: *const c_char) -> *const c_char {
         }
     };
 
-    // Create the correct driver based on vendor/model
-    let mmap = match (vendor.to_lowercase().as_str(), model.as_str()) {
-        ("baofeng", "UV-5R") => {
-            use crate::drivers::uv5r::UV5RRadio;
-
-            let mut radio = UV5RRadio::new();
-            if let Err(e) = radio.process_mmap(&base_mmap) {
-                let err_msg = format!("Failed to process memory map: {}", e);
-                tracing::error!("save_file: {}", err_msg);
-                return CString::new(err_msg).unwrap().into_raw();
-            }
-
-            // Update only non-empty memories
-            for mem in &state.memories {
-                if !mem.empty {
-                    if let Err(e) = radio.set_memory(mem) {
-                        let err_msg = format!("Failed to update memory #{}: {}", mem.number, e);
-                        tracing::error!("save_file: {}", err_msg);
-                        return CString::new(err_msg).unwrap().into_raw();
-                    }
-                }
-            }
-
-            // Get the updated mmap
-            match radio.mmap {
-                Some(m) => m,
-                None => {
-                    let err_msg = "Radio driver lost memory map";
-                    tracing::error!("save_file: {}", err_msg);
-                    return CString::new(err_msg).unwrap().into_raw();
-                }
-            }
+    // Create the correct driver through the registry
+    let mut radio = match create_clone_driver(&vendor, &model) {
+        Ok(radio) => radio,
+        Err(err_msg) => {
+            tracing::error!("save_file: {}", err_msg);
+            return CString::new(err_msg).unwrap().into_raw();
         }
-        ("kenwood", "TH-D75") | ("kenwood", "TH-D74") | _ => {
-            use crate::drivers::thd75::THD75Radio;
+    };
+    if let Err(e) = radio.load_mmap(&base_mmap) {
+        let err_msg = format!("Failed to process memory map: {}", e);
+        tracing::error!("save_file: {}", err_msg);
+        return CString::new(err_msg).unwrap().into_raw();
+    }
 
-            let mut radio = THD75Radio::new();
-            if let Err(e) = radio.process_mmap(&base_mmap) {
-                let err_msg = format!("Failed to process memory map: {}", e);
+    // Update only non-empty memories
+    for mem in &state.memories {
+        if !mem.empty {
+            if let Err(e) = radio.set_memory(mem) {
+                let err_msg = format!("Failed to update memory #{}: {}", mem.number, e);
                 tracing::error!("save_file: {}", err_msg);
                 return CString::new(err_msg).unwrap().into_raw();
             }
+        }
+    }
 
-            // Update only non-empty memories
-            for mem in &state.memories {
-                if !mem.empty {
-                    if let Err(e) = radio.set_memory(mem) {
-                        let err_msg = format!("Failed to update memory #{}: {}", mem.number, e);
-                        tracing::error!("save_file: {}", err_msg);
-                        return CString::new(err_msg).unwrap().into_raw();
-                    }
-                }
-            }
-
-            // Get the updated mmap
-            match radio.mmap.clone() {
-                Some(m) => m,
-                None => {
-                    let err_msg = "Memory map not available after update";
-                    tracing::error!("save_file: {}", err_msg);
-                    return CString::new(err_msg).unwrap().into_raw();
-                }
-            }
+    // Get the updated mmap
+    let mmap = match radio.mmap() {
+        Some(m) => m.clone(),
+        None => {
+            let err_msg = "Memory map not available after update";
+            tracing::error!("save_file: {}", err_msg);
+            return CString::new(err_msg).unwrap().into_raw();
         }
     };
 
-    // Create metadata with actual vendor/model
-    let metadata = Metadata::new(&vendor, &model);
+    // Create metadata with actual vendor/model and detected sub-model
+    let mut metadata = Metadata::new(&vendor, &model);
+    metadata.variant = radio.variant().unwrap_or_default().to_string();
 
     // Save to file
-    if let Err(e) = save_img(&path, &mmap, &metadata) {
+    let saved = if d75::is_mcp_file(&path) {
+        save_d75(&path, &mmap, &metadata).map_err(|e| e.to_string())
+    } else {
+        save_img(&path, &mmap, &metadata).map_err(|e| e.to_string())
+    };
+    if let Err(e) = saved {
         let err_msg = format!("Failed to save file: {}", e);
         tracing::error!("save_file: {}", err_msg);
         return CString::new(err_msg).unwrap().into_raw();
@@ -1922,8 +2072,8 @@ pub unsafe extern "C" fn import_from_csv(path: *const c_char) -> *const c_char {
 
     // Import memories from CSV
     use crate::formats::import_csv;
-    let memories = match import_csv(&path) {
-        Ok(mems) => mems,
+    let report = match import_csv(&path) {
+        Ok(report) => report,
         Err(e) => {
             let err_msg = format!("Failed to import CSV: {}", e);
             tracing::error!("import_from_csv: {}", err_msg);
@@ -1931,6 +2081,10 @@ pub unsafe extern "C" fn import_from_csv(path: *const c_char) -> *const c_char {
         }
     };
 
+    // Keep the report for get_import_report()
+    *IMPORT_REPORT.lock().unwrap() = Some((report.to_string(), report.is_clean()));
+    let memories = report.memories;
+
     // Use default bank names since CSV doesn't contain memory map
     let bank_names: Vec<String> = (0..10).map(|i| format!("Bank {}", i)).collect();
 
@@ -1951,7 +2105,7 @@ pub unsafe extern "C" fn import_from_csv(path: *const c_char) -> *const c_char {
     tracing::info!("CSV imported successfully: {} memories", memories.len());
 
     // Build bank/group organization
-    let bank_groups = build_bank_info(&memories);
+    let bank_groups = build_bank_info(&memories, None);
 
     // Update global state
     let mut data = MEMORY_DATA.lock().unwrap();
@@ -1962,7 +2116,7 @@ pub unsafe extern "C" fn import_from_csv(path: *const c_char) -> *const c_char {
         is_modified: true, // Mark as modified since imported from CSV
         mmap: None,        // No memory map from CSV import
         bank_names,
-        clipboard: None,
+        clipboard: data.as_mut().and_then(|s| s.clipboard.take()),
         band_groups,
         band_display_names,
         bank_groups,
@@ -1974,6 +2128,23 @@ pub unsafe extern "C" fn import_from_csv(path: *const c_char) -> *const c_char {
     std::ptr::null()
 }
 
+/// FFI: Get the report from the last CSV import
+/// Returns the summary line followed by one line per skipped row or defaulted
+/// field, or NULL if nothing was imported. `out_clean` is set to 1 if every
+/// row imported unchanged. Caller must free with free_error_message()
+#[no_mangle]
+pub unsafe extern "C" fn get_import_report(out_clean: *mut i32) -> *const c_char {
+    let Some((text, clean)) = IMPORT_REPORT.lock().unwrap().take() else {
+        return std::ptr::null();
+    };
+    if !out_clean.is_null() {
+        *out_clean = clean as i32;
+    }
+    CString::new(text)
+        .map(|s| s.into_raw() as *const c_char)
+        .unwrap_or(std::ptr::null())
+}
+
 /// FFI: Create a new empty file
 #[no_mangle]
 pub extern "C" fn new_file() {
@@ -2117,6 +2288,33 @@ pub extern "C" fn get_serial_ports() -> *const c_char {
     }
 }
 
+/// FFI: Identify the radio on a serial port ("Vendor,Model", empty if nothing answered)
+#[no_mangle]
+pub unsafe extern "C" fn probe_radio(port: *const c_char) -> *const c_char {
+    static mut PROBE_BUF: Option<CString> = None;
+
+    let port_str = CStr::from_ptr(port).to_str().unwrap_or("").to_string();
+
+    let found = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => match runtime.block_on(crate::drivers::probe_port(&port_str)) {
+            Ok(info) => format!("{},{}", info.vendor, info.model),
+            Err(e) => {
+                tracing::warn!("No radio detected on {}: {}", port_str, e);
+                String::new()
+            }
+        },
+        Err(e) => {
+            tracing::error!("Failed to create async runtime: {}", e);
+            String::new()
+        }
+    };
+
+    unsafe {
+        PROBE_BUF = Some(CString::new(found).unwrap());
+        PROBE_BUF.as_ref().unwrap().as_ptr()
+    }
+}
+
 /// FFI: Get list of standard CTCSS tones (comma-separated)
 #[no_mangle]
 pub extern "C" fn get_ctcss_tones() -> *const c_char {
@@ -2145,15 +2343,20 @@ pub extern "C" fn get_bank_names() -> *const c_char {
 
     let bank_names = if let Some(state) = data.as_ref() {
         // Try to read bank names from memory map
-        if let Some(ref mmap) = state.mmap {
-            use crate::drivers::thd75::THD75Radio;
-            let mut radio = THD75Radio::new();
-            radio.mmap = Some(mmap.clone());
-
+        let radio = match (&state.mmap, &state.radio_vendor, &state.radio_model) {
+            (Some(mmap), Some(vendor), Some(model)) => {
+                create_clone_driver(vendor, model).ok().map(|mut radio| {
+                    radio.load_mmap(mmap).ok();
+                    radio
+                })
+            }
+            _ => None,
+        };
+        if let Some(radio) = radio {
             match radio.get_bank_names() {
-                Ok(names) => names.join(","),
-                Err(_) => {
-                    // Fallback to default names on error
+                Ok(names) if !names.is_empty() => names.join(","),
+                _ => {
+                    // Fallback to default names on error or for radios without banks
                     (0..10)
                         .map(|i| format!("Bank {}", i))
                         .collect::<Vec<_>>()
@@ -2161,7 +2364,7 @@ pub extern "C" fn get_bank_names() -> *const c_char {
                 }
             }
         } else {
-            // No mmap, return default names
+            // No mmap or unknown radio, return default names
             (0..10)
                 .map(|i| format!("Bank {}", i))
                 .collect::<Vec<_>>()
@@ -2187,7 +2390,6 @@ pub extern "C" fn get_bank_names() -> *const c_char {
 pub extern "C" fn get_radio_features() -> *const c_char {
     static mut FEATURES_BUF: Option<CString> = None;
 
-    use crate::drivers::Radio;
     use serde_json::json;
 
     let data = MEMORY_DATA.lock().unwrap();
@@ -2202,23 +2404,7 @@ pub extern "C" fn get_radio_features() -> *const c_char {
         };
 
         // Get features based on radio type
-        let features = match (vendor, model) {
-            ("Baofeng", "UV-5R") => {
-                use crate::drivers::uv5r::UV5RRadio;
-                let radio = UV5RRadio::new();
-                radio.get_features()
-            }
-            ("Kenwood", "TH-D75") | ("Kenwood", "TH-D74") => {
-                use crate::drivers::thd75::THD75Radio;
-                let radio = THD75Radio::new();
-                radio.get_features()
-            }
-            _ => {
-                // Unknown radio, return defaults
-                use crate::core::features::RadioFeatures;
-                RadioFeatures::default()
-            }
-        };
+        let features = radio_features(vendor, model);
 
         // Extract power levels as strings
         let power_levels: Vec<String> = features
@@ -2282,35 +2468,14 @@ pub unsafe extern "C" fn download_from_radio(
             // TODO: Update progress bar
         });
 
-        crate::gui::radio_ops::download_from_radio(port_str, vendor_str, model_str, progress_fn)
-            .await
+        crate::radio_ops::download_from_radio(port_str, vendor_str, model_str, progress_fn).await
     });
 
     match result {
         Ok((memories, mmap)) => {
             // Get bank names from the downloaded mmap using the correct driver
-            let bank_names = {
-                use crate::drivers::CloneModeRadio;
-
-                match (vendor_clone.to_lowercase().as_str(), model_clone.as_str()) {
-                    ("baofeng", "UV-5R") => {
-                        // UV-5R doesn't support banks
-                        vec![]
-                    }
-                    ("kenwood", "TH-D75") | ("kenwood", "TH-D74") => {
-                        use crate::drivers::thd75::THD75Radio;
-                        let mut radio = THD75Radio::new();
-                        radio.process_mmap(&mmap).ok();
-                        radio
-                            .get_bank_names()
-                            .unwrap_or_else(|_| (0..30).map(|i| format!("Bank {}", i)).collect())
-                    }
-                    _ => {
-                        // Default: no banks
-                        vec![]
-                    }
-                }
-            };
+            let mut radio = mmap_radio(&vendor_clone, &model_clone, &mmap);
+            let bank_names = radio_bank_names(radio.as_deref());
 
             // Convert to CStrings (include all memories, even empty ones)
             let mut all_cstrings = Vec::new();
@@ -2327,7 +2492,8 @@ pub unsafe extern "C" fn download_from_radio(
             let (band_groups, band_display_names) = build_band_info(&memories);
 
             // Build bank/group organization
-            let bank_groups_map = build_bank_info(&memories);
+            let bank_groups_map =
+                build_bank_info(&memories, radio.as_mut().and_then(|r| r.bank_model()));
 
             // Update global state
             let mut data = MEMORY_DATA.lock().unwrap();
@@ -2338,7 +2504,7 @@ pub unsafe extern "C" fn download_from_radio(
                 is_modified: false,
                 mmap: Some(mmap), // Store mmap from radio download
                 bank_names,
-                clipboard: None,
+                clipboard: data.as_mut().and_then(|s| s.clipboard.take()),
                 band_groups,
                 band_display_names,
                 bank_groups: bank_groups_map,
@@ -2408,14 +2574,15 @@ pub unsafe extern "C" fn start_download_async(
                 });
             });
 
-            crate::gui::radio_ops::download_from_radio(port_str, vendor_str, model_str, progress_fn)
+            crate::radio_ops::download_from_radio(port_str, vendor_str, model_str, progress_fn)
                 .await
+                .map_err(|e| e.to_string())
         });
 
         // Store result with vendor/model info
         let mut state = DOWNLOAD_STATE.lock().unwrap();
         *state = DownloadState::Complete(
-            result.map(|(memories, mmap)| (memories, mmap, vendor_clone, model_clone))
+            result.map(|(memories, mmap)| (memories, mmap, vendor_clone, model_clone)),
         );
     });
 }
@@ -2478,28 +2645,8 @@ pub extern "C" fn get_download_result() -> *const c_char {
     match result {
         DownloadState::Complete(Ok((memories, mmap, vendor, model))) => {
             // Get bank names from the downloaded mmap using the correct driver
-            let bank_names = {
-                use crate::drivers::CloneModeRadio;
-
-                match (vendor.to_lowercase().as_str(), model.as_str()) {
-                    ("baofeng", "UV-5R") => {
-                        // UV-5R doesn't support banks
-                        vec![]
-                    }
-                    ("kenwood", "TH-D75") | ("kenwood", "TH-D74") => {
-                        use crate::drivers::thd75::THD75Radio;
-                        let mut radio = THD75Radio::new();
-                        radio.process_mmap(&mmap).ok();
-                        radio
-                            .get_bank_names()
-                            .unwrap_or_else(|_| (0..30).map(|i| format!("Bank {}", i)).collect())
-                    }
-                    _ => {
-                        // Default: no banks
-                        vec![]
-                    }
-                }
-            };
+            let mut radio = mmap_radio(&vendor, &model, &mmap);
+            let bank_names = radio_bank_names(radio.as_deref());
 
             // Convert to CStrings (include all memories, even empty ones)
             let mut all_cstrings = Vec::new();
@@ -2516,7 +2663,8 @@ pub extern "C" fn get_download_result() -> *const c_char {
             let (band_groups, band_display_names) = build_band_info(&memories);
 
             // Build bank/group organization
-            let bank_groups_map = build_bank_info(&memories);
+            let bank_groups_map =
+                build_bank_info(&memories, radio.as_mut().and_then(|r| r.bank_model()));
 
             // Update global state
             let mut data = MEMORY_DATA.lock().unwrap();
@@ -2527,7 +2675,7 @@ pub extern "C" fn get_download_result() -> *const c_char {
                 is_modified: false,
                 mmap: Some(mmap), // Store mmap from radio download
                 bank_names,
-                clipboard: None,
+                clipboard: data.as_mut().and_then(|s| s.clipboard.take()),
                 band_groups,
                 band_display_names,
                 bank_groups: bank_groups_map,
@@ -2623,7 +2771,7 @@ pub unsafe extern "C" fn start_upload_async(
                 });
             });
 
-            crate::gui::radio_ops::upload_to_radio(
+            crate::radio_ops::upload_to_radio(
                 port_str,
                 mmap,
                 memories,
@@ -2632,6 +2780,7 @@ pub unsafe extern "C" fn start_upload_async(
                 progress_fn,
             )
             .await
+            .map_err(|e| e.to_string())
         });
 
         // Store result
@@ -2753,6 +2902,18 @@ pub unsafe extern "C" fn update_memory(
     let rpt1call_str = CStr::from_ptr(rpt1call).to_str().unwrap_or("").to_string();
     let rpt2call_str = CStr::from_ptr(rpt2call).to_str().unwrap_or("").to_string();
 
+    let fields = (
+        duplex_str.parse::<Duplex>(),
+        mode_str.parse::<Mode>(),
+        tmode_str.parse::<ToneMode>(),
+    );
+    let (duplex, mode, tmode) = match fields {
+        (Ok(duplex), Ok(mode), Ok(tmode)) => (duplex, mode, tmode),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+            return CString::new(e.to_string()).unwrap().into_raw();
+        }
+    };
+
     // Parse power level if provided
     // Match the power label string against the radio's valid power levels
     let power_level = if !power_str.is_empty() {
@@ -2763,24 +2924,7 @@ pub unsafe extern "C" fn update_memory(
         };
 
         if !vendor.is_empty() && !model.is_empty() {
-            use crate::drivers::Radio;
-
-            let features = match (vendor, model) {
-                ("Baofeng", "UV-5R") => {
-                    use crate::drivers::uv5r::UV5RRadio;
-                    let radio = UV5RRadio::new();
-                    radio.get_features()
-                }
-                ("Kenwood", "TH-D75") | ("Kenwood", "TH-D74") => {
-                    use crate::drivers::thd75::THD75Radio;
-                    let radio = THD75Radio::new();
-                    radio.get_features()
-                }
-                _ => {
-                    use crate::core::features::RadioFeatures;
-                    RadioFeatures::default()
-                }
-            };
+            let features = radio_features(vendor, model);
 
             // Find power level by label
             features
@@ -2799,11 +2943,11 @@ pub unsafe extern "C" fn update_memory(
     let mem = &mut state.memories[row];
     mem.freq = freq;
     mem.name = name_str;
-    mem.duplex = duplex_str;
+    mem.duplex = duplex;
     mem.offset = offset;
-    mem.mode = mode_str;
+    mem.mode = mode;
     mem.tuning_step = tuning_step;
-    mem.tmode = tmode_str;
+    mem.tmode = tmode;
     mem.rtone = rtone;
     mem.ctone = ctone;
     mem.bank = bank;
@@ -2823,7 +2967,12 @@ pub unsafe extern "C" fn update_memory(
     state.cstrings[row] = cstrings;
 
     // Rebuild bank organization since the memory may have moved to a different bank
-    state.bank_groups = build_bank_info(&state.memories);
+    let mut radio = state_radio(state).unwrap_or_else(|err_msg| {
+        tracing::warn!("update_memory: {}", err_msg);
+        None
+    });
+    state.bank_groups =
+        build_bank_info(&state.memories, radio.as_mut().and_then(|r| r.bank_model()));
 
     // Mark as modified
     state.is_modified = true;
@@ -2850,10 +2999,10 @@ pub extern "C" fn delete_memory_at(row: usize) -> *const c_char {
     mem.empty = true;
     mem.freq = 0;
     mem.name = String::new();
-    mem.duplex = String::new();
+    mem.duplex = Duplex::Simplex;
     mem.offset = 0;
-    mem.mode = String::new();
-    mem.tmode = String::new();
+    mem.mode = Mode::default();
+    mem.tmode = ToneMode::None;
     mem.rtone = 0.0;
     mem.ctone = 0.0;
     mem.dv_urcall = String::new();
@@ -2909,8 +3058,28 @@ pub extern "C" fn paste_memory_at(row: usize) -> *const c_char {
     let target_number = state.memories[row].number;
     let target_band = state.memories[row].band;
 
+    // Adapt the memory to the loaded radio (it may come from a different one)
+    let mut new_mem = match (&state.radio_vendor, &state.radio_model) {
+        (Some(vendor), Some(model)) => {
+            let (mem, msgs) = radio_features(vendor, model).coerce_memory(&clipboard_mem);
+            if !msgs.is_empty() {
+                let mut report = format!(
+                    "Memory #{} was changed to fit the {} {}",
+                    target_number, vendor, model
+                );
+                for msg in &msgs {
+                    tracing::warn!("Paste into memory #{}: {}", target_number, msg.message());
+                    report.push('\n');
+                    report.push_str(msg.message());
+                }
+                *PASTE_REPORT.lock().unwrap() = Some(report);
+            }
+            mem
+        }
+        _ => clipboard_mem,
+    };
+
     // Update the memory at the row
-    let mut new_mem = clipboard_mem;
     new_mem.number = target_number; // Keep the target slot's channel number
     new_mem.band = target_band; // Keep the target slot's band assignment
     new_mem.modified = true; // Mark as modified for efficient upload
@@ -2931,6 +3100,19 @@ pub extern "C" fn paste_memory_at(row: usize) -> *const c_char {
     std::ptr::null()
 }
 
+/// FFI: Get the changes made by the last paste
+/// Returns the summary line followed by one line per change, or NULL if the
+/// memory was pasted unchanged. Caller must free with free_error_message()
+#[no_mangle]
+pub extern "C" fn get_paste_report() -> *const c_char {
+    let Some(text) = PASTE_REPORT.lock().unwrap().take() else {
+        return std::ptr::null();
+    };
+    CString::new(text)
+        .map(|s| s.into_raw() as *const c_char)
+        .unwrap_or(std::ptr::null())
+}
+
 /// Check if there's a memory in the clipboard
 #[no_mangle]
 pub extern "C" fn has_clipboard_memory() -> i32 {
@@ -2950,17 +3132,17 @@ fn create_test_memories() -> Vec<Memory> {
             let mut mem = Memory::new(1);
             mem.freq = 146_520_000;
             mem.name = "Simplex".to_string();
-            mem.mode = "FM".to_string();
+            mem.mode = Mode::Fm;
             mem
         },
         {
             let mut mem = Memory::new(2);
             mem.freq = 146_940_000;
             mem.name = "W6CX Rpt".to_string();
-            mem.mode = "FM".to_string();
-            mem.duplex = "-".to_string();
+            mem.mode = Mode::Fm;
+            mem.duplex = Duplex::Minus;
             mem.offset = 600_000;
-            mem.tmode = "Tone".to_string();
+            mem.tmode = ToneMode::Tone;
             mem.rtone = 100.0;
             mem
         },
@@ -2968,10 +3150,10 @@ fn create_test_memories() -> Vec<Memory> {
             let mut mem = Memory::new(3);
             mem.freq = 147_330_000;
             mem.name = "N6NFI Rpt".to_string();
-            mem.mode = "FM".to_string();
-            mem.duplex = "+".to_string();
+            mem.mode = Mode::Fm;
+            mem.duplex = Duplex::Plus;
             mem.offset = 600_000;
-            mem.tmode = "TSQL".to_string();
+            mem.tmode = ToneMode::Tsql;
             mem.ctone = 88.5;
             mem
         },
@@ -2979,7 +3161,7 @@ fn create_test_memories() -> Vec<Memory> {
             let mut mem = Memory::new(4);
             mem.freq = 145_230_000;
             mem.name = "DV Memory".to_string();
-            mem.mode = "DV".to_string();
+            mem.mode = Mode::Dv;
             mem.dv_urcall = "CQCQCQ".to_string();
             mem.dv_rpt1call = "W3POG  B".to_string();
             mem.dv_rpt2call = "W3POG  G".to_string();
@@ -3085,6 +3267,44 @@ pub fn run_qt_app() -> i32 {
                     }
                 });
 
+            // Double-click a bank/group to rename it
+            QObject::connect(tree, &QTreeWidget::itemDoubleClicked,
+                [=](QTreeWidgetItem* item, int) {
+                    bool is_bank_filter = item->data(0, Qt::UserRole + 1).toBool();
+                    uint8_t bank_num = item->data(0, Qt::UserRole).toUInt();
+                    if (!is_bank_filter || bank_num == 255) {
+                        return;
+                    }
+
+                    const char* name_cstr = get_bank_name_by_number(bank_num);
+                    QString current_name = QString::fromUtf8(name_cstr);
+                    free_error_message(name_cstr);
+
+                    bool ok = false;
+                    QString new_name = QInputDialog::getText(window, "Rename Bank",
+                        "Bank name:", QLineEdit::Normal, current_name, &ok);
+                    if (!ok) {
+                        return;
+                    }
+
+                    const char* error = rename_bank(bank_num, new_name.toUtf8().constData());
+                    if (error) {
+                        QMessageBox::warning(window, "Failed to Rename Bank", QString::fromUtf8(error));
+                        free_error_message(error);
+                        return;
+                    }
+
+                    // Rebuild the tree, then reselect the renamed bank
+                    refreshTreeWithBanks(tree);
+                    for (int i = 0; i < tree->topLevelItemCount(); ++i) {
+                        QTreeWidgetItem* bank_item = tree->topLevelItem(i);
+                        if (bank_item->data(0, Qt::UserRole).toUInt() == bank_num) {
+                            tree->setCurrentItem(bank_item);
+                            break;
+                        }
+                    }
+                });
+
             // Unified refresh function that checks for band/bank organization
             auto refreshMemoryView = [=]() {
                 if (has_band_organization()) {
@@ -3118,7 +3338,8 @@ pub fn run_qt_app() -> i32 {
 
             fileMenu->addAction("&Open...", [=]() {
                 QString fileName = QFileDialog::getOpenFileName(window,
-                    "Open CHIRP Image", "", "CHIRP Image (*.img)");
+                    "Open CHIRP Image", "",
+                    "CHIRP Image (*.img);;Kenwood MCP (*.d74 *.d75);;Raw Radio Dump (*.img *.bin *.dat);;All Files (*)");
                 if (!fileName.isEmpty()) {
                     const char* error = load_file(fileName.toUtf8().constData());
                     if (error) {
@@ -3126,7 +3347,7 @@ pub fn run_qt_app() -> i32 {
                         QMessageBox::critical(window, "Failed to Open File",
                             QString("Could not open file:\n%1\n\nError: %2\n\n"
                                    "Please ensure:\n"
-                                   "• File is a valid CHIRP image (.img)\n"
+                                   "• File is a valid CHIRP image (.img) or raw dump of a supported radio\n"
                                    "• File is not corrupted\n"
                                    "• You have permission to read the file")
                             .arg(fileName).arg(errorMsg));
@@ -3144,7 +3365,7 @@ pub fn run_qt_app() -> i32 {
                 if (!filepath) {
                     // No current file, show Save As dialog
                     QString fileName = QFileDialog::getSaveFileName(window,
-                        "Save CHIRP Image", "", "CHIRP Image (*.img)");
+                        "Save CHIRP Image", "", "CHIRP Image (*.img);;Kenwood MCP (*.d74 *.d75)");
                     if (fileName.isEmpty()) {
                         return;
                     }
@@ -3181,7 +3402,7 @@ pub fn run_qt_app() -> i32 {
 
             fileMenu->addAction("Save &As...", [=]() {
                 QString fileName = QFileDialog::getSaveFileName(window,
-                    "Save CHIRP Image", "", "CHIRP Image (*.img)");
+                    "Save CHIRP Image", "", "CHIRP Image (*.img);;Kenwood MCP (*.d74 *.d75)");
                 if (!fileName.isEmpty()) {
                     const char* error = save_file(fileName.toUtf8().constData());
                     if (error) {
@@ -3220,9 +3441,25 @@ pub fn run_qt_app() -> i32 {
                     } else {
                         refreshMemoryView();
                         window->setWindowTitle("CHIRP-RS - Imported from CSV");
-                        QMessageBox::information(window, "Import Successful",
-                            QString("Successfully imported %1 memories from CSV")
-                                .arg(get_memory_count()));
+
+                        // Show skipped rows and defaulted fields instead of dropping them silently
+                        int clean = 1;
+                        const char* report = get_import_report(&clean);
+                        QString reportText = report ? QString::fromUtf8(report) : QString();
+                        if (report) {
+                            free_error_message(report);
+                        }
+                        QString summary = reportText.section('\n', 0, 0);
+                        if (clean) {
+                            QMessageBox::information(window, "Import Successful", summary);
+                        } else {
+                            QMessageBox box(QMessageBox::Warning, "Import Report",
+                                summary + "\n\nSome rows were skipped or had fields replaced "
+                                "by defaults. See the details for each line.",
+                                QMessageBox::Ok, window);
+                            box.setDetailedText(reportText.section('\n', 1));
+                            box.exec();
+                        }
                     }
                 }
             });
@@ -3310,6 +3547,19 @@ pub fn run_qt_app() -> i32 {
                             free_error_message(error);
                         } else {
                             refreshCurrentBandTable(table, tree);
+
+                            // Show what had to change for this radio, like the CSV import report
+                            const char* report = get_paste_report();
+                            if (report) {
+                                QString reportText = QString::fromUtf8(report);
+                                free_error_message(report);
+                                QMessageBox box(QMessageBox::Warning, "Paste Report",
+                                    reportText.section('\n', 0, 0) + ". See the details for "
+                                    "each change.",
+                                    QMessageBox::Ok, window);
+                                box.setDetailedText(reportText.section('\n', 1));
+                                box.exec();
+                            }
                         }
                     } else if (selectedAction == clearAction) {
                         const char* error = delete_memory_at(globalRow);
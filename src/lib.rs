@@ -4,10 +4,13 @@
 #![recursion_limit = "8192"]
 
 pub mod bitwise;
+pub mod channel_copy;
 pub mod core;
 pub mod drivers;
+pub mod emulator;
 pub mod formats;
 pub mod memmap;
+pub mod radio_ops;
 pub mod serial;
 
 #[cfg(feature = "gui")]
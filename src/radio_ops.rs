@@ -0,0 +1,385 @@
+// Radio operations - download/upload through the driver registry
+// UI-independent: shared by the Qt GUI and chirp-cli. Port settings come from
+// each driver's ConnectionProfile.
+
+use crate::core::Memory;
+use crate::drivers::traits::StatusCallback;
+use crate::drivers::{get_driver, CommandModeRadio, DynCloneModeRadio, RadioDriver, RadioError};
+use crate::memmap::MemoryMap;
+use crate::serial::{SerialError, Transport};
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum RadioOpError {
+    #[error("Unknown radio: {0}")]
+    UnknownRadio(String),
+
+    #[error("No driver implementation for {0}")]
+    NoImplementation(String),
+
+    #[error("Failed to open port {port}: {source}")]
+    Open {
+        port: String,
+        #[source]
+        source: SerialError,
+    },
+
+    #[error("Serial port error: {0}")]
+    Port(#[from] SerialError),
+
+    #[error("Download failed: {0}")]
+    Download(#[source] RadioError),
+
+    #[error("Upload failed: {0}")]
+    Upload(#[source] RadioError),
+
+    #[error("Failed to process memory map: {0}")]
+    MemoryMap(#[source] RadioError),
+
+    #[error("Failed to parse memories: {0}")]
+    Memories(#[source] RadioError),
+
+    #[error("Failed to update memory #{number}: {source}")]
+    UpdateMemory {
+        number: u32,
+        #[source]
+        source: RadioError,
+    },
+}
+
+/// Result type for radio operations
+pub type RadioOpResult<T> = Result<T, RadioOpError>;
+
+/// Progress callback type for UI updates
+pub type ProgressFn = Arc<dyn Fn(usize, usize, String) + Send + Sync>;
+
+/// Create a driver from the registry
+fn create_driver(vendor: &str, model: &str) -> RadioOpResult<RadioDriver> {
+    let name = format!("{} {}", vendor, model);
+    let driver_info = get_driver(vendor, model).ok_or(RadioOpError::UnknownRadio(name.clone()))?;
+
+    tracing::debug!(
+        "Found driver: {} (clone_mode: {})",
+        driver_info.full_name(),
+        driver_info.is_clone_mode
+    );
+
+    driver_info
+        .create()
+        .ok_or(RadioOpError::NoImplementation(name))
+}
+
+/// Open a port with the driver's connection profile
+fn open_port(port_name: &str, driver: &RadioDriver) -> RadioOpResult<Box<dyn Transport>> {
+    let profile = driver.radio().connection_profile();
+    tracing::debug!("Opening {} with {:?}", port_name, profile);
+
+    let port = profile
+        .open(port_name)
+        .map_err(|source| RadioOpError::Open {
+            port: port_name.to_string(),
+            source,
+        })?;
+
+    tracing::debug!("Opened serial port {}", port_name);
+    Ok(port)
+}
+
+fn status_callback(progress_fn: ProgressFn) -> Option<StatusCallback> {
+    Some(Box::new(
+        move |current: usize, total: usize, message: &str| {
+            progress_fn(current, total, message.to_string());
+        },
+    ))
+}
+
+/// Download memories from a radio
+/// Returns (memories, mmap) so the mmap can be stored and used for uploads
+pub async fn download_from_radio(
+    port_name: String,
+    vendor: String,
+    model: String,
+    progress_fn: ProgressFn,
+) -> RadioOpResult<(Vec<Memory>, MemoryMap)> {
+    tracing::debug!("download_from_radio called");
+    tracing::debug!("  port: {}", port_name);
+    tracing::debug!("  vendor: {}", vendor);
+    tracing::debug!("  model: {}", model);
+
+    let driver = create_driver(&vendor, &model)?;
+    let mut port = open_port(&port_name, &driver)?;
+    download(&mut port, driver, progress_fn).await
+}
+
+/// Download memories over an already open transport
+pub async fn download(
+    port: &mut dyn Transport,
+    driver: RadioDriver,
+    progress_fn: ProgressFn,
+) -> RadioOpResult<(Vec<Memory>, MemoryMap)> {
+    let (memories, mmap) = match driver {
+        // Clone mode radios (e.g., TH-D75)
+        RadioDriver::CloneMode(driver) => download_clone_mode(port, driver, progress_fn).await?,
+        // Command-based radios (e.g., IC-9700, IC-705)
+        RadioDriver::CommandMode(driver) => {
+            download_command_mode(port, driver, progress_fn).await?
+        }
+    };
+
+    tracing::debug!("Downloaded {} memories", memories.len());
+
+    Ok((memories, mmap))
+}
+
+/// Download from a clone-mode radio (TH-D75, TH-D74, UV-5R)
+async fn download_clone_mode(
+    port: &mut dyn Transport,
+    mut driver: Box<dyn DynCloneModeRadio>,
+    progress_fn: ProgressFn,
+) -> RadioOpResult<(Vec<Memory>, MemoryMap)> {
+    tracing::debug!("Using {} driver instance", driver.get_name());
+
+    let mmap = driver
+        .download(port, status_callback(progress_fn))
+        .await
+        .map_err(RadioOpError::Download)?;
+    driver.load_mmap(&mmap).map_err(RadioOpError::MemoryMap)?;
+    let memories = driver.get_memories().map_err(RadioOpError::Memories)?;
+    Ok((memories, mmap))
+}
+
+/// Download from a command-based radio (IC-9700)
+async fn download_command_mode(
+    port: &mut dyn Transport,
+    mut driver: Box<dyn CommandModeRadio>,
+    progress_fn: ProgressFn,
+) -> RadioOpResult<(Vec<Memory>, MemoryMap)> {
+    tracing::debug!("Using {} driver instance", driver.get_name());
+
+    let memories = driver
+        .download(port, status_callback(progress_fn))
+        .await
+        .map_err(RadioOpError::Download)?;
+
+    // Command-mode radios don't use clone mode, so create empty mmap
+    // Upload will use command-based protocol
+    let mmap = MemoryMap::new(vec![]);
+
+    Ok((memories, mmap))
+}
+
+/// Upload memories to a radio
+/// Requires the mmap from the original download to preserve all radio settings
+pub async fn upload_to_radio(
+    port_name: String,
+    mmap: MemoryMap,
+    memories: Vec<Memory>,
+    vendor: String,
+    model: String,
+    progress_fn: ProgressFn,
+) -> RadioOpResult<()> {
+    tracing::debug!("upload_to_radio called");
+    tracing::debug!("  port: {}", port_name);
+    tracing::debug!("  vendor: {}", vendor);
+    tracing::debug!("  model: {}", model);
+    tracing::debug!("  memories: {}", memories.len());
+
+    let driver = create_driver(&vendor, &model)?;
+    let mut port = open_port(&port_name, &driver)?;
+    upload(&mut port, driver, mmap, memories, progress_fn).await
+}
+
+/// Upload memories over an already open transport
+pub async fn upload(
+    port: &mut dyn Transport,
+    driver: RadioDriver,
+    mmap: MemoryMap,
+    memories: Vec<Memory>,
+    progress_fn: ProgressFn,
+) -> RadioOpResult<()> {
+    match driver {
+        RadioDriver::CloneMode(driver) => {
+            upload_clone_mode(port, driver, mmap, memories, progress_fn).await?
+        }
+        RadioDriver::CommandMode(driver) => {
+            upload_command_mode(port, driver, memories, progress_fn).await?
+        }
+    };
+
+    tracing::debug!("Upload complete");
+
+    Ok(())
+}
+
+/// Upload to a clone-mode radio (TH-D75, TH-D74, UV-5R)
+/// Uses the mmap from the original download and updates it with the edited memories
+async fn upload_clone_mode(
+    port: &mut dyn Transport,
+    mut driver: Box<dyn DynCloneModeRadio>,
+    mmap: MemoryMap,
+    memories: Vec<Memory>,
+    progress_fn: ProgressFn,
+) -> RadioOpResult<()> {
+    // Clear buffers before upload
+    port.clear_all()?;
+
+    tracing::debug!("Using {} driver instance for upload", driver.get_name());
+    driver.load_mmap(&mmap).map_err(RadioOpError::MemoryMap)?;
+
+    tracing::info!("Updating memories in mmap...");
+
+    // Update only non-empty memory channels that exist on this radio
+    let (_, last) = driver.get_features().memory_bounds;
+    for mem in &memories {
+        if mem.number > last {
+            continue;
+        }
+
+        if !mem.empty {
+            driver
+                .set_memory(mem)
+                .map_err(|source| RadioOpError::UpdateMemory {
+                    number: mem.number,
+                    source,
+                })?;
+        }
+    }
+
+    // Get the modified memory map
+    let modified_mmap = driver.mmap().cloned().ok_or_else(|| {
+        RadioOpError::MemoryMap(RadioError::Radio(
+            "Memory map not available after update".to_string(),
+        ))
+    })?;
+
+    tracing::info!("Uploading to radio...");
+
+    // Upload the modified memory map to radio
+    driver
+        .upload(port, &modified_mmap, status_callback(progress_fn))
+        .await
+        .map_err(RadioOpError::Upload)?;
+
+    Ok(())
+}
+
+/// Upload to a command-based radio (IC-9700)
+async fn upload_command_mode(
+    port: &mut dyn Transport,
+    mut driver: Box<dyn CommandModeRadio>,
+    memories: Vec<Memory>,
+    progress_fn: ProgressFn,
+) -> RadioOpResult<()> {
+    // Filter to only modified memories for efficient upload
+    let modified_memories: Vec<Memory> = memories.into_iter().filter(|m| m.modified).collect();
+
+    tracing::info!("Upload: {} modified memories", modified_memories.len());
+
+    if modified_memories.is_empty() {
+        tracing::info!("No modified memories to upload");
+        return Ok(());
+    }
+
+    driver
+        .upload(port, &modified_memories, status_callback(progress_fn))
+        .await
+        .map_err(RadioOpError::Upload)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Mode;
+    use crate::drivers::icomciv::{IcomCivRadio, IC9700};
+    use crate::drivers::{init_drivers, thd75::THD75Radio};
+    use crate::emulator::{CivBusSimulator, EmulatedPort, THD75Emulator};
+
+    fn no_progress() -> ProgressFn {
+        Arc::new(|_, _, _| {})
+    }
+
+    #[test]
+    fn test_driver_connection_profiles() {
+        init_drivers();
+        let profile = |vendor, model| {
+            create_driver(vendor, model)
+                .unwrap()
+                .radio()
+                .connection_profile()
+        };
+
+        let kenwood = profile("Kenwood", "TH-D75");
+        assert_eq!(kenwood.baud_rate, 9600);
+        assert!(kenwood.hardware_flow);
+        assert!(kenwood.dtr && !kenwood.rts);
+
+        let icom = profile("Icom", "IC-9700");
+        assert_eq!(icom.baud_rate, 19200);
+        assert!(!icom.hardware_flow);
+        assert!(icom.dtr && !icom.rts);
+
+        let baofeng = profile("Baofeng", "UV-5R");
+        assert!(!baofeng.hardware_flow && !baofeng.dtr && !baofeng.rts);
+
+        assert!(matches!(
+            create_driver("Nobody", "X-1"),
+            Err(RadioOpError::UnknownRadio(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_open_error() {
+        init_drivers();
+        let result = download_from_radio(
+            "/dev/chirp-rs-no-such-port".to_string(),
+            "Kenwood".to_string(),
+            "TH-D75".to_string(),
+            no_progress(),
+        )
+        .await;
+        assert!(matches!(result, Err(RadioOpError::Open { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_download_clone_mode() {
+        let path =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("test_data/radio_dump.bin");
+        let image = MemoryMap::new(std::fs::read(path).unwrap());
+        let mut port = EmulatedPort::new(THD75Emulator::new(image.clone()));
+
+        let driver = RadioDriver::CloneMode(Box::new(THD75Radio::new()));
+        let (memories, mmap) = download(&mut port, driver, no_progress()).await.unwrap();
+        assert_eq!(mmap, image);
+        assert!(memories.iter().any(|m| !m.empty));
+    }
+
+    #[tokio::test]
+    async fn test_upload_command_mode_only_modified() {
+        let mut port = EmulatedPort::new(CivBusSimulator::new(0xA2)).with_baud_rate(19200);
+
+        let mut memories = Vec::new();
+        for number in 1..=2 {
+            let mut mem = Memory::new(number);
+            mem.freq = 146_520_000;
+            mem.mode = Mode::Fm;
+            mem.band = Some(1);
+            mem.modified = number == 2;
+            memories.push(mem);
+        }
+
+        let driver = RadioDriver::CommandMode(Box::new(IcomCivRadio::new(&IC9700)));
+        upload(
+            &mut port,
+            driver,
+            MemoryMap::new(vec![]),
+            memories,
+            no_progress(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(port.radio().writes(), &[(1, 2)]);
+    }
+}
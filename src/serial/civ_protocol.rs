@@ -2,7 +2,7 @@
 // Reference: chirp/drivers/icomciv.py
 
 use crate::drivers::{RadioError, RadioResult};
-use crate::serial::SerialPort;
+use crate::serial::Transport;
 
 /// CI-V frame structure: 0xFE 0xFE <dst> <src> <cmd> [sub] [data...] 0xFD
 pub struct CivFrame {
@@ -37,9 +37,9 @@ impl CivFrame {
     }
 
     /// Send the frame over the serial port
-    pub async fn send(
+    pub async fn send<T: Transport + ?Sized>(
         &self,
-        port: &mut SerialPort,
+        port: &mut T,
         src: u8,
         dst: u8,
         expect_echo: bool,
@@ -77,7 +77,7 @@ impl CivFrame {
     }
 
     /// Receive a frame from the serial port
-    pub async fn receive(port: &mut SerialPort) -> RadioResult<Self> {
+    pub async fn receive<T: Transport + ?Sized>(port: &mut T) -> RadioResult<Self> {
         let mut data = Vec::new();
 
         // Read until we get 0xFD end marker
@@ -148,6 +148,11 @@ impl CivFrame {
         })
     }
 
+    /// Check if the frame is an NG reply (0xFA), either as the command or the first data byte
+    pub fn is_ng(&self) -> bool {
+        self.cmd == 0xFA || self.data.first() == Some(&0xFA)
+    }
+
     /// Check if the frame indicates an empty memory
     pub fn is_empty_memory(&self) -> bool {
         // Empty memory is indicated by 0xFF at the end of data
@@ -177,7 +182,7 @@ impl CivProtocol {
     }
 
     /// Detect if the serial interface echoes frames
-    pub async fn detect_echo(&mut self, port: &mut SerialPort) -> RadioResult<bool> {
+    pub async fn detect_echo<T: Transport + ?Sized>(&mut self, port: &mut T) -> RadioResult<bool> {
         // Send a simple test frame
         let test_frame = vec![0xFE, 0xFE, 0xE0, 0xE0, 0xFA, 0xFD];
         port.write_all(&test_frame).await?;
@@ -202,9 +207,9 @@ impl CivProtocol {
     }
 
     /// Send a CI-V frame and receive response
-    pub async fn send_command(
+    pub async fn send_command<T: Transport + ?Sized>(
         &self,
-        port: &mut SerialPort,
+        port: &mut T,
         cmd: u8,
         sub: Option<u8>,
         data: &[u8],
@@ -235,10 +240,25 @@ impl CivProtocol {
         Ok(response)
     }
 
+    /// Ask the radio at this model's address for its transceiver ID (0x19 0x00)
+    /// Returns the CI-V address the radio reports, normally its model code
+    pub async fn read_transceiver_id<T: Transport + ?Sized>(
+        &self,
+        port: &mut T,
+    ) -> RadioResult<u8> {
+        let response = self.send_command(port, 0x19, Some(0x00), &[]).await?;
+        match response.data() {
+            [id, ..] => Ok(*id),
+            [] => Err(RadioError::InvalidResponse(
+                "Empty transceiver ID response".to_string(),
+            )),
+        }
+    }
+
     /// Read a memory from the radio
-    pub async fn read_memory(
+    pub async fn read_memory<T: Transport + ?Sized>(
         &self,
-        port: &mut SerialPort,
+        port: &mut T,
         bank: u8,
         channel: u16,
     ) -> RadioResult<Vec<u8>> {
@@ -286,9 +306,9 @@ impl CivProtocol {
     }
 
     /// Write a memory to the radio
-    pub async fn write_memory(
+    pub async fn write_memory<T: Transport + ?Sized>(
         &self,
-        port: &mut SerialPort,
+        port: &mut T,
         bank: u8,
         channel: u16,
         memory_data: &[u8],
@@ -328,7 +348,7 @@ impl CivProtocol {
 
         // Check for error (NAK = 0xFA) or success (ACK = 0xFB or empty)
         // For write operations, the radio typically returns an ACK with just 0xFB
-        if !response.data().is_empty() && response.data()[0] == 0xFA {
+        if response.is_ng() {
             tracing::error!(
                 "Radio rejected write_memory (NAK): bank={}, ch={}, data_len={}",
                 bank,
@@ -342,9 +362,9 @@ impl CivProtocol {
     }
 
     /// Erase a memory (mark as empty)
-    pub async fn erase_memory(
+    pub async fn erase_memory<T: Transport + ?Sized>(
         &self,
-        port: &mut SerialPort,
+        port: &mut T,
         bank: u8,
         channel: u16,
     ) -> RadioResult<()> {
@@ -374,7 +394,7 @@ impl CivProtocol {
 
         // Check for error (NAK = 0xFA) or success (ACK = 0xFB or empty)
         // Same logic as write_memory
-        if !response.data().is_empty() && response.data()[0] == 0xFA {
+        if response.is_ng() {
             tracing::error!(
                 "Radio rejected erase_memory (NAK): bank={}, ch={}",
                 bank,
@@ -406,6 +426,28 @@ mod tests {
         assert_eq!(frame.data(), &[0x01, 0x02, 0x03]);
     }
 
+    #[tokio::test]
+    async fn test_read_transceiver_id_over_mock() {
+        use crate::serial::mock::MockSerialPort;
+
+        let mut port = MockSerialPort::new();
+        port.push_read_data(&[0xFE, 0xFE, 0xE0, 0xA2, 0x19, 0x00, 0xA2, 0xFD]);
+
+        let civ = CivProtocol::new(0xA2, 0xE0);
+        assert_eq!(civ.read_transceiver_id(&mut port).await.unwrap(), 0xA2);
+        assert_eq!(
+            port.get_written_data(),
+            vec![0xFE, 0xFE, 0xA2, 0xE0, 0x19, 0x00, 0xFD]
+        );
+    }
+
+    #[test]
+    fn test_is_ng() {
+        // NG reply: FE FE E0 A2 FA FD
+        assert!(CivFrame::new(0xFA, None).is_ng());
+        assert!(!CivFrame::new(0xFB, None).is_ng());
+    }
+
     #[test]
     fn test_is_empty_memory() {
         let mut frame = CivFrame::new(0x1A, Some(0x00));
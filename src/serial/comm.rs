@@ -1,6 +1,8 @@
 // Serial port abstraction with async support
 // Wraps the serialport crate with tokio async functionality
 
+use super::transport::Transport;
+use crate::drivers::traits::BoxFuture;
 use std::io;
 use std::time::Duration;
 use thiserror::Error;
@@ -102,7 +104,7 @@ impl SerialPort {
             .open()
             .map_err(|e| SerialError::Port(e.to_string()))?;
 
-        // Note: DTR/RTS are set per-radio-type in radio_ops, not here
+        // Note: DTR/RTS come from each driver's ConnectionProfile, not here
         // Setting RTS=true by default causes issues with Icom CI-V radios
         // which interpret RTS high as "transmitting"
 
@@ -257,6 +259,60 @@ impl SerialPort {
     }
 }
 
+impl Transport for SerialPort {
+    fn name(&self) -> &str {
+        &self.port_name
+    }
+
+    fn timeout(&self) -> Duration {
+        self.config.timeout
+    }
+
+    fn read<'a>(&'a mut self, buf: &'a mut [u8]) -> BoxFuture<'a, Result<usize>> {
+        Box::pin(SerialPort::read(self, buf))
+    }
+
+    fn write<'a>(&'a mut self, buf: &'a [u8]) -> BoxFuture<'a, Result<usize>> {
+        Box::pin(SerialPort::write(self, buf))
+    }
+
+    fn flush(&mut self) -> BoxFuture<'_, Result<()>> {
+        Box::pin(SerialPort::flush(self))
+    }
+
+    fn clear_input(&mut self) -> Result<()> {
+        SerialPort::clear_input(self)
+    }
+
+    fn clear_output(&mut self) -> Result<()> {
+        SerialPort::clear_output(self)
+    }
+
+    fn set_dtr(&mut self, level: bool) -> Result<()> {
+        SerialPort::set_dtr(self, level)
+    }
+
+    fn set_rts(&mut self, level: bool) -> Result<()> {
+        SerialPort::set_rts(self, level)
+    }
+
+    fn set_baud_rate(&mut self, baud_rate: u32) -> Result<()> {
+        SerialPort::set_baud_rate(self, baud_rate)
+    }
+
+    fn read_exact<'a>(&'a mut self, buf: &'a mut [u8]) -> BoxFuture<'a, Result<()>> {
+        Box::pin(SerialPort::read_exact(self, buf))
+    }
+
+    fn write_all<'a>(&'a mut self, buf: &'a [u8]) -> BoxFuture<'a, Result<()>> {
+        Box::pin(SerialPort::write_all(self, buf))
+    }
+
+    fn clear_all(&mut self) -> Result<()> {
+        SerialPort::clear_all(self)
+    }
+}
+
 /// List available serial ports
 pub fn list_ports() -> Result<Vec<String>> {
     serialport::available_ports()
@@ -1,6 +1,8 @@
 // Mock serial port for testing without hardware
 
 use super::comm::{SerialConfig, SerialError};
+use super::transport::Transport;
+use crate::drivers::traits::BoxFuture;
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 
@@ -18,6 +20,12 @@ pub struct MockSerialPort {
 
     /// Simulated delay for read/write operations (in ms)
     delay_ms: u64,
+
+    /// Current DTR line state
+    dtr: bool,
+
+    /// Current RTS line state
+    rts: bool,
 }
 
 impl MockSerialPort {
@@ -28,6 +36,8 @@ impl MockSerialPort {
             write_buffer: Arc::new(Mutex::new(Vec::new())),
             config: SerialConfig::default(),
             delay_ms: 0,
+            dtr: false,
+            rts: false,
         }
     }
 
@@ -76,6 +86,21 @@ impl MockSerialPort {
         self.read_buffer.lock().unwrap().len()
     }
 
+    /// Current DTR line state
+    pub fn dtr(&self) -> bool {
+        self.dtr
+    }
+
+    /// Current RTS line state
+    pub fn rts(&self) -> bool {
+        self.rts
+    }
+
+    /// Current baud rate
+    pub fn baud_rate(&self) -> u32 {
+        self.config.baud_rate
+    }
+
     /// Simulate reading bytes
     pub async fn read(&mut self, buf: &mut [u8]) -> Result<usize, SerialError> {
         if self.delay_ms > 0 {
@@ -143,6 +168,61 @@ impl MockSerialPort {
     }
 }
 
+impl Transport for MockSerialPort {
+    fn name(&self) -> &str {
+        "mock"
+    }
+
+    fn timeout(&self) -> std::time::Duration {
+        self.config.timeout
+    }
+
+    fn read<'a>(&'a mut self, buf: &'a mut [u8]) -> BoxFuture<'a, Result<usize, SerialError>> {
+        Box::pin(MockSerialPort::read(self, buf))
+    }
+
+    fn write<'a>(&'a mut self, buf: &'a [u8]) -> BoxFuture<'a, Result<usize, SerialError>> {
+        Box::pin(MockSerialPort::write(self, buf))
+    }
+
+    fn flush(&mut self) -> BoxFuture<'_, Result<(), SerialError>> {
+        Box::pin(MockSerialPort::flush(self))
+    }
+
+    fn clear_input(&mut self) -> Result<(), SerialError> {
+        self.read_buffer.lock().unwrap().clear();
+        Ok(())
+    }
+
+    fn clear_output(&mut self) -> Result<(), SerialError> {
+        // Writes are delivered immediately, nothing is ever pending
+        Ok(())
+    }
+
+    fn set_dtr(&mut self, level: bool) -> Result<(), SerialError> {
+        self.dtr = level;
+        Ok(())
+    }
+
+    fn set_rts(&mut self, level: bool) -> Result<(), SerialError> {
+        self.rts = level;
+        Ok(())
+    }
+
+    fn set_baud_rate(&mut self, baud_rate: u32) -> Result<(), SerialError> {
+        self.config.baud_rate = baud_rate;
+        Ok(())
+    }
+
+    fn read_exact<'a>(&'a mut self, buf: &'a mut [u8]) -> BoxFuture<'a, Result<(), SerialError>> {
+        Box::pin(MockSerialPort::read_exact(self, buf))
+    }
+
+    fn write_all<'a>(&'a mut self, buf: &'a [u8]) -> BoxFuture<'a, Result<(), SerialError>> {
+        Box::pin(MockSerialPort::write_all(self, buf))
+    }
+}
+
 impl Default for MockSerialPort {
     fn default() -> Self {
         Self::new()
@@ -158,7 +238,7 @@ pub fn mock_port_with_response(response: &[u8]) -> MockSerialPort {
 
 /// Helper to create a mock port that simulates a radio download
 /// Responds to each block request with the corresponding block of data
-pub fn mock_clone_mode_radio(memory_data: Vec<u8>, block_size: usize) -> MockSerialPort {
+pub fn mock_clone_mode_radio(memory_data: Vec<u8>, _block_size: usize) -> MockSerialPort {
     let mut port = MockSerialPort::new();
 
     // Pre-load all blocks
@@ -225,6 +305,26 @@ mod tests {
         assert!(!port.was_written(b"NOTFOUND"));
     }
 
+    #[tokio::test]
+    async fn test_mock_as_transport() {
+        let mut mock = MockSerialPort::new();
+        mock.push_read_data(b"stale");
+
+        let port: &mut dyn Transport = &mut mock;
+        port.set_dtr(true).unwrap();
+        port.set_baud_rate(57600).unwrap();
+        port.clear_input().unwrap();
+        port.write_all(b"ID\r").await.unwrap();
+
+        let mut buf = [0u8; 1];
+        assert!(port.read_exact(&mut buf).await.is_err());
+
+        assert!(mock.dtr());
+        assert!(!mock.rts());
+        assert_eq!(mock.baud_rate(), 57600);
+        assert_eq!(mock.get_written_data(), b"ID\r");
+    }
+
     #[tokio::test]
     async fn test_mock_with_delay() {
         let mut port = MockSerialPort::new().with_delay(10);
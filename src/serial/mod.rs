@@ -1,11 +1,17 @@
 // Serial communication module for radio I/O
 pub mod civ_protocol;
 pub mod comm;
+pub mod profile;
 pub mod protocol;
+pub mod tcp;
+pub mod transport;
 
 #[cfg(test)]
 pub mod mock;
 
 pub use civ_protocol::{CivFrame, CivProtocol};
 pub use comm::{SerialConfig, SerialError, SerialPort};
+pub use profile::ConnectionProfile;
 pub use protocol::{BlockProtocol, ProgressCallback};
+pub use tcp::{TcpMode, TcpTransport};
+pub use transport::{open_transport, Transport};
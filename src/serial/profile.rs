@@ -0,0 +1,115 @@
+// Per-radio link settings
+// Each driver declares how its programming cable expects the port to be set
+// up, so callers open ports without knowing anything about the vendor.
+
+use super::comm::{Result, SerialConfig};
+use super::transport::{open_transport, Transport};
+use std::time::Duration;
+
+/// Serial settings a radio needs for programming
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectionProfile {
+    /// Baud rate to open the port at
+    pub baud_rate: u32,
+    /// RTS/CTS hardware flow control
+    pub hardware_flow: bool,
+    /// DTR level once the port is open
+    pub dtr: bool,
+    /// RTS level once the port is open (RTS high means transmit on Icom CI-V)
+    pub rts: bool,
+    /// Read/write timeout
+    pub timeout: Duration,
+}
+
+impl Default for ConnectionProfile {
+    fn default() -> Self {
+        Self::new(9600)
+    }
+}
+
+impl ConnectionProfile {
+    /// Profile with no flow control, DTR and RTS low and a 10 second timeout
+    pub const fn new(baud_rate: u32) -> Self {
+        Self {
+            baud_rate,
+            hardware_flow: false,
+            dtr: false,
+            rts: false,
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Use RTS/CTS hardware flow control
+    pub const fn with_hardware_flow(mut self) -> Self {
+        self.hardware_flow = true;
+        self
+    }
+
+    /// Set the DTR and RTS levels applied after opening
+    pub const fn with_lines(mut self, dtr: bool, rts: bool) -> Self {
+        self.dtr = dtr;
+        self.rts = rts;
+        self
+    }
+
+    /// Set the read/write timeout
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Port configuration for this profile
+    pub fn serial_config(&self) -> SerialConfig {
+        let config = SerialConfig::new(self.baud_rate).with_timeout(self.timeout);
+        if self.hardware_flow {
+            config.with_hardware_flow()
+        } else {
+            config
+        }
+    }
+
+    /// Set the line states and discard anything already buffered
+    pub fn apply<T: Transport + ?Sized>(&self, port: &mut T) -> Result<()> {
+        port.set_dtr(self.dtr)?;
+        port.set_rts(self.rts)?;
+        port.clear_all()
+    }
+
+    /// Open a transport by name (see `open_transport`) and apply this profile
+    pub fn open(&self, name: &str) -> Result<Box<dyn Transport>> {
+        let mut port = open_transport(name, self.serial_config())?;
+        self.apply(&mut port)?;
+        Ok(port)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serial::mock::MockSerialPort;
+
+    #[test]
+    fn test_profile_config_and_lines() {
+        let profile = ConnectionProfile::new(19200)
+            .with_hardware_flow()
+            .with_lines(true, false)
+            .with_timeout(Duration::from_secs(2));
+
+        let config = profile.serial_config();
+        assert_eq!(config.baud_rate, 19200);
+        assert_eq!(config.flow_control, serialport::FlowControl::Hardware);
+        assert_eq!(config.timeout, Duration::from_secs(2));
+        assert_eq!(
+            ConnectionProfile::default().serial_config().flow_control,
+            serialport::FlowControl::None
+        );
+
+        let mut port = MockSerialPort::new();
+        port.set_rts(true).unwrap();
+        port.push_read_data(&[0x06]);
+        profile.apply(&mut port).unwrap();
+        assert!(port.dtr());
+        assert!(!port.rts());
+        assert_eq!(port.bytes_available(), 0);
+    }
+}
@@ -1,7 +1,8 @@
 // Protocol helpers for block-based radio communication
 // Many radios transfer memory in fixed-size blocks
 
-use super::comm::{SerialError, SerialPort};
+use super::comm::SerialError;
+use super::transport::Transport;
 use std::sync::Arc;
 
 /// Progress callback type
@@ -25,7 +26,7 @@ impl BlockProtocol {
 
     /// Calculate number of blocks needed
     pub fn num_blocks(&self) -> usize {
-        (self.total_size + self.block_size - 1) / self.block_size
+        self.total_size.div_ceil(self.block_size)
     }
 
     /// Get the size of a specific block (last block may be smaller)
@@ -43,9 +44,9 @@ impl BlockProtocol {
     }
 
     /// Download blocks from radio
-    pub async fn download<F>(
+    pub async fn download<F, T: Transport + ?Sized>(
         &self,
-        port: &mut SerialPort,
+        port: &mut T,
         request_block: F,
         progress: Option<ProgressCallback>,
     ) -> Result<Vec<u8>, SerialError>
@@ -85,9 +86,9 @@ impl BlockProtocol {
     }
 
     /// Upload blocks to radio
-    pub async fn upload<F>(
+    pub async fn upload<F, T: Transport + ?Sized>(
         &self,
-        port: &mut SerialPort,
+        port: &mut T,
         data: &[u8],
         send_block: F,
         progress: Option<ProgressCallback>,
@@ -136,9 +137,9 @@ impl BlockProtocol {
     }
 
     /// Download with automatic block request (simple sequential protocol)
-    pub async fn download_simple(
+    pub async fn download_simple<T: Transport + ?Sized>(
         &self,
-        port: &mut SerialPort,
+        port: &mut T,
         init_command: &[u8],
         progress: Option<ProgressCallback>,
     ) -> Result<Vec<u8>, SerialError> {
@@ -171,9 +172,9 @@ impl BlockProtocol {
     }
 
     /// Upload with simple protocol (send all data at once)
-    pub async fn upload_simple(
+    pub async fn upload_simple<T: Transport + ?Sized>(
         &self,
-        port: &mut SerialPort,
+        port: &mut T,
         data: &[u8],
         init_command: &[u8],
         progress: Option<ProgressCallback>,
@@ -216,8 +217,8 @@ impl BlockProtocol {
 }
 
 /// Helper to read until a specific byte is encountered
-pub async fn read_until(
-    port: &mut SerialPort,
+pub async fn read_until<T: Transport + ?Sized>(
+    port: &mut T,
     delimiter: u8,
     max_len: usize,
 ) -> Result<Vec<u8>, SerialError> {
@@ -237,7 +238,10 @@ pub async fn read_until(
 }
 
 /// Helper to read a specific response pattern
-pub async fn expect_response(port: &mut SerialPort, expected: &[u8]) -> Result<(), SerialError> {
+pub async fn expect_response<T: Transport + ?Sized>(
+    port: &mut T,
+    expected: &[u8],
+) -> Result<(), SerialError> {
     let mut response = vec![0u8; expected.len()];
     port.read_exact(&mut response).await?;
 
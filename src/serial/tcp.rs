@@ -0,0 +1,584 @@
+// Network serial transport (raw TCP and RFC 2217)
+// Reference: RFC 854 (Telnet), RFC 2217 (Telnet Com Port Control Option)
+//
+// Raw mode just tunnels bytes (ser2net "raw"/"tcp" ports). RFC 2217 mode
+// speaks telnet to the far end so baud rate, line settings, DTR/RTS and
+// buffer purges are applied to the remote serial port.
+
+use super::comm::{Result, SerialConfig, SerialError};
+use super::transport::Transport;
+use crate::drivers::traits::BoxFuture;
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+use tokio::time::timeout;
+
+// Telnet commands (RFC 854)
+const IAC: u8 = 255;
+const DONT: u8 = 254;
+const DO: u8 = 253;
+const WONT: u8 = 252;
+const WILL: u8 = 251;
+const SB: u8 = 250;
+const SE: u8 = 240;
+
+// Telnet options
+const OPT_BINARY: u8 = 0;
+const OPT_SGA: u8 = 3;
+const OPT_COM_PORT: u8 = 44;
+
+// COM-PORT-OPTION client commands (RFC 2217 section 3)
+const SET_BAUDRATE: u8 = 1;
+const SET_DATASIZE: u8 = 2;
+const SET_PARITY: u8 = 3;
+const SET_STOPSIZE: u8 = 4;
+const SET_CONTROL: u8 = 5;
+const PURGE_DATA: u8 = 12;
+
+// SET-CONTROL values
+const CONTROL_FLOW_NONE: u8 = 1;
+const CONTROL_FLOW_XONXOFF: u8 = 2;
+const CONTROL_FLOW_HARDWARE: u8 = 3;
+const CONTROL_DTR_ON: u8 = 8;
+const CONTROL_DTR_OFF: u8 = 9;
+const CONTROL_RTS_ON: u8 = 11;
+const CONTROL_RTS_OFF: u8 = 12;
+
+// PURGE-DATA values
+const PURGE_RX: u8 = 1;
+const PURGE_TX: u8 = 2;
+
+/// How often a blocked read polls the socket
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Wire protocol spoken over the TCP connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TcpMode {
+    /// Plain byte stream; line control is fixed at the far end
+    Raw,
+    /// Telnet with the COM-PORT-OPTION (RFC 2217)
+    Rfc2217,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TelnetState {
+    Data,
+    Iac,
+    Negotiate(u8),
+    Sub,
+    SubIac,
+}
+
+/// Incremental telnet decoder
+///
+/// Strips telnet commands out of the received stream, keeping state across
+/// reads so sequences split between TCP segments decode correctly.
+#[derive(Debug)]
+struct TelnetCodec {
+    state: TelnetState,
+    sub: Vec<u8>,
+}
+
+impl TelnetCodec {
+    fn new() -> Self {
+        Self {
+            state: TelnetState::Data,
+            sub: Vec::new(),
+        }
+    }
+
+    /// Decode received bytes into `out`, queueing negotiation replies in `replies`
+    fn decode(&mut self, input: &[u8], out: &mut Vec<u8>, replies: &mut Vec<u8>) {
+        for &byte in input {
+            self.state = match self.state {
+                TelnetState::Data if byte == IAC => TelnetState::Iac,
+                TelnetState::Data => {
+                    out.push(byte);
+                    TelnetState::Data
+                }
+                TelnetState::Iac => match byte {
+                    IAC => {
+                        out.push(IAC);
+                        TelnetState::Data
+                    }
+                    DO | DONT | WILL | WONT => TelnetState::Negotiate(byte),
+                    SB => {
+                        self.sub.clear();
+                        TelnetState::Sub
+                    }
+                    // NOP, GA and friends carry no data
+                    _ => TelnetState::Data,
+                },
+                TelnetState::Negotiate(cmd) => {
+                    Self::negotiate(cmd, byte, replies);
+                    TelnetState::Data
+                }
+                TelnetState::Sub if byte == IAC => TelnetState::SubIac,
+                TelnetState::Sub => {
+                    self.sub.push(byte);
+                    TelnetState::Sub
+                }
+                TelnetState::SubIac => match byte {
+                    SE => {
+                        self.subnegotiation();
+                        TelnetState::Data
+                    }
+                    _ => {
+                        self.sub.push(byte);
+                        TelnetState::Sub
+                    }
+                },
+            };
+        }
+    }
+
+    /// Refuse any option we did not ask for
+    fn negotiate(cmd: u8, option: u8, replies: &mut Vec<u8>) {
+        let wanted = matches!(option, OPT_BINARY | OPT_SGA | OPT_COM_PORT);
+        match cmd {
+            DO if !wanted => replies.extend_from_slice(&[IAC, WONT, option]),
+            WILL if !wanted => replies.extend_from_slice(&[IAC, DONT, option]),
+            _ => {}
+        }
+    }
+
+    fn subnegotiation(&self) {
+        if let [OPT_COM_PORT, cmd, value @ ..] = self.sub.as_slice() {
+            // Server replies echo the command number plus 100
+            tracing::trace!(
+                "RFC 2217 reply: command {} value {:02X?}",
+                cmd.wrapping_sub(100),
+                value
+            );
+        }
+    }
+}
+
+/// Double any IAC bytes so data passes through telnet unchanged
+fn escape_iac(data: &[u8]) -> Vec<u8> {
+    let mut escaped = Vec::with_capacity(data.len());
+    for &byte in data {
+        escaped.push(byte);
+        if byte == IAC {
+            escaped.push(IAC);
+        }
+    }
+    escaped
+}
+
+/// Build an `IAC SB COM-PORT-OPTION <cmd> <value> IAC SE` frame
+fn com_port_command(cmd: u8, value: &[u8]) -> Vec<u8> {
+    let mut frame = vec![IAC, SB, OPT_COM_PORT, cmd];
+    frame.extend(escape_iac(value));
+    frame.extend_from_slice(&[IAC, SE]);
+    frame
+}
+
+/// Serial port reached over TCP
+pub struct TcpTransport {
+    stream: TcpStream,
+    mode: TcpMode,
+    config: SerialConfig,
+    name: String,
+    codec: TelnetCodec,
+    pending: Vec<u8>,
+}
+
+impl TcpTransport {
+    /// Connect to `host:port`
+    ///
+    /// In RFC 2217 mode the line settings from `config` are pushed to the far end.
+    pub fn connect(address: &str, mode: TcpMode, config: SerialConfig) -> Result<Self> {
+        let addrs = address
+            .to_socket_addrs()
+            .map_err(|e| SerialError::InvalidConfig(format!("{}: {}", address, e)))?;
+
+        let mut last_err = None;
+        let mut stream = None;
+        for addr in addrs {
+            match TcpStream::connect_timeout(&addr, config.timeout) {
+                Ok(s) => {
+                    stream = Some(s);
+                    break;
+                }
+                Err(e) => last_err = Some(e),
+            }
+        }
+        let stream = match (stream, last_err) {
+            (Some(s), _) => s,
+            (None, Some(e)) => return Err(SerialError::Port(format!("{}: {}", address, e))),
+            (None, None) => {
+                return Err(SerialError::InvalidConfig(format!(
+                    "{}: no addresses found",
+                    address
+                )))
+            }
+        };
+
+        stream.set_nodelay(true)?;
+        stream.set_read_timeout(Some(POLL_INTERVAL))?;
+        stream.set_write_timeout(Some(config.timeout))?;
+
+        let scheme = match mode {
+            TcpMode::Raw => "tcp",
+            TcpMode::Rfc2217 => "rfc2217",
+        };
+        let mut transport = Self {
+            stream,
+            mode,
+            config,
+            name: format!("{}://{}", scheme, address),
+            codec: TelnetCodec::new(),
+            pending: Vec::new(),
+        };
+
+        if mode == TcpMode::Rfc2217 {
+            transport.negotiate()?;
+        }
+
+        Ok(transport)
+    }
+
+    /// Get the wire protocol in use
+    pub fn mode(&self) -> TcpMode {
+        self.mode
+    }
+
+    /// Announce our options and push the configured line settings
+    fn negotiate(&mut self) -> Result<()> {
+        self.send_raw(&[
+            IAC,
+            WILL,
+            OPT_COM_PORT,
+            IAC,
+            WILL,
+            OPT_BINARY,
+            IAC,
+            DO,
+            OPT_BINARY,
+            IAC,
+            DO,
+            OPT_SGA,
+        ])?;
+
+        let data_bits = match self.config.data_bits {
+            serialport::DataBits::Five => 5,
+            serialport::DataBits::Six => 6,
+            serialport::DataBits::Seven => 7,
+            serialport::DataBits::Eight => 8,
+        };
+        let parity = match self.config.parity {
+            serialport::Parity::None => 1,
+            serialport::Parity::Odd => 2,
+            serialport::Parity::Even => 3,
+        };
+        let stop_bits = match self.config.stop_bits {
+            serialport::StopBits::One => 1,
+            serialport::StopBits::Two => 2,
+        };
+        let flow = match self.config.flow_control {
+            serialport::FlowControl::None => CONTROL_FLOW_NONE,
+            serialport::FlowControl::Software => CONTROL_FLOW_XONXOFF,
+            serialport::FlowControl::Hardware => CONTROL_FLOW_HARDWARE,
+        };
+
+        self.send_raw(&com_port_command(
+            SET_BAUDRATE,
+            &self.config.baud_rate.to_be_bytes(),
+        ))?;
+        self.send_raw(&com_port_command(SET_DATASIZE, &[data_bits]))?;
+        self.send_raw(&com_port_command(SET_PARITY, &[parity]))?;
+        self.send_raw(&com_port_command(SET_STOPSIZE, &[stop_bits]))?;
+        self.send_raw(&com_port_command(SET_CONTROL, &[flow]))
+    }
+
+    fn send_raw(&mut self, bytes: &[u8]) -> Result<()> {
+        self.stream.write_all(bytes).map_err(SerialError::Io)
+    }
+
+    /// Send a COM-PORT-OPTION command, or ignore it in raw mode
+    fn send_control(&mut self, cmd: u8, value: &[u8]) -> Result<()> {
+        match self.mode {
+            TcpMode::Rfc2217 => self.send_raw(&com_port_command(cmd, value)),
+            // DTR/RTS changes can matter to the radio, purges don't
+            TcpMode::Raw if cmd == SET_CONTROL => {
+                tracing::warn!(
+                    "{}: DTR/RTS can't be changed over raw TCP; use rfc2217:// if the cable needs them",
+                    self.name
+                );
+                Ok(())
+            }
+            TcpMode::Raw => {
+                tracing::debug!(
+                    "{}: line control command {} ignored in raw mode",
+                    self.name,
+                    cmd
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// Read whatever the socket has, decoding telnet in RFC 2217 mode
+    /// Returns Ok(None) if nothing arrived within the poll interval
+    fn poll(&mut self, len: usize) -> io::Result<Option<Vec<u8>>> {
+        let mut raw = vec![0u8; len.max(1)];
+        let n = match self.stream.read(&mut raw) {
+            Ok(0) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "Connection closed",
+                ))
+            }
+            Ok(n) => n,
+            Err(ref e)
+                if e.kind() == io::ErrorKind::WouldBlock || e.kind() == io::ErrorKind::TimedOut =>
+            {
+                return Ok(None)
+            }
+            Err(e) => return Err(e),
+        };
+
+        match self.mode {
+            TcpMode::Raw => {
+                raw.truncate(n);
+                Ok(Some(raw))
+            }
+            TcpMode::Rfc2217 => {
+                let mut data = Vec::with_capacity(n);
+                let mut replies = Vec::new();
+                self.codec.decode(&raw[..n], &mut data, &mut replies);
+                if !replies.is_empty() {
+                    self.stream.write_all(&replies)?;
+                }
+                Ok(Some(data))
+            }
+        }
+    }
+
+    /// Read up to n bytes with timeout
+    pub async fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let limit = self.config.timeout;
+        timeout(limit, async {
+            loop {
+                if !self.pending.is_empty() {
+                    let n = self.pending.len().min(buf.len());
+                    buf[..n].copy_from_slice(&self.pending[..n]);
+                    self.pending.drain(..n);
+                    return Ok(n);
+                }
+                match self.poll(buf.len())? {
+                    Some(data) => self.pending.extend(data),
+                    None => tokio::time::sleep(POLL_INTERVAL).await,
+                }
+            }
+        })
+        .await
+        .map_err(|_| SerialError::Timeout(limit))?
+        .map_err(SerialError::Io)
+    }
+
+    /// Write bytes and return number written
+    pub async fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        match self.mode {
+            TcpMode::Raw => self.send_raw(buf)?,
+            TcpMode::Rfc2217 => self.send_raw(&escape_iac(buf))?,
+        }
+        Ok(buf.len())
+    }
+}
+
+impl Transport for TcpTransport {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn timeout(&self) -> Duration {
+        self.config.timeout
+    }
+
+    fn read<'a>(&'a mut self, buf: &'a mut [u8]) -> BoxFuture<'a, Result<usize>> {
+        Box::pin(TcpTransport::read(self, buf))
+    }
+
+    fn write<'a>(&'a mut self, buf: &'a [u8]) -> BoxFuture<'a, Result<usize>> {
+        Box::pin(TcpTransport::write(self, buf))
+    }
+
+    fn flush(&mut self) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move { self.stream.flush().map_err(SerialError::Io) })
+    }
+
+    fn clear_input(&mut self) -> Result<()> {
+        // Drop anything already buffered locally or waiting on the socket
+        self.pending.clear();
+        while self.poll(4096)?.is_some() {}
+        self.send_control(PURGE_DATA, &[PURGE_RX])
+    }
+
+    fn clear_output(&mut self) -> Result<()> {
+        self.send_control(PURGE_DATA, &[PURGE_TX])
+    }
+
+    fn set_dtr(&mut self, level: bool) -> Result<()> {
+        let value = if level {
+            CONTROL_DTR_ON
+        } else {
+            CONTROL_DTR_OFF
+        };
+        self.send_control(SET_CONTROL, &[value])
+    }
+
+    fn set_rts(&mut self, level: bool) -> Result<()> {
+        let value = if level {
+            CONTROL_RTS_ON
+        } else {
+            CONTROL_RTS_OFF
+        };
+        self.send_control(SET_CONTROL, &[value])
+    }
+
+    fn set_baud_rate(&mut self, baud_rate: u32) -> Result<()> {
+        // Silently staying at the old rate would only surface later as a timeout
+        if self.mode == TcpMode::Raw && baud_rate != self.config.baud_rate {
+            return Err(SerialError::InvalidConfig(format!(
+                "{} can't switch to {} baud; use an rfc2217:// port",
+                self.name, baud_rate
+            )));
+        }
+        self.send_control(SET_BAUDRATE, &baud_rate.to_be_bytes())?;
+        self.config.baud_rate = baud_rate;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::thread;
+
+    #[test]
+    fn test_escape_iac() {
+        assert_eq!(escape_iac(&[0x01, IAC, 0x02]), vec![0x01, IAC, IAC, 0x02]);
+        assert_eq!(
+            com_port_command(SET_BAUDRATE, &57600u32.to_be_bytes()),
+            vec![
+                IAC,
+                SB,
+                OPT_COM_PORT,
+                SET_BAUDRATE,
+                0x00,
+                0x00,
+                0xE1,
+                0x00,
+                IAC,
+                SE
+            ]
+        );
+    }
+
+    #[test]
+    fn test_telnet_decode() {
+        let mut codec = TelnetCodec::new();
+        let mut out = Vec::new();
+        let mut replies = Vec::new();
+
+        // Data with an escaped IAC, a reply to our baud change and an unknown DO
+        let stream = [
+            b'O',
+            b'K',
+            IAC,
+            IAC,
+            IAC,
+            SB,
+            OPT_COM_PORT,
+            101,
+            0,
+            0,
+            0xE1,
+            0,
+            IAC,
+            SE,
+            IAC,
+            DO,
+            24,
+            b'!',
+        ];
+        // Split mid-sequence to check state carries across reads
+        codec.decode(&stream[..5], &mut out, &mut replies);
+        codec.decode(&stream[5..], &mut out, &mut replies);
+
+        assert_eq!(out, vec![b'O', b'K', IAC, b'!']);
+        assert_eq!(replies, vec![IAC, WONT, 24]);
+    }
+
+    #[tokio::test]
+    async fn test_raw_roundtrip() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let server = thread::spawn(move || {
+            let (mut conn, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 3];
+            conn.read_exact(&mut buf).unwrap();
+            conn.write_all(b"TH-D75\r").unwrap();
+            buf
+        });
+
+        let config = SerialConfig::new(9600).with_timeout(Duration::from_secs(2));
+        let mut port = TcpTransport::connect(&addr, TcpMode::Raw, config).unwrap();
+        port.set_dtr(true).unwrap();
+        port.set_baud_rate(9600).unwrap();
+        assert!(matches!(
+            port.set_baud_rate(57600),
+            Err(SerialError::InvalidConfig(_))
+        ));
+        Transport::write_all(&mut port, b"ID\r").await.unwrap();
+
+        let mut reply = [0u8; 7];
+        Transport::read_exact(&mut port, &mut reply).await.unwrap();
+        assert_eq!(&reply, b"TH-D75\r");
+        assert_eq!(&server.join().unwrap(), b"ID\r");
+    }
+
+    #[tokio::test]
+    async fn test_rfc2217_control() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let server = thread::spawn(move || {
+            let (mut conn, _) = listener.accept().unwrap();
+            conn.set_read_timeout(Some(Duration::from_millis(200)))
+                .unwrap();
+            let mut received = Vec::new();
+            let mut buf = [0u8; 256];
+            while let Ok(n) = conn.read(&mut buf) {
+                if n == 0 {
+                    break;
+                }
+                received.extend_from_slice(&buf[..n]);
+            }
+            received
+        });
+
+        let config = SerialConfig::new(9600).with_timeout(Duration::from_secs(2));
+        let mut port = TcpTransport::connect(&addr, TcpMode::Rfc2217, config).unwrap();
+        port.set_baud_rate(57600).unwrap();
+        port.set_rts(false).unwrap();
+        Transport::write_all(&mut port, &[IAC]).await.unwrap();
+        drop(port);
+
+        let received = server.join().unwrap();
+        let contains = |needle: &[u8]| received.windows(needle.len()).any(|w| w == needle);
+        assert!(contains(&[IAC, WILL, OPT_COM_PORT]));
+        assert!(contains(&com_port_command(
+            SET_BAUDRATE,
+            &9600u32.to_be_bytes()
+        )));
+        assert!(contains(&com_port_command(
+            SET_BAUDRATE,
+            &57600u32.to_be_bytes()
+        )));
+        assert!(contains(&com_port_command(SET_CONTROL, &[CONTROL_RTS_OFF])));
+        assert!(received.ends_with(&[IAC, IAC]));
+    }
+}
@@ -0,0 +1,183 @@
+// Transport abstraction for radio I/O
+// Drivers talk to a `Transport` instead of a concrete serial port so the same
+// clone/CI-V code runs over a local serial port, a network bridge or a mock.
+
+use super::comm::{Result, SerialConfig, SerialError, SerialPort};
+use super::tcp::{TcpMode, TcpTransport};
+use crate::drivers::traits::BoxFuture;
+use std::io;
+use std::time::Duration;
+
+/// Byte-stream link to a radio
+///
+/// The async methods return boxed futures so the trait stays object-safe and
+/// callers can hold a `Box<dyn Transport>` picked at runtime.
+pub trait Transport {
+    /// Human-readable name of the link (device path or URL)
+    fn name(&self) -> &str;
+
+    /// Read/write timeout
+    fn timeout(&self) -> Duration;
+
+    /// Read up to `buf.len()` bytes
+    fn read<'a>(&'a mut self, buf: &'a mut [u8]) -> BoxFuture<'a, Result<usize>>;
+
+    /// Write bytes and return number written
+    fn write<'a>(&'a mut self, buf: &'a [u8]) -> BoxFuture<'a, Result<usize>>;
+
+    /// Flush the output buffer
+    fn flush(&mut self) -> BoxFuture<'_, Result<()>>;
+
+    /// Discard any unread input
+    fn clear_input(&mut self) -> Result<()>;
+
+    /// Discard any unsent output
+    fn clear_output(&mut self) -> Result<()>;
+
+    /// Set DTR (Data Terminal Ready)
+    fn set_dtr(&mut self, level: bool) -> Result<()>;
+
+    /// Set RTS (Request To Send)
+    fn set_rts(&mut self, level: bool) -> Result<()>;
+
+    /// Change the baud rate
+    fn set_baud_rate(&mut self, baud_rate: u32) -> Result<()>;
+
+    /// Read exactly `buf.len()` bytes
+    fn read_exact<'a>(&'a mut self, buf: &'a mut [u8]) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let mut total_read = 0;
+            while total_read < buf.len() {
+                match self.read(&mut buf[total_read..]).await? {
+                    0 => {
+                        return Err(SerialError::Io(io::Error::new(
+                            io::ErrorKind::UnexpectedEof,
+                            "Port closed",
+                        )))
+                    }
+                    n => total_read += n,
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Write all bytes
+    fn write_all<'a>(&'a mut self, buf: &'a [u8]) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let mut written = 0;
+            while written < buf.len() {
+                match self.write(&buf[written..]).await? {
+                    0 => {
+                        return Err(SerialError::Io(io::Error::new(
+                            io::ErrorKind::WriteZero,
+                            "Port closed",
+                        )))
+                    }
+                    n => written += n,
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Clear both input and output buffers
+    fn clear_all(&mut self) -> Result<()> {
+        self.clear_input()?;
+        self.clear_output()
+    }
+}
+
+/// Open a transport by name
+///
+/// `tcp://host:port` opens a raw TCP tunnel (e.g. ser2net), `rfc2217://host:port`
+/// a telnet COM-PORT-OPTION connection, and anything else a local serial device.
+pub fn open_transport(name: &str, config: SerialConfig) -> Result<Box<dyn Transport>> {
+    if let Some(address) = name.strip_prefix("tcp://") {
+        Ok(Box::new(TcpTransport::connect(
+            address,
+            TcpMode::Raw,
+            config,
+        )?))
+    } else if let Some(address) = name.strip_prefix("rfc2217://") {
+        Ok(Box::new(TcpTransport::connect(
+            address,
+            TcpMode::Rfc2217,
+            config,
+        )?))
+    } else {
+        Ok(Box::new(SerialPort::open(name, config)?))
+    }
+}
+
+impl<T: Transport + ?Sized> Transport for Box<T> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn timeout(&self) -> Duration {
+        (**self).timeout()
+    }
+
+    fn read<'a>(&'a mut self, buf: &'a mut [u8]) -> BoxFuture<'a, Result<usize>> {
+        (**self).read(buf)
+    }
+
+    fn write<'a>(&'a mut self, buf: &'a [u8]) -> BoxFuture<'a, Result<usize>> {
+        (**self).write(buf)
+    }
+
+    fn flush(&mut self) -> BoxFuture<'_, Result<()>> {
+        (**self).flush()
+    }
+
+    fn clear_input(&mut self) -> Result<()> {
+        (**self).clear_input()
+    }
+
+    fn clear_output(&mut self) -> Result<()> {
+        (**self).clear_output()
+    }
+
+    fn set_dtr(&mut self, level: bool) -> Result<()> {
+        (**self).set_dtr(level)
+    }
+
+    fn set_rts(&mut self, level: bool) -> Result<()> {
+        (**self).set_rts(level)
+    }
+
+    fn set_baud_rate(&mut self, baud_rate: u32) -> Result<()> {
+        (**self).set_baud_rate(baud_rate)
+    }
+
+    fn read_exact<'a>(&'a mut self, buf: &'a mut [u8]) -> BoxFuture<'a, Result<()>> {
+        (**self).read_exact(buf)
+    }
+
+    fn write_all<'a>(&'a mut self, buf: &'a [u8]) -> BoxFuture<'a, Result<()>> {
+        (**self).write_all(buf)
+    }
+
+    fn clear_all(&mut self) -> Result<()> {
+        (**self).clear_all()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_open_transport_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let port = open_transport(&format!("tcp://{}", addr), SerialConfig::new(9600)).unwrap();
+        assert_eq!(port.name(), format!("tcp://{}", addr));
+
+        assert!(open_transport("tcp://no-port-given", SerialConfig::new(9600)).is_err());
+        assert!(open_transport("/dev/chirp-rs-no-such-port", SerialConfig::new(9600)).is_err());
+    }
+}